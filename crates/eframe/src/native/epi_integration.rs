@@ -171,6 +171,53 @@ pub fn window_builder<E>(
     window_builder
 }
 
+/// The [`winit::window::WindowBuilder`] for the native window of an [`egui::ViewportBuilder`].
+///
+/// Anything not set in the [`egui::ViewportBuilder`] falls back to the [`epi::NativeOptions`] of the app.
+pub fn viewport_window_builder(
+    native_options: &epi::NativeOptions,
+    builder: &egui::ViewportBuilder,
+) -> winit::window::WindowBuilder {
+    use winit::window::WindowLevel;
+
+    let egui::ViewportBuilder {
+        title,
+        position,
+        inner_size,
+        min_inner_size,
+        resizable,
+        decorations,
+        always_on_top,
+        transparent,
+    } = builder;
+
+    let mut window_builder = winit::window::WindowBuilder::new()
+        .with_title(title.as_deref().unwrap_or("egui"))
+        .with_decorations(decorations.unwrap_or(native_options.decorated))
+        .with_resizable(resizable.unwrap_or(native_options.resizable))
+        .with_transparent(transparent.unwrap_or(native_options.transparent))
+        .with_window_level(if always_on_top.unwrap_or(native_options.always_on_top) {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        });
+
+    if let Some(pos) = *position {
+        window_builder = window_builder.with_position(winit::dpi::LogicalPosition {
+            x: pos.x as f64,
+            y: pos.y as f64,
+        });
+    }
+    if let Some(inner_size) = *inner_size {
+        window_builder = window_builder.with_inner_size(points_to_size(inner_size));
+    }
+    if let Some(min_inner_size) = *min_inner_size {
+        window_builder = window_builder.with_min_inner_size(points_to_size(min_inner_size));
+    }
+
+    window_builder_drag_and_drop(window_builder, native_options.drag_and_drop_support)
+}
+
 pub fn apply_native_options_to_window(
     window: &winit::window::Window,
     native_options: &crate::NativeOptions,
@@ -323,6 +370,39 @@ pub fn create_storage(_app_name: &str) -> Option<Box<dyn epi::Storage>> {
 
 // ----------------------------------------------------------------------------
 
+/// The native window of a viewport other than the root viewport.
+///
+/// See [`egui::Context::show_viewport`].
+pub struct ViewportWindow {
+    pub window: winit::window::Window,
+    pub egui_winit: egui_winit::State,
+
+    /// The latest ui code and builder of the viewport.
+    pub viewport: egui::ViewportOutput,
+}
+
+impl ViewportWindow {
+    pub fn new<E>(
+        event_loop: &EventLoopWindowTarget<E>,
+        viewport_id: egui::ViewportId,
+        viewport: egui::ViewportOutput,
+        window: winit::window::Window,
+        max_texture_side: usize,
+    ) -> Self {
+        let mut egui_winit = egui_winit::State::new(event_loop);
+        egui_winit.set_viewport_id(viewport_id);
+        egui_winit.set_max_texture_side(max_texture_side);
+        egui_winit.set_pixels_per_point(native_pixels_per_point(&window));
+        window.set_ime_allowed(true);
+
+        Self {
+            window,
+            egui_winit,
+            viewport,
+        }
+    }
+}
+
 /// Everything needed to make a winit-based integration for [`epi`].
 pub struct EpiIntegration {
     pub frame: epi::Frame,
@@ -352,6 +432,7 @@ impl EpiIntegration {
         #[cfg(feature = "wgpu")] wgpu_render_state: Option<egui_wgpu::RenderState>,
     ) -> Self {
         let egui_ctx = egui::Context::default();
+        egui_ctx.set_embed_viewports(false);
 
         let memory = load_egui_memory(storage.as_deref()).unwrap_or_default();
         egui_ctx.memory_mut(|mem| *mem = memory);
@@ -530,6 +611,26 @@ impl EpiIntegration {
         full_output
     }
 
+    /// Run the ui code of a viewport other than the root viewport.
+    pub fn update_viewport(&mut self, viewport_window: &mut ViewportWindow) -> egui::FullOutput {
+        let ViewportWindow {
+            window,
+            egui_winit,
+            viewport,
+        } = viewport_window;
+
+        let raw_input = egui_winit.take_egui_input(window);
+        let mut full_output = self.egui_ctx.run(raw_input, |egui_ctx| {
+            crate::profile_scope!("viewport ui");
+            viewport.show(egui_ctx);
+        });
+
+        let platform_output = std::mem::take(&mut full_output.platform_output);
+        egui_winit.handle_platform_output(window, &self.egui_ctx, platform_output);
+
+        full_output
+    }
+
     pub fn post_rendering(&mut self, app: &mut dyn epi::App, window: &winit::window::Window) {
         let inner_size = window.inner_size();
         let window_size_px = [inner_size.width, inner_size.height];
@@ -70,11 +70,16 @@ trait WinitApp {
 
     fn integration(&self) -> Option<&EpiIntegration>;
 
+    /// The window of the root viewport.
     fn window(&self) -> Option<&winit::window::Window>;
 
+    /// Is this the root window, or the window of one of the other viewports?
+    fn has_window(&self, window_id: winit::window::WindowId) -> bool;
+
     fn save_and_destroy(&mut self);
 
-    fn run_ui_and_paint(&mut self) -> EventResult;
+    /// Run the ui and paint all windows.
+    fn run_ui_and_paint(&mut self, event_loop: &EventLoopWindowTarget<UserEvent>) -> EventResult;
 
     fn on_event(
         &mut self,
@@ -145,11 +150,16 @@ fn run_and_return(
             // See: https://github.com/rust-windowing/winit/issues/1619
             winit::event::Event::RedrawEventsCleared if cfg!(windows) => {
                 next_repaint_time = extremely_far_future();
-                winit_app.run_ui_and_paint()
+                winit_app.run_ui_and_paint(event_loop)
             }
-            winit::event::Event::RedrawRequested(_) if !cfg!(windows) => {
-                next_repaint_time = extremely_far_future();
-                winit_app.run_ui_and_paint()
+            winit::event::Event::RedrawRequested(window_id) if !cfg!(windows) => {
+                if winit_app.window().map(|window| window.id()) == Some(*window_id) {
+                    next_repaint_time = extremely_far_future();
+                    winit_app.run_ui_and_paint(event_loop)
+                } else {
+                    // The other viewports are painted together with the root viewport:
+                    EventResult::RepaintNext
+                }
             }
 
             winit::event::Event::UserEvent(UserEvent::RequestRepaint { when, frame_nr }) => {
@@ -170,8 +180,7 @@ fn run_and_return(
             }
 
             winit::event::Event::WindowEvent { window_id, .. }
-                if winit_app.window().is_none()
-                    || *window_id != winit_app.window().unwrap().id() =>
+                if !winit_app.has_window(*window_id) =>
             {
                 // This can happen if we close a window, and then reopen a new one,
                 // or if we have multiple windows open.
@@ -195,7 +204,7 @@ fn run_and_return(
                 if cfg!(windows) {
                     // Fix flickering on Windows, see https://github.com/emilk/egui/pull/2280
                     next_repaint_time = extremely_far_future();
-                    winit_app.run_ui_and_paint();
+                    winit_app.run_ui_and_paint(event_loop);
                 } else {
                     // Fix for https://github.com/emilk/egui/issues/2425
                     next_repaint_time = Instant::now();
@@ -262,11 +271,16 @@ fn run_and_exit(event_loop: EventLoop<UserEvent>, mut winit_app: impl WinitApp +
             // See: https://github.com/rust-windowing/winit/issues/1619
             winit::event::Event::RedrawEventsCleared if cfg!(windows) => {
                 next_repaint_time = extremely_far_future();
-                winit_app.run_ui_and_paint()
+                winit_app.run_ui_and_paint(event_loop)
             }
-            winit::event::Event::RedrawRequested(_) if !cfg!(windows) => {
-                next_repaint_time = extremely_far_future();
-                winit_app.run_ui_and_paint()
+            winit::event::Event::RedrawRequested(window_id) if !cfg!(windows) => {
+                if winit_app.window().map(|window| window.id()) == Some(window_id) {
+                    next_repaint_time = extremely_far_future();
+                    winit_app.run_ui_and_paint(event_loop)
+                } else {
+                    // The other viewports are painted together with the root viewport:
+                    EventResult::RepaintNext
+                }
             }
 
             winit::event::Event::UserEvent(UserEvent::RequestRepaint { when, frame_nr }) => {
@@ -295,7 +309,7 @@ fn run_and_exit(event_loop: EventLoop<UserEvent>, mut winit_app: impl WinitApp +
                 if cfg!(windows) {
                     // Fix flickering on Windows, see https://github.com/emilk/egui/pull/2280
                     next_repaint_time = extremely_far_future();
-                    winit_app.run_ui_and_paint();
+                    winit_app.run_ui_and_paint(event_loop);
                 } else {
                     // Fix for https://github.com/emilk/egui/issues/2425
                     next_repaint_time = Instant::now();
@@ -336,7 +350,10 @@ mod glow_integration {
     use egui::NumExt as _;
     use glutin::{
         display::GetGlDisplay,
-        prelude::{GlDisplay, NotCurrentGlContextSurfaceAccessor, PossiblyCurrentGlContext},
+        prelude::{
+            GlDisplay, NotCurrentGlContextSurfaceAccessor, PossiblyCurrentContextGlSurfaceAccessor,
+            PossiblyCurrentGlContext,
+        },
         surface::GlSurface,
     };
     use raw_window_handle::HasRawWindowHandle;
@@ -367,6 +384,139 @@ mod glow_integration {
         // Conceptually this will be split out eventually so that the rest of the state
         // can be persistent.
         gl_window: GlutinWindowContext,
+
+        /// Extra native windows, see [`egui::Context::show_viewport`].
+        viewports: egui::ViewportIdMap<GlowViewport>,
+    }
+
+    /// A native window of a viewport other than the root.
+    struct GlowViewport {
+        // The surface must be dropped before its window.
+        gl_surface: glutin::surface::Surface<glutin::surface::WindowSurface>,
+        window: epi_integration::ViewportWindow,
+    }
+
+    impl GlowWinitRunning {
+        /// Open windows for new viewports, and close the windows of viewports that are no longer shown.
+        fn update_viewports(
+            &mut self,
+            event_loop: &EventLoopWindowTarget<UserEvent>,
+            native_options: &epi::NativeOptions,
+            viewports: egui::ViewportIdMap<egui::ViewportOutput>,
+        ) -> Result<()> {
+            self.viewports.retain(|id, _| viewports.contains_key(id));
+
+            for (viewport_id, viewport) in viewports {
+                if let Some(glow_viewport) = self.viewports.get_mut(&viewport_id) {
+                    glow_viewport.window.viewport = viewport;
+                } else {
+                    let window_builder =
+                        epi_integration::viewport_window_builder(native_options, &viewport.builder);
+                    let (window, gl_surface) = self
+                        .gl_window
+                        .create_viewport_window(event_loop, window_builder)?;
+                    let window = epi_integration::ViewportWindow::new(
+                        event_loop,
+                        viewport_id,
+                        viewport,
+                        window,
+                        self.painter.max_texture_side(),
+                    );
+                    self.viewports
+                        .insert(viewport_id, GlowViewport { gl_surface, window });
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Run the ui of all viewports other than the root, and paint them.
+        ///
+        /// Returns when the viewports want to be repainted.
+        fn paint_viewports(&mut self) -> Result<std::time::Duration> {
+            let mut repaint_after = std::time::Duration::MAX;
+
+            for glow_viewport in self.viewports.values_mut() {
+                crate::profile_scope!("viewport");
+                let GlowViewport { gl_surface, window } = glow_viewport;
+
+                self.gl_window.make_current(gl_surface)?;
+
+                let full_output = self.integration.update_viewport(window);
+                repaint_after = repaint_after.min(full_output.repaint_after);
+
+                let clipped_primitives = {
+                    crate::profile_scope!("tessellate");
+                    self.integration.egui_ctx.tessellate(full_output.shapes)
+                };
+
+                let screen_size_in_pixels: [u32; 2] = window.window.inner_size().into();
+                egui_glow::painter::clear(
+                    &self.gl,
+                    screen_size_in_pixels,
+                    self.app
+                        .clear_color(&self.integration.egui_ctx.style().visuals),
+                );
+                self.painter.paint_and_update_textures(
+                    screen_size_in_pixels,
+                    self.integration.egui_ctx.pixels_per_point(),
+                    &clipped_primitives,
+                    &full_output.textures_delta,
+                );
+
+                crate::profile_scope!("swap_buffers");
+                gl_surface.swap_buffers(self.gl_window.context())?;
+            }
+
+            if !self.viewports.is_empty() {
+                self.gl_window.make_main_current()?;
+            }
+
+            Ok(repaint_after)
+        }
+
+        fn on_viewport_event(
+            &mut self,
+            window_id: winit::window::WindowId,
+            event: &winit::event::WindowEvent<'_>,
+        ) -> EventResult {
+            let viewport = if let Some(viewport) = self
+                .viewports
+                .values_mut()
+                .find(|viewport| viewport.window.window.id() == window_id)
+            {
+                viewport
+            } else {
+                return EventResult::Wait; // A window that was just closed
+            };
+
+            match event {
+                winit::event::WindowEvent::Resized(physical_size) => {
+                    // Resize with 0 width and height is used by winit to signal a minimize event on Windows.
+                    if physical_size.width > 0 && physical_size.height > 0 {
+                        self.gl_window
+                            .resize_surface(&viewport.gl_surface, *physical_size);
+                    }
+                }
+                winit::event::WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                    self.gl_window
+                        .resize_surface(&viewport.gl_surface, **new_inner_size);
+                }
+                _ => {}
+            }
+
+            let event_response = viewport
+                .window
+                .egui_winit
+                .on_event(&self.integration.egui_ctx, event);
+
+            if event_response.repaint {
+                // All viewports are painted together with the root viewport.
+                EventResult::RepaintNext
+            } else {
+                EventResult::Wait
+            }
+        }
     }
 
     /// This struct will contain both persistent and temporary glutin state.
@@ -531,23 +681,7 @@ mod glow_integration {
                 glutin_winit::finalize_window(event_loop, self.builder.clone(), &self.gl_config)
                     .expect("failed to finalize glutin window")
             });
-            // surface attributes
-            let (width, height): (u32, u32) = window.inner_size().into();
-            let width = std::num::NonZeroU32::new(width.at_least(1)).unwrap();
-            let height = std::num::NonZeroU32::new(height.at_least(1)).unwrap();
-            let surface_attributes =
-                glutin::surface::SurfaceAttributesBuilder::<glutin::surface::WindowSurface>::new()
-                    .build(window.raw_window_handle(), width, height);
-            log::debug!(
-                "creating surface with attributes: {:?}",
-                &surface_attributes
-            );
-            // create surface
-            let gl_surface = unsafe {
-                self.gl_config
-                    .display()
-                    .create_window_surface(&self.gl_config, &surface_attributes)?
-            };
+            let gl_surface = self.create_surface(&window)?;
             log::debug!("surface created successfully: {gl_surface:?}.making context current");
             // make surface and context current.
             let not_current_gl_context = self
@@ -568,6 +702,78 @@ mod glow_integration {
             Ok(())
         }
 
+        #[allow(unsafe_code)]
+        fn create_surface(
+            &self,
+            window: &winit::window::Window,
+        ) -> Result<glutin::surface::Surface<glutin::surface::WindowSurface>> {
+            let (width, height): (u32, u32) = window.inner_size().into();
+            let width = std::num::NonZeroU32::new(width.at_least(1)).unwrap();
+            let height = std::num::NonZeroU32::new(height.at_least(1)).unwrap();
+            let surface_attributes =
+                glutin::surface::SurfaceAttributesBuilder::<glutin::surface::WindowSurface>::new()
+                    .build(window.raw_window_handle(), width, height);
+            log::debug!(
+                "creating surface with attributes: {:?}",
+                &surface_attributes
+            );
+            Ok(unsafe {
+                self.gl_config
+                    .display()
+                    .create_window_surface(&self.gl_config, &surface_attributes)?
+            })
+        }
+
+        /// Create the window and surface of a viewport other than the root.
+        ///
+        /// The surface shares the GL context (and so all textures) with the main window.
+        fn create_viewport_window(
+            &self,
+            event_loop: &EventLoopWindowTarget<UserEvent>,
+            window_builder: winit::window::WindowBuilder,
+        ) -> Result<(
+            winit::window::Window,
+            glutin::surface::Surface<glutin::surface::WindowSurface>,
+        )> {
+            let window =
+                glutin_winit::finalize_window(event_loop, window_builder, &self.gl_config)?;
+            let gl_surface = self.create_surface(&window)?;
+
+            // Only the main window waits for vsync,
+            // or painting all windows would take one vsync interval per window.
+            let context = self.context();
+            context.make_current(&gl_surface)?;
+            if let Err(err) =
+                gl_surface.set_swap_interval(context, glutin::surface::SwapInterval::DontWait)
+            {
+                log::debug!("failed to set swap interval of viewport: {err:?}");
+            }
+            self.make_main_current()?;
+
+            Ok((window, gl_surface))
+        }
+
+        fn context(&self) -> &glutin::context::PossiblyCurrentContext {
+            self.current_gl_context
+                .as_ref()
+                .expect("failed to get current context")
+        }
+
+        fn make_current(
+            &self,
+            surface: &glutin::surface::Surface<glutin::surface::WindowSurface>,
+        ) -> glutin::error::Result<()> {
+            self.context().make_current(surface)
+        }
+
+        fn make_main_current(&self) -> glutin::error::Result<()> {
+            self.make_current(
+                self.gl_surface
+                    .as_ref()
+                    .expect("failed to get surface to make current"),
+            )
+        }
+
         /// only applies for android. but we basically drop surface + window and make context not current
         fn on_suspend(&mut self) -> Result<()> {
             log::debug!("received suspend event. dropping window and surface");
@@ -587,18 +793,28 @@ mod glow_integration {
         }
 
         fn resize(&self, physical_size: winit::dpi::PhysicalSize<u32>) {
+            self.resize_surface(
+                self.gl_surface
+                    .as_ref()
+                    .expect("failed to get surface to resize"),
+                physical_size,
+            );
+        }
+
+        fn resize_surface(
+            &self,
+            surface: &glutin::surface::Surface<glutin::surface::WindowSurface>,
+            physical_size: winit::dpi::PhysicalSize<u32>,
+        ) {
             let width = std::num::NonZeroU32::new(physical_size.width.at_least(1)).unwrap();
             let height = std::num::NonZeroU32::new(physical_size.height.at_least(1)).unwrap();
-            self.gl_surface
-                .as_ref()
-                .expect("failed to get surface to resize")
-                .resize(
-                    self.current_gl_context
-                        .as_ref()
-                        .expect("failed to get current context to resize surface"),
-                    width,
-                    height,
-                );
+            surface.resize(
+                self.current_gl_context
+                    .as_ref()
+                    .expect("failed to get current context to resize surface"),
+                width,
+                height,
+            );
         }
 
         fn swap_buffers(&self) -> glutin::error::Result<()> {
@@ -762,6 +978,7 @@ mod glow_integration {
                 painter,
                 integration,
                 app,
+                viewports: Default::default(),
             });
 
             Ok(())
@@ -787,101 +1004,140 @@ mod glow_integration {
             self.running.as_ref().map(|r| r.gl_window.window())
         }
 
+        fn has_window(&self, window_id: winit::window::WindowId) -> bool {
+            self.running.as_ref().map_or(false, |r| {
+                r.gl_window.window.as_ref().map(|window| window.id()) == Some(window_id)
+                    || r.viewports
+                        .values()
+                        .any(|viewport| viewport.window.window.id() == window_id)
+            })
+        }
+
         fn save_and_destroy(&mut self) {
             if let Some(mut running) = self.running.take() {
                 running
                     .integration
                     .save(running.app.as_mut(), running.gl_window.window());
                 running.app.on_exit(Some(&running.gl));
+                running.viewports.clear();
                 running.painter.destroy();
             }
         }
 
-        fn run_ui_and_paint(&mut self) -> EventResult {
+        fn run_ui_and_paint(
+            &mut self,
+            event_loop: &EventLoopWindowTarget<UserEvent>,
+        ) -> EventResult {
             if let Some(running) = &mut self.running {
                 #[cfg(feature = "puffin")]
                 puffin::GlobalProfiler::lock().new_frame();
                 crate::profile_scope!("frame");
 
-                let GlowWinitRunning {
-                    gl_window,
-                    gl,
-                    app,
-                    integration,
-                    painter,
-                } = running;
-
-                let window = gl_window.window();
-
-                let screen_size_in_pixels: [u32; 2] = window.inner_size().into();
+                let (repaint_after, viewports) = {
+                    let GlowWinitRunning {
+                        gl_window,
+                        gl,
+                        app,
+                        integration,
+                        painter,
+                        ..
+                    } = running;
+
+                    let window = gl_window.window();
+
+                    let screen_size_in_pixels: [u32; 2] = window.inner_size().into();
+
+                    egui_glow::painter::clear(
+                        gl,
+                        screen_size_in_pixels,
+                        app.clear_color(&integration.egui_ctx.style().visuals),
+                    );
+
+                    let egui::FullOutput {
+                        platform_output,
+                        repaint_after,
+                        textures_delta,
+                        shapes,
+                        viewports,
+                    } = integration.update(app.as_mut(), window);
+
+                    integration.handle_platform_output(window, platform_output);
+
+                    let clipped_primitives = {
+                        crate::profile_scope!("tessellate");
+                        integration.egui_ctx.tessellate(shapes)
+                    };
+
+                    painter.paint_and_update_textures(
+                        screen_size_in_pixels,
+                        integration.egui_ctx.pixels_per_point(),
+                        &clipped_primitives,
+                        &textures_delta,
+                    );
+
+                    let screenshot_requested = &mut integration.frame.output.screenshot_requested;
+
+                    if *screenshot_requested {
+                        *screenshot_requested = false;
+                        let screenshot = painter.read_screen_rgba(screen_size_in_pixels);
+                        integration.frame.screenshot.set(Some(screenshot));
+                    }
 
-                egui_glow::painter::clear(
-                    gl,
-                    screen_size_in_pixels,
-                    app.clear_color(&integration.egui_ctx.style().visuals),
-                );
+                    integration.post_rendering(app.as_mut(), window);
 
-                let egui::FullOutput {
-                    platform_output,
-                    repaint_after,
-                    textures_delta,
-                    shapes,
-                } = integration.update(app.as_mut(), window);
+                    {
+                        crate::profile_scope!("swap_buffers");
+                        gl_window.swap_buffers().unwrap();
+                    }
 
-                integration.handle_platform_output(window, platform_output);
+                    integration.post_present(window);
+
+                    #[cfg(feature = "__screenshot")]
+                    // give it time to settle:
+                    if integration.egui_ctx.frame_nr() == 2 {
+                        if let Ok(path) = std::env::var("EFRAME_SCREENSHOT_TO") {
+                            assert!(
+                                path.ends_with(".png"),
+                                "Expected EFRAME_SCREENSHOT_TO to end with '.png', got {path:?}"
+                            );
+                            let screenshot = painter.read_screen_rgba(screen_size_in_pixels);
+                            image::save_buffer(
+                                &path,
+                                screenshot.as_raw(),
+                                screenshot.width() as u32,
+                                screenshot.height() as u32,
+                                image::ColorType::Rgba8,
+                            )
+                            .unwrap_or_else(|err| {
+                                panic!("Failed to save screenshot to {path:?}: {err}");
+                            });
+                            eprintln!("Screenshot saved to {path:?}.");
+                            std::process::exit(0);
+                        }
+                    }
 
-                let clipped_primitives = {
-                    crate::profile_scope!("tessellate");
-                    integration.egui_ctx.tessellate(shapes)
+                    (repaint_after, viewports)
                 };
 
-                painter.paint_and_update_textures(
-                    screen_size_in_pixels,
-                    integration.egui_ctx.pixels_per_point(),
-                    &clipped_primitives,
-                    &textures_delta,
-                );
-
-                let screenshot_requested = &mut integration.frame.output.screenshot_requested;
-
-                if *screenshot_requested {
-                    *screenshot_requested = false;
-                    let screenshot = painter.read_screen_rgba(screen_size_in_pixels);
-                    integration.frame.screenshot.set(Some(screenshot));
-                }
-
-                integration.post_rendering(app.as_mut(), window);
-
+                // Extra native windows:
+                let repaint_after = match running
+                    .update_viewports(event_loop, &self.native_options, viewports)
+                    .and_then(|()| running.paint_viewports())
                 {
-                    crate::profile_scope!("swap_buffers");
-                    gl_window.swap_buffers().unwrap();
-                }
-
-                integration.post_present(window);
-
-                #[cfg(feature = "__screenshot")]
-                // give it time to settle:
-                if integration.egui_ctx.frame_nr() == 2 {
-                    if let Ok(path) = std::env::var("EFRAME_SCREENSHOT_TO") {
-                        assert!(
-                            path.ends_with(".png"),
-                            "Expected EFRAME_SCREENSHOT_TO to end with '.png', got {path:?}"
-                        );
-                        let screenshot = painter.read_screen_rgba(screen_size_in_pixels);
-                        image::save_buffer(
-                            &path,
-                            screenshot.as_raw(),
-                            screenshot.width() as u32,
-                            screenshot.height() as u32,
-                            image::ColorType::Rgba8,
-                        )
-                        .unwrap_or_else(|err| {
-                            panic!("Failed to save screenshot to {path:?}: {err}");
-                        });
-                        eprintln!("Screenshot saved to {path:?}.");
-                        std::process::exit(0);
+                    Ok(viewports_repaint_after) => repaint_after.min(viewports_repaint_after),
+                    Err(err) => {
+                        log::error!("Failed to paint viewports: {err}");
+                        repaint_after
                     }
-                }
+                };
+
+                let GlowWinitRunning {
+                    gl_window,
+                    app,
+                    integration,
+                    ..
+                } = running;
+                let window = gl_window.window();
 
                 let control_flow = if integration.should_close() {
                     EventResult::Exit
@@ -946,6 +1202,18 @@ mod glow_integration {
                     EventResult::Wait
                 }
 
+                winit::event::Event::WindowEvent { window_id, event }
+                    if self.running.as_ref().map_or(false, |running| {
+                        running.gl_window.window.as_ref().map(|window| window.id())
+                            != Some(*window_id)
+                    }) =>
+                {
+                    self.running
+                        .as_mut()
+                        .unwrap()
+                        .on_viewport_event(*window_id, event)
+                }
+
                 winit::event::Event::WindowEvent { event, .. } => {
                     if let Some(running) = &mut self.running {
                         // On Windows, if a window is resized by the user, it should repaint synchronously, inside the
@@ -1067,6 +1335,128 @@ mod wgpu_integration {
         painter: egui_wgpu::winit::Painter,
         integration: epi_integration::EpiIntegration,
         app: Box<dyn epi::App>,
+
+        /// Extra native windows, see [`egui::Context::show_viewport`].
+        viewports: egui::ViewportIdMap<epi_integration::ViewportWindow>,
+    }
+
+    impl WgpuWinitRunning {
+        /// Open windows for new viewports, and close the windows of viewports that are no longer shown.
+        fn update_viewports(
+            &mut self,
+            event_loop: &EventLoopWindowTarget<UserEvent>,
+            native_options: &epi::NativeOptions,
+            viewports: egui::ViewportIdMap<egui::ViewportOutput>,
+        ) -> Result<()> {
+            let painter = &mut self.painter;
+            self.viewports.retain(|id, viewport| {
+                let keep = viewports.contains_key(id);
+                if !keep {
+                    pollster::block_on(painter.set_window_for(viewport.window.id(), None)).ok();
+                }
+                keep
+            });
+
+            for (viewport_id, viewport) in viewports {
+                if let Some(viewport_window) = self.viewports.get_mut(&viewport_id) {
+                    viewport_window.viewport = viewport;
+                } else {
+                    let window =
+                        epi_integration::viewport_window_builder(native_options, &viewport.builder)
+                            .build(event_loop)?;
+                    pollster::block_on(self.painter.set_window_for(window.id(), Some(&window)))?;
+                    let viewport_window = epi_integration::ViewportWindow::new(
+                        event_loop,
+                        viewport_id,
+                        viewport,
+                        window,
+                        self.painter.max_texture_side().unwrap_or(2048),
+                    );
+                    self.viewports.insert(viewport_id, viewport_window);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Run the ui of all viewports other than the root, and paint them.
+        ///
+        /// Returns when the viewports want to be repainted.
+        fn paint_viewports(&mut self) -> std::time::Duration {
+            let mut repaint_after = std::time::Duration::MAX;
+
+            for viewport_window in self.viewports.values_mut() {
+                crate::profile_scope!("viewport");
+
+                let full_output = self.integration.update_viewport(viewport_window);
+                repaint_after = repaint_after.min(full_output.repaint_after);
+
+                let clipped_primitives = {
+                    crate::profile_scope!("tessellate");
+                    self.integration.egui_ctx.tessellate(full_output.shapes)
+                };
+
+                self.painter.paint_and_update_textures_for(
+                    viewport_window.window.id(),
+                    self.integration.egui_ctx.pixels_per_point(),
+                    self.app
+                        .clear_color(&self.integration.egui_ctx.style().visuals),
+                    &clipped_primitives,
+                    &full_output.textures_delta,
+                    false,
+                );
+            }
+
+            repaint_after
+        }
+
+        fn on_viewport_event(
+            &mut self,
+            window_id: winit::window::WindowId,
+            event: &winit::event::WindowEvent<'_>,
+        ) -> EventResult {
+            let viewport_window = if let Some(viewport_window) = self
+                .viewports
+                .values_mut()
+                .find(|viewport_window| viewport_window.window.id() == window_id)
+            {
+                viewport_window
+            } else {
+                return EventResult::Wait; // A window that was just closed
+            };
+
+            match event {
+                winit::event::WindowEvent::Resized(physical_size) => {
+                    // Resize with 0 width and height is used by winit to signal a minimize event on Windows.
+                    if physical_size.width > 0 && physical_size.height > 0 {
+                        self.painter.on_window_resized_for(
+                            window_id,
+                            physical_size.width,
+                            physical_size.height,
+                        );
+                    }
+                }
+                winit::event::WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                    self.painter.on_window_resized_for(
+                        window_id,
+                        new_inner_size.width,
+                        new_inner_size.height,
+                    );
+                }
+                _ => {}
+            }
+
+            let event_response = viewport_window
+                .egui_winit
+                .on_event(&self.integration.egui_ctx, event);
+
+            if event_response.repaint {
+                // All viewports are painted together with the root viewport.
+                EventResult::RepaintNext
+            } else {
+                EventResult::Wait
+            }
+        }
     }
 
     struct WgpuWinitApp {
@@ -1125,19 +1515,18 @@ mod wgpu_integration {
             &mut self,
             window: winit::window::Window,
         ) -> std::result::Result<(), egui_wgpu::WgpuError> {
-            self.window = Some(window);
             if let Some(running) = &mut self.running {
-                pollster::block_on(running.painter.set_window(self.window.as_ref()))?;
+                pollster::block_on(running.painter.set_window_for(window.id(), Some(&window)))?;
             }
+            self.window = Some(window);
             Ok(())
         }
 
         #[allow(unsafe_code)]
         #[cfg(target_os = "android")]
         fn drop_window(&mut self) -> std::result::Result<(), egui_wgpu::WgpuError> {
-            self.window = None;
-            if let Some(running) = &mut self.running {
-                pollster::block_on(running.painter.set_window(None))?;
+            if let (Some(running), Some(window)) = (&mut self.running, self.window.take()) {
+                pollster::block_on(running.painter.set_window_for(window.id(), None))?;
             }
             Ok(())
         }
@@ -1158,7 +1547,7 @@ mod wgpu_integration {
                 ),
                 self.native_options.transparent,
             );
-            pollster::block_on(painter.set_window(Some(&window)))?;
+            pollster::block_on(painter.set_window_for(window.id(), Some(&window)))?;
 
             let wgpu_render_state = painter.render_state();
 
@@ -1219,6 +1608,7 @@ mod wgpu_integration {
                 painter,
                 integration,
                 app,
+                viewports: Default::default(),
             });
             self.window = Some(window);
 
@@ -1245,6 +1635,15 @@ mod wgpu_integration {
             self.window.as_ref()
         }
 
+        fn has_window(&self, window_id: winit::window::WindowId) -> bool {
+            self.window.as_ref().map(|window| window.id()) == Some(window_id)
+                || self.running.as_ref().map_or(false, |r| {
+                    r.viewports
+                        .values()
+                        .any(|viewport_window| viewport_window.window.id() == window_id)
+                })
+        }
+
         fn save_and_destroy(&mut self) {
             if let Some(mut running) = self.running.take() {
                 if let Some(window) = &self.window {
@@ -1257,50 +1656,75 @@ mod wgpu_integration {
                 #[cfg(not(feature = "glow"))]
                 running.app.on_exit();
 
+                running.viewports.clear();
                 running.painter.destroy();
             }
         }
 
-        fn run_ui_and_paint(&mut self) -> EventResult {
+        fn run_ui_and_paint(
+            &mut self,
+            event_loop: &EventLoopWindowTarget<UserEvent>,
+        ) -> EventResult {
             if let (Some(running), Some(window)) = (&mut self.running, &self.window) {
                 #[cfg(feature = "puffin")]
                 puffin::GlobalProfiler::lock().new_frame();
                 crate::profile_scope!("frame");
 
-                let WgpuWinitRunning {
-                    app,
-                    integration,
-                    painter,
-                } = running;
-
-                let egui::FullOutput {
-                    platform_output,
-                    repaint_after,
-                    textures_delta,
-                    shapes,
-                } = integration.update(app.as_mut(), window);
+                let (repaint_after, viewports) = {
+                    let WgpuWinitRunning {
+                        app,
+                        integration,
+                        painter,
+                        ..
+                    } = running;
+
+                    let egui::FullOutput {
+                        platform_output,
+                        repaint_after,
+                        textures_delta,
+                        shapes,
+                        viewports,
+                    } = integration.update(app.as_mut(), window);
+
+                    integration.handle_platform_output(window, platform_output);
+
+                    let clipped_primitives = {
+                        crate::profile_scope!("tessellate");
+                        integration.egui_ctx.tessellate(shapes)
+                    };
+
+                    let screenshot_requested = &mut integration.frame.output.screenshot_requested;
+
+                    let screenshot = painter.paint_and_update_textures_for(
+                        window.id(),
+                        integration.egui_ctx.pixels_per_point(),
+                        app.clear_color(&integration.egui_ctx.style().visuals),
+                        &clipped_primitives,
+                        &textures_delta,
+                        *screenshot_requested,
+                    );
+                    *screenshot_requested = false;
+                    integration.frame.screenshot.set(screenshot);
 
-                integration.handle_platform_output(window, platform_output);
+                    integration.post_rendering(app.as_mut(), window);
+                    integration.post_present(window);
 
-                let clipped_primitives = {
-                    crate::profile_scope!("tessellate");
-                    integration.egui_ctx.tessellate(shapes)
+                    (repaint_after, viewports)
                 };
 
-                let screenshot_requested = &mut integration.frame.output.screenshot_requested;
-
-                let screenshot = painter.paint_and_update_textures(
-                    integration.egui_ctx.pixels_per_point(),
-                    app.clear_color(&integration.egui_ctx.style().visuals),
-                    &clipped_primitives,
-                    &textures_delta,
-                    *screenshot_requested,
-                );
-                *screenshot_requested = false;
-                integration.frame.screenshot.set(screenshot);
+                // Extra native windows:
+                let repaint_after =
+                    match running.update_viewports(event_loop, &self.native_options, viewports) {
+                        Ok(()) => repaint_after.min(running.paint_viewports()),
+                        Err(err) => {
+                            log::error!("Failed to open viewport window: {err}");
+                            repaint_after
+                        }
+                    };
 
-                integration.post_rendering(app.as_mut(), window);
-                integration.post_present(window);
+                let WgpuWinitRunning {
+                    app, integration, ..
+                } = running;
 
                 let control_flow = if integration.should_close() {
                     EventResult::Exit
@@ -1377,7 +1801,17 @@ mod wgpu_integration {
                     EventResult::Wait
                 }
 
-                winit::event::Event::WindowEvent { event, .. } => {
+                winit::event::Event::WindowEvent { window_id, event }
+                    if self.window.as_ref().map(|window| window.id()) != Some(*window_id) =>
+                {
+                    if let Some(running) = &mut self.running {
+                        running.on_viewport_event(*window_id, event)
+                    } else {
+                        EventResult::Wait
+                    }
+                }
+
+                winit::event::Event::WindowEvent { window_id, event } => {
                     if let Some(running) = &mut self.running {
                         // On Windows, if a window is resized by the user, it should repaint synchronously, inside the
                         // event handler.
@@ -1405,7 +1839,8 @@ mod wgpu_integration {
                                 // See: https://github.com/rust-windowing/winit/issues/208
                                 // This solves an issue where the app would panic when minimizing on Windows.
                                 if physical_size.width > 0 && physical_size.height > 0 {
-                                    running.painter.on_window_resized(
+                                    running.painter.on_window_resized_for(
+                                        *window_id,
                                         physical_size.width,
                                         physical_size.height,
                                     );
@@ -1416,9 +1851,11 @@ mod wgpu_integration {
                                 ..
                             } => {
                                 repaint_asap = true;
-                                running
-                                    .painter
-                                    .on_window_resized(new_inner_size.width, new_inner_size.height);
+                                running.painter.on_window_resized_for(
+                                    *window_id,
+                                    new_inner_size.width,
+                                    new_inner_size.height,
+                                );
                             }
                             winit::event::WindowEvent::CloseRequested
                                 if running.integration.should_close() =>
@@ -186,6 +186,7 @@ impl AppRunner {
             repaint_after,
             textures_delta,
             shapes,
+            viewports: _, // Viewports are embedded as windows, so this is always empty.
         } = full_output;
 
         self.handle_platform_output(platform_output);
@@ -89,3 +89,23 @@ fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
     let out_color_gamma = in.color * tex_gamma;
     return out_color_gamma;
 }
+
+// Opacity from a signed distance field, with the edge at 0.5, smoothed over about one physical pixel.
+fn sdf_coverage(in: VertexOutput) -> f32 {
+    // The alpha channel is linear, also in sRGB textures.
+    let distance = textureSample(r_tex_color, r_tex_sampler, in.tex_coord).a;
+    // smoothstep is undefined for an empty edge, e.g. where the distance is constant:
+    let half_width = max(0.5 * fwidth(distance), 1e-4);
+    return smoothstep(0.5 - half_width, 0.5 + half_width, distance);
+}
+
+@fragment
+fn fs_main_linear_framebuffer_sdf(in: VertexOutput) -> @location(0) vec4<f32> {
+    let out_color_gamma = in.color * sdf_coverage(in);
+    return vec4<f32>(linear_from_gamma_rgb(out_color_gamma.rgb), out_color_gamma.a);
+}
+
+@fragment
+fn fs_main_gamma_framebuffer_sdf(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color * sdf_coverage(in);
+}
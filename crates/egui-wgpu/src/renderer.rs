@@ -2,7 +2,10 @@
 
 use std::num::NonZeroU64;
 use std::ops::Range;
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 
 use type_map::concurrent::TypeMap;
 use wgpu;
@@ -149,6 +152,9 @@ struct SlicedBuffer {
 pub struct Renderer {
     pipeline: wgpu::RenderPipeline,
 
+    /// Used for font textures containing signed distance fields, see [`epaint::FontImage::sdf`].
+    sdf_pipeline: wgpu::RenderPipeline,
+
     index_buffer: SlicedBuffer,
     vertex_buffer: SlicedBuffer,
 
@@ -164,6 +170,9 @@ pub struct Renderer {
     next_user_texture_id: u64,
     samplers: HashMap<epaint::textures::TextureOptions, wgpu::Sampler>,
 
+    /// Font textures containing signed distance fields, drawn with [`Self::sdf_pipeline`].
+    sdf_textures: HashSet<epaint::TextureId>,
+
     /// Storage for use by [`epaint::PaintCallback`]'s that need to store resources such as render
     /// pipelines that must have the lifetime of the renderpass.
     pub paint_callback_resources: TypeMap,
@@ -262,8 +271,16 @@ impl Renderer {
             bias: wgpu::DepthBiasState::default(),
         });
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("egui_pipeline"),
+        let fs_entry_point = if output_color_format.is_srgb() {
+            log::warn!("Detected a linear (sRGBA aware) framebuffer {:?}. egui prefers Rgba8Unorm or Bgra8Unorm", output_color_format);
+            "fs_main_linear_framebuffer"
+        } else {
+            "fs_main_gamma_framebuffer" // this is what we prefer
+        };
+
+        let create_pipeline = |label: &str, fs_entry_point: &str| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
             layout: Some(&pipeline_layout),
             vertex: wgpu::VertexState {
                 entry_point: "vs_main",
@@ -286,7 +303,7 @@ impl Renderer {
                 polygon_mode: wgpu::PolygonMode::default(),
                 strip_index_format: None,
             },
-            depth_stencil,
+            depth_stencil: depth_stencil.clone(),
             multisample: wgpu::MultisampleState {
                 alpha_to_coverage_enabled: false,
                 count: msaa_samples,
@@ -295,12 +312,7 @@ impl Renderer {
 
             fragment: Some(wgpu::FragmentState {
                 module: &module,
-                entry_point: if output_color_format.is_srgb() {
-                    log::warn!("Detected a linear (sRGBA aware) framebuffer {:?}. egui prefers Rgba8Unorm or Bgra8Unorm", output_color_format);
-                    "fs_main_linear_framebuffer"
-                } else {
-                    "fs_main_gamma_framebuffer" // this is what we prefer
-                },
+                entry_point: fs_entry_point,
                 targets: &[Some(wgpu::ColorTargetState {
                     format: output_color_format,
                     blend: Some(wgpu::BlendState {
@@ -319,7 +331,10 @@ impl Renderer {
                 })],
             }),
             multiview: None,
-        });
+        })
+        };
+        let pipeline = create_pipeline("egui_pipeline", fs_entry_point);
+        let sdf_pipeline = create_pipeline("egui_sdf_pipeline", &format!("{fs_entry_point}_sdf"));
 
         const VERTEX_BUFFER_START_CAPACITY: wgpu::BufferAddress =
             (std::mem::size_of::<Vertex>() * 1024) as _;
@@ -328,6 +343,7 @@ impl Renderer {
 
         Self {
             pipeline,
+            sdf_pipeline,
             vertex_buffer: SlicedBuffer {
                 buffer: create_vertex_buffer(device, VERTEX_BUFFER_START_CAPACITY),
                 slices: Vec::with_capacity(64),
@@ -349,6 +365,7 @@ impl Renderer {
             textures: HashMap::new(),
             next_user_texture_id: 0,
             samplers: HashMap::new(),
+            sdf_textures: HashSet::new(),
             paint_callback_resources: TypeMap::default(),
         }
     }
@@ -369,6 +386,9 @@ impl Renderer {
         // run.
         let mut needs_reset = true;
 
+        // Whether or not `sdf_pipeline` is the current pipeline.
+        let mut is_sdf_pipeline_set = false;
+
         let mut index_buffer_slices = self.index_buffer.slices.iter();
         let mut vertex_buffer_slices = self.vertex_buffer.slices.iter();
 
@@ -388,6 +408,7 @@ impl Renderer {
                 );
                 render_pass.set_pipeline(&self.pipeline);
                 render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                is_sdf_pipeline_set = false;
                 needs_reset = false;
             }
 
@@ -413,6 +434,15 @@ impl Renderer {
                     let vertex_buffer_slice = vertex_buffer_slices.next().unwrap();
 
                     if let Some((_texture, bind_group)) = self.textures.get(&mesh.texture_id) {
+                        let is_sdf = self.sdf_textures.contains(&mesh.texture_id);
+                        if is_sdf != is_sdf_pipeline_set {
+                            render_pass.set_pipeline(if is_sdf {
+                                &self.sdf_pipeline
+                            } else {
+                                &self.pipeline
+                            });
+                            is_sdf_pipeline_set = is_sdf;
+                        }
                         render_pass.set_bind_group(1, bind_group, &[]);
                         render_pass.set_index_buffer(
                             self.index_buffer.buffer.slice(
@@ -519,6 +549,13 @@ impl Renderer {
                     image.pixels.len(),
                     "Mismatch between texture size and texel count"
                 );
+                if image_delta.is_whole() {
+                    if image.sdf {
+                        self.sdf_textures.insert(id);
+                    } else {
+                        self.sdf_textures.remove(&id);
+                    }
+                }
                 Cow::Owned(image.srgba_pixels(None).collect::<Vec<_>>())
             }
         };
@@ -600,6 +637,7 @@ impl Renderer {
 
     pub fn free_texture(&mut self, id: &epaint::TextureId) {
         self.textures.remove(id);
+        self.sdf_textures.remove(id);
     }
 
     /// Get the WGPU texture and bind group associated to a texture that has been allocated by egui.
@@ -902,13 +940,20 @@ fn create_sampler(
         epaint::textures::TextureFilter::Nearest => wgpu::FilterMode::Nearest,
         epaint::textures::TextureFilter::Linear => wgpu::FilterMode::Linear,
     };
+    let address_mode = match options.wrap_mode {
+        epaint::textures::TextureWrapMode::ClampToEdge => wgpu::AddressMode::ClampToEdge,
+        epaint::textures::TextureWrapMode::Repeat => wgpu::AddressMode::Repeat,
+        epaint::textures::TextureWrapMode::MirroredRepeat => wgpu::AddressMode::MirrorRepeat,
+    };
     device.create_sampler(&wgpu::SamplerDescriptor {
         label: Some(&format!(
-            "egui sampler (mag: {:?}, min {:?})",
-            mag_filter, min_filter
+            "egui sampler (mag: {:?}, min {:?}, wrap: {:?})",
+            mag_filter, min_filter, address_mode
         )),
         mag_filter,
         min_filter,
+        address_mode_u: address_mode,
+        address_mode_v: address_mode,
         ..Default::default()
     })
 }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{renderer, RenderState, SurfaceErrorAction, WgpuConfiguration};
 
@@ -7,6 +7,8 @@ struct SurfaceState {
     alpha_mode: wgpu::CompositeAlphaMode,
     width: u32,
     height: u32,
+    depth_texture_view: Option<wgpu::TextureView>,
+    msaa_texture_view: Option<wgpu::TextureView>,
 }
 
 /// A texture and a buffer for reading the rendered frame back to the cpu.
@@ -70,19 +72,23 @@ impl BufferPadding {
 
 /// Everything you need to paint egui with [`wgpu`] on [`winit`].
 ///
+/// One [`Painter`] can paint to several windows, which then share the same [`RenderState`]
+/// (and so the same textures). Each window is identified by its [`winit::window::WindowId`].
+///
 /// Alternatively you can use [`crate::renderer`] directly.
 pub struct Painter {
     configuration: WgpuConfiguration,
     msaa_samples: u32,
     support_transparent_backbuffer: bool,
     depth_format: Option<wgpu::TextureFormat>,
-    depth_texture_view: Option<wgpu::TextureView>,
-    msaa_texture_view: Option<wgpu::TextureView>,
     screen_capture_state: Option<CaptureState>,
 
     instance: wgpu::Instance,
     render_state: Option<RenderState>,
-    surface_state: Option<SurfaceState>,
+    surfaces: HashMap<winit::window::WindowId, SurfaceState>,
+
+    /// The window given to [`Self::set_window`], for painters that only paint to one window.
+    main_window: Option<winit::window::WindowId>,
 }
 
 impl Painter {
@@ -114,13 +120,12 @@ impl Painter {
             msaa_samples,
             support_transparent_backbuffer,
             depth_format,
-            depth_texture_view: None,
             screen_capture_state: None,
 
             instance,
             render_state: None,
-            surface_state: None,
-            msaa_texture_view: None,
+            surfaces: Default::default(),
+            main_window: None,
         }
     }
 
@@ -150,13 +155,14 @@ impl Painter {
         );
     }
 
-    /// Updates (or clears) the [`winit::window::Window`] associated with the [`Painter`]
+    /// Updates (or clears) the [`winit::window::Window`] with the given id.
     ///
     /// This creates a [`wgpu::Surface`] for the given Window (as well as initializing render
     /// state if needed) that is used for egui rendering.
     ///
-    /// This must be called before trying to render via
-    /// [`paint_and_update_textures`](Self::paint_and_update_textures)
+    /// This must be called for each window before trying to render to it via
+    /// [`paint_and_update_textures_for`](Self::paint_and_update_textures_for).
+    /// Call it with `None` when a window is closed.
     ///
     /// # Portability
     ///
@@ -164,9 +170,9 @@ impl Painter {
     /// a window surface between `Resumed` and `Paused` lifecycle events, and Winit will panic on
     /// attempts to query the raw window handle while paused._
     ///
-    /// On Android [`set_window`](Self::set_window) should be called with `Some(window)` for each
+    /// On Android [`set_window_for`](Self::set_window_for) should be called with `Some(window)` for each
     /// `Resumed` event and `None` for each `Paused` event. Currently, on all other platforms
-    /// [`set_window`](Self::set_window) may be called with `Some(window)` as soon as you have a
+    /// [`set_window_for`](Self::set_window_for) may be called with `Some(window)` as soon as you have a
     /// valid [`winit::window::Window`].
     ///
     /// # Safety
@@ -177,8 +183,9 @@ impl Painter {
     ///
     /// # Errors
     /// If the provided wgpu configuration does not match an available device.
-    pub async fn set_window(
+    pub async fn set_window_for(
         &mut self,
+        window_id: winit::window::WindowId,
         window: Option<&winit::window::Window>,
     ) -> Result<(), crate::WgpuError> {
         match window {
@@ -219,21 +226,57 @@ impl Painter {
                 };
 
                 let size = window.inner_size();
-                self.surface_state = Some(SurfaceState {
-                    surface,
-                    width: size.width,
-                    height: size.height,
-                    alpha_mode,
-                });
-                self.resize_and_generate_depth_texture_view_and_msaa_view(size.width, size.height);
+                self.surfaces.insert(
+                    window_id,
+                    SurfaceState {
+                        surface,
+                        width: size.width,
+                        height: size.height,
+                        alpha_mode,
+                        depth_texture_view: None,
+                        msaa_texture_view: None,
+                    },
+                );
+                self.resize_and_generate_depth_texture_view_and_msaa_view(
+                    window_id,
+                    size.width,
+                    size.height,
+                );
             }
             None => {
-                self.surface_state = None;
+                self.surfaces.remove(&window_id);
             }
         }
         Ok(())
     }
 
+    /// Updates (or clears) the one window this painter paints to.
+    ///
+    /// This is [`Self::set_window_for`] for painters that only have one window,
+    /// which is then used by [`Self::on_window_resized`] and [`Self::paint_and_update_textures`].
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::set_window_for`].
+    ///
+    /// # Errors
+    /// If the provided wgpu configuration does not match an available device.
+    pub async fn set_window(
+        &mut self,
+        window: Option<&winit::window::Window>,
+    ) -> Result<(), crate::WgpuError> {
+        match window {
+            Some(window) => {
+                self.main_window = Some(window.id());
+                self.set_window_for(window.id(), Some(window)).await
+            }
+            None => match self.main_window.take() {
+                Some(window_id) => self.set_window_for(window_id, None).await,
+                None => Ok(()),
+            },
+        }
+    }
+
     /// Returns the maximum texture dimension supported if known
     ///
     /// This API will only return a known dimension after `set_window()` has been called
@@ -247,18 +290,19 @@ impl Painter {
 
     fn resize_and_generate_depth_texture_view_and_msaa_view(
         &mut self,
+        window_id: winit::window::WindowId,
         width_in_pixels: u32,
         height_in_pixels: u32,
     ) {
         let render_state = self.render_state.as_ref().unwrap();
-        let surface_state = self.surface_state.as_mut().unwrap();
+        let surface_state = self.surfaces.get_mut(&window_id).unwrap();
 
         surface_state.width = width_in_pixels;
         surface_state.height = height_in_pixels;
 
         Self::configure_surface(surface_state, render_state, self.configuration.present_mode);
 
-        self.depth_texture_view = self.depth_format.map(|depth_format| {
+        surface_state.depth_texture_view = self.depth_format.map(|depth_format| {
             render_state
                 .device
                 .create_texture(&wgpu::TextureDescriptor {
@@ -279,34 +323,40 @@ impl Painter {
                 .create_view(&wgpu::TextureViewDescriptor::default())
         });
 
-        self.msaa_texture_view = (self.msaa_samples > 1)
-            .then_some(self.render_state.as_ref())
-            .flatten()
-            .map(|render_state| {
-                let texture_format = render_state.target_format;
-                render_state
-                    .device
-                    .create_texture(&wgpu::TextureDescriptor {
-                        label: Some("egui_msaa_texture"),
-                        size: wgpu::Extent3d {
-                            width: width_in_pixels,
-                            height: height_in_pixels,
-                            depth_or_array_layers: 1,
-                        },
-                        mip_level_count: 1,
-                        sample_count: self.msaa_samples,
-                        dimension: wgpu::TextureDimension::D2,
-                        format: texture_format,
-                        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-                        view_formats: &[texture_format],
-                    })
-                    .create_view(&wgpu::TextureViewDescriptor::default())
-            });
+        surface_state.msaa_texture_view =
+            (self.msaa_samples > 1)
+                .then_some(render_state)
+                .map(|render_state| {
+                    let texture_format = render_state.target_format;
+                    render_state
+                        .device
+                        .create_texture(&wgpu::TextureDescriptor {
+                            label: Some("egui_msaa_texture"),
+                            size: wgpu::Extent3d {
+                                width: width_in_pixels,
+                                height: height_in_pixels,
+                                depth_or_array_layers: 1,
+                            },
+                            mip_level_count: 1,
+                            sample_count: self.msaa_samples,
+                            dimension: wgpu::TextureDimension::D2,
+                            format: texture_format,
+                            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                            view_formats: &[texture_format],
+                        })
+                        .create_view(&wgpu::TextureViewDescriptor::default())
+                });
     }
 
-    pub fn on_window_resized(&mut self, width_in_pixels: u32, height_in_pixels: u32) {
-        if self.surface_state.is_some() {
+    pub fn on_window_resized_for(
+        &mut self,
+        window_id: winit::window::WindowId,
+        width_in_pixels: u32,
+        height_in_pixels: u32,
+    ) {
+        if self.surfaces.contains_key(&window_id) {
             self.resize_and_generate_depth_texture_view_and_msaa_view(
+                window_id,
                 width_in_pixels,
                 height_in_pixels,
             );
@@ -315,6 +365,15 @@ impl Painter {
         }
     }
 
+    /// Resize the window given to [`Self::set_window`].
+    pub fn on_window_resized(&mut self, width_in_pixels: u32, height_in_pixels: u32) {
+        if let Some(window_id) = self.main_window {
+            self.on_window_resized_for(window_id, width_in_pixels, height_in_pixels);
+        } else {
+            log::warn!("Ignoring window resize notification with no surface created via Painter::set_window()");
+        }
+    }
+
     // CaptureState only needs to be updated when the size of the two textures don't match and we want to
     // capture a frame
     fn update_capture_state(
@@ -414,7 +473,9 @@ impl Painter {
         })
     }
 
-    // Returns a vector with the frame's pixel data if it was requested.
+    /// Paint to the window given to [`Self::set_window`].
+    ///
+    /// Returns a vector with the frame's pixel data if it was requested.
     pub fn paint_and_update_textures(
         &mut self,
         pixels_per_point: f32,
@@ -422,11 +483,34 @@ impl Painter {
         clipped_primitives: &[epaint::ClippedPrimitive],
         textures_delta: &epaint::textures::TexturesDelta,
         capture: bool,
+    ) -> Option<epaint::ColorImage> {
+        let window_id = self.main_window?;
+        self.paint_and_update_textures_for(
+            window_id,
+            pixels_per_point,
+            clear_color,
+            clipped_primitives,
+            textures_delta,
+            capture,
+        )
+    }
+
+    /// Paint to the window with the given id.
+    ///
+    /// Returns a vector with the frame's pixel data if it was requested.
+    pub fn paint_and_update_textures_for(
+        &mut self,
+        window_id: winit::window::WindowId,
+        pixels_per_point: f32,
+        clear_color: [f32; 4],
+        clipped_primitives: &[epaint::ClippedPrimitive],
+        textures_delta: &epaint::textures::TexturesDelta,
+        capture: bool,
     ) -> Option<epaint::ColorImage> {
         crate::profile_function!();
 
         let render_state = self.render_state.as_mut()?;
-        let surface_state = self.surface_state.as_ref()?;
+        let surface_state = self.surfaces.get(&window_id)?;
 
         let output_frame = {
             crate::profile_scope!("get_current_texture");
@@ -504,7 +588,7 @@ impl Painter {
             };
 
             let (view, resolve_target) = (self.msaa_samples > 1)
-                .then_some(self.msaa_texture_view.as_ref())
+                .then_some(surface_state.msaa_texture_view.as_ref())
                 .flatten()
                 .map_or((&frame_view, None), |texture_view| {
                     (texture_view, Some(&frame_view))
@@ -524,7 +608,7 @@ impl Painter {
                         store: true,
                     },
                 })],
-                depth_stencil_attachment: self.depth_texture_view.as_ref().map(|view| {
+                depth_stencil_attachment: surface_state.depth_texture_view.as_ref().map(|view| {
                     wgpu::RenderPassDepthStencilAttachment {
                         view,
                         depth_ops: Some(wgpu::Operations {
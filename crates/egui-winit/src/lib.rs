@@ -134,6 +134,20 @@ impl State {
         self.egui_input.max_texture_side = Some(max_texture_side);
     }
 
+    /// Call this if the window of this [`State`] is not the root viewport,
+    /// so that [`Self::take_egui_input`] sets [`egui::RawInput::viewport_id`].
+    ///
+    /// See [`egui::Context::show_viewport`].
+    pub fn set_viewport_id(&mut self, viewport_id: egui::ViewportId) {
+        self.egui_input.viewport_id = viewport_id;
+    }
+
+    /// Which viewport this [`State`] produces input for.
+    #[inline]
+    pub fn viewport_id(&self) -> egui::ViewportId {
+        self.egui_input.viewport_id
+    }
+
     /// Call this when a new native Window is created for rendering to initialize the `pixels_per_point`
     /// for that window.
     ///
@@ -371,9 +385,16 @@ impl State {
                 }
             }
 
+            WindowEvent::CloseRequested => {
+                self.egui_input.viewport_close_requested = true;
+                EventResponse {
+                    repaint: true,
+                    consumed: false,
+                }
+            }
+
             // Things that may require repaint:
-            WindowEvent::CloseRequested
-            | WindowEvent::CursorEntered { .. }
+            WindowEvent::CursorEntered { .. }
             | WindowEvent::Destroyed
             | WindowEvent::Occluded(_)
             | WindowEvent::Resized(_)
@@ -27,12 +27,23 @@ pub struct Frame {
 
     pub shadow: Shadow,
 
-    pub fill: Color32,
+    /// More shadows, painted on top of [`Self::shadow`].
+    ///
+    /// Inset shadows are painted on top of the fill, but below the stroke.
+    /// Unused slots are [`Shadow::NONE`].
+    /// There is a fixed number of slots so that [`Frame`] stays [`Copy`], see [`Self::add_shadow`].
+    pub extra_shadows: [Shadow; Frame::MAX_EXTRA_SHADOWS],
+
+    /// A color or a [`Gradient`].
+    pub fill: Fill,
 
     pub stroke: Stroke,
 }
 
 impl Frame {
+    /// How many shadows a frame can have besides [`Self::shadow`].
+    pub const MAX_EXTRA_SHADOWS: usize = 3;
+
     pub fn none() -> Self {
         Self::default()
     }
@@ -50,7 +61,7 @@ impl Frame {
     pub fn side_top_panel(style: &Style) -> Self {
         Self {
             inner_margin: Margin::symmetric(8.0, 2.0),
-            fill: style.visuals.panel_fill,
+            fill: style.visuals.panel_fill.into(),
             ..Default::default()
         }
     }
@@ -58,7 +69,7 @@ impl Frame {
     pub fn central_panel(style: &Style) -> Self {
         Self {
             inner_margin: Margin::same(8.0),
-            fill: style.visuals.panel_fill,
+            fill: style.visuals.panel_fill.into(),
             ..Default::default()
         }
     }
@@ -68,7 +79,7 @@ impl Frame {
             inner_margin: style.spacing.window_margin,
             rounding: style.visuals.window_rounding,
             shadow: style.visuals.window_shadow,
-            fill: style.visuals.window_fill(),
+            fill: style.visuals.window_fill().into(),
             stroke: style.visuals.window_stroke(),
             ..Default::default()
         }
@@ -79,7 +90,7 @@ impl Frame {
             inner_margin: style.spacing.menu_margin,
             rounding: style.visuals.menu_rounding,
             shadow: style.visuals.popup_shadow,
-            fill: style.visuals.window_fill(),
+            fill: style.visuals.window_fill().into(),
             stroke: style.visuals.window_stroke(),
             ..Default::default()
         }
@@ -90,7 +101,7 @@ impl Frame {
             inner_margin: style.spacing.menu_margin,
             rounding: style.visuals.menu_rounding,
             shadow: style.visuals.popup_shadow,
-            fill: style.visuals.window_fill(),
+            fill: style.visuals.window_fill().into(),
             stroke: style.visuals.window_stroke(),
             ..Default::default()
         }
@@ -104,7 +115,7 @@ impl Frame {
         Self {
             inner_margin: Margin::same(2.0),
             rounding: style.visuals.widgets.noninteractive.rounding,
-            fill: style.visuals.extreme_bg_color,
+            fill: style.visuals.extreme_bg_color.into(),
             stroke: style.visuals.window_stroke(),
             ..Default::default()
         }
@@ -113,16 +124,30 @@ impl Frame {
     /// A dark canvas to draw on.
     pub fn dark_canvas(style: &Style) -> Self {
         Self {
-            fill: Color32::from_black_alpha(250),
+            fill: Color32::from_black_alpha(250).into(),
             ..Self::canvas(style)
         }
     }
 }
 
 impl Frame {
+    /// Fill the frame with a single color or a [`Gradient`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// egui::Frame::none()
+    ///     .fill(egui::epaint::Gradient::vertical(
+    ///         egui::Color32::from_gray(70),
+    ///         egui::Color32::from_gray(50),
+    ///     ))
+    ///     .show(ui, |ui| {
+    ///         ui.label("Header");
+    ///     });
+    /// # });
+    /// ```
     #[inline]
-    pub fn fill(mut self, fill: Color32) -> Self {
-        self.fill = fill;
+    pub fn fill(mut self, fill: impl Into<Fill>) -> Self {
+        self.fill = fill.into();
         self
     }
 
@@ -164,10 +189,34 @@ impl Frame {
         self
     }
 
+    /// Add another shadow, on top of the previous ones.
+    ///
+    /// A frame has room for at most [`Self::MAX_EXTRA_SHADOWS`] shadows besides [`Self::shadow`].
+    /// This is a hard limit: once the frame is full, the frame is returned unchanged
+    /// and a warning is logged (if the `log` feature is enabled).
+    /// Empty shadows ([`Shadow::is_empty`]) don't take up room.
+    #[inline]
+    pub fn add_shadow(mut self, shadow: Shadow) -> Self {
+        if let Some(slot) = self.extra_shadows.iter_mut().find(|slot| slot.is_empty()) {
+            *slot = shadow;
+        } else {
+            #[cfg(feature = "log")]
+            log::warn!(
+                "egui: A Frame can have at most {} extra shadows, ignoring {:?}",
+                Self::MAX_EXTRA_SHADOWS,
+                shadow
+            );
+        }
+        self
+    }
+
     pub fn multiply_with_opacity(mut self, opacity: f32) -> Self {
         self.fill = self.fill.linear_multiply(opacity);
         self.stroke.color = self.stroke.color.linear_multiply(opacity);
         self.shadow.color = self.shadow.color.linear_multiply(opacity);
+        for shadow in &mut self.extra_shadows {
+            shadow.color = shadow.color.linear_multiply(opacity);
+        }
         self
     }
 }
@@ -233,23 +282,44 @@ impl Frame {
             outer_margin: _,
             rounding,
             shadow,
+            extra_shadows,
             fill,
             stroke,
         } = *self;
 
-        let frame_shape = Shape::Rect(epaint::RectShape {
-            rect: outer_rect,
-            rounding,
-            fill,
-            stroke,
-        });
+        let shadows = std::iter::once(shadow)
+            .chain(extra_shadows)
+            .filter(|shadow| !shadow.is_empty());
+        let has_inset_shadows = shadows.clone().any(|shadow| shadow.inset);
+
+        // Keep the border within the rect we were given:
+        let mut frame_shape = epaint::RectShape::new(outer_rect, rounding, fill, stroke)
+            .with_stroke_alignment(StrokeAlignment::Inside);
+
+        let mut shapes: Vec<Shape> = shadows
+            .clone()
+            .filter(|shadow| !shadow.inset)
+            .map(|shadow| Shape::Mesh(shadow.tessellate(outer_rect, rounding)))
+            .collect();
+
+        if has_inset_shadows {
+            // Fill, then the inset shadows, then the stroke:
+            let stroke = std::mem::take(&mut frame_shape.stroke);
+            shapes.push(frame_shape.into());
+            shapes.extend(
+                shadows
+                    .filter(|shadow| shadow.inset)
+                    .map(|shadow| Shape::Mesh(shadow.tessellate(outer_rect, rounding))),
+            );
+            frame_shape.fill = Fill::TRANSPARENT;
+            frame_shape.stroke = stroke;
+        }
 
-        if shadow == Default::default() {
-            frame_shape
+        if shapes.is_empty() {
+            frame_shape.into()
         } else {
-            let shadow = shadow.tessellate(outer_rect, rounding);
-            let shadow = Shape::Mesh(shadow);
-            Shape::Vec(vec![shadow, frame_shape])
+            shapes.push(frame_shape.into());
+            Shape::Vec(shapes)
         }
     }
 }
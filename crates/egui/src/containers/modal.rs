@@ -0,0 +1,160 @@
+//! A [`Modal`] is a dialog that blocks all interaction with the rest of the ui while it is shown.
+
+use crate::*;
+
+/// A modal dialog, shown in the middle of the screen above a dimmed backdrop.
+///
+/// While a modal is shown, nothing below it can be clicked, hovered or focused.
+/// Only the top-most modal gets input, so you can stack several modals on top of each other.
+/// Popups and tooltips opened from inside the modal work as usual.
+///
+/// The modal is shown for as long as you call [`Self::show`].
+/// It doesn't close itself, but tells you when the user wants it closed, using [`ModalResponse::should_close`].
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// # let mut show_dialog = true;
+/// if show_dialog {
+///     let modal = egui::Modal::new(egui::Id::new("confirm_dialog"))
+///         .close_on_backdrop_click(true)
+///         .show(ctx, |ui| {
+///             ui.label("Are you sure?");
+///             ui.button("Yes").clicked()
+///         });
+///     if modal.inner || modal.should_close {
+///         show_dialog = false;
+///     }
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Debug)]
+pub struct Modal {
+    id: Id,
+    frame: Option<Frame>,
+    backdrop_color: Color32,
+    close_on_escape: bool,
+    close_on_backdrop_click: bool,
+}
+
+impl Modal {
+    /// The id must be unique, and should not change.
+    pub fn new(id: Id) -> Self {
+        Self {
+            id,
+            frame: None,
+            backdrop_color: Color32::from_black_alpha(100),
+            close_on_escape: true,
+            close_on_backdrop_click: false,
+        }
+    }
+
+    /// Change the background of the dialog. Default: [`Frame::window`].
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// The color painted over everything below the modal.
+    ///
+    /// Use [`Color32::TRANSPARENT`] to not dim the rest of the ui.
+    pub fn backdrop_color(mut self, backdrop_color: Color32) -> Self {
+        self.backdrop_color = backdrop_color;
+        self
+    }
+
+    /// Should pressing Escape set [`ModalResponse::should_close`]? Default: `true`.
+    pub fn close_on_escape(mut self, close_on_escape: bool) -> Self {
+        self.close_on_escape = close_on_escape;
+        self
+    }
+
+    /// Should clicking outside of the dialog set [`ModalResponse::should_close`]? Default: `false`.
+    pub fn close_on_backdrop_click(mut self, close_on_backdrop_click: bool) -> Self {
+        self.close_on_backdrop_click = close_on_backdrop_click;
+        self
+    }
+
+    /// The layer of the backdrop. Everything below this layer is blocked.
+    pub fn backdrop_layer_id(&self) -> LayerId {
+        LayerId::new(Order::Modal, self.id.with("backdrop"))
+    }
+
+    /// The layer of the dialog.
+    pub fn layer_id(&self) -> LayerId {
+        LayerId::new(Order::Modal, self.id)
+    }
+
+    pub fn show<R>(
+        self,
+        ctx: &Context,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> ModalResponse<R> {
+        let backdrop_layer_id = self.backdrop_layer_id();
+        let layer_id = self.layer_id();
+        let Self {
+            id,
+            frame,
+            backdrop_color,
+            close_on_escape,
+            close_on_backdrop_click,
+        } = self;
+
+        ctx.memory_mut(|mem| mem.set_modal_layer(backdrop_layer_id));
+
+        // The backdrop covers the whole screen, so nothing below it can be hovered:
+        let screen_rect = ctx.screen_rect();
+        let backdrop_response = Area::new(backdrop_layer_id.id)
+            .order(Order::Modal)
+            .fixed_pos(screen_rect.min)
+            .show(ctx, |ui| {
+                ui.painter().rect_filled(screen_rect, 0.0, backdrop_color);
+                // Only hover, so the backdrop can't get keyboard focus:
+                ui.allocate_rect(screen_rect, Sense::hover())
+            })
+            .inner;
+
+        let frame = frame.unwrap_or_else(|| Frame::window(&ctx.style()));
+        let InnerResponse { inner, response } = Area::new(id)
+            .order(Order::Modal)
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| frame.show(ui, add_contents).inner);
+
+        // Keep the dialog above its backdrop, and both above any modal shown before them.
+        // Layers that want to be on top keep their relative order.
+        ctx.move_to_top(backdrop_layer_id);
+        ctx.move_to_top(layer_id);
+
+        let is_top_modal = ctx.memory(|mem| mem.allows_interaction(backdrop_layer_id));
+        let should_close = is_top_modal
+            && ((close_on_escape
+                && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)))
+                || (close_on_backdrop_click
+                    && backdrop_response.hovered()
+                    && ctx.input(|i| i.pointer.primary_clicked())));
+
+        ModalResponse {
+            inner,
+            response,
+            backdrop_response,
+            should_close,
+        }
+    }
+}
+
+/// Returned by [`Modal::show`].
+pub struct ModalResponse<R> {
+    /// What the user closure returned.
+    pub inner: R,
+
+    /// The response of the dialog.
+    pub response: Response,
+
+    /// The response of the backdrop, i.e. everything outside of the dialog.
+    pub backdrop_response: Response,
+
+    /// The user pressed Escape or clicked the backdrop (if configured).
+    ///
+    /// Stop calling [`Modal::show`] to close the modal.
+    pub should_close: bool,
+}
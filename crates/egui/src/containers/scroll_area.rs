@@ -475,12 +475,12 @@ impl ScrollArea {
             let content_response = ui.interact(inner_rect, id.with("area"), Sense::drag());
 
             if content_response.dragged() {
+                let velocity = ui.input(|input| input.pointer.velocity());
+                let velocity = ui.ctx().screen_to_layer_vec(ui.layer_id(), velocity);
                 for d in 0..2 {
                     if has_bar[d] {
-                        ui.input(|input| {
-                            state.offset[d] -= input.pointer.delta()[d];
-                            state.vel[d] = input.pointer.velocity()[d];
-                        });
+                        state.offset[d] -= content_response.drag_delta()[d];
+                        state.vel[d] = velocity[d];
                         state.scroll_stuck_to_end[d] = false;
                     } else {
                         state.vel[d] = 0.0;
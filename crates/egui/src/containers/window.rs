@@ -574,7 +574,9 @@ fn move_and_resize_window(ctx: &Context, window_interaction: &WindowInteraction)
         return None;
     }
 
+    let layer_id = window_interaction.area_layer_id;
     let pointer_pos = ctx.input(|i| i.pointer.interact_pos())?;
+    let pointer_pos = ctx.screen_to_layer_pos(layer_id, pointer_pos);
     let mut rect = window_interaction.start_rect; // prevent drift
 
     if window_interaction.is_resize() {
@@ -598,7 +600,7 @@ fn move_and_resize_window(ctx: &Context, window_interaction: &WindowInteraction)
         // but instead let other widgets to the steal. HACK.
         if !ctx.input(|i| i.pointer.any_pressed()) {
             let press_origin = ctx.input(|i| i.pointer.press_origin())?;
-            let delta = pointer_pos - press_origin;
+            let delta = pointer_pos - ctx.screen_to_layer_pos(layer_id, press_origin);
             rect = rect.translate(delta);
         }
     }
@@ -667,6 +669,9 @@ fn resize_hover(
         }
     }
 
+    // `rect` is in the coordinates of the layer:
+    let pointer = ctx.screen_to_layer_pos(area_layer_id, pointer);
+
     if ctx.memory(|mem| mem.interaction.drag_interest) {
         // Another widget will become active if we drag here
         return None;
@@ -47,10 +47,10 @@ impl Default for WrappedTextureManager {
 
 /// Logic related to repainting the ui.
 struct Repaint {
-    /// The current frame number.
+    /// The current frame number of each viewport.
     ///
-    /// Incremented at the end of each frame.
-    frame_nr: u64,
+    /// Incremented at the end of each frame of that viewport.
+    frame_nrs: ViewportIdMap<u64>,
 
     /// The duration backend will poll for new events, before forcing another egui update
     /// even if there's no new events.
@@ -68,7 +68,7 @@ struct Repaint {
 impl Default for Repaint {
     fn default() -> Self {
         Self {
-            frame_nr: 0,
+            frame_nrs: Default::default(),
             repaint_after: std::time::Duration::from_millis(100),
             // Start with painting an extra frame to compensate for some widgets
             // that take two frames before they "settle":
@@ -80,6 +80,10 @@ impl Default for Repaint {
 }
 
 impl Repaint {
+    fn frame_nr(&self, viewport_id: ViewportId) -> u64 {
+        self.frame_nrs.get(&viewport_id).copied().unwrap_or(0)
+    }
+
     fn request_repaint(&mut self) {
         self.request_repaint_after(std::time::Duration::ZERO);
     }
@@ -99,7 +103,7 @@ impl Repaint {
             if let Some(callback) = &self.request_repaint_callback {
                 let info = RequestRepaintInfo {
                     after,
-                    current_frame_nr: self.frame_nr,
+                    current_frame_nr: self.frame_nr(ViewportId::ROOT),
                 };
                 (callback)(info);
             }
@@ -112,7 +116,7 @@ impl Repaint {
     }
 
     // returns how long to wait until repaint
-    fn end_frame(&mut self) -> std::time::Duration {
+    fn end_frame(&mut self, viewport_id: ViewportId) -> std::time::Duration {
         // if repaint_requests is greater than zero. just set the duration to zero for immediate
         // repaint. if there's no repaint requests, then we can use the actual repaint_after instead.
         let repaint_after = if self.repaint_requests > 0 {
@@ -124,7 +128,7 @@ impl Repaint {
         self.repaint_after = std::time::Duration::MAX;
 
         self.requested_repaint_last_frame = repaint_after.is_zero();
-        self.frame_nr += 1;
+        *self.frame_nrs.entry(viewport_id).or_default() += 1;
 
         repaint_after
     }
@@ -132,6 +136,49 @@ impl Repaint {
 
 // ----------------------------------------------------------------------------
 
+/// The parts of [`ContextImpl`] that each viewport has its own copy of.
+///
+/// The state of the viewport that is currently running lives directly in [`ContextImpl`],
+/// and is swapped with the stored one at the start of a frame for another viewport.
+#[derive(Default)]
+struct ViewportState {
+    input: InputState,
+    frame_state: FrameState,
+    graphics: GraphicLayers,
+    output: PlatformOutput,
+    layer_rects_this_frame: ahash::HashMap<LayerId, Vec<(Id, Rect)>>,
+    layer_rects_prev_frame: ahash::HashMap<LayerId, Vec<(Id, Rect)>>,
+    areas: crate::memory::Areas,
+    interaction: crate::memory::Interaction,
+    window_interaction: Option<crate::window::WindowInteraction>,
+}
+
+impl ViewportState {
+    fn swap_with(&mut self, ctx: &mut ContextImpl) {
+        use std::mem::swap;
+        swap(&mut self.input, &mut ctx.input);
+        swap(&mut self.frame_state, &mut ctx.frame_state);
+        swap(&mut self.graphics, &mut ctx.graphics);
+        swap(&mut self.output, &mut ctx.output);
+        swap(
+            &mut self.layer_rects_this_frame,
+            &mut ctx.layer_rects_this_frame,
+        );
+        swap(
+            &mut self.layer_rects_prev_frame,
+            &mut ctx.layer_rects_prev_frame,
+        );
+        swap(&mut self.areas, &mut ctx.memory.areas);
+        swap(&mut self.interaction, &mut ctx.memory.interaction);
+        swap(
+            &mut self.window_interaction,
+            &mut ctx.memory.window_interaction,
+        );
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Default)]
 struct ContextImpl {
     /// `None` until the start of the first frame.
@@ -161,6 +208,25 @@ struct ContextImpl {
     /// Read
     layer_rects_prev_frame: ahash::HashMap<LayerId, Vec<(Id, Rect)>>,
 
+    /// The viewport of the current (or last) frame.
+    viewport_id: ViewportId,
+
+    /// The state of all viewports except [`Self::viewport_id`].
+    viewport_states: ViewportIdMap<ViewportState>,
+
+    /// Viewports requested with [`Context::show_viewport`] during this frame.
+    viewports: ViewportIdMap<ViewportOutput>,
+
+    /// Set by integrations that can create native windows for viewports.
+    native_viewports: bool,
+
+    /// Embedded viewports whose [`Window`] was closed this frame.
+    embedded_close_requested: ahash::HashSet<ViewportId>,
+    embedded_close_requested_prev_frame: ahash::HashSet<ViewportId>,
+
+    /// The image (and other resource) loaders, see [`crate::load`].
+    loaders: Arc<crate::load::Loaders>,
+
     #[cfg(feature = "accesskit")]
     is_accesskit_enabled: bool,
     #[cfg(feature = "accesskit")]
@@ -169,8 +235,19 @@ struct ContextImpl {
 
 impl ContextImpl {
     fn begin_frame_mut(&mut self, mut new_raw_input: RawInput) {
+        self.switch_viewport(new_raw_input.viewport_id);
+        if self.viewport_id.is_root() {
+            self.embedded_close_requested_prev_frame =
+                std::mem::take(&mut self.embedded_close_requested);
+        }
+
         self.repaint.start_frame();
 
+        if !self.viewport_id.is_root() && new_raw_input.viewport_close_requested {
+            // The root viewport must run to see this, see `Context::viewport_close_requested`:
+            self.repaint.request_repaint();
+        }
+
         if let Some(new_pixels_per_point) = self.memory.new_pixels_per_point.take() {
             new_raw_input.pixels_per_point = Some(new_pixels_per_point);
 
@@ -222,6 +299,20 @@ impl ContextImpl {
         }
     }
 
+    /// Store the state of the current viewport, and load the state of `viewport_id`.
+    fn switch_viewport(&mut self, viewport_id: ViewportId) {
+        if viewport_id == self.viewport_id {
+            return;
+        }
+        let mut state = self
+            .viewport_states
+            .remove(&viewport_id)
+            .unwrap_or_default();
+        state.swap_with(self);
+        self.viewport_states.insert(self.viewport_id, state);
+        self.viewport_id = viewport_id;
+    }
+
     /// Load fonts unless already loaded.
     fn update_fonts_mut(&mut self) {
         let pixels_per_point = self.input.pixels_per_point();
@@ -237,6 +328,7 @@ impl ContextImpl {
             Fonts::new(pixels_per_point, max_texture_side, font_definitions)
         });
 
+        fonts.set_rasterization(self.memory.options.text_rasterization);
         fonts.begin_frame(pixels_per_point, max_texture_side);
 
         if self.memory.options.preload_font_glyphs {
@@ -534,6 +626,12 @@ impl Context {
     ) -> R {
         self.write(move |ctx| writer(&mut ctx.memory.options.tessellation_options))
     }
+
+    /// The image (and other resource) loaders, see [`crate::load`].
+    #[inline]
+    pub fn loaders(&self) -> Arc<crate::load::Loaders> {
+        self.read(|ctx| ctx.loaders.clone())
+    }
 }
 
 impl Context {
@@ -668,6 +766,8 @@ impl Context {
                 if hovered {
                     let pointer_pos = ctx.input.pointer.interact_pos();
                     if let Some(pointer_pos) = pointer_pos {
+                        let pointer_pos =
+                            ctx.memory.areas.screen_to_layer_pos(layer_id, pointer_pos);
                         if let Some(rects) = ctx.layer_rects_prev_frame.get(&layer_id) {
                             for &(prev_id, prev_rect) in rects.iter().rev() {
                                 if prev_id == id {
@@ -742,7 +842,11 @@ impl Context {
             changed: false, // must be set by the widget itself
         };
 
-        if !enabled || !sense.focusable || !layer_id.allow_interaction() {
+        if !enabled
+            || !sense.focusable
+            || !layer_id.allow_interaction()
+            || !self.memory(|mem| mem.allows_interaction(layer_id))
+        {
             // Not interested or allowed input:
             self.memory_mut(|mem| mem.surrender_focus(id));
             return response;
@@ -841,7 +945,10 @@ impl Context {
             }
 
             if response.is_pointer_button_down_on {
-                response.interact_pointer_pos = input.pointer.interact_pos();
+                response.interact_pointer_pos = input
+                    .pointer
+                    .interact_pos()
+                    .map(|pos| memory.areas.screen_to_layer_pos(layer_id, pos));
             }
 
             if input.pointer.any_down() {
@@ -942,8 +1049,17 @@ impl Context {
     /// Starts at zero, and is incremented at the end of [`Self::run`] or by [`Self::end_frame`].
     ///
     /// Between calls to [`Self::run`], this is the frame number of the coming frame.
+    ///
+    /// This only counts the frames of the root viewport, see [`Self::frame_nr_for`].
     pub fn frame_nr(&self) -> u64 {
-        self.read(|ctx| ctx.repaint.frame_nr)
+        self.frame_nr_for(ViewportId::ROOT)
+    }
+
+    /// The current frame number of the given viewport.
+    ///
+    /// Every viewport counts its own frames, starting at zero.
+    pub fn frame_nr_for(&self, viewport_id: ViewportId) -> u64 {
+        self.read(|ctx| ctx.repaint.frame_nr(viewport_id))
     }
 
     /// Call this if there is need to repaint the UI, i.e. if you are showing an animation.
@@ -1129,7 +1245,7 @@ impl Context {
     ///         });
     ///
     ///         // Show the image:
-    ///         ui.image(texture, texture.size_vec2());
+    ///         ui.image(texture);
     ///     }
     /// }
     /// ```
@@ -1257,19 +1373,37 @@ impl Context {
             }
         }
 
-        let repaint_after = self.write(|ctx| ctx.repaint.end_frame());
+        let repaint_after = self.write(|ctx| ctx.repaint.end_frame(ctx.viewport_id));
         let shapes = self.drain_paint_lists();
 
+        let viewports = self.write(|ctx| {
+            if ctx.viewport_id.is_root() {
+                let viewports = std::mem::take(&mut ctx.viewports);
+                // Forget about viewports that are no longer shown:
+                ctx.viewport_states
+                    .retain(|id, _| viewports.contains_key(id));
+                viewports
+            } else {
+                Default::default()
+            }
+        });
+
         FullOutput {
             platform_output,
             repaint_after,
             textures_delta,
             shapes,
+            viewports,
         }
     }
 
     fn drain_paint_lists(&self) -> Vec<ClippedShape> {
-        self.write(|ctx| ctx.graphics.drain(ctx.memory.areas.order()).collect())
+        self.write(|ctx| {
+            let areas = &ctx.memory.areas;
+            ctx.graphics
+                .drain(areas.order(), areas.transforms())
+                .collect()
+        })
     }
 
     /// Tessellate the given shapes into triangle meshes.
@@ -1287,9 +1421,9 @@ impl Context {
                 .as_ref()
                 .expect("tessellate called before first call to Context::run()")
                 .texture_atlas();
-            let (font_tex_size, prepared_discs) = {
+            let (font_tex_size, prepared_discs, font_atlas_stats) = {
                 let atlas = texture_atlas.lock();
-                (atlas.size(), atlas.prepared_discs())
+                (atlas.size(), atlas.prepared_discs(), atlas.stats())
             };
 
             let paint_stats = PaintStats::from_shapes(&shapes);
@@ -1300,7 +1434,9 @@ impl Context {
                 prepared_discs,
                 shapes,
             );
-            ctx.paint_stats = paint_stats.with_clipped_primitives(&clipped_primitives);
+            ctx.paint_stats = paint_stats
+                .with_clipped_primitives(&clipped_primitives)
+                .with_font_atlas(font_atlas_stats);
             clipped_primitives
         })
     }
@@ -1433,6 +1569,38 @@ impl Context {
         }
     }
 
+    /// Transform all the graphics of the given layer, e.g. to rotate or zoom it,
+    /// from this frame on, until it is changed again.
+    ///
+    /// Unlike [`Self::translate_layer`], this also affects interaction:
+    /// the pointer is transformed back onto the layer when hit-testing its widgets,
+    /// and positions in [`Response`] are in the coordinates of the layer.
+    /// Use [`Self::screen_to_layer_pos`] for positions you read from [`InputState`].
+    ///
+    /// Set it to [`Affine2::IDENTITY`] to stop transforming the layer.
+    ///
+    /// This can be used to make a zoomable and pannable canvas with interactive widgets on it.
+    pub fn set_transform_layer(&self, layer_id: LayerId, transform: Affine2) {
+        self.memory_mut(|mem| mem.areas.set_transform(layer_id, transform));
+    }
+
+    /// The transform set with [`Self::set_transform_layer`], if any.
+    pub fn layer_transform(&self, layer_id: LayerId) -> Option<Affine2> {
+        self.memory(|mem| mem.areas.transform(layer_id))
+    }
+
+    /// Where on the given layer a position on the screen is,
+    /// taking the transform set with [`Self::set_transform_layer`] into account.
+    pub fn screen_to_layer_pos(&self, layer_id: LayerId, pos: Pos2) -> Pos2 {
+        self.memory(|mem| mem.areas.screen_to_layer_pos(layer_id, pos))
+    }
+
+    /// Like [`Self::screen_to_layer_pos`], but for a movement, e.g. [`PointerState::delta`].
+    pub fn screen_to_layer_vec(&self, layer_id: LayerId, vec: Vec2) -> Vec2 {
+        self.screen_to_layer_pos(layer_id, Pos2::ZERO + vec)
+            - self.screen_to_layer_pos(layer_id, Pos2::ZERO)
+    }
+
     /// Top-most layer at the given position.
     pub fn layer_id_at(&self, pos: Pos2) -> Option<LayerId> {
         self.memory(|mem| {
@@ -1451,7 +1619,8 @@ impl Context {
         rect.is_positive() && {
             let pointer_pos = self.input(|i| i.pointer.interact_pos());
             if let Some(pointer_pos) = pointer_pos {
-                rect.contains(pointer_pos) && self.layer_id_at(pointer_pos) == Some(layer_id)
+                rect.contains(self.screen_to_layer_pos(layer_id, pointer_pos))
+                    && self.layer_id_at(pointer_pos) == Some(layer_id)
             } else {
                 false
             }
@@ -1546,6 +1715,18 @@ impl Context {
                     self.tessellation_options_mut(move |o| *o = tessellation_options);
                 }
             });
+
+        CollapsingHeader::new("🔤 Text rasterization")
+            .default_open(false)
+            .show(ui, |ui| {
+                let prev_text_rasterization = self.options(|o| o.text_rasterization);
+                let mut text_rasterization = prev_text_rasterization;
+                ui.add(&mut text_rasterization);
+                ui.vertical_centered(|ui| reset_button(ui, &mut text_rasterization));
+                if text_rasterization != prev_text_rasterization {
+                    self.options_mut(move |o| o.text_rasterization = text_rasterization);
+                }
+            });
     }
 
     pub fn inspection_ui(&self, ui: &mut Ui) {
@@ -1658,13 +1839,13 @@ impl Context {
                                 let mut size = vec2(w as f32, h as f32);
                                 size *= (max_preview_size.x / size.x).min(1.0);
                                 size *= (max_preview_size.y / size.y).min(1.0);
-                                ui.image(texture_id, size).on_hover_ui(|ui| {
+                                ui.image((texture_id, size)).on_hover_ui(|ui| {
                                     // show larger on hover
                                     let max_size = 0.5 * ui.ctx().screen_rect().size();
                                     let mut size = vec2(w as f32, h as f32);
                                     size *= max_size.x / size.x.max(max_size.x);
                                     size *= max_size.y / size.y.max(max_size.y);
-                                    ui.image(texture_id, size);
+                                    ui.image((texture_id, size));
                                 });
 
                                 ui.label(format!("{} x {}", w, h));
@@ -1785,6 +1966,112 @@ impl Context {
     }
 }
 
+/// ## Viewports
+impl Context {
+    /// The viewport (native window) of the current frame.
+    ///
+    /// This is [`ViewportId::ROOT`] unless the integration supports multiple native windows
+    /// and is running the ui of one shown with [`Self::show_viewport`].
+    pub fn viewport_id(&self) -> ViewportId {
+        self.read(|ctx| ctx.viewport_id)
+    }
+
+    /// If `true` (the default), [`Self::show_viewport`] shows the viewports as [`Window`]s inside the root viewport.
+    ///
+    /// Integrations that can create native windows for viewports should set this to `false`.
+    pub fn embed_viewports(&self) -> bool {
+        self.read(|ctx| !ctx.native_viewports)
+    }
+
+    /// See [`Self::embed_viewports`].
+    pub fn set_embed_viewports(&self, embed_viewports: bool) {
+        self.write(|ctx| ctx.native_viewports = !embed_viewports);
+    }
+
+    /// Show an extra native window, e.g. a tool palette or a detached panel.
+    ///
+    /// Call this every frame of the root viewport for as long as you want the window to stay open.
+    /// The integration creates the window using `builder` the first time it is shown,
+    /// and closes it once you stop calling this.
+    ///
+    /// `viewport_ui` is called for each frame of the new viewport, inside a [`CentralPanel`].
+    /// The viewport gets its own [`RawInput`] and [`InputState`],
+    /// so [`Self::input`] and [`Self::pointer_hover_pos`] refer to the new window while it runs.
+    ///
+    /// Viewports can only be shown from the root viewport;
+    /// calls from inside another viewport are ignored.
+    ///
+    /// If [`Self::embed_viewports`] is `true`, the viewport is shown as a [`Window`] instead.
+    pub fn show_viewport(
+        &self,
+        viewport_id: ViewportId,
+        builder: ViewportBuilder,
+        viewport_ui: impl Fn(&mut Ui) + Send + Sync + 'static,
+    ) {
+        crate::egui_assert!(
+            !viewport_id.is_root(),
+            "The root viewport is created by the integration"
+        );
+
+        if self.embed_viewports() {
+            let title = builder
+                .title
+                .clone()
+                .unwrap_or_else(|| "Viewport".to_owned());
+            let mut open = true;
+            let mut window = Window::new(title)
+                .id(viewport_id.0)
+                .open(&mut open)
+                .title_bar(builder.decorations.unwrap_or(true))
+                .resizable(builder.resizable.unwrap_or(true));
+            if let Some(position) = builder.position {
+                window = window.default_pos(position);
+            }
+            if let Some(inner_size) = builder.inner_size {
+                window = window.default_size(inner_size);
+            }
+            window.show(self, |ui| viewport_ui(ui));
+            if !open {
+                self.write(|ctx| {
+                    ctx.embedded_close_requested.insert(viewport_id);
+                });
+            }
+        } else {
+            self.write(|ctx| {
+                if ctx.viewport_id.is_root() {
+                    ctx.viewports.insert(
+                        viewport_id,
+                        ViewportOutput {
+                            builder,
+                            ui: Arc::new(viewport_ui),
+                        },
+                    );
+                }
+            });
+        }
+    }
+
+    /// Did the user ask to close the window of this viewport, e.g. by clicking its close button?
+    ///
+    /// Stop calling [`Self::show_viewport`] to close the window.
+    pub fn viewport_close_requested(&self, viewport_id: ViewportId) -> bool {
+        self.read(|ctx| {
+            if ctx
+                .embedded_close_requested_prev_frame
+                .contains(&viewport_id)
+            {
+                true
+            } else if viewport_id == ctx.viewport_id {
+                ctx.input.raw.viewport_close_requested
+            } else {
+                ctx.viewport_states
+                    .get(&viewport_id)
+                    .map_or(false, |state| state.input.raw.viewport_close_requested)
+            }
+        })
+    }
+}
+
 /// ## Accessibility
 impl Context {
     /// Call the provided function with the given ID pushed on the stack of
@@ -1874,3 +2161,86 @@ fn context_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Context>();
 }
+
+#[test]
+fn native_viewports_have_their_own_input() {
+    let ctx = Context::default();
+    ctx.set_embed_viewports(false);
+    let child = ViewportId::from_hash_of("child");
+
+    let root_input = RawInput {
+        events: vec![Event::PointerMoved(pos2(10.0, 10.0))],
+        ..Default::default()
+    };
+    let output = ctx.run(root_input, |ctx| {
+        ctx.show_viewport(child, ViewportBuilder::default(), |ui| {
+            ui.label("child");
+        });
+    });
+    assert!(output.viewports.contains_key(&child));
+
+    let child_input = RawInput {
+        viewport_id: child,
+        viewport_close_requested: true,
+        ..Default::default()
+    };
+    let output = ctx.run(child_input, |ctx| {
+        assert_eq!(ctx.viewport_id(), child);
+        assert_eq!(ctx.pointer_hover_pos(), None);
+        assert!(ctx.viewport_close_requested(child));
+        ctx.show_viewport(
+            ViewportId::from_hash_of("ignored"),
+            Default::default(),
+            |_| {},
+        );
+    });
+    assert!(output.viewports.is_empty());
+
+    // Only the frames of the root viewport are counted by `frame_nr`:
+    assert_eq!(ctx.frame_nr(), 1);
+    assert_eq!(ctx.frame_nr_for(child), 1);
+
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        assert!(ctx.viewport_id().is_root());
+        assert_eq!(ctx.pointer_hover_pos(), Some(pos2(10.0, 10.0)));
+        assert!(ctx.viewport_close_requested(child));
+    });
+}
+
+#[test]
+fn transformed_layer_hit_testing() {
+    let ctx = Context::default();
+    let layer_id = LayerId::new(Order::Middle, Id::new("canvas"));
+    ctx.set_transform_layer(layer_id, Affine2::from_scale(2.0));
+
+    let widget_rect = Rect::from_min_size(pos2(10.0, 10.0), vec2(20.0, 20.0));
+    let mut hover_pos = None;
+    for _ in 0..3 {
+        let input = RawInput {
+            // (25, 25) on the layer:
+            events: vec![Event::PointerMoved(pos2(50.0, 50.0))],
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            Area::new("canvas").fixed_pos(Pos2::ZERO).show(ctx, |ui| {
+                ui.set_min_size(vec2(40.0, 40.0));
+                hover_pos = ui.allocate_rect(widget_rect, Sense::click()).hover_pos();
+            });
+        });
+    }
+    assert_eq!(hover_pos, Some(pos2(25.0, 25.0)));
+    assert_eq!(
+        ctx.screen_to_layer_pos(layer_id, pos2(50.0, 50.0)),
+        pos2(25.0, 25.0)
+    );
+
+    // Without the transform, the pointer is outside of the widget:
+    ctx.set_transform_layer(layer_id, Affine2::IDENTITY);
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        Area::new("canvas").fixed_pos(Pos2::ZERO).show(ctx, |ui| {
+            ui.set_min_size(vec2(40.0, 40.0));
+            hover_pos = ui.allocate_rect(widget_rect, Sense::click()).hover_pos();
+        });
+    });
+    assert_eq!(hover_pos, None);
+}
@@ -13,6 +13,12 @@ use crate::emath::*;
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct RawInput {
+    /// Which viewport (native window) this input is for.
+    ///
+    /// Only integrations that support multiple native windows need to set this.
+    /// See [`crate::Context::show_viewport`].
+    pub viewport_id: crate::ViewportId,
+
     /// Position and size of the area that egui should use, in points.
     /// Usually you would set this to
     ///
@@ -67,11 +73,17 @@ pub struct RawInput {
     ///
     /// False when the user alt-tab away from the application, for instance.
     pub focused: bool,
+
+    /// The user asked to close the native window of the viewport, e.g. by clicking its close button.
+    ///
+    /// Read this from the root viewport with [`crate::Context::viewport_close_requested`].
+    pub viewport_close_requested: bool,
 }
 
 impl Default for RawInput {
     fn default() -> Self {
         Self {
+            viewport_id: Default::default(),
             screen_rect: None,
             pixels_per_point: None,
             max_texture_side: None,
@@ -82,6 +94,7 @@ impl Default for RawInput {
             hovered_files: Default::default(),
             dropped_files: Default::default(),
             focused: true, // integrations opt into global focus tracking
+            viewport_close_requested: false,
         }
     }
 }
@@ -93,6 +106,7 @@ impl RawInput {
     /// * [`Self::dropped_files`] is moved.
     pub fn take(&mut self) -> RawInput {
         RawInput {
+            viewport_id: self.viewport_id,
             screen_rect: self.screen_rect.take(),
             pixels_per_point: self.pixels_per_point.take(),
             max_texture_side: self.max_texture_side.take(),
@@ -103,12 +117,14 @@ impl RawInput {
             hovered_files: self.hovered_files.clone(),
             dropped_files: std::mem::take(&mut self.dropped_files),
             focused: self.focused,
+            viewport_close_requested: std::mem::take(&mut self.viewport_close_requested),
         }
     }
 
     /// Add on new input.
     pub fn append(&mut self, newer: Self) {
         let Self {
+            viewport_id,
             screen_rect,
             pixels_per_point,
             max_texture_side,
@@ -119,8 +135,10 @@ impl RawInput {
             mut hovered_files,
             mut dropped_files,
             focused,
+            viewport_close_requested,
         } = newer;
 
+        self.viewport_id = viewport_id;
         self.screen_rect = screen_rect.or(self.screen_rect);
         self.pixels_per_point = pixels_per_point.or(self.pixels_per_point);
         self.max_texture_side = max_texture_side.or(self.max_texture_side);
@@ -131,6 +149,7 @@ impl RawInput {
         self.hovered_files.append(&mut hovered_files);
         self.dropped_files.append(&mut dropped_files);
         self.focused = focused;
+        self.viewport_close_requested |= viewport_close_requested;
     }
 }
 
@@ -915,6 +934,7 @@ fn format_kb_shortcut() {
 impl RawInput {
     pub fn ui(&self, ui: &mut crate::Ui) {
         let Self {
+            viewport_id,
             screen_rect,
             pixels_per_point,
             max_texture_side,
@@ -925,8 +945,10 @@ impl RawInput {
             hovered_files,
             dropped_files,
             focused,
+            viewport_close_requested,
         } = self;
 
+        ui.label(format!("viewport_id: {:?}", viewport_id));
         ui.label(format!("screen_rect: {:?} points", screen_rect));
         ui.label(format!("pixels_per_point: {:?}", pixels_per_point))
             .on_hover_text(
@@ -943,6 +965,10 @@ impl RawInput {
         ui.label(format!("hovered_files: {}", hovered_files.len()));
         ui.label(format!("dropped_files: {}", dropped_files.len()));
         ui.label(format!("focused: {}", focused));
+        ui.label(format!(
+            "viewport_close_requested: {}",
+            viewport_close_requested
+        ));
         ui.scope(|ui| {
             ui.set_min_height(150.0);
             ui.label(format!("events: {:#?}", events))
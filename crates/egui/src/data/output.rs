@@ -30,6 +30,17 @@ pub struct FullOutput {
     ///
     /// You can use [`crate::Context::tessellate`] to turn this into triangles.
     pub shapes: Vec<epaint::ClippedShape>,
+
+    /// The viewports (extra native windows) that should be open,
+    /// as requested with [`crate::Context::show_viewport`] during a frame of the root viewport.
+    ///
+    /// Integrations that support multiple native windows should create a window for each new viewport,
+    /// close the windows of viewports that are no longer in this list,
+    /// and run [`crate::ViewportOutput::show`] for each frame of a viewport.
+    ///
+    /// Always empty for frames of other viewports than the root,
+    /// or if [`crate::Context::embed_viewports`] is `true`.
+    pub viewports: crate::ViewportIdMap<crate::ViewportOutput>,
 }
 
 impl FullOutput {
@@ -40,12 +51,14 @@ impl FullOutput {
             repaint_after,
             textures_delta,
             shapes,
+            viewports,
         } = newer;
 
         self.platform_output.append(platform_output);
         self.repaint_after = repaint_after; // if the last frame doesn't need a repaint, then we don't need to repaint
         self.textures_delta.append(textures_delta);
         self.shapes = shapes; // Only paint the latest
+        self.viewports = viewports; // Only the latest
     }
 }
 
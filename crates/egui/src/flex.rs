@@ -0,0 +1,789 @@
+//! A flexbox-style layout, see [`Flex`].
+
+use crate::*;
+
+/// How the free space along the main axis is distributed in each line of a [`Flex`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FlexJustify {
+    /// Pack the items at the start of the line.
+    #[default]
+    Start,
+
+    /// Pack the items at the end of the line.
+    End,
+
+    /// Pack the items in the middle of the line.
+    Center,
+
+    /// The first item at the start, the last item at the end, and the rest spread evenly between them.
+    SpaceBetween,
+
+    /// Equal space around each item, so the space at the ends is half of that between items.
+    SpaceAround,
+
+    /// Equal space between the items and at the ends.
+    SpaceEvenly,
+}
+
+/// How an item of a [`Flex`] is aligned across its line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FlexAlign {
+    /// At the top of a horizontal line, or to the left of a vertical one.
+    Start,
+
+    /// In the middle of the line.
+    #[default]
+    Center,
+
+    /// At the bottom of a horizontal line, or to the right of a vertical one.
+    End,
+
+    /// Fill the whole line.
+    Stretch,
+}
+
+/// How a single item in a [`Flex`] is sized and aligned.
+///
+/// All sizes are along the main axis of the [`Flex`],
+/// i.e. widths for a horizontal one and heights for a vertical one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FlexItem {
+    grow: f32,
+    shrink: f32,
+    basis: Option<f32>,
+    min_size: f32,
+    max_size: f32,
+    align_self: Option<FlexAlign>,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+            min_size: 0.0,
+            max_size: f32::INFINITY,
+            align_self: None,
+        }
+    }
+}
+
+impl FlexItem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How much of the free space in the line this item takes, relative to the other items.
+    ///
+    /// Default: `0.0` (don't grow).
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// How much this item shrinks when the line is too long, relative to the other items
+    /// (weighted by their basis).
+    ///
+    /// Default: `1.0`. Use `0.0` to never shrink below the basis.
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// The size of the item before growing or shrinking.
+    ///
+    /// By default this is the size of the contents, as measured the previous frame.
+    ///
+    /// Contents that fill all the space they are given (e.g. a [`TextEdit`] with an infinite desired width)
+    /// have no natural size, so give them a basis, e.g. `FlexItem::new().grow(1.0).basis(0.0)`.
+    pub fn basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// Never shrink the item below this size. Default: `0.0`.
+    pub fn min_size(mut self, min_size: f32) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Never grow the item above this size. Default: infinity.
+    pub fn max_size(mut self, max_size: f32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Align this item differently from [`Flex::align_items`].
+    pub fn align_self(mut self, align: FlexAlign) -> Self {
+        self.align_self = Some(align);
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ItemState {
+    item: FlexItem,
+
+    /// The size the contents want, measured when they were last shown.
+    ///
+    /// Contents that are given less space than this (e.g. a label that wraps)
+    /// can't tell how much they want, so then this is kept until they are given enough again.
+    size: Vec2,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct State {
+    items: Vec<ItemState>,
+}
+
+impl State {
+    fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_temp(id))
+    }
+
+    fn store(self, ctx: &Context, id: Id) {
+        // Not persisted, for the same reasons as `Grid`.
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A flexbox-style layout, for toolbars and forms that adapt to the available space.
+///
+/// The items are laid out along the main axis, and if [`Self::wrap`] is set they wrap into several lines.
+/// Each item has a [`FlexItem`] that decides how it grows or shrinks to fill the line,
+/// and the lines are then aligned according to [`Self::justify`] and [`Self::align_items`].
+///
+/// The sizes of the items are measured when they are shown, and used to lay them out the next frame.
+/// Like [`Grid`], the layout is therefore invisible the first frame,
+/// and a repaint is requested whenever the sizes change.
+///
+/// The [`Flex`] fills the available space along its main axis (if it is finite),
+/// e.g. the full width of the parent [`Ui`] for [`Self::horizontal`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut search = String::new();
+/// egui::Flex::horizontal()
+///     .align_items(egui::FlexAlign::Center)
+///     .show(ui, |flex| {
+///         flex.add(egui::FlexItem::new(), egui::Label::new("Search:"));
+///         flex.add(
+///             egui::FlexItem::new().grow(1.0).basis(0.0),
+///             egui::TextEdit::singleline(&mut search).desired_width(f32::INFINITY),
+///         );
+///         if flex.add(egui::FlexItem::new(), egui::Button::new("Go")).clicked() {
+///             /* … */
+///         }
+///     });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Copy, Debug)]
+pub struct Flex {
+    id_source: Option<Id>,
+    direction: Direction,
+    wrap: bool,
+    gap: Option<Vec2>,
+    justify: FlexJustify,
+    align_items: FlexAlign,
+}
+
+impl Flex {
+    /// Lay out the items in this direction.
+    ///
+    /// When wrapping, lines are added below (for horizontal directions) or to the right (for vertical ones).
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            id_source: None,
+            direction,
+            wrap: false,
+            gap: None,
+            justify: FlexJustify::default(),
+            align_items: FlexAlign::default(),
+        }
+    }
+
+    /// Lay out the items left to right.
+    pub fn horizontal() -> Self {
+        Self::new(Direction::LeftToRight)
+    }
+
+    /// Lay out the items top-down.
+    pub fn vertical() -> Self {
+        Self::new(Direction::TopDown)
+    }
+
+    /// Set this if you have several [`Flex`] without anything else between them,
+    /// or if the number of widgets before it changes.
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// Start a new line when an item doesn't fit on the current one. Default: `false`.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// The horizontal (`x`) and vertical (`y`) space between items and between lines.
+    ///
+    /// Default: [`crate::style::Spacing::item_spacing`].
+    pub fn gap(mut self, gap: impl Into<Vec2>) -> Self {
+        self.gap = Some(gap.into());
+        self
+    }
+
+    /// How to distribute the space that is left in each line after the items have grown.
+    /// Default: [`FlexJustify::Start`].
+    pub fn justify(mut self, justify: FlexJustify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// How to align the items across their line. Default: [`FlexAlign::Center`].
+    pub fn align_items(mut self, align_items: FlexAlign) -> Self {
+        self.align_items = align_items;
+        self
+    }
+}
+
+impl Flex {
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut FlexUi) -> R,
+    ) -> InnerResponse<R> {
+        self.show_dyn(ui, Box::new(add_contents))
+    }
+
+    fn show_dyn<'c, R>(
+        self,
+        ui: &mut Ui,
+        add_contents: Box<dyn FnOnce(&mut FlexUi) -> R + 'c>,
+    ) -> InnerResponse<R> {
+        let id = match self.id_source {
+            Some(id_source) => ui.make_persistent_id(id_source),
+            None => ui.next_auto_id(),
+        };
+        let prev_state = State::load(ui.ctx(), id);
+        let is_first_frame = prev_state.is_none();
+        let prev_state = prev_state.unwrap_or_default();
+
+        let gap = self.gap.unwrap_or_else(|| ui.spacing().item_spacing);
+        let available_rect = ui.available_rect_before_wrap();
+        let main_axis = self.main_axis();
+        let (rects, size) =
+            self.layout_items(gap, available_rect.size()[main_axis], &prev_state.items);
+
+        let mut child_ui = ui.child_ui(available_rect, *ui.layout());
+        child_ui.set_visible(!is_first_frame); // Avoid visible first-frame jitter
+        let mut flex_ui = FlexUi {
+            ui: child_ui,
+            origin: available_rect.min,
+            available_size: available_rect.size(),
+            rects,
+            prev_state,
+            curr_state: State::default(),
+            bounds: Rect::from_min_size(available_rect.min, size),
+        };
+        let inner = add_contents(&mut flex_ui);
+
+        let FlexUi {
+            bounds,
+            prev_state,
+            curr_state,
+            ..
+        } = flex_ui;
+        if curr_state != prev_state {
+            curr_state.store(ui.ctx(), id);
+            ui.ctx().request_repaint();
+        }
+
+        let response = ui.allocate_rect(bounds, Sense::hover());
+        InnerResponse::new(inner, response)
+    }
+
+    fn main_axis(&self) -> usize {
+        match self.direction {
+            Direction::LeftToRight | Direction::RightToLeft => 0,
+            Direction::TopDown | Direction::BottomUp => 1,
+        }
+    }
+
+    /// Where to put each item, relative to the top-left corner of the [`Flex`],
+    /// and how large the whole [`Flex`] is.
+    fn layout_items(
+        &self,
+        gap: Vec2,
+        available_main: f32,
+        items: &[ItemState],
+    ) -> (Vec<Rect>, Vec2) {
+        let main_axis = self.main_axis();
+        let cross_axis = 1 - main_axis;
+        let (main_gap, cross_gap) = (gap[main_axis], gap[cross_axis]);
+
+        let hypothetical: Vec<f32> = items
+            .iter()
+            .map(|state| {
+                let item = &state.item;
+                item.basis
+                    .unwrap_or(state.size[main_axis])
+                    .at_most(item.max_size)
+                    .at_least(item.min_size)
+            })
+            .collect();
+
+        // Break into lines:
+        let mut lines: Vec<std::ops::Range<usize>> = vec![];
+        let mut line_start = 0;
+        let mut line_length = 0.0;
+        for (i, &size) in hypothetical.iter().enumerate() {
+            let length_with_item = if i == line_start {
+                size
+            } else {
+                line_length + main_gap + size
+            };
+            if self.wrap && i > line_start && length_with_item > available_main {
+                lines.push(line_start..i);
+                line_start = i;
+                line_length = size;
+            } else {
+                line_length = length_with_item;
+            }
+        }
+        if line_start < items.len() {
+            lines.push(line_start..items.len());
+        }
+
+        let mut rects = vec![Rect::NOTHING; items.len()];
+        let mut main_size: f32 = 0.0;
+        let mut cross_offset = 0.0;
+        for (line_index, line) in lines.into_iter().enumerate() {
+            let line_items = &items[line.clone()];
+            let num_gaps = (line.len() - 1) as f32;
+            let used = hypothetical[line.clone()].iter().sum::<f32>() + num_gaps * main_gap;
+            let free = if available_main.is_finite() {
+                available_main - used
+            } else {
+                0.0
+            };
+
+            let sizes = resolve_flexible_lengths(line_items, &hypothetical[line.clone()], free);
+            let used = sizes.iter().sum::<f32>() + num_gaps * main_gap;
+            let remaining = if available_main.is_finite() {
+                (available_main - used).at_least(0.0)
+            } else {
+                0.0
+            };
+            main_size = main_size.max(used);
+
+            let n = line.len() as f32;
+            let (mut main_offset, extra_gap) = match self.justify {
+                FlexJustify::SpaceBetween if n > 1.0 => (0.0, remaining / (n - 1.0)),
+                FlexJustify::Start | FlexJustify::SpaceBetween => (0.0, 0.0),
+                FlexJustify::End => (remaining, 0.0),
+                FlexJustify::Center => (remaining / 2.0, 0.0),
+                FlexJustify::SpaceAround => (remaining / n / 2.0, remaining / n),
+                FlexJustify::SpaceEvenly => (remaining / (n + 1.0), remaining / (n + 1.0)),
+            };
+
+            let line_cross = line_items
+                .iter()
+                .map(|state| state.size[cross_axis])
+                .fold(0.0, f32::max);
+            if line_index > 0 {
+                cross_offset += cross_gap;
+            }
+
+            for ((i, state), main_length) in line.zip(line_items).zip(sizes) {
+                let content_cross = state.size[cross_axis];
+                let (cross_pos, cross_length) =
+                    match state.item.align_self.unwrap_or(self.align_items) {
+                        FlexAlign::Start => (0.0, content_cross),
+                        FlexAlign::Center => ((line_cross - content_cross) / 2.0, content_cross),
+                        FlexAlign::End => (line_cross - content_cross, content_cross),
+                        FlexAlign::Stretch => (0.0, line_cross),
+                    };
+
+                let mut min = Pos2::ZERO;
+                let mut size = Vec2::ZERO;
+                min[main_axis] = main_offset;
+                min[cross_axis] = cross_offset + cross_pos;
+                size[main_axis] = main_length;
+                size[cross_axis] = cross_length;
+                rects[i] = Rect::from_min_size(min, size);
+
+                main_offset += main_length + main_gap + extra_gap;
+            }
+
+            cross_offset += line_cross;
+        }
+
+        if available_main.is_finite() {
+            main_size = available_main;
+        }
+
+        if matches!(self.direction, Direction::RightToLeft | Direction::BottomUp) {
+            for rect in &mut rects {
+                let (min, max) = (rect.min[main_axis], rect.max[main_axis]);
+                rect.min[main_axis] = main_size - max;
+                rect.max[main_axis] = main_size - min;
+            }
+        }
+
+        let mut size = Vec2::ZERO;
+        size[main_axis] = main_size;
+        size[cross_axis] = cross_offset;
+        (rects, size)
+    }
+}
+
+/// Grow or shrink the items of a line so that they use up `free` space (which can be negative),
+/// respecting their min and max sizes.
+fn resolve_flexible_lengths(items: &[ItemState], hypothetical: &[f32], free: f32) -> Vec<f32> {
+    let growing = free > 0.0;
+    let weight = |i: usize| {
+        if growing {
+            items[i].item.grow
+        } else {
+            items[i].item.shrink * hypothetical[i]
+        }
+    };
+
+    let mut sizes = hypothetical.to_vec();
+    if free == 0.0 {
+        return sizes;
+    }
+
+    // Items that are done: either not flexible, or clamped to their min/max size.
+    let mut frozen: Vec<bool> = (0..items.len()).map(|i| weight(i) <= 0.0).collect();
+
+    // Every round either freezes at least one more item, or is the last one:
+    loop {
+        let remaining = free
+            - (0..items.len())
+                .filter(|&i| frozen[i])
+                .map(|i| sizes[i] - hypothetical[i])
+                .sum::<f32>();
+        let total_weight: f32 = (0..items.len()).filter(|&i| !frozen[i]).map(weight).sum();
+        if total_weight <= 0.0 {
+            break;
+        }
+
+        let mut clamped_any = false;
+        for i in 0..items.len() {
+            if frozen[i] {
+                continue;
+            }
+            let item = &items[i].item;
+            let size = hypothetical[i] + remaining * weight(i) / total_weight;
+            let clamped = size.at_most(item.max_size).at_least(item.min_size);
+            sizes[i] = clamped;
+            if clamped != size {
+                frozen[i] = true;
+                clamped_any = true;
+            }
+        }
+        if !clamped_any {
+            break;
+        }
+    }
+
+    sizes
+}
+
+// ----------------------------------------------------------------------------
+
+/// The contents of a [`Flex`], given to the closure in [`Flex::show`].
+///
+/// Each call to [`Self::add`] or [`Self::add_ui`] adds one item.
+pub struct FlexUi {
+    ui: Ui,
+    origin: Pos2,
+    available_size: Vec2,
+
+    /// Where to put each item, from the state of the previous frame.
+    rects: Vec<Rect>,
+    prev_state: State,
+    curr_state: State,
+
+    /// The area used so far.
+    bounds: Rect,
+}
+
+impl FlexUi {
+    /// Add a widget as an item.
+    pub fn add(&mut self, item: FlexItem, widget: impl Widget) -> Response {
+        self.add_ui(item, |ui| ui.add(widget)).inner
+    }
+
+    /// Add an item with any contents, e.g. several widgets in a [`Ui::horizontal`].
+    ///
+    /// The contents are laid out top-down in the rectangle of the item.
+    pub fn add_ui<R>(
+        &mut self,
+        item: FlexItem,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let index = self.curr_state.items.len();
+        let prev = self.prev_state.items.get(index).copied();
+
+        // Items we haven't seen before are measured in the full available space, but not shown:
+        let rect = match (prev, self.rects.get(index)) {
+            (Some(_), Some(rect)) => rect.translate(self.origin.to_vec2()),
+            _ => Rect::from_min_size(self.origin, self.available_size),
+        };
+
+        let mut child_ui = self.ui.child_ui_with_id_source(
+            rect,
+            Layout::top_down(Align::Min),
+            ("flex_item", index),
+        );
+        if prev.is_none() {
+            child_ui.set_visible(false);
+        }
+        let inner = add_contents(&mut child_ui);
+        let content_rect = child_ui.min_rect();
+
+        let mut size = content_rect.size();
+        if let Some(prev) = prev {
+            for axis in 0..2 {
+                let given = rect.size()[axis];
+                if given < prev.size[axis] - 0.5 {
+                    // Shrunk contents (e.g. wrapped labels) only tell how small they can be made,
+                    // so keep asking for their full size to regrow once there is space again:
+                    size[axis] = size[axis].max(prev.size[axis]);
+                } else if (size[axis] - given).abs() < 0.5 && (given - prev.size[axis]).abs() >= 0.5
+                {
+                    // Contents that fill exactly the space they are given (e.g. justified layouts)
+                    // would otherwise never give it back, so keep asking for what they asked for before:
+                    size[axis] = prev.size[axis];
+                }
+            }
+        }
+        self.curr_state.items.push(ItemState { item, size });
+
+        if prev.is_some() {
+            self.bounds = self.bounds.union(rect).union(content_rect);
+        }
+        let response = self.ui.interact(rect, child_ui.id(), Sense::hover());
+        InnerResponse::new(inner, response)
+    }
+
+    /// The [`Ui`] the items are added to, e.g. to check the style.
+    pub fn ui(&self) -> &Ui {
+        &self.ui
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(items: &[(FlexItem, Vec2)]) -> Vec<ItemState> {
+        items
+            .iter()
+            .map(|&(item, size)| ItemState { item, size })
+            .collect()
+    }
+
+    #[test]
+    fn grow_and_justify() {
+        let flex = Flex::horizontal();
+        let gap = vec2(10.0, 5.0);
+
+        // A fixed item, and one that grows to fill the rest:
+        let row = items(&[
+            (FlexItem::new(), vec2(50.0, 20.0)),
+            (FlexItem::new().grow(1.0).basis(0.0), vec2(300.0, 10.0)),
+        ]);
+        let (rects, size) = flex.layout_items(gap, 200.0, &row);
+        assert_eq!(size, vec2(200.0, 20.0));
+        assert_eq!(
+            rects[0],
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(50.0, 20.0))
+        );
+        assert_eq!(
+            rects[1],
+            Rect::from_min_size(pos2(60.0, 5.0), vec2(140.0, 10.0))
+        );
+
+        // A max size stops the growing, and the rest of the space is justified:
+        let row = items(&[
+            (FlexItem::new(), vec2(50.0, 20.0)),
+            (FlexItem::new().grow(1.0).max_size(40.0), vec2(30.0, 20.0)),
+        ]);
+        let flex = flex.justify(FlexJustify::SpaceBetween);
+        let (rects, _) = flex.layout_items(gap, 200.0, &row);
+        assert_eq!(
+            rects[1],
+            Rect::from_min_size(pos2(160.0, 0.0), vec2(40.0, 20.0))
+        );
+
+        // Right-to-left mirrors everything:
+        let flex = Flex::new(Direction::RightToLeft).justify(FlexJustify::SpaceBetween);
+        let (rects, _) = flex.layout_items(gap, 200.0, &row);
+        assert_eq!(
+            rects[0],
+            Rect::from_min_size(pos2(150.0, 0.0), vec2(50.0, 20.0))
+        );
+        assert_eq!(
+            rects[1],
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(40.0, 20.0))
+        );
+    }
+
+    #[test]
+    fn shrink() {
+        let flex = Flex::horizontal();
+        let row = items(&[
+            (FlexItem::new(), vec2(100.0, 20.0)),
+            (FlexItem::new(), vec2(300.0, 20.0)),
+            (FlexItem::new().shrink(0.0), vec2(50.0, 20.0)),
+        ]);
+
+        // 100 too wide: the first two shrink in proportion to their size, the last doesn't shrink at all.
+        let (rects, _) = flex.layout_items(Vec2::ZERO, 350.0, &row);
+        assert_eq!(rects[0].width(), 75.0);
+        assert_eq!(rects[1].width(), 225.0);
+        assert_eq!(rects[2].width(), 50.0);
+
+        // A min size stops the shrinking, and the others shrink more instead:
+        let row = items(&[
+            (FlexItem::new().min_size(90.0), vec2(100.0, 20.0)),
+            (FlexItem::new(), vec2(300.0, 20.0)),
+            (FlexItem::new().shrink(0.0), vec2(50.0, 20.0)),
+        ]);
+        let (rects, _) = flex.layout_items(Vec2::ZERO, 350.0, &row);
+        assert_eq!(rects[0].width(), 90.0);
+        assert_eq!(rects[1].width(), 210.0);
+    }
+
+    #[test]
+    fn wrap_and_align() {
+        let flex = Flex::horizontal()
+            .wrap(true)
+            .align_items(FlexAlign::Start)
+            .justify(FlexJustify::Center);
+        let gap = vec2(10.0, 5.0);
+        let row = items(&[
+            (FlexItem::new(), vec2(80.0, 20.0)),
+            (FlexItem::new().align_self(FlexAlign::End), vec2(80.0, 10.0)),
+            (
+                FlexItem::new().align_self(FlexAlign::Stretch),
+                vec2(80.0, 10.0),
+            ),
+            (FlexItem::new(), vec2(80.0, 30.0)),
+        ]);
+
+        // Two items per line:
+        let (rects, size) = flex.layout_items(gap, 200.0, &row);
+        assert_eq!(size, vec2(200.0, 20.0 + 5.0 + 30.0));
+        assert_eq!(
+            rects[0],
+            Rect::from_min_size(pos2(15.0, 0.0), vec2(80.0, 20.0))
+        );
+        assert_eq!(
+            rects[1],
+            Rect::from_min_size(pos2(105.0, 10.0), vec2(80.0, 10.0))
+        );
+        assert_eq!(
+            rects[2],
+            Rect::from_min_size(pos2(15.0, 25.0), vec2(80.0, 30.0))
+        );
+        assert_eq!(
+            rects[3],
+            Rect::from_min_size(pos2(105.0, 25.0), vec2(80.0, 30.0))
+        );
+    }
+
+    #[test]
+    fn measures_the_previous_frame() {
+        let ctx = Context::default();
+        let mut rects = vec![];
+        let mut available = Rect::NOTHING;
+        for _ in 0..3 {
+            let _ = ctx.run(Default::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    available = ui.available_rect_before_wrap();
+                    Flex::horizontal()
+                        .gap(vec2(10.0, 0.0))
+                        .justify(FlexJustify::End)
+                        .show(ui, |flex| {
+                            rects = vec![
+                                flex.add_ui(FlexItem::new(), |ui| {
+                                    ui.allocate_space(vec2(30.0, 20.0));
+                                })
+                                .response
+                                .rect,
+                                flex.add_ui(FlexItem::new().grow(1.0).max_size(50.0), |ui| {
+                                    ui.allocate_space(vec2(20.0, 20.0));
+                                })
+                                .response
+                                .rect,
+                            ];
+                        });
+                });
+            });
+        }
+
+        // The second item grows to its max size, and both are pushed to the end:
+        let right_top = available.right_top();
+        assert_eq!(
+            rects[0],
+            Rect::from_min_size(right_top - vec2(90.0, 0.0), vec2(30.0, 20.0))
+        );
+        assert_eq!(
+            rects[1],
+            Rect::from_min_size(right_top - vec2(50.0, 0.0), vec2(50.0, 20.0))
+        );
+    }
+
+    #[test]
+    fn wrapped_items_regrow() {
+        let ctx = Context::default();
+        let mut label_rect = Rect::NOTHING;
+        let mut run = |width: f32| {
+            for _ in 0..3 {
+                let _ = ctx.run(Default::default(), |ctx| {
+                    CentralPanel::default().show(ctx, |ui| {
+                        ui.allocate_ui(vec2(width, 500.0), |ui| {
+                            Flex::horizontal().show(ui, |flex| {
+                                label_rect = flex
+                                    .add(
+                                        FlexItem::new(),
+                                        Label::new("A label that wraps when there is no space")
+                                            .wrap(true),
+                                    )
+                                    .rect;
+                            });
+                        });
+                    });
+                });
+            }
+            label_rect
+        };
+
+        let wide = run(500.0);
+        assert!(wide.width() < 500.0);
+
+        // Shrinking wraps the label:
+        let narrow = run(100.0);
+        assert!(narrow.width() <= 100.0);
+        assert!(narrow.height() > wide.height());
+
+        // …and it gets its full width back once there is space again:
+        assert_eq!(run(500.0), wide);
+    }
+}
@@ -35,7 +35,7 @@ impl Id {
     ///
     /// The null [`Id`] is still a valid id to use in all circumstances,
     /// though obviously it will lead to a lot of collisions if you do use it!
-    pub fn null() -> Self {
+    pub const fn null() -> Self {
         Self(0)
     }
 
@@ -70,10 +70,17 @@ impl Id {
         self.0
     }
 
+    /// The AccessKit node id of the widget with this [`Id`].
     #[cfg(feature = "accesskit")]
-    pub(crate) fn accesskit_id(&self) -> accesskit::NodeId {
+    pub fn accesskit_id(&self) -> accesskit::NodeId {
         std::num::NonZeroU64::new(self.0).unwrap().into()
     }
+
+    /// The [`Id`] of the widget with the given AccessKit node id.
+    #[cfg(feature = "accesskit")]
+    pub fn from_accesskit_id(id: accesskit::NodeId) -> Self {
+        Self(id.0.get() as u64)
+    }
 }
 
 impl std::fmt::Debug for Id {
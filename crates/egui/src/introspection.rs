@@ -97,6 +97,7 @@ impl Widget for &epaint::stats::PaintStats {
                 clipped_primitives,
                 vertices,
                 indices,
+                font_atlas,
             } = self;
 
             ui.label("Intermediate:");
@@ -124,6 +125,16 @@ impl Widget for &epaint::stats::PaintStats {
             label(ui, indices, "indices").on_hover_text("Three 32-bit indices per triangles");
             ui.add_space(10.0);
 
+            ui.label("Font atlas:");
+            let [width, height] = font_atlas.size;
+            ui.label(format!("{:6} x {} texels", width, height));
+            ui.label(format!("{:5.1}% full", font_atlas.fill_ratio * 100.0))
+                .on_hover_text("Unused fonts are evicted when this gets high");
+            ui.label(format!("{:6} fonts evicted", font_atlas.num_evictions));
+            ui.label(format!("{:6} resets", font_atlas.num_resets))
+                .on_hover_text("Number of times the whole font atlas was recreated");
+            ui.add_space(10.0);
+
             // ui.label("Total:");
             // ui.label(self.total().format(""));
         })
@@ -184,6 +195,32 @@ impl Widget for &mut epaint::TessellationOptions {
     }
 }
 
+impl Widget for &mut epaint::text::TextRasterization {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            let epaint::text::TextRasterization {
+                subpixel_positioning,
+                gamma,
+                sdf,
+            } = self;
+
+            ui.add_enabled_ui(!*sdf, |ui| {
+                ui.checkbox(subpixel_positioning, "Subpixel positioning")
+                    .on_hover_text("Position glyphs at fractions of a pixel, for more even spacing between letters. Uses more of the font atlas.");
+                ui.add(
+                    crate::Slider::new(gamma, 0.2..=2.0)
+                        .text("Gamma")
+                        .fixed_decimals(2),
+                )
+                .on_hover_text("Lower values make text bolder, higher values thinner.");
+            });
+            ui.checkbox(sdf, "Signed distance fields")
+                .on_hover_text("Rasterize each glyph once and scale it to all sizes. Keeps zoomed text crisp, but requires renderer support (egui_glow or egui-wgpu).");
+        })
+        .response
+    }
+}
+
 impl Widget for &memory::Interaction {
     fn ui(self, ui: &mut Ui) -> Response {
         ui.vertical(|ui| {
@@ -17,6 +17,12 @@ pub enum Order {
     /// Normal moveable windows that you reorder by click
     Middle,
 
+    /// [`crate::Modal`] dialogs and their backdrops.
+    ///
+    /// While a modal is shown, only the top-most modal and the layers above it
+    /// (popups opened from it, tooltips, …) can be interacted with.
+    Modal,
+
     /// Popups, menus etc that should always be painted on top of windows
     /// Foreground objects can also have tooltips
     Foreground,
@@ -30,11 +36,12 @@ pub enum Order {
 }
 
 impl Order {
-    const COUNT: usize = 6;
+    const COUNT: usize = 7;
     const ALL: [Order; Self::COUNT] = [
         Self::Background,
         Self::PanelResizeLine,
         Self::Middle,
+        Self::Modal,
         Self::Foreground,
         Self::Tooltip,
         Self::Debug,
@@ -47,6 +54,7 @@ impl Order {
             Self::Background
             | Self::PanelResizeLine
             | Self::Middle
+            | Self::Modal
             | Self::Foreground
             | Self::Debug => true,
             Self::Tooltip => false,
@@ -59,6 +67,7 @@ impl Order {
             Self::Background => "backg",
             Self::PanelResizeLine => "panel",
             Self::Middle => "middl",
+            Self::Modal => "modal",
             Self::Foreground => "foreg",
             Self::Tooltip => "toolt",
             Self::Debug => "debug",
@@ -158,6 +167,16 @@ impl PaintList {
             shape.translate(delta);
         }
     }
+
+    /// Transform each [`Shape`] and clip rectangle, in-place.
+    ///
+    /// The clip rectangles become the bounding rectangles of the transformed ones.
+    pub fn transform(&mut self, transform: Affine2) {
+        for ClippedShape(clip_rect, shape) in &mut self.0 {
+            *clip_rect = transform.transform_rect_bounds(*clip_rect);
+            shape.transform(transform);
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -170,9 +189,19 @@ impl GraphicLayers {
             .or_default()
     }
 
-    pub fn drain(&mut self, area_order: &[LayerId]) -> impl ExactSizeIterator<Item = ClippedShape> {
+    pub fn drain(
+        &mut self,
+        area_order: &[LayerId],
+        transforms: &ahash::HashMap<LayerId, Affine2>,
+    ) -> impl ExactSizeIterator<Item = ClippedShape> {
         let mut all_shapes: Vec<_> = Default::default();
 
+        for (layer_id, transform) in transforms {
+            if let Some(list) = self.0[layer_id.order as usize].get_mut(&layer_id.id) {
+                list.transform(*transform);
+            }
+        }
+
         for &order in &Order::ALL {
             let order_map = &mut self.0[order as usize];
 
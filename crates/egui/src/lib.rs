@@ -84,7 +84,7 @@
 //! ui.separator();
 //!
 //! # let my_image = egui::TextureId::default();
-//! ui.image(my_image, [640.0, 480.0]);
+//! ui.image((my_image, egui::vec2(640.0, 480.0)));
 //!
 //! ui.collapsing("Click to see what is hidden!", |ui| {
 //!     ui.label("Not much, as it turns out");
@@ -304,6 +304,7 @@ mod animation_manager;
 pub mod containers;
 mod context;
 mod data;
+pub mod flex;
 mod frame_state;
 pub(crate) mod grid;
 pub mod gui_zoom;
@@ -312,6 +313,7 @@ mod input_state;
 pub mod introspection;
 pub mod layers;
 mod layout;
+pub mod load;
 mod memory;
 pub mod menu;
 pub mod os;
@@ -322,6 +324,7 @@ mod sense;
 pub mod style;
 mod ui;
 pub mod util;
+pub mod viewport;
 pub mod widget_text;
 pub mod widgets;
 
@@ -335,11 +338,13 @@ pub use epaint::emath;
 #[cfg(feature = "color-hex")]
 pub use ecolor::hex_color;
 pub use ecolor::{Color32, Rgba};
-pub use emath::{lerp, pos2, remap, remap_clamp, vec2, Align, Align2, NumExt, Pos2, Rect, Vec2};
+pub use emath::{
+    lerp, pos2, remap, remap_clamp, vec2, Affine2, Align, Align2, NumExt, Pos2, Rect, Vec2,
+};
 pub use epaint::{
     mutex,
-    text::{FontData, FontDefinitions, FontFamily, FontId, FontTweak},
-    textures::{TextureFilter, TextureOptions, TexturesDelta},
+    text::{FontData, FontDefinitions, FontFamily, FontId, FontStyle, FontTweak, FontWeight},
+    textures::{TextureFilter, TextureOptions, TextureWrapMode, TexturesDelta},
     ClippedPrimitive, ColorImage, FontImage, ImageData, Mesh, PaintCallback, PaintCallbackInfo,
     Rounding, Shape, Stroke, TextureHandle, TextureId,
 };
@@ -359,11 +364,13 @@ pub use {
         input::*,
         output::{self, CursorIcon, FullOutput, PlatformOutput, UserAttentionType, WidgetInfo},
     },
+    flex::{Flex, FlexAlign, FlexItem, FlexJustify, FlexUi},
     grid::Grid,
     id::{Id, IdMap},
     input_state::{InputState, MultiTouchInfo, PointerState},
     layers::{LayerId, Order},
     layout::*,
+    load::{ImageSource, SizedTexture},
     memory::{Memory, Options},
     painter::Painter,
     response::{InnerResponse, Response},
@@ -371,6 +378,7 @@ pub use {
     style::{FontSelection, Margin, Style, TextStyle, Visuals},
     text::{Galley, TextFormat},
     ui::Ui,
+    viewport::{ViewportBuilder, ViewportId, ViewportIdMap, ViewportOutput},
     widget_text::{RichText, WidgetText},
     widgets::*,
 };
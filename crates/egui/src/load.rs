@@ -0,0 +1,735 @@
+//! Loading images (and other resources) by URI.
+//!
+//! Instead of juggling [`TextureHandle`](crate::TextureHandle)s yourself, you can ask egui for an image by its URI,
+//! e.g. `ui.image("file://assets/ferris.png")`.
+//! egui will pass the URI through a chain of _loaders_, and cache the results:
+//!
+//! - A [`BytesLoader`] turns a URI into raw bytes, e.g. by reading a file or fetching it over the network.
+//! - An [`ImageLoader`] decodes those bytes into a [`ColorImage`], e.g. a PNG or SVG decoder.
+//! - A [`TextureLoader`] uploads the [`ColorImage`] to the GPU.
+//!
+//! egui itself only comes with a loader for `bytes://` URIs (see [`Context::include_bytes`])
+//! and a [`TextureLoader`]. The image decoders and the `file://` loader live in `egui_extras`,
+//! and are installed with `egui_extras::install_image_loaders`.
+//!
+//! Loading can be asynchronous: a loader may return a `Pending` result, and request a repaint once it is done.
+//! The [`crate::Image`] widget shows a spinner while it waits.
+//!
+//! You can implement the loader traits yourself, e.g. to load images from a zip archive,
+//! and add them with [`Context::add_bytes_loader`], [`Context::add_image_loader`] or [`Context::add_texture_loader`].
+//! Loaders added later are tried first.
+
+mod bytes_loader;
+mod texture_loader;
+
+use std::{borrow::Cow, fmt::Debug, ops::Deref, sync::Arc};
+
+use ahash::HashMap;
+use epaint::{mutex::Mutex, textures::TextureOptions, ColorImage, TextureHandle, TextureId, Vec2};
+
+use crate::Context;
+
+pub use self::{bytes_loader::DefaultBytesLoader, texture_loader::DefaultTextureLoader};
+
+/// Why a loader failed to load something.
+#[derive(Clone, Debug)]
+pub enum LoadError {
+    /// No image loaders are installed.
+    NoImageLoaders,
+
+    /// This loader does not support this URI scheme or image format.
+    ///
+    /// The next loader in the chain will be tried.
+    NotSupported,
+
+    /// The loader supports this URI, but failed to load it, e.g. because the file is missing.
+    Loading(String),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoImageLoaders => f.write_str(
+                "No image loaders are installed. If you're trying to load some images \
+                for the first time, follow the steps outlined in https://docs.rs/egui/latest/egui/load/index.html",
+            ),
+            Self::NotSupported => f.write_str("Image scheme or format not supported."),
+            Self::Loading(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+pub type Result<T, E = LoadError> = std::result::Result<T, E>;
+
+/// At what size should an image be loaded?
+///
+/// Only matters for image formats that can be rendered at any size, like SVG.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SizeHint {
+    /// The size the image was designed for.
+    #[default]
+    Original,
+
+    /// Scale to this width, keeping the aspect ratio.
+    Width(u32),
+
+    /// Scale to this height, keeping the aspect ratio.
+    Height(u32),
+
+    /// Scale to this size, in pixels.
+    Size(u32, u32),
+}
+
+// ----------------------------------------------------------------------------
+
+/// Raw bytes of a resource, e.g. the contents of a `.png` file.
+#[derive(Clone)]
+pub enum Bytes {
+    Static(&'static [u8]),
+    Shared(Arc<[u8]>),
+}
+
+impl Debug for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Static(bytes) => f.debug_tuple("Static").field(&bytes.len()).finish(),
+            Self::Shared(bytes) => f.debug_tuple("Shared").field(&bytes.len()).finish(),
+        }
+    }
+}
+
+impl From<&'static [u8]> for Bytes {
+    #[inline]
+    fn from(value: &'static [u8]) -> Self {
+        Self::Static(value)
+    }
+}
+
+impl<const N: usize> From<&'static [u8; N]> for Bytes {
+    #[inline]
+    fn from(value: &'static [u8; N]) -> Self {
+        Self::Static(value)
+    }
+}
+
+impl From<Arc<[u8]>> for Bytes {
+    #[inline]
+    fn from(value: Arc<[u8]>) -> Self {
+        Self::Shared(value)
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        Self::Shared(value.into())
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Static(bytes) => bytes,
+            Self::Shared(bytes) => bytes,
+        }
+    }
+}
+
+/// The result of [`BytesLoader::load`].
+#[derive(Clone, Debug)]
+pub enum BytesPoll {
+    /// Still loading. Show a placeholder.
+    Pending {
+        /// The size of the image, if the loader knows it already.
+        size: Option<Vec2>,
+    },
+
+    /// Done loading.
+    Ready {
+        /// The size of the image, if the loader knows it already.
+        size: Option<Vec2>,
+
+        /// The contents of the resource.
+        bytes: Bytes,
+
+        /// The MIME type of the resource, if known, e.g. `image/png`.
+        mime: Option<String>,
+    },
+}
+
+pub type BytesLoadResult = Result<BytesPoll>;
+
+/// Turns a URI into raw bytes.
+///
+/// Implementations should cache their results, so that loading the same URI every frame is cheap.
+pub trait BytesLoader {
+    /// Unique id of this loader, e.g. its type name.
+    ///
+    /// Used by [`Context::is_loader_installed`] to avoid installing the same loader twice.
+    fn id(&self) -> &str;
+
+    /// Try loading the bytes at `uri`.
+    ///
+    /// Return [`LoadError::NotSupported`] if this loader doesn't handle the URI scheme,
+    /// so that the next loader is tried.
+    ///
+    /// If loading is asynchronous, return [`BytesPoll::Pending`], and call [`Context::request_repaint`] when done.
+    fn load(&self, ctx: &Context, uri: &str) -> BytesLoadResult;
+
+    /// Forget the cached bytes of `uri`, so they are loaded again next time.
+    fn forget(&self, uri: &str);
+
+    /// Forget all cached bytes.
+    fn forget_all(&self);
+
+    /// How much memory the cache uses, in bytes.
+    fn byte_size(&self) -> usize;
+}
+
+// ----------------------------------------------------------------------------
+
+/// The result of [`ImageLoader::load`].
+#[derive(Clone)]
+pub enum ImagePoll {
+    /// Still loading. Show a placeholder.
+    Pending {
+        /// The size of the image, if the loader knows it already.
+        size: Option<Vec2>,
+    },
+
+    /// Done loading.
+    Ready { image: Arc<ColorImage> },
+}
+
+pub type ImageLoadResult = Result<ImagePoll>;
+
+/// Decodes an image, usually from bytes loaded with [`Context::try_load_bytes`].
+///
+/// Implementations should cache their results, so that loading the same URI every frame is cheap.
+pub trait ImageLoader {
+    /// Unique id of this loader, e.g. its type name.
+    fn id(&self) -> &str;
+
+    /// Try loading the image at `uri`.
+    ///
+    /// Return [`LoadError::NotSupported`] if this loader doesn't handle the image format,
+    /// so that the next loader is tried.
+    fn load(&self, ctx: &Context, uri: &str, size_hint: SizeHint) -> ImageLoadResult;
+
+    /// Forget the cached image of `uri`, so it is loaded again next time.
+    fn forget(&self, uri: &str);
+
+    /// Forget all cached images.
+    fn forget_all(&self);
+
+    /// How much memory the cache uses, in bytes.
+    fn byte_size(&self) -> usize;
+}
+
+// ----------------------------------------------------------------------------
+
+/// A texture together with its size, in points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SizedTexture {
+    pub id: TextureId,
+    pub size: Vec2,
+}
+
+impl SizedTexture {
+    pub fn new(id: impl Into<TextureId>, size: impl Into<Vec2>) -> Self {
+        Self {
+            id: id.into(),
+            size: size.into(),
+        }
+    }
+
+    /// Use the size of the texture in pixels as its size in points.
+    pub fn from_handle(handle: &TextureHandle) -> Self {
+        Self::new(handle, handle.size_vec2())
+    }
+}
+
+impl From<(TextureId, Vec2)> for SizedTexture {
+    #[inline]
+    fn from((id, size): (TextureId, Vec2)) -> Self {
+        Self::new(id, size)
+    }
+}
+
+impl<'a> From<&'a TextureHandle> for SizedTexture {
+    #[inline]
+    fn from(handle: &'a TextureHandle) -> Self {
+        Self::from_handle(handle)
+    }
+}
+
+/// The result of [`TextureLoader::load`].
+#[derive(Clone, Copy, Debug)]
+pub enum TexturePoll {
+    /// Still loading. Show a placeholder.
+    Pending {
+        /// The size of the image, if the loader knows it already.
+        size: Option<Vec2>,
+    },
+
+    /// Done loading.
+    Ready { texture: SizedTexture },
+}
+
+impl TexturePoll {
+    /// The size of the image, if known.
+    pub fn size(&self) -> Option<Vec2> {
+        match self {
+            Self::Pending { size } => *size,
+            Self::Ready { texture } => Some(texture.size),
+        }
+    }
+
+    /// The texture, if done loading.
+    pub fn texture_id(&self) -> Option<TextureId> {
+        match self {
+            Self::Pending { .. } => None,
+            Self::Ready { texture } => Some(texture.id),
+        }
+    }
+}
+
+pub type TextureLoadResult = Result<TexturePoll>;
+
+/// Uploads an image, usually loaded with [`Context::try_load_image`], to a texture.
+pub trait TextureLoader {
+    /// Unique id of this loader, e.g. its type name.
+    fn id(&self) -> &str;
+
+    /// Try loading the texture of `uri`.
+    fn load(
+        &self,
+        ctx: &Context,
+        uri: &str,
+        texture_options: TextureOptions,
+        size_hint: SizeHint,
+    ) -> TextureLoadResult;
+
+    /// Forget the cached texture of `uri`, freeing it.
+    fn forget(&self, uri: &str);
+
+    /// Forget all cached textures.
+    fn forget_all(&self);
+
+    /// How much GPU memory the cached textures use, in bytes.
+    fn byte_size(&self) -> usize;
+}
+
+// ----------------------------------------------------------------------------
+
+type BytesLoaderImpl = Arc<dyn BytesLoader + Send + Sync + 'static>;
+type ImageLoaderImpl = Arc<dyn ImageLoader + Send + Sync + 'static>;
+type TextureLoaderImpl = Arc<dyn TextureLoader + Send + Sync + 'static>;
+
+/// The loaders installed in a [`Context`].
+///
+/// Each list is tried from the back, so loaders added later take precedence.
+pub struct Loaders {
+    /// Serves the bytes added with [`Context::include_bytes`].
+    pub include: Arc<DefaultBytesLoader>,
+    pub bytes: Mutex<Vec<BytesLoaderImpl>>,
+    pub image: Mutex<Vec<ImageLoaderImpl>>,
+    pub texture: Mutex<Vec<TextureLoaderImpl>>,
+}
+
+impl Default for Loaders {
+    fn default() -> Self {
+        let include = Arc::new(DefaultBytesLoader::default());
+        Self {
+            bytes: Mutex::new(vec![include.clone()]),
+            image: Mutex::new(Vec::new()),
+            texture: Mutex::new(vec![Arc::new(DefaultTextureLoader::default())]),
+            include,
+        }
+    }
+}
+
+/// Try each loader, most recently added first, until one supports the URI.
+fn try_loaders<L: ?Sized, T>(
+    loaders: &[Arc<L>],
+    load: impl Fn(&L) -> Result<T>,
+) -> Option<Result<T>> {
+    loaders
+        .iter()
+        .rev()
+        .map(|loader| load(loader))
+        .find(|result| !matches!(result, Err(LoadError::NotSupported)))
+}
+
+/// ## Loading images and other resources by URI
+impl Context {
+    /// Associate some static bytes with a `uri`.
+    ///
+    /// The same `uri` may be passed to [`crate::Ui::image`] later to load the bytes as an image.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.ctx().include_bytes("bytes://my_icon.svg", b"<svg></svg>");
+    /// ui.image("bytes://my_icon.svg");
+    /// # });
+    /// ```
+    pub fn include_bytes(&self, uri: impl Into<Cow<'static, str>>, bytes: impl Into<Bytes>) {
+        self.loaders().include.insert(uri, bytes);
+    }
+
+    /// Is a loader with this [`BytesLoader::id`] (or image/texture loader id) installed?
+    pub fn is_loader_installed(&self, id: &str) -> bool {
+        let loaders = self.loaders();
+        let is_installed = loaders.bytes.lock().iter().any(|l| l.id() == id)
+            || loaders.image.lock().iter().any(|l| l.id() == id)
+            || loaders.texture.lock().iter().any(|l| l.id() == id);
+        is_installed
+    }
+
+    /// Add a new bytes loader. It will be tried before all previously added ones.
+    pub fn add_bytes_loader(&self, loader: Arc<dyn BytesLoader + Send + Sync + 'static>) {
+        self.loaders().bytes.lock().push(loader);
+    }
+
+    /// Add a new image loader. It will be tried before all previously added ones.
+    pub fn add_image_loader(&self, loader: Arc<dyn ImageLoader + Send + Sync + 'static>) {
+        self.loaders().image.lock().push(loader);
+    }
+
+    /// Add a new texture loader. It will be tried before all previously added ones.
+    pub fn add_texture_loader(&self, loader: Arc<dyn TextureLoader + Send + Sync + 'static>) {
+        self.loaders().texture.lock().push(loader);
+    }
+
+    /// Forget everything cached for `uri`, so it is loaded again next time it is used.
+    ///
+    /// Call this when e.g. the file changed on disk.
+    pub fn forget_image(&self, uri: &str) {
+        let loaders = self.loaders();
+        for loader in loaders.bytes.lock().iter() {
+            loader.forget(uri);
+        }
+        for loader in loaders.image.lock().iter() {
+            loader.forget(uri);
+        }
+        for loader in loaders.texture.lock().iter() {
+            loader.forget(uri);
+        }
+    }
+
+    /// Forget all cached images, bytes and textures of the loaders.
+    pub fn forget_all_images(&self) {
+        let loaders = self.loaders();
+        for loader in loaders.bytes.lock().iter() {
+            loader.forget_all();
+        }
+        for loader in loaders.image.lock().iter() {
+            loader.forget_all();
+        }
+        for loader in loaders.texture.lock().iter() {
+            loader.forget_all();
+        }
+    }
+
+    /// Try loading the raw bytes of `uri` using the installed [`BytesLoader`]s.
+    ///
+    /// # Errors
+    /// [`LoadError::NotSupported`] if no loader supports the URI,
+    /// or [`LoadError::Loading`] if loading failed.
+    pub fn try_load_bytes(&self, uri: &str) -> BytesLoadResult {
+        let loaders = self.loaders().bytes.lock().clone();
+        try_loaders(&loaders, |loader| loader.load(self, uri))
+            .unwrap_or(Err(LoadError::NotSupported))
+    }
+
+    /// Try loading and decoding the image at `uri` using the installed [`ImageLoader`]s.
+    ///
+    /// # Errors
+    /// [`LoadError::NoImageLoaders`] if there are no image loaders installed,
+    /// [`LoadError::NotSupported`] if none of them support the image,
+    /// or [`LoadError::Loading`] if loading failed.
+    pub fn try_load_image(&self, uri: &str, size_hint: SizeHint) -> ImageLoadResult {
+        let loaders = self.loaders().image.lock().clone();
+        if loaders.is_empty() {
+            return Err(LoadError::NoImageLoaders);
+        }
+        try_loaders(&loaders, |loader| loader.load(self, uri, size_hint))
+            .unwrap_or(Err(LoadError::NotSupported))
+    }
+
+    /// Try loading the image at `uri` into a texture, using the installed [`TextureLoader`]s.
+    ///
+    /// This is what [`crate::Image`] uses.
+    ///
+    /// # Errors
+    /// See [`Self::try_load_image`].
+    pub fn try_load_texture(
+        &self,
+        uri: &str,
+        texture_options: TextureOptions,
+        size_hint: SizeHint,
+    ) -> TextureLoadResult {
+        let loaders = self.loaders().texture.lock().clone();
+        try_loaders(&loaders, |loader| {
+            loader.load(self, uri, texture_options, size_hint)
+        })
+        .unwrap_or(Err(LoadError::NotSupported))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Where an [`crate::Image`] comes from.
+#[derive(Clone, Debug)]
+pub enum ImageSource {
+    /// Load the image from a URI, e.g. `file://assets/ferris.png`, using the installed loaders.
+    Uri(Cow<'static, str>),
+
+    /// An already loaded texture.
+    Texture(SizedTexture),
+
+    /// Decode these bytes using the installed image loaders.
+    ///
+    /// The `uri` is used as the cache key, so it must be unique for these bytes.
+    Bytes {
+        uri: Cow<'static, str>,
+        bytes: Bytes,
+    },
+}
+
+impl ImageSource {
+    /// The URI of the image, if it is loaded through the loaders.
+    pub fn uri(&self) -> Option<&str> {
+        match self {
+            Self::Uri(uri) | Self::Bytes { uri, .. } => Some(uri),
+            Self::Texture(_) => None,
+        }
+    }
+
+    /// Load the image as a texture, using the installed loaders if needed.
+    ///
+    /// # Errors
+    /// See [`Context::try_load_image`].
+    pub fn load(
+        &self,
+        ctx: &Context,
+        texture_options: TextureOptions,
+        size_hint: SizeHint,
+    ) -> TextureLoadResult {
+        match self {
+            Self::Texture(texture) => Ok(TexturePoll::Ready { texture: *texture }),
+            Self::Uri(uri) => ctx.try_load_texture(uri, texture_options, size_hint),
+            Self::Bytes { uri, bytes } => {
+                ctx.include_bytes(uri.clone(), bytes.clone());
+                ctx.try_load_texture(uri, texture_options, size_hint)
+            }
+        }
+    }
+}
+
+impl From<&'static str> for ImageSource {
+    #[inline]
+    fn from(uri: &'static str) -> Self {
+        Self::Uri(uri.into())
+    }
+}
+
+impl From<String> for ImageSource {
+    #[inline]
+    fn from(uri: String) -> Self {
+        Self::Uri(uri.into())
+    }
+}
+
+impl From<SizedTexture> for ImageSource {
+    #[inline]
+    fn from(texture: SizedTexture) -> Self {
+        Self::Texture(texture)
+    }
+}
+
+impl From<(TextureId, Vec2)> for ImageSource {
+    #[inline]
+    fn from(texture: (TextureId, Vec2)) -> Self {
+        Self::Texture(texture.into())
+    }
+}
+
+impl<'a> From<&'a TextureHandle> for ImageSource {
+    #[inline]
+    fn from(handle: &'a TextureHandle) -> Self {
+        Self::Texture(handle.into())
+    }
+}
+
+impl<T: Into<Bytes>> From<(&'static str, T)> for ImageSource {
+    #[inline]
+    fn from((uri, bytes): (&'static str, T)) -> Self {
+        Self::Bytes {
+            uri: uri.into(),
+            bytes: bytes.into(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A cache keyed by URI, shared by the default loaders.
+pub(crate) type UriMap<V> = HashMap<Cow<'static, str>, V>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes any bytes into an image of the hinted size (1x1 by default), counting the decodes.
+    #[derive(Default)]
+    struct CountingLoader(Mutex<usize>);
+
+    impl ImageLoader for CountingLoader {
+        fn id(&self) -> &str {
+            "CountingLoader"
+        }
+
+        fn load(&self, ctx: &Context, uri: &str, size_hint: SizeHint) -> ImageLoadResult {
+            match ctx.try_load_bytes(uri)? {
+                BytesPoll::Ready { .. } => {
+                    *self.0.lock() += 1;
+                    let size = match size_hint {
+                        SizeHint::Size(w, h) => [w as usize, h as usize],
+                        _ => [1, 1],
+                    };
+                    Ok(ImagePoll::Ready {
+                        image: Arc::new(ColorImage::new(size, crate::Color32::RED)),
+                    })
+                }
+                BytesPoll::Pending { size } => Ok(ImagePoll::Pending { size }),
+            }
+        }
+
+        fn forget(&self, _: &str) {}
+
+        fn forget_all(&self) {}
+
+        fn byte_size(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn load_texture_from_bytes() {
+        let ctx = Context::default();
+        let source = ImageSource::from(("bytes://red.png", b"not really a png"));
+
+        assert!(matches!(
+            source.load(&ctx, Default::default(), SizeHint::Original),
+            Err(LoadError::NoImageLoaders)
+        ));
+
+        let loader = Arc::new(CountingLoader::default());
+        ctx.add_image_loader(loader.clone());
+        assert!(ctx.is_loader_installed("CountingLoader"));
+
+        for _ in 0..2 {
+            let poll = source
+                .load(&ctx, Default::default(), SizeHint::Original)
+                .unwrap();
+            assert_eq!(poll.size(), Some(Vec2::splat(1.0)));
+            assert!(poll.texture_id().is_some());
+        }
+        assert_eq!(*loader.0.lock(), 1, "the texture should be cached");
+
+        assert!(matches!(
+            ctx.try_load_texture(
+                "bytes://missing.png",
+                Default::default(),
+                SizeHint::Original
+            ),
+            Err(LoadError::Loading(_))
+        ));
+    }
+
+    #[test]
+    fn textures_are_cached_per_size_hint() {
+        let ctx = Context::default();
+        let loader = Arc::new(CountingLoader::default());
+        ctx.add_image_loader(loader.clone());
+        let source = ImageSource::from(("bytes://icon.svg", b"not really an svg"));
+
+        for _ in 0..2 {
+            for size in [2, 4] {
+                let poll = source
+                    .load(&ctx, Default::default(), SizeHint::Size(size, size))
+                    .unwrap();
+                assert_eq!(poll.size(), Some(Vec2::splat(size as f32)));
+            }
+        }
+        assert_eq!(*loader.0.lock(), 2, "each size should be decoded once");
+    }
+
+    /// Ignores the size hint, like the decoders of raster images.
+    struct RasterLoader(Arc<ColorImage>);
+
+    impl ImageLoader for RasterLoader {
+        fn id(&self) -> &str {
+            "RasterLoader"
+        }
+
+        fn load(&self, _: &Context, _: &str, _: SizeHint) -> ImageLoadResult {
+            Ok(ImagePoll::Ready {
+                image: self.0.clone(),
+            })
+        }
+
+        fn forget(&self, _: &str) {}
+
+        fn forget_all(&self) {}
+
+        fn byte_size(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn textures_are_not_kept_for_every_size() {
+        let load = |ctx: &Context, textures: &DefaultTextureLoader, uri: &str, size: u32| {
+            let hint = SizeHint::Size(size, size);
+            let poll = textures.load(ctx, uri, Default::default(), hint).unwrap();
+            assert!(poll.texture_id().is_some());
+        };
+
+        // Resizing an image that is rasterized at the size it is shown at
+        // only keeps the sizes of the current and previous frame:
+        let ctx = Context::default();
+        ctx.add_image_loader(Arc::new(CountingLoader::default()));
+        ctx.include_bytes("bytes://icon.svg", b"not really an svg");
+        let textures = DefaultTextureLoader::default();
+        for size in 1..=10 {
+            let _ = ctx.run(Default::default(), |ctx| {
+                load(ctx, &textures, "bytes://icon.svg", size);
+            });
+        }
+        assert_eq!(textures.byte_size(), (9 * 9 + 10 * 10) * 4);
+
+        // Images that don't depend on the size hint share a single texture:
+        let ctx = Context::default();
+        let image = ColorImage::new([3, 3], crate::Color32::RED);
+        ctx.add_image_loader(Arc::new(RasterLoader(Arc::new(image))));
+        let textures = DefaultTextureLoader::default();
+        for size in 1..=10 {
+            load(&ctx, &textures, "bytes://ferris.png", size);
+        }
+        assert_eq!(textures.byte_size(), 3 * 3 * 4);
+    }
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+/// Serves the bytes added with [`Context::include_bytes`].
+///
+/// Any URI can be included this way, but `bytes://` URIs can only be loaded by this loader.
+#[derive(Default)]
+pub struct DefaultBytesLoader {
+    cache: Mutex<UriMap<Bytes>>,
+}
+
+impl DefaultBytesLoader {
+    pub fn insert(&self, uri: impl Into<Cow<'static, str>>, bytes: impl Into<Bytes>) {
+        self.cache
+            .lock()
+            .entry(uri.into())
+            .or_insert_with(|| bytes.into());
+    }
+}
+
+impl BytesLoader for DefaultBytesLoader {
+    fn id(&self) -> &str {
+        "egui::load::DefaultBytesLoader"
+    }
+
+    fn load(&self, _: &Context, uri: &str) -> BytesLoadResult {
+        match self.cache.lock().get(uri).cloned() {
+            Some(bytes) => Ok(BytesPoll::Ready {
+                size: None,
+                bytes,
+                mime: None,
+            }),
+            None if uri.starts_with("bytes://") => Err(LoadError::Loading(format!(
+                "Bytes not found for {uri:?}. Did you forget to call Context::include_bytes?"
+            ))),
+            None => Err(LoadError::NotSupported),
+        }
+    }
+
+    fn forget(&self, uri: &str) {
+        let _ = self.cache.lock().remove(uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache.lock().values().map(|bytes| bytes.len()).sum()
+    }
+}
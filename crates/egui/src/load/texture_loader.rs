@@ -0,0 +1,118 @@
+use super::*;
+
+struct Entry {
+    /// The size hint the texture was loaded with,
+    /// or `None` if the image doesn't depend on it (e.g. a PNG, which is always decoded at its original size).
+    size_hint: Option<SizeHint>,
+
+    /// The image the texture was made from, to tell if another size hint gives the same image.
+    image: Arc<ColorImage>,
+
+    handle: TextureHandle,
+
+    /// The last frame the texture was asked for.
+    last_used: u64,
+}
+
+/// Uploads the images decoded by the [`ImageLoader`]s to textures, and keeps them alive.
+///
+/// Images that are rasterized at the size they are shown at (e.g. SVGs) get a texture per size,
+/// but only the sizes asked for in the current or previous frame are kept,
+/// so resizing such an image doesn't pile up textures.
+#[derive(Default)]
+pub struct DefaultTextureLoader {
+    cache: Mutex<HashMap<(String, TextureOptions), Vec<Entry>>>,
+}
+
+impl DefaultTextureLoader {
+    /// Use the cached texture of `image`, if any, for all size hints from now on.
+    fn reuse_texture(
+        &self,
+        key: &(String, TextureOptions),
+        image: &Arc<ColorImage>,
+        frame_nr: u64,
+    ) -> Option<SizedTexture> {
+        let mut cache = self.cache.lock();
+        let entry = cache
+            .get_mut(key)?
+            .iter_mut()
+            .find(|entry| Arc::ptr_eq(&entry.image, image))?;
+        entry.size_hint = None;
+        entry.last_used = frame_nr;
+        Some(SizedTexture::from_handle(&entry.handle))
+    }
+}
+
+impl TextureLoader for DefaultTextureLoader {
+    fn id(&self) -> &str {
+        "egui::load::DefaultTextureLoader"
+    }
+
+    fn load(
+        &self,
+        ctx: &Context,
+        uri: &str,
+        texture_options: TextureOptions,
+        size_hint: SizeHint,
+    ) -> TextureLoadResult {
+        let key = (uri.to_owned(), texture_options);
+        let frame_nr = ctx.frame_nr();
+
+        if let Some(entry) = self.cache.lock().get_mut(&key).and_then(|entries| {
+            entries
+                .iter_mut()
+                .find(|entry| entry.size_hint.map_or(true, |hint| hint == size_hint))
+        }) {
+            entry.last_used = frame_nr;
+            let texture = SizedTexture::from_handle(&entry.handle);
+            return Ok(TexturePoll::Ready { texture });
+        }
+
+        // The cache is not locked here, since loading calls back into the context:
+        match ctx.try_load_image(uri, size_hint)? {
+            ImagePoll::Pending { size } => Ok(TexturePoll::Pending { size }),
+            ImagePoll::Ready { image } => {
+                // Image loaders that ignore the size hint give us the image we already have:
+                if let Some(texture) = self.reuse_texture(&key, &image, frame_nr) {
+                    return Ok(TexturePoll::Ready { texture });
+                }
+
+                let handle = ctx.load_texture(uri, ColorImage::clone(&image), texture_options);
+                let texture = SizedTexture::from_handle(&handle);
+
+                let mut cache = self.cache.lock();
+                let entries = cache.entry(key).or_default();
+                entries.retain(|entry| entry.last_used + 1 >= frame_nr);
+                entries.push(Entry {
+                    size_hint: Some(size_hint),
+                    image,
+                    handle,
+                    last_used: frame_nr,
+                });
+                Ok(TexturePoll::Ready { texture })
+            }
+        }
+    }
+
+    fn forget(&self, uri: &str) {
+        self.cache
+            .lock()
+            .retain(|(cached_uri, _), _| cached_uri != uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache
+            .lock()
+            .values()
+            .flatten()
+            .map(|entry| {
+                let [w, h] = entry.handle.size();
+                w * h * 4
+            })
+            .sum()
+    }
+}
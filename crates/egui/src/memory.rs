@@ -1,4 +1,6 @@
-use crate::{area, window, Id, IdMap, InputState, LayerId, Pos2, Rect, Style};
+use std::{any::Any, sync::Arc};
+
+use crate::{area, window, Affine2, Id, IdMap, InputState, LayerId, Pos2, Rect, Style};
 
 // ----------------------------------------------------------------------------
 
@@ -103,6 +105,11 @@ pub struct Options {
     /// Controls the tessellator.
     pub tessellation_options: epaint::TessellationOptions,
 
+    /// Controls how text is rasterized, e.g. with subpixel positioning.
+    ///
+    /// Changing this clears the font atlas.
+    pub text_rasterization: epaint::text::TextRasterization,
+
     /// This is a signal to any backend that we want the [`crate::PlatformOutput::events`] read out loud.
     ///
     /// The only change to egui is that labels can be focused by pressing tab.
@@ -133,6 +140,7 @@ impl Default for Options {
         Self {
             style: Default::default(),
             tessellation_options: Default::default(),
+            text_rasterization: Default::default(),
             screen_reader: false,
             preload_font_glyphs: true,
             warn_on_id_clash: cfg!(debug_assertions),
@@ -173,6 +181,20 @@ pub(crate) struct Interaction {
     /// Any interest in catching clicks this frame?
     /// Cleared to false at start of each frame.
     pub drag_interest: bool,
+
+    /// What is being dragged, see [`Memory::set_dnd_payload`].
+    /// Cleared when the drag ends.
+    pub drag_payload: Option<DragPayload>,
+}
+
+/// The payload of a drag-and-drop operation.
+#[derive(Clone)]
+pub(crate) struct DragPayload(Arc<dyn Any + Send + Sync>);
+
+impl std::fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragPayload").finish_non_exhaustive()
+    }
 }
 
 /// Keeps tracks of what widget has keyboard focus
@@ -229,6 +251,7 @@ impl Interaction {
             // pointer button was not down last frame
             self.click_id = None;
             self.drag_id = None;
+            self.drag_payload = None;
         }
 
         self.focus.begin_frame(new_input);
@@ -374,6 +397,28 @@ impl Memory {
         self.areas.layer_id_at(pos, resize_interact_radius_side)
     }
 
+    /// The layer of the top-most [`crate::Modal`] shown last frame, if any.
+    ///
+    /// Only this layer and the layers above it can be interacted with.
+    pub fn top_modal_layer(&self) -> Option<LayerId> {
+        self.areas.top_modal_layer
+    }
+
+    /// Mark `layer_id` as a modal layer for this frame.
+    ///
+    /// Starting next frame, layers below the top-most modal layer will not receive any input.
+    /// This is called by [`crate::Modal`].
+    pub fn set_modal_layer(&mut self, layer_id: LayerId) {
+        self.areas.set_modal_layer(layer_id);
+    }
+
+    /// Can widgets in this layer be interacted with, or is it blocked by a [`crate::Modal`]?
+    pub fn allows_interaction(&self, layer_id: LayerId) -> bool {
+        self.areas.top_modal_layer.map_or(true, |modal| {
+            layer_id == modal || self.areas.is_above(layer_id, modal)
+        })
+    }
+
     /// An iterator over all layers. Back-to-front. Top is last.
     pub fn layer_ids(&self) -> impl ExactSizeIterator<Item = LayerId> + '_ {
         self.areas.order().iter().copied()
@@ -480,6 +525,40 @@ impl Memory {
     #[inline(always)]
     pub fn stop_dragging(&mut self) {
         self.interaction.drag_id = None;
+        self.interaction.drag_payload = None;
+    }
+
+    /// Attach a payload to the current drag, for drop targets to receive.
+    ///
+    /// The payload is cleared when the drag ends.
+    /// Usually you call [`Response::dnd_set_drag_payload`](crate::Response::dnd_set_drag_payload) instead.
+    pub fn set_dnd_payload<Payload: Any + Send + Sync>(&mut self, payload: Payload) {
+        self.interaction.drag_payload = Some(DragPayload(Arc::new(payload)));
+    }
+
+    /// Is anything with a payload being dragged?
+    pub fn has_dnd_payload(&self) -> bool {
+        self.interaction.drag_payload.is_some()
+    }
+
+    /// The payload of the current drag, if it is of type `Payload`.
+    pub fn dnd_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
+        let payload = self.interaction.drag_payload.clone()?;
+        payload.0.downcast().ok()
+    }
+
+    /// Remove the payload of the current drag, if it is of type `Payload`.
+    ///
+    /// This makes sure only one drop target receives the payload.
+    pub fn take_dnd_payload<Payload: Any + Send + Sync>(&mut self) -> Option<Arc<Payload>> {
+        let payload = self.interaction.drag_payload.take()?;
+        match payload.0.downcast() {
+            Ok(payload) => Some(payload),
+            Err(payload) => {
+                self.interaction.drag_payload = Some(DragPayload(payload));
+                None
+            }
+        }
     }
 
     /// Forget window positions, sizes etc.
@@ -557,6 +636,18 @@ pub struct Areas {
     /// So if you close three windows and then reopen them all in one frame,
     /// they will all be sent to the top, but keep their previous internal order.
     wants_to_be_on_top: ahash::HashSet<LayerId>,
+
+    /// The top-most modal layer of the previous frame, see [`Memory::top_modal_layer`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    top_modal_layer: Option<LayerId>,
+
+    /// The top-most modal layer shown so far this frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    top_modal_layer_current_frame: Option<LayerId>,
+
+    /// Layers that are painted with a transform, see [`crate::Context::set_transform_layer`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    transforms: ahash::HashMap<LayerId, Affine2>,
 }
 
 impl Areas {
@@ -586,6 +677,7 @@ impl Areas {
         for layer in self.order.iter().rev() {
             if self.is_visible(layer) {
                 if let Some(state) = self.areas.get(&layer.id) {
+                    let pos = self.screen_to_layer_pos(*layer, pos);
                     let mut rect = state.rect();
                     if state.interactable {
                         // Allow us to resize by dragging just outside the window:
@@ -600,6 +692,31 @@ impl Areas {
         None
     }
 
+    /// The transform the layer is painted with, if any.
+    pub fn transform(&self, layer_id: LayerId) -> Option<Affine2> {
+        self.transforms.get(&layer_id).copied()
+    }
+
+    pub(crate) fn set_transform(&mut self, layer_id: LayerId, transform: Affine2) {
+        if transform.is_identity() {
+            self.transforms.remove(&layer_id);
+        } else {
+            self.transforms.insert(layer_id, transform);
+        }
+    }
+
+    pub(crate) fn transforms(&self) -> &ahash::HashMap<LayerId, Affine2> {
+        &self.transforms
+    }
+
+    /// Where on the layer a position on screen is, taking its transform into account.
+    pub fn screen_to_layer_pos(&self, layer_id: LayerId, pos: Pos2) -> Pos2 {
+        match self.transform(layer_id).and_then(|t| t.inverse()) {
+            Some(inverse) => inverse * pos,
+            None => pos,
+        }
+    }
+
     pub fn visible_last_frame(&self, layer_id: &LayerId) -> bool {
         self.visible_last_frame.contains(layer_id)
     }
@@ -624,6 +741,30 @@ impl Areas {
             .collect()
     }
 
+    /// Is `layer_id` painted above `other`?
+    ///
+    /// Layers that haven't been painted yet are considered to be on top.
+    pub(crate) fn is_above(&self, layer_id: LayerId, other: LayerId) -> bool {
+        if layer_id.order != other.order {
+            return layer_id.order > other.order;
+        }
+        let index_of = |layer_id| self.order.iter().position(|x| *x == layer_id);
+        match (index_of(layer_id), index_of(other)) {
+            (Some(index), Some(other_index)) => index > other_index,
+            (None, _) => true,
+            (Some(_), None) => false,
+        }
+    }
+
+    pub(crate) fn set_modal_layer(&mut self, layer_id: LayerId) {
+        let is_top = self
+            .top_modal_layer_current_frame
+            .map_or(true, |top| self.is_above(layer_id, top));
+        if is_top {
+            self.top_modal_layer_current_frame = Some(layer_id);
+        }
+    }
+
     pub fn move_to_top(&mut self, layer_id: LayerId) {
         self.visible_current_frame.insert(layer_id);
         self.wants_to_be_on_top.insert(layer_id);
@@ -639,9 +780,12 @@ impl Areas {
             visible_current_frame,
             order,
             wants_to_be_on_top,
+            top_modal_layer,
+            top_modal_layer_current_frame,
             ..
         } = self;
 
+        *top_modal_layer = top_modal_layer_current_frame.take();
         std::mem::swap(visible_last_frame, visible_current_frame);
         visible_current_frame.clear();
         order.sort_by_key(|layer| (layer.order, wants_to_be_on_top.contains(layer)));
@@ -2,13 +2,13 @@ use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 use crate::{
-    emath::{Align2, Pos2, Rect, Vec2},
+    emath::{Affine2, Align2, Pos2, Rect, Vec2},
     layers::{LayerId, PaintList, ShapeIdx},
     Color32, Context, FontId,
 };
 use epaint::{
     text::{Fonts, Galley},
-    CircleShape, RectShape, Rounding, Shape, Stroke,
+    CircleShape, Fill, Gradient, RectShape, Rounding, Shape, Stroke,
 };
 
 /// Helper to paint shapes and text to a specific region on a specific layer.
@@ -29,6 +29,9 @@ pub struct Painter {
     /// If set, all shapes will have their colors modified to be closer to this.
     /// This is used to implement grayed out interfaces.
     fade_to_color: Option<Color32>,
+
+    /// If set, all shapes (and the clip rectangle) will be transformed by this.
+    transform: Option<Affine2>,
 }
 
 impl Painter {
@@ -39,6 +42,7 @@ impl Painter {
             layer_id,
             clip_rect,
             fade_to_color: None,
+            transform: None,
         }
     }
 
@@ -50,6 +54,7 @@ impl Painter {
             layer_id,
             clip_rect: self.clip_rect,
             fade_to_color: None,
+            transform: self.transform,
         }
     }
 
@@ -63,9 +68,49 @@ impl Painter {
             layer_id: self.layer_id,
             clip_rect: rect.intersect(self.clip_rect),
             fade_to_color: self.fade_to_color,
+            transform: self.transform,
         }
     }
 
+    /// Transform everything painted with the returned [`Painter`],
+    /// on top of any transform this one already has.
+    ///
+    /// Only the painting is affected. To also transform the interaction with widgets,
+    /// use [`Context::set_transform_layer`] instead.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let rect = ui.max_rect();
+    /// let painter = ui.painter().clone().with_transform(egui::Affine2::around(
+    ///     rect.center(),
+    ///     2.0,
+    ///     std::f32::consts::FRAC_PI_4,
+    /// ));
+    /// painter.rect_filled(egui::Rect::from_center_size(rect.center(), egui::vec2(10.0, 10.0)), 0.0, egui::Color32::RED);
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn with_transform(mut self, transform: Affine2) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Transform everything painted from now on,
+    /// on top of any transform this [`Painter`] already has.
+    pub fn set_transform(&mut self, transform: Affine2) {
+        let transform = match self.transform {
+            Some(current) => current * transform,
+            None => transform,
+        };
+        self.transform = (!transform.is_identity()).then_some(transform);
+    }
+
+    /// The transform everything painted is transformed by, if any.
+    #[inline(always)]
+    pub fn transform(&self) -> Option<Affine2> {
+        self.transform
+    }
+
     /// Redirect where you are painting.
     pub fn set_layer_id(&mut self, layer_id: LayerId) {
         self.layer_id = layer_id;
@@ -92,6 +137,7 @@ impl Painter {
             layer_id: self.layer_id,
             clip_rect: rect.intersect(self.clip_rect),
             fade_to_color: self.fade_to_color,
+            transform: self.transform,
         }
     }
 }
@@ -162,6 +208,17 @@ impl Painter {
         if let Some(fade_to_color) = self.fade_to_color {
             tint_shape_towards(shape, fade_to_color);
         }
+        if let Some(transform) = self.transform {
+            shape.transform(transform);
+        }
+    }
+
+    /// The clip rectangle on screen, taking [`Self::transform`] into account.
+    fn screen_clip_rect(&self) -> Rect {
+        match self.transform {
+            Some(transform) => transform.transform_rect_bounds(self.clip_rect),
+            None => self.clip_rect,
+        }
     }
 
     /// It is up to the caller to make sure there is room for this.
@@ -173,7 +230,8 @@ impl Painter {
         } else {
             let mut shape = shape.into();
             self.transform_shape(&mut shape);
-            self.paint_list(|l| l.add(self.clip_rect, shape))
+            let clip_rect = self.screen_clip_rect();
+            self.paint_list(|l| l.add(clip_rect, shape))
         }
     }
 
@@ -184,12 +242,13 @@ impl Painter {
         if self.fade_to_color == Some(Color32::TRANSPARENT) {
             return;
         }
-        if self.fade_to_color.is_some() {
+        if self.fade_to_color.is_some() || self.transform.is_some() {
+            let clip_rect = self.screen_clip_rect();
             let shapes = shapes.into_iter().map(|mut shape| {
                 self.transform_shape(&mut shape);
                 shape
             });
-            self.paint_list(|l| l.extend(self.clip_rect, shapes));
+            self.paint_list(|l| l.extend(clip_rect, shapes));
         } else {
             self.paint_list(|l| l.extend(self.clip_rect, shapes));
         };
@@ -202,7 +261,8 @@ impl Painter {
         }
         let mut shape = shape.into();
         self.transform_shape(&mut shape);
-        self.paint_list(|l| l.set(idx, self.clip_rect, shape));
+        let clip_rect = self.screen_clip_rect();
+        self.paint_list(|l| l.set(idx, clip_rect, shape));
     }
 }
 
@@ -279,30 +339,24 @@ impl Painter {
         fill_color: impl Into<Color32>,
         stroke: impl Into<Stroke>,
     ) {
-        self.add(CircleShape {
+        self.add(CircleShape::new(
             center,
             radius,
-            fill: fill_color.into(),
-            stroke: stroke.into(),
-        });
+            Fill::Solid(fill_color.into()),
+            stroke,
+        ));
     }
 
     pub fn circle_filled(&self, center: Pos2, radius: f32, fill_color: impl Into<Color32>) {
-        self.add(CircleShape {
+        self.add(CircleShape::filled(
             center,
             radius,
-            fill: fill_color.into(),
-            stroke: Default::default(),
-        });
+            Fill::Solid(fill_color.into()),
+        ));
     }
 
     pub fn circle_stroke(&self, center: Pos2, radius: f32, stroke: impl Into<Stroke>) {
-        self.add(CircleShape {
-            center,
-            radius,
-            fill: Default::default(),
-            stroke: stroke.into(),
-        });
+        self.add(CircleShape::stroke(center, radius, stroke));
     }
 
     pub fn rect(
@@ -312,12 +366,12 @@ impl Painter {
         fill_color: impl Into<Color32>,
         stroke: impl Into<Stroke>,
     ) {
-        self.add(RectShape {
+        self.add(RectShape::new(
             rect,
-            rounding: rounding.into(),
-            fill: fill_color.into(),
-            stroke: stroke.into(),
-        });
+            rounding,
+            Fill::Solid(fill_color.into()),
+            stroke,
+        ));
     }
 
     pub fn rect_filled(
@@ -326,12 +380,11 @@ impl Painter {
         rounding: impl Into<Rounding>,
         fill_color: impl Into<Color32>,
     ) {
-        self.add(RectShape {
+        self.add(RectShape::filled(
             rect,
-            rounding: rounding.into(),
-            fill: fill_color.into(),
-            stroke: Default::default(),
-        });
+            rounding,
+            Fill::Solid(fill_color.into()),
+        ));
     }
 
     pub fn rect_stroke(
@@ -340,12 +393,29 @@ impl Painter {
         rounding: impl Into<Rounding>,
         stroke: impl Into<Stroke>,
     ) {
-        self.add(RectShape {
-            rect,
-            rounding: rounding.into(),
-            fill: Default::default(),
-            stroke: stroke.into(),
-        });
+        self.add(RectShape::stroke(rect, rounding, stroke));
+    }
+
+    /// A circle filled with a [`Gradient`], relative to the bounding rectangle of the circle.
+    pub fn circle_gradient(
+        &self,
+        center: Pos2,
+        radius: f32,
+        gradient: Gradient,
+        stroke: impl Into<Stroke>,
+    ) {
+        self.add(CircleShape::new(center, radius, gradient, stroke));
+    }
+
+    /// A rectangle filled with a [`Gradient`], relative to `rect`.
+    pub fn rect_gradient(
+        &self,
+        rect: Rect,
+        rounding: impl Into<Rounding>,
+        gradient: Gradient,
+        stroke: impl Into<Stroke>,
+    ) {
+        self.add(RectShape::new(rect, rounding, gradient, stroke));
     }
 
     /// Show an arrow starting at `origin` and going in the direction of `vec`, with the length `vec.length()`.
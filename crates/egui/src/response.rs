@@ -1,3 +1,5 @@
+use std::{any::Any, sync::Arc};
+
 use crate::{
     emath::{Align, Pos2, Rect, Vec2},
     menu, Context, CursorIcon, Id, LayerId, PointerButton, Sense, Ui, WidgetText,
@@ -181,6 +183,7 @@ impl Response {
         // We do not use self.clicked(), because we want to catch all clicks within our frame,
         // even if we aren't clickable (or even enabled).
         // This is important for windows and such that should close then the user clicks elsewhere.
+        let transform = self.ctx.layer_transform(self.layer_id);
         self.ctx.input(|i| {
             let pointer = &i.pointer;
 
@@ -192,6 +195,9 @@ impl Response {
                 if self.hovered() {
                     false
                 } else if let Some(pos) = pointer.interact_pos() {
+                    let pos = transform
+                        .and_then(|t| t.inverse())
+                        .map_or(pos, |inverse| inverse * pos);
                     !self.rect.contains(pos)
                 } else {
                     false // clicked without a pointer, weird
@@ -307,10 +313,47 @@ impl Response {
         self.drag_released() && self.ctx.input(|i| i.pointer.button_released(button))
     }
 
+    /// Attach a payload to this drag, for drop targets to receive
+    /// with [`Self::dnd_hover_payload`] and [`Self::dnd_release_payload`].
+    ///
+    /// Does nothing if the widget is not being dragged.
+    /// See also [`Ui::dnd_drag_source`](crate::Ui::dnd_drag_source).
+    pub fn dnd_set_drag_payload<Payload: Any + Send + Sync>(&self, payload: Payload) {
+        if self.dragged() {
+            self.ctx.memory_mut(|mem| mem.set_dnd_payload(payload));
+        }
+    }
+
+    /// Something of type `Payload` is being dragged over this widget.
+    pub fn dnd_hover_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
+        if self.contains_pointer() {
+            self.ctx.memory(|mem| mem.dnd_payload())
+        } else {
+            None
+        }
+    }
+
+    /// Something of type `Payload` was dropped on this widget this frame.
+    ///
+    /// The payload is removed, so only one widget receives it.
+    pub fn dnd_release_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
+        if self.contains_pointer() && self.ctx.input(|i| i.pointer.any_released()) {
+            self.ctx.memory_mut(|mem| mem.take_dnd_payload())
+        } else {
+            None
+        }
+    }
+
+    /// Is the pointer above the widget, even if we are interacting with another widget?
+    fn contains_pointer(&self) -> bool {
+        self.ctx.rect_contains_pointer(self.layer_id, self.rect)
+    }
+
     /// If dragged, how many points were we dragged and in what direction?
     pub fn drag_delta(&self) -> Vec2 {
         if self.dragged() {
-            self.ctx.input(|i| i.pointer.delta())
+            let delta = self.ctx.input(|i| i.pointer.delta());
+            self.ctx.screen_to_layer_vec(self.layer_id, delta)
         } else {
             Vec2::ZERO
         }
@@ -326,7 +369,8 @@ impl Response {
     /// None if the pointer is outside the response area.
     pub fn hover_pos(&self) -> Option<Pos2> {
         if self.hovered() {
-            self.ctx.input(|i| i.pointer.hover_pos())
+            let pos = self.ctx.input(|i| i.pointer.hover_pos())?;
+            Some(self.ctx.screen_to_layer_pos(self.layer_id, pos))
         } else {
             None
         }
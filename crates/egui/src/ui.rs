@@ -1,5 +1,6 @@
 // #![warn(missing_docs)]
 
+use std::any::Any;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -1545,10 +1546,18 @@ impl Ui {
         response
     }
 
-    /// Show an image here with the given size.
+    /// Show an image available at the given URI, or a texture you loaded yourself.
     ///
-    /// In order to display an image you must first acquire a [`TextureHandle`].
-    /// This is best done with [`egui_extras::RetainedImage`](https://docs.rs/egui_extras/latest/egui_extras/image/struct.RetainedImage.html) or [`Context::load_texture`].
+    /// Images loaded by URI use the installed loaders (see [`crate::load`]),
+    /// e.g. with [`egui_extras::install_image_loaders`](https://docs.rs/egui_extras/latest/egui_extras/fn.install_image_loaders.html).
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.image("file://assets/ferris.png");
+    /// # });
+    /// ```
+    ///
+    /// You can also show a [`TextureHandle`], e.g. one you loaded with [`Context::load_texture`]:
     ///
     /// ```
     /// struct MyImage {
@@ -1567,15 +1576,15 @@ impl Ui {
     ///         });
     ///
     ///         // Show the image:
-    ///         ui.image(texture, texture.size_vec2());
+    ///         ui.image(texture);
     ///     }
     /// }
     /// ```
     ///
     /// See also [`crate::Image`] and [`crate::ImageButton`].
     #[inline]
-    pub fn image(&mut self, texture_id: impl Into<TextureId>, size: impl Into<Vec2>) -> Response {
-        Image::new(texture_id, size).ui(self)
+    pub fn image(&mut self, source: impl Into<crate::load::ImageSource>) -> Response {
+        Image::from_source(source).ui(self)
     }
 }
 
@@ -1746,6 +1755,116 @@ impl Ui {
         })
     }
 
+    /// Make the contents draggable, carrying `payload` to wherever they are dropped.
+    ///
+    /// While dragged, the contents follow the mouse cursor.
+    /// Receive the payload with [`Self::dnd_drop_zone`] or [`Response::dnd_release_payload`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let item_id = egui::Id::new("item");
+    /// ui.dnd_drag_source(item_id, 42_u32, |ui| {
+    ///     ui.label("Drag me");
+    /// });
+    ///
+    /// let (_, dropped) = ui.dnd_drop_zone::<u32, _>(egui::Frame::group(ui.style()), |ui| {
+    ///     ui.label("Drop here");
+    /// });
+    /// if let Some(number) = dropped {
+    ///     println!("Dropped {number}");
+    /// }
+    /// # });
+    /// ```
+    pub fn dnd_drag_source<Payload, R>(
+        &mut self,
+        id: Id,
+        payload: Payload,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<R>
+    where
+        Payload: Any + Send + Sync,
+    {
+        let is_being_dragged = self.memory(|mem| mem.is_being_dragged(id));
+
+        let InnerResponse { inner, response } = if is_being_dragged {
+            self.ctx().set_cursor_icon(CursorIcon::Grabbing);
+
+            // Paint the contents to a new layer:
+            let layer_id = LayerId::new(Order::Tooltip, id);
+            let inner_response = self.with_layer_id(layer_id, add_contents);
+
+            // Now we move the visuals of the contents to where the mouse is.
+            // The contents can't be interacted with while dragged anyway,
+            // since anything in `Order::Tooltip` always gets an empty [`Response`].
+            if let Some(pointer_pos) = self.ctx().pointer_interact_pos() {
+                let delta = pointer_pos - inner_response.response.rect.center();
+                self.ctx().translate_layer(layer_id, delta);
+            }
+
+            inner_response
+        } else {
+            self.scope(add_contents)
+        };
+
+        let response = self
+            .interact(response.rect, id, Sense::drag())
+            .on_hover_cursor(CursorIcon::Grab);
+        response.dnd_set_drag_payload(payload);
+
+        InnerResponse::new(inner, response)
+    }
+
+    /// A [`Frame`] that things of type `Payload` can be dropped on.
+    ///
+    /// The frame is highlighted when something that can be dropped is dragged over it,
+    /// and grayed out while something of another type is dragged.
+    ///
+    /// Returns the dropped payload on the frame the mouse button was released.
+    /// See [`Self::dnd_drag_source`] for an example.
+    pub fn dnd_drop_zone<Payload, R>(
+        &mut self,
+        frame: Frame,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> (InnerResponse<R>, Option<Arc<Payload>>)
+    where
+        Payload: Any + Send + Sync,
+    {
+        let is_anything_being_dragged = self.memory(|mem| mem.has_dnd_payload());
+        let can_accept_what_is_being_dragged =
+            self.memory(|mem| mem.dnd_payload::<Payload>().is_some());
+
+        let mut prepared = frame.begin(self);
+        let inner = add_contents(&mut prepared.content_ui);
+
+        let margin = prepared.frame.inner_margin;
+        let content_rect = prepared.content_ui.min_rect();
+        let frame_rect = Rect::from_min_max(
+            content_rect.min - margin.left_top(),
+            content_rect.max + margin.right_bottom(),
+        );
+        let hovered = self.rect_contains_pointer(frame_rect);
+
+        let style = if is_anything_being_dragged && can_accept_what_is_being_dragged && hovered {
+            self.visuals().widgets.active
+        } else {
+            self.visuals().widgets.inactive
+        };
+
+        let mut fill = style.bg_fill;
+        let mut stroke = style.bg_stroke;
+        if is_anything_being_dragged && !can_accept_what_is_being_dragged {
+            fill = self.visuals().gray_out(fill);
+            stroke.color = self.visuals().gray_out(stroke.color);
+        }
+        prepared.frame.fill = fill.into();
+        prepared.frame.stroke = stroke;
+
+        let response = prepared.end(self);
+        let payload = response.dnd_release_payload::<Payload>();
+
+        (InnerResponse::new(inner, response), payload)
+    }
+
     /// A [`CollapsingHeader`] that starts out collapsed.
     pub fn collapsing<R>(
         &mut self,
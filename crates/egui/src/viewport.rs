@@ -0,0 +1,205 @@
+//! Viewports are native OS windows that share one egui [`Context`].
+//!
+//! The first window the integration creates is the _root_ viewport ([`ViewportId::ROOT`]).
+//! From inside that window you can ask for more windows with [`Context::show_viewport`]:
+//!
+//! ```
+//! # let ctx = egui::Context::default();
+//! # ctx.begin_frame(Default::default());
+//! ctx.show_viewport(
+//!     egui::ViewportId::from_hash_of("tool palette"),
+//!     egui::ViewportBuilder::default()
+//!         .with_title("Tools")
+//!         .with_inner_size(egui::vec2(200.0, 300.0)),
+//!     |ui| {
+//!         ui.label("Hello from another window!");
+//!     },
+//! );
+//! # let _ = ctx.end_frame();
+//! ```
+//!
+//! Each viewport has its own [`crate::RawInput`], [`crate::InputState`], layers, focus and pointer interaction,
+//! but they all share fonts, textures, style, and [`crate::Memory::data`].
+//!
+//! If the integration doesn't support multiple native windows
+//! (see [`Context::set_embed_viewports`]), the viewports are instead shown as [`crate::Window`]s
+//! inside the root viewport.
+
+use std::sync::Arc;
+
+use crate::{Context, Id, Pos2, Ui, Vec2};
+
+/// Identifies a viewport, i.e. a native window.
+///
+/// The root viewport, which the integration always creates, is [`ViewportId::ROOT`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ViewportId(pub Id);
+
+impl Default for ViewportId {
+    #[inline]
+    fn default() -> Self {
+        Self::ROOT
+    }
+}
+
+impl ViewportId {
+    /// The first window created by the integration.
+    pub const ROOT: Self = Self(Id::null());
+
+    /// Create a [`ViewportId`] by hashing something unique, e.g. the title of the window.
+    #[inline]
+    pub fn from_hash_of(source: impl std::hash::Hash) -> Self {
+        Self(Id::new(source))
+    }
+
+    /// Is this the root viewport?
+    #[inline]
+    pub fn is_root(&self) -> bool {
+        *self == Self::ROOT
+    }
+}
+
+impl From<ViewportId> for Id {
+    #[inline]
+    fn from(id: ViewportId) -> Self {
+        id.0
+    }
+}
+
+/// A map from [`ViewportId`] to some value.
+pub type ViewportIdMap<V> = std::collections::HashMap<ViewportId, V, crate::id::BuildIdHasher>;
+
+// ----------------------------------------------------------------------------
+
+/// How the native window of a viewport should look when it is created.
+///
+/// Use the builder methods, e.g. [`Self::with_title`].
+/// Fields left as `None` use the defaults of the integration.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewportBuilder {
+    /// The title of the window.
+    pub title: Option<String>,
+
+    /// Initial position of the window, in points, relative to the top left corner of the desktop.
+    pub position: Option<Pos2>,
+
+    /// Initial size of the inside of the window, in points.
+    pub inner_size: Option<Vec2>,
+
+    /// The window can't be made smaller than this, in points.
+    pub min_inner_size: Option<Vec2>,
+
+    /// Can the user resize the window?
+    pub resizable: Option<bool>,
+
+    /// Show the title bar and borders of the window?
+    pub decorations: Option<bool>,
+
+    /// Keep the window above all other windows?
+    pub always_on_top: Option<bool>,
+
+    /// Make the background of the window transparent?
+    pub transparent: Option<bool>,
+}
+
+impl ViewportBuilder {
+    /// The title of the window.
+    #[inline]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Initial position of the window, in points.
+    #[inline]
+    pub fn with_position(mut self, position: impl Into<Pos2>) -> Self {
+        self.position = Some(position.into());
+        self
+    }
+
+    /// Initial size of the inside of the window, in points.
+    #[inline]
+    pub fn with_inner_size(mut self, inner_size: impl Into<Vec2>) -> Self {
+        self.inner_size = Some(inner_size.into());
+        self
+    }
+
+    /// The window can't be made smaller than this, in points.
+    #[inline]
+    pub fn with_min_inner_size(mut self, min_inner_size: impl Into<Vec2>) -> Self {
+        self.min_inner_size = Some(min_inner_size.into());
+        self
+    }
+
+    /// Can the user resize the window?
+    #[inline]
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = Some(resizable);
+        self
+    }
+
+    /// Show the title bar and borders of the window?
+    #[inline]
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.decorations = Some(decorations);
+        self
+    }
+
+    /// Keep the window above all other windows?
+    #[inline]
+    pub fn with_always_on_top(mut self, always_on_top: bool) -> Self {
+        self.always_on_top = Some(always_on_top);
+        self
+    }
+
+    /// Make the background of the window transparent?
+    #[inline]
+    pub fn with_transparent(mut self, transparent: bool) -> Self {
+        self.transparent = Some(transparent);
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The ui code of a viewport.
+///
+/// It is called once per frame of the viewport, which may be on a different frame than the root viewport,
+/// so it can't borrow anything. Share state with the rest of your app using e.g. `Arc<Mutex<…>>`.
+pub type ViewportUiCallback = dyn Fn(&mut Ui) + Send + Sync;
+
+/// A viewport that was requested with [`Context::show_viewport`] during a frame of the root viewport.
+///
+/// Found in [`crate::FullOutput::viewports`].
+#[derive(Clone)]
+pub struct ViewportOutput {
+    /// How to create the native window.
+    pub builder: ViewportBuilder,
+
+    /// The ui code to run for each frame of the viewport.
+    pub ui: Arc<ViewportUiCallback>,
+}
+
+impl ViewportOutput {
+    /// Run the ui code of the viewport in a [`crate::CentralPanel`] that covers the whole window.
+    ///
+    /// Call this inside of [`Context::run`], with [`crate::RawInput::viewport_id`] set to the viewport.
+    pub fn show(&self, ctx: &Context) {
+        crate::CentralPanel::default().show(ctx, |ui| (self.ui)(ui));
+    }
+}
+
+impl std::fmt::Debug for ViewportOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ViewportOutput")
+            .field("builder", &self.builder)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ViewportOutput {
+    fn eq(&self, other: &Self) -> bool {
+        self.builder == other.builder && Arc::ptr_eq(&self.ui, &other.ui)
+    }
+}
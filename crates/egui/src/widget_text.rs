@@ -2,8 +2,8 @@ use std::borrow::Cow;
 use std::sync::Arc;
 
 use crate::{
-    style::WidgetVisuals, text::LayoutJob, Align, Color32, FontFamily, FontSelection, Galley, Pos2,
-    Style, TextStyle, Ui, Visuals,
+    style::WidgetVisuals, text::LayoutJob, Align, Color32, FontFamily, FontSelection, FontStyle,
+    FontWeight, Galley, Pos2, Style, TextStyle, Ui, Visuals,
 };
 
 /// Text and optional style choices for it.
@@ -26,6 +26,7 @@ pub struct RichText {
     text: String,
     size: Option<f32>,
     family: Option<FontFamily>,
+    weight: Option<FontWeight>,
     text_style: Option<TextStyle>,
     background_color: Color32,
     text_color: Option<Color32>,
@@ -111,13 +112,34 @@ impl RichText {
         self
     }
 
-    /// Select the font and size.
+    /// Select the font weight, e.g. [`FontWeight::LIGHT`].
+    ///
+    /// The closest face registered in [`crate::FontDefinitions::faces`] will be used,
+    /// or the `wght` axis of a variable font.
+    ///
+    /// This overrides the bold weight of [`Self::strong`].
+    #[inline]
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Select the font, size, weight and style.
     /// This overrides the value from [`Self::text_style`].
     #[inline]
     pub fn font(mut self, font_id: crate::FontId) -> Self {
-        let crate::FontId { size, family } = font_id;
+        let crate::FontId {
+            size,
+            family,
+            weight,
+            style,
+        } = font_id;
         self.size = Some(size);
         self.family = Some(family);
+        self.weight = Some(weight);
+        if style == FontStyle::Italic {
+            self.italics = true;
+        }
         self
     }
 
@@ -154,7 +176,7 @@ impl RichText {
         self.text_style(TextStyle::Monospace)
     }
 
-    /// Extra strong text (stronger color).
+    /// Extra strong text (stronger color, and bold if the font has a bold face).
     #[inline]
     pub fn strong(mut self) -> Self {
         self.strong = true;
@@ -186,7 +208,9 @@ impl RichText {
         self
     }
 
-    /// Tilt the characters to the right.
+    /// Use the italic face of the font.
+    ///
+    /// If there is no italic face, the characters are tilted to the right instead.
     #[inline]
     pub fn italics(mut self) -> Self {
         self.italics = true;
@@ -239,6 +263,14 @@ impl RichText {
         if let Some(family) = &self.family {
             font_id.family = family.clone();
         }
+        if let Some(weight) = self.weight {
+            font_id.weight = weight;
+        } else if self.strong {
+            font_id.weight = FontWeight::BOLD;
+        }
+        if self.italics {
+            font_id.style = FontStyle::Italic;
+        }
         fonts.row_height(&font_id)
     }
 
@@ -254,12 +286,13 @@ impl RichText {
             text,
             size,
             family,
+            weight,
             text_style,
             background_color,
             text_color: _, // already used by `get_text_color`
             code,
-            strong: _, // already used by `get_text_color`
-            weak: _,   // already used by `get_text_color`
+            strong,
+            weak: _, // already used by `get_text_color`
             strikethrough,
             underline,
             italics,
@@ -283,6 +316,14 @@ impl RichText {
             if let Some(family) = family {
                 font_id.family = family;
             }
+            if let Some(weight) = weight {
+                font_id.weight = weight;
+            } else if strong {
+                font_id.weight = FontWeight::BOLD;
+            }
+            if italics {
+                font_id.style = FontStyle::Italic;
+            }
             font_id
         };
 
@@ -311,7 +352,7 @@ impl RichText {
             font_id,
             color: text_color,
             background: background_color,
-            italics,
+            italics: false, // handled by `font_id.style`
             underline,
             strikethrough,
             valign,
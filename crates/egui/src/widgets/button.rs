@@ -159,9 +159,15 @@ impl Widget for Button {
             button_padding.y = 0.0;
         }
 
+        let image_size = image.as_ref().map(|image| {
+            image
+                .load_and_calc_size(ui.ctx())
+                .unwrap_or_else(|| Vec2::splat(ui.spacing().interact_size.y))
+        });
+
         let mut text_wrap_width = ui.available_width() - 2.0 * button_padding.x;
-        if let Some(image) = image {
-            text_wrap_width -= image.size().x + ui.spacing().icon_spacing;
+        if let Some(image_size) = image_size {
+            text_wrap_width -= image_size.x + ui.spacing().icon_spacing;
         }
         if !shortcut_text.is_empty() {
             text_wrap_width -= 60.0; // Some space for the shortcut text (which we never wrap).
@@ -172,9 +178,9 @@ impl Widget for Button {
             .then(|| shortcut_text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button));
 
         let mut desired_size = text.size();
-        if let Some(image) = image {
-            desired_size.x += image.size().x + ui.spacing().icon_spacing;
-            desired_size.y = desired_size.y.max(image.size().y);
+        if let Some(image_size) = image_size {
+            desired_size.x += image_size.x + ui.spacing().icon_spacing;
+            desired_size.y = desired_size.y.max(image_size.y);
         }
         if let Some(shortcut_text) = &shortcut_text {
             desired_size.x += ui.spacing().item_spacing.x + shortcut_text.size().x;
@@ -200,10 +206,10 @@ impl Widget for Button {
                     .rect(rect.expand(visuals.expansion), rounding, fill, stroke);
             }
 
-            let text_pos = if let Some(image) = image {
+            let text_pos = if let Some(image_size) = image_size {
                 let icon_spacing = ui.spacing().icon_spacing;
                 pos2(
-                    rect.min.x + button_padding.x + image.size().x + icon_spacing,
+                    rect.min.x + button_padding.x + image_size.x + icon_spacing,
                     rect.center().y - 0.5 * text.size().y,
                 )
             } else {
@@ -225,13 +231,13 @@ impl Widget for Button {
                 );
             }
 
-            if let Some(image) = image {
+            if let (Some(image), Some(image_size)) = (image, image_size) {
                 let image_rect = Rect::from_min_size(
                     pos2(
                         rect.min.x + button_padding.x,
-                        rect.center().y - 0.5 - (image.size().y / 2.0),
+                        rect.center().y - 0.5 - (image_size.y / 2.0),
                     ),
-                    image.size(),
+                    image_size,
                 );
                 image.paint_at(ui, image_rect);
             }
@@ -317,12 +323,12 @@ impl<'a> Widget for Checkbox<'a> {
             // let visuals = ui.style().interact_selectable(&response, *checked); // too colorful
             let visuals = ui.style().interact(&response);
             let (small_icon_rect, big_icon_rect) = ui.spacing().icon_rectangles(rect);
-            ui.painter().add(epaint::RectShape {
-                rect: big_icon_rect.expand(visuals.expansion),
-                rounding: visuals.rounding,
-                fill: visuals.bg_fill,
-                stroke: visuals.bg_stroke,
-            });
+            ui.painter().add(epaint::RectShape::new(
+                big_icon_rect.expand(visuals.expansion),
+                visuals.rounding,
+                visuals.bg_fill,
+                visuals.bg_stroke,
+            ));
 
             if *checked {
                 // Check mark:
@@ -426,21 +432,20 @@ impl Widget for RadioButton {
 
             let painter = ui.painter();
 
-            painter.add(epaint::CircleShape {
-                center: big_icon_rect.center(),
-                radius: big_icon_rect.width() / 2.0 + visuals.expansion,
-                fill: visuals.bg_fill,
-                stroke: visuals.bg_stroke,
-            });
+            painter.add(epaint::CircleShape::new(
+                big_icon_rect.center(),
+                big_icon_rect.width() / 2.0 + visuals.expansion,
+                visuals.bg_fill,
+                visuals.bg_stroke,
+            ));
 
             if checked {
-                painter.add(epaint::CircleShape {
-                    center: small_icon_rect.center(),
-                    radius: small_icon_rect.width() / 3.0,
-                    fill: visuals.fg_stroke.color, // Intentional to use stroke and not fill
+                painter.add(epaint::CircleShape::filled(
+                    small_icon_rect.center(),
+                    small_icon_rect.width() / 3.0,
                     // fill: ui.visuals().selection.stroke.color, // too much color
-                    stroke: Default::default(),
-                });
+                    visuals.fg_stroke.color, // Intentional to use stroke and not fill
+                ));
             }
 
             if let Some(text) = text {
@@ -478,6 +483,16 @@ impl ImageButton {
         }
     }
 
+    /// A button showing any [`widgets::Image`], e.g. one loaded by URI with [`widgets::Image::from_uri`].
+    pub fn from_image(image: widgets::Image) -> Self {
+        Self {
+            image,
+            sense: Sense::click(),
+            frame: true,
+            selected: false,
+        }
+    }
+
     /// Select UV range. Default is (0,0) in top-left, (1,1) bottom right.
     pub fn uv(mut self, uv: impl Into<Rect>) -> Self {
         self.image = self.image.uv(uv);
@@ -525,7 +540,10 @@ impl Widget for ImageButton {
         } else {
             Vec2::ZERO
         };
-        let padded_size = image.size() + 2.0 * padding;
+        let image_size = image
+            .load_and_calc_size(ui.ctx())
+            .unwrap_or_else(|| Vec2::splat(ui.spacing().interact_size.y));
+        let padded_size = image_size + 2.0 * padding;
         let (rect, response) = ui.allocate_exact_size(padded_size, sense);
         response.widget_info(|| WidgetInfo::new(WidgetType::ImageButton));
 
@@ -557,7 +575,7 @@ impl Widget for ImageButton {
 
             let image_rect = ui
                 .layout()
-                .align_size_within_rect(image.size(), rect.shrink2(padding));
+                .align_size_within_rect(image_size, rect.shrink2(padding));
             // let image_rect = image_rect.expand2(expansion); // can make it blurry, so let's not
             image.paint_at(ui, image_rect);
 
@@ -205,12 +205,12 @@ fn color_slider_2d(
         let x = lerp(rect.left()..=rect.right(), *x_value);
         let y = lerp(rect.bottom()..=rect.top(), *y_value);
         let picked_color = color_at(*x_value, *y_value);
-        ui.painter().add(epaint::CircleShape {
-            center: pos2(x, y),
-            radius: rect.width() / 12.0,
-            fill: picked_color,
-            stroke: Stroke::new(visuals.fg_stroke.width, contrast_color(picked_color)),
-        });
+        ui.painter().add(epaint::CircleShape::new(
+            pos2(x, y),
+            rect.width() / 12.0,
+            picked_color,
+            Stroke::new(visuals.fg_stroke.width, contrast_color(picked_color)),
+        ));
     }
 
     response
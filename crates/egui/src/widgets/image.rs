@@ -1,10 +1,27 @@
-use crate::*;
-use emath::Rot2;
+use std::borrow::Cow;
 
-/// An widget to show an image of a given size.
+use crate::{
+    load::{Bytes, ImageSource, SizeHint, SizedTexture, TextureLoadResult, TexturePoll},
+    *,
+};
+
+/// An widget to show an image.
+///
+/// The image can be loaded by URI using the installed loaders (see [`crate::load`]):
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// ui.add(egui::Image::from_uri("file://assets/ferris.png").max_size(egui::vec2(200.0, 200.0)));
 ///
-/// In order to display an image you must first acquire a [`TextureHandle`].
-/// This is best done with [`egui_extras::RetainedImage`](https://docs.rs/egui_extras/latest/egui_extras/image/struct.RetainedImage.html) or [`Context::load_texture`].
+/// // Shorter version:
+/// ui.image("file://assets/ferris.png");
+/// # });
+/// ```
+///
+/// While the image is loading a spinner is shown in its place.
+///
+/// You can also show a [`TextureHandle`] that you loaded yourself,
+/// e.g. with [`Context::load_texture`]:
 ///
 /// ```
 /// struct MyImage {
@@ -26,37 +43,81 @@ use emath::Rot2;
 ///         ui.add(egui::Image::new(texture, texture.size_vec2()));
 ///
 ///         // Shorter version:
-///         ui.image(texture, texture.size_vec2());
+///         ui.image(texture);
 ///     }
 /// }
 /// ```
 ///
 /// Se also [`crate::Ui::image`] and [`crate::ImageButton`].
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct Image {
-    texture_id: TextureId,
+    source: ImageSource,
     uv: Rect,
-    size: Vec2,
+    size: Option<Vec2>,
+    max_size: Vec2,
     bg_fill: Color32,
     tint: Color32,
     sense: Sense,
-    rotation: Option<(Rot2, Vec2)>,
+    rotation: Option<(f32, Vec2)>,
+    nine_slice_border: Option<f32>,
+    texture_options: TextureOptions,
+    show_loading_spinner: bool,
 }
 
 impl Image {
+    /// Show a texture at the given size.
     pub fn new(texture_id: impl Into<TextureId>, size: impl Into<Vec2>) -> Self {
+        Self::from_source(SizedTexture::new(texture_id, size))
+    }
+
+    /// Load the image from a URI, e.g. `file://assets/ferris.png`, using the installed loaders.
+    ///
+    /// See [`crate::load`].
+    pub fn from_uri(uri: impl Into<Cow<'static, str>>) -> Self {
+        Self::from_source(ImageSource::Uri(uri.into()))
+    }
+
+    /// Decode the image from these bytes, e.g. from `include_bytes!`, using the installed loaders.
+    ///
+    /// The `uri` is used to cache the decoded image, so it must be unique for these bytes, e.g. `bytes://ferris.png`.
+    pub fn from_bytes(uri: impl Into<Cow<'static, str>>, bytes: impl Into<Bytes>) -> Self {
+        Self::from_source(ImageSource::Bytes {
+            uri: uri.into(),
+            bytes: bytes.into(),
+        })
+    }
+
+    pub fn from_source(source: impl Into<ImageSource>) -> Self {
         Self {
-            texture_id: texture_id.into(),
+            source: source.into(),
             uv: Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
-            size: size.into(),
+            size: None,
+            max_size: Vec2::INFINITY,
             bg_fill: Default::default(),
             tint: Color32::WHITE,
             sense: Sense::hover(),
             rotation: None,
+            nine_slice_border: None,
+            texture_options: Default::default(),
+            show_loading_spinner: true,
         }
     }
 
+    /// Show the image at exactly this size, ignoring its aspect ratio.
+    ///
+    /// By default the original size of the image is used, shrunk to fit [`Self::max_size`].
+    pub fn fit_to_exact_size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Shrink the image to fit within this size, keeping its aspect ratio.
+    pub fn max_size(mut self, max_size: impl Into<Vec2>) -> Self {
+        self.max_size = max_size.into();
+        self
+    }
+
     /// Select UV range. Default is (0,0) in top-left, (1,1) bottom right.
     pub fn uv(mut self, uv: impl Into<Rect>) -> Self {
         self.uv = uv.into();
@@ -90,52 +151,162 @@ impl Image {
     ///
     /// To rotate about the center you can pass `Vec2::splat(0.5)` as the origin.
     pub fn rotate(mut self, angle: f32, origin: Vec2) -> Self {
-        self.rotation = Some((Rot2::from_angle(angle), origin));
+        self.rotation = Some((angle, origin));
+        self
+    }
+
+    /// Use nine-slice scaling: keep a border of this size on every side of the image unstretched,
+    /// and only stretch the edges along their length and the center to fill the rest.
+    ///
+    /// The border is measured in the original size of the image,
+    /// i.e. in texels for a [`TextureHandle`] or an image loaded by URI.
+    /// It is painted at the same size, regardless of the size of the image.
+    ///
+    /// Useful for skinning panels and buttons with a texture.
+    /// See [`epaint::NineSlice`] for more control.
+    pub fn nine_slice(mut self, border: f32) -> Self {
+        self.nine_slice_border = Some(border);
+        self
+    }
+
+    /// How to filter and wrap the texture, when the image is loaded by URI.
+    ///
+    /// Use a repeating [`TextureWrapMode`] together with a [`Self::uv`] larger than 0-1 to tile the image.
+    pub fn texture_options(mut self, texture_options: TextureOptions) -> Self {
+        self.texture_options = texture_options;
+        self
+    }
+
+    /// Show a spinner while the image is loading? Default: `true`.
+    pub fn show_loading_spinner(mut self, show: bool) -> Self {
+        self.show_loading_spinner = show;
         self
     }
 }
 
 impl Image {
-    pub fn size(&self) -> Vec2 {
-        self.size
+    pub fn source(&self) -> &ImageSource {
+        &self.source
+    }
+
+    /// The size the image will be shown at, if it is known without loading the image.
+    pub fn size(&self) -> Option<Vec2> {
+        match &self.source {
+            ImageSource::Texture(texture) => Some(self.calc_size(texture.size)),
+            ImageSource::Uri(_) | ImageSource::Bytes { .. } => self.size,
+        }
+    }
+
+    /// Load the image using the installed loaders, if needed.
+    ///
+    /// # Errors
+    /// See [`Context::try_load_image`].
+    pub fn load(&self, ctx: &Context) -> TextureLoadResult {
+        let size_hint = match self.size {
+            Some(size) => {
+                let size = size * ctx.pixels_per_point();
+                SizeHint::Size(size.x.round() as u32, size.y.round() as u32)
+            }
+            None => SizeHint::Original,
+        };
+        self.source.load(ctx, self.texture_options, size_hint)
+    }
+
+    /// Load the image, and return the size it will be shown at, if known.
+    pub fn load_and_calc_size(&self, ctx: &Context) -> Option<Vec2> {
+        let image_size = self.load(ctx).ok()?.size()?;
+        Some(self.calc_size(image_size))
+    }
+
+    /// The size to show an image of the given size at.
+    fn calc_size(&self, image_size: Vec2) -> Vec2 {
+        if let Some(size) = self.size {
+            return size;
+        }
+        let scale = (self.max_size.x / image_size.x)
+            .min(self.max_size.y / image_size.y)
+            .min(1.0);
+        image_size * scale
     }
 
     pub fn paint_at(&self, ui: &mut Ui, rect: Rect) {
         if ui.is_rect_visible(rect) {
-            use epaint::*;
-            let Self {
-                texture_id,
-                uv,
-                size,
-                bg_fill,
-                tint,
-                sense: _,
-                rotation,
-            } = self;
-
-            if *bg_fill != Default::default() {
-                let mut mesh = Mesh::default();
-                mesh.add_colored_rect(rect, *bg_fill);
-                ui.painter().add(Shape::mesh(mesh));
-            }
+            let texture = self.load(ui.ctx());
+            self.paint_texture_load_result(ui, &texture, rect);
+        }
+    }
+
+    fn paint_texture_load_result(&self, ui: &mut Ui, texture: &TextureLoadResult, rect: Rect) {
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
 
-            {
-                // TODO(emilk): builder pattern for Mesh
-                let mut mesh = Mesh::with_texture(*texture_id);
-                mesh.add_rect_with_uv(rect, *uv, *tint);
-                if let Some((rot, origin)) = rotation {
-                    mesh.rotate(*rot, rect.min + *origin * *size);
+        match texture {
+            Ok(TexturePoll::Ready { texture }) => self.paint_texture(ui, texture, rect),
+            Ok(TexturePoll::Pending { .. }) => {
+                if self.show_loading_spinner {
+                    Spinner::new().paint_at(ui, rect);
                 }
-                ui.painter().add(Shape::mesh(mesh));
+            }
+            Err(_) => {
+                let font_id = TextStyle::Body.resolve(ui.style());
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    "⚠",
+                    font_id,
+                    ui.visuals().error_fg_color,
+                );
             }
         }
     }
+
+    fn paint_texture(&self, ui: &mut Ui, texture: &SizedTexture, rect: Rect) {
+        use epaint::*;
+        let Self {
+            uv,
+            bg_fill,
+            tint,
+            rotation,
+            nine_slice_border,
+            ..
+        } = self;
+
+        if *bg_fill != Default::default() {
+            let mut mesh = Mesh::default();
+            mesh.add_colored_rect(rect, *bg_fill);
+            ui.painter().add(Shape::mesh(mesh));
+        }
+
+        let mut image = ImageShape::new(texture.id, rect, *uv, *tint);
+        if let Some((angle, origin)) = rotation {
+            image = image.with_rotation(*angle, *origin);
+        }
+        if let Some(border) = nine_slice_border {
+            image = image.with_nine_slice(NineSlice::from_border(*uv, texture.size, *border));
+        }
+        ui.painter().add(image);
+    }
 }
 
 impl Widget for Image {
     fn ui(self, ui: &mut Ui) -> Response {
-        let (rect, response) = ui.allocate_exact_size(self.size, self.sense);
-        self.paint_at(ui, rect);
-        response
+        let texture = self.load(ui.ctx());
+        let size = texture
+            .as_ref()
+            .ok()
+            .and_then(|poll| poll.size())
+            .map(|image_size| self.calc_size(image_size))
+            .or(self.size)
+            .unwrap_or_else(|| Vec2::splat(ui.spacing().interact_size.y));
+
+        let (rect, response) = ui.allocate_exact_size(size, self.sense);
+        self.paint_texture_load_result(ui, &texture, rect);
+
+        if let Err(err) = texture {
+            response.on_hover_text(err.to_string())
+        } else {
+            response
+        }
     }
 }
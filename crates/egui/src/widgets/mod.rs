@@ -109,7 +109,11 @@ pub fn reset_button_with<T: PartialEq>(ui: &mut Ui, value: &mut T, reset_value:
 // ----------------------------------------------------------------------------
 
 pub fn stroke_ui(ui: &mut crate::Ui, stroke: &mut epaint::Stroke, text: &str) {
-    let epaint::Stroke { width, color } = stroke;
+    let epaint::Stroke {
+        width,
+        color,
+        style: _,
+    } = stroke;
     ui.horizontal(|ui| {
         ui.add(DragValue::new(width).speed(0.1).clamp_range(0.0..=5.0))
             .on_hover_text("Width");
@@ -125,15 +129,28 @@ pub fn stroke_ui(ui: &mut crate::Ui, stroke: &mut epaint::Stroke, text: &str) {
 }
 
 pub(crate) fn shadow_ui(ui: &mut Ui, shadow: &mut epaint::Shadow, text: &str) {
-    let epaint::Shadow { extrusion, color } = shadow;
+    let epaint::Shadow {
+        offset,
+        blur,
+        spread,
+        color,
+        inset,
+    } = shadow;
     ui.horizontal(|ui| {
         ui.label(text);
+        ui.add(DragValue::new(&mut offset.x).speed(1.0).prefix("x: "))
+            .on_hover_text("Offset");
+        ui.add(DragValue::new(&mut offset.y).speed(1.0).prefix("y: "))
+            .on_hover_text("Offset");
+        ui.add(DragValue::new(blur).speed(1.0).clamp_range(0.0..=100.0))
+            .on_hover_text("Blur");
         ui.add(
-            DragValue::new(extrusion)
+            DragValue::new(spread)
                 .speed(1.0)
-                .clamp_range(0.0..=100.0),
+                .clamp_range(-100.0..=100.0),
         )
-        .on_hover_text("Extrusion");
+        .on_hover_text("Spread");
+        ui.checkbox(inset, "Inset");
         ui.color_edit_button_srgba(color);
     });
 }
@@ -150,12 +150,7 @@ impl BoxElem {
             &self.point_at(self.argument - self.box_width / 2.0, self.spread.quartile1),
             &self.point_at(self.argument + self.box_width / 2.0, self.spread.quartile3),
         );
-        let rect = Shape::Rect(RectShape {
-            rect,
-            rounding: Rounding::none(),
-            fill,
-            stroke,
-        });
+        let rect = Shape::Rect(RectShape::new(rect, Rounding::none(), fill, stroke));
         shapes.push(rect);
 
         let line_between = |v1, v2| {
@@ -883,12 +883,9 @@ impl PlotItem for Points {
 
                 match shape {
                     MarkerShape::Circle => {
-                        shapes.push(Shape::Circle(epaint::CircleShape {
-                            center,
-                            radius,
-                            fill,
-                            stroke,
-                        }));
+                        shapes.push(Shape::Circle(epaint::CircleShape::new(
+                            center, radius, fill, stroke,
+                        )));
                     }
                     MarkerShape::Diamond => {
                         let points = vec![
@@ -114,12 +114,12 @@ impl LegendEntry {
 
         let painter = ui.painter();
 
-        painter.add(epaint::CircleShape {
-            center: icon_rect.center(),
-            radius: icon_size * 0.5,
-            fill: visuals.bg_fill,
-            stroke: visuals.bg_stroke,
-        });
+        painter.add(epaint::CircleShape::new(
+            icon_rect.center(),
+            icon_size * 0.5,
+            visuals.bg_fill,
+            visuals.bg_stroke,
+        ));
 
         if *checked {
             let fill = if *color == Color32::TRANSPARENT {
@@ -238,7 +238,7 @@ impl Widget for &mut LegendWidget {
                     inner_margin: vec2(8.0, 4.0).into(),
                     rounding: ui.style().visuals.window_rounding,
                     shadow: epaint::Shadow::NONE,
-                    fill: ui.style().visuals.extreme_bg_color,
+                    fill: ui.style().visuals.extreme_bg_color.into(),
                     stroke: ui.style().visuals.window_stroke(),
                     ..Default::default()
                 }
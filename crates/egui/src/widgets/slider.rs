@@ -663,12 +663,12 @@ impl<'a> Slider<'a> {
                 );
             }
 
-            ui.painter().add(epaint::CircleShape {
+            ui.painter().add(epaint::CircleShape::new(
                 center,
-                radius: self.handle_radius(rect) + visuals.expansion,
-                fill: visuals.bg_fill,
-                stroke: visuals.fg_stroke,
-            });
+                self.handle_radius(rect) + visuals.expansion,
+                visuals.bg_fill,
+                visuals.fg_stroke,
+            ));
         }
     }
 
@@ -1,4 +1,4 @@
-use epaint::{emath::lerp, vec2, Color32, Pos2, Shape, Stroke};
+use epaint::{emath::lerp, vec2, Color32, Pos2, Rect, Shape, Stroke};
 
 use crate::{Response, Sense, Ui, Widget};
 
@@ -33,19 +33,15 @@ impl Spinner {
     }
 }
 
-impl Widget for Spinner {
-    fn ui(self, ui: &mut Ui) -> Response {
-        let size = self
-            .size
-            .unwrap_or_else(|| ui.style().spacing.interact_size.y);
-        let color = self
-            .color
-            .unwrap_or_else(|| ui.visuals().strong_text_color());
-        let (rect, response) = ui.allocate_exact_size(vec2(size, size), Sense::hover());
-
+impl Spinner {
+    /// Paint the spinner in the given rectangle.
+    pub fn paint_at(&self, ui: &Ui, rect: Rect) {
         if ui.is_rect_visible(rect) {
             ui.ctx().request_repaint();
 
+            let color = self
+                .color
+                .unwrap_or_else(|| ui.visuals().strong_text_color());
             let radius = (rect.height() / 2.0) - 2.0;
             let n_points = 20;
             let time = ui.input(|i| i.time);
@@ -61,7 +57,16 @@ impl Widget for Spinner {
             ui.painter()
                 .add(Shape::line(points, Stroke::new(3.0, color)));
         }
+    }
+}
 
+impl Widget for Spinner {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let size = self
+            .size
+            .unwrap_or_else(|| ui.style().spacing.interact_size.y);
+        let (rect, response) = ui.allocate_exact_size(vec2(size, size), Sense::hover());
+        self.paint_at(ui, rect);
         response
     }
 }
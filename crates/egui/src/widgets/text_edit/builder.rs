@@ -368,31 +368,31 @@ impl<'t> TextEdit<'t> {
             let frame_rect = frame_rect.expand(visuals.expansion);
             let shape = if is_mutable {
                 if output.response.has_focus() {
-                    epaint::RectShape {
-                        rect: frame_rect,
-                        rounding: visuals.rounding,
+                    epaint::RectShape::new(
+                        frame_rect,
+                        visuals.rounding,
                         // fill: ui.visuals().selection.bg_fill,
-                        fill: ui.visuals().extreme_bg_color,
-                        stroke: ui.visuals().selection.stroke,
-                    }
+                        ui.visuals().extreme_bg_color,
+                        ui.visuals().selection.stroke,
+                    )
                 } else {
-                    epaint::RectShape {
-                        rect: frame_rect,
-                        rounding: visuals.rounding,
-                        fill: ui.visuals().extreme_bg_color,
-                        stroke: visuals.bg_stroke, // TODO(emilk): we want to show something here, or a text-edit field doesn't "pop".
-                    }
+                    epaint::RectShape::new(
+                        frame_rect,
+                        visuals.rounding,
+                        ui.visuals().extreme_bg_color,
+                        visuals.bg_stroke, // TODO(emilk): we want to show something here, or a text-edit field doesn't "pop".
+                    )
                 }
             } else {
                 let visuals = &ui.style().visuals.widgets.inactive;
-                epaint::RectShape {
-                    rect: frame_rect,
-                    rounding: visuals.rounding,
+                epaint::RectShape::new(
+                    frame_rect,
+                    visuals.rounding,
                     // fill: ui.visuals().extreme_bg_color,
                     // fill: visuals.bg_fill,
-                    fill: Color32::TRANSPARENT,
-                    stroke: visuals.bg_stroke, // TODO(emilk): we want to show something here, or a text-edit field doesn't "pop".
-                }
+                    Color32::TRANSPARENT,
+                    visuals.bg_stroke, // TODO(emilk): we want to show something here, or a text-edit field doesn't "pop".
+                )
             };
 
             ui.painter().set(where_to_put_background, shape);
@@ -500,6 +500,7 @@ impl<'t> TextEdit<'t> {
 
         if interactive {
             if let Some(pointer_pos) = ui.ctx().pointer_interact_pos() {
+                let pointer_pos = ui.ctx().screen_to_layer_pos(ui.layer_id(), pointer_pos);
                 if response.hovered() && text.is_mutable() {
                     ui.output_mut(|o| o.mutable_text_under_cursor = true);
                 }
@@ -1107,26 +1108,32 @@ fn paint_cursor_selection(
 
     for ri in min.row..=max.row {
         let row = &galley.rows[ri];
-        let left = if ri == min.row {
-            row.x_offset(min.column)
+        let start_column = if ri == min.row { min.column } else { 0 };
+        let end_column = if ri == max.row {
+            max.column
         } else {
-            row.rect.left()
+            row.char_count_excluding_newline()
         };
-        let right = if ri == max.row {
-            row.x_offset(max.column)
+
+        // Mixed-direction text can need several rectangles per row:
+        let mut x_ranges = if ri == min.row || ri == max.row || row.has_rtl() {
+            row.x_ranges(start_column..end_column)
         } else {
-            let newline_size = if row.ends_with_newline {
-                row.height() / 2.0 // visualize that we select the newline
-            } else {
-                0.0
-            };
-            row.rect.right() + newline_size
+            vec![row.rect.left()..=row.rect.right()]
         };
-        let rect = Rect::from_min_max(
-            pos + vec2(left, row.min_y()),
-            pos + vec2(right, row.max_y()),
-        );
-        painter.rect_filled(rect, 0.0, color);
+        if ri != max.row && row.ends_with_newline {
+            // visualize that we select the newline
+            let newline_size = row.height() / 2.0;
+            x_ranges.push(row.rect.right()..=row.rect.right() + newline_size);
+        }
+
+        for x_range in x_ranges {
+            let rect = Rect::from_min_max(
+                pos + vec2(*x_range.start(), row.min_y()),
+                pos + vec2(*x_range.end(), row.max_y()),
+            );
+            painter.rect_filled(rect, 0.0, color);
+        }
     }
 }
 
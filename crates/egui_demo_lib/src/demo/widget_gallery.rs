@@ -209,7 +209,7 @@ impl WidgetGallery {
         let img_size = 16.0 * texture.size_vec2() / texture.size_vec2().y;
 
         ui.add(doc_link_label("Image", "Image"));
-        ui.image(texture, img_size);
+        ui.image((texture.id(), img_size));
         ui.end_row();
 
         ui.add(doc_link_label("ImageButton", "ImageButton"));
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use egui::{mutex::Mutex, TextureFilter, TextureOptions};
 
 #[cfg(feature = "svg")]
@@ -118,6 +120,7 @@ impl RetainedImage {
         self.with_options(TextureOptions {
             magnification: filter,
             minification: filter,
+            ..Default::default()
         })
     }
 
@@ -182,7 +185,209 @@ impl RetainedImage {
         // We need to convert the SVG to a texture to display it:
         // Future improvement: tell backend to do mip-mapping of the image to
         // make it look smoother when downsized.
-        ui.image(self.texture_id(ui.ctx()), desired_size)
+        ui.image((self.texture_id(ui.ctx()), desired_size))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// One frame of an [`AnimatedImage`].
+pub struct AnimationFrame {
+    pub image: ColorImage,
+
+    /// How long to show this frame for.
+    pub delay: Duration,
+}
+
+/// An animated image, e.g. a GIF or APNG, to be shown in egui.
+///
+/// Load once, and save somewhere in your app state.
+/// All frames are uploaded to the GPU the first time the image is shown.
+///
+/// The animation loops forever, and is driven by [`egui::InputState::time`],
+/// so all animated images on screen stay in sync.
+/// egui is only repainted when it is time to show the next frame
+/// (see [`egui::Context::request_repaint_after`]).
+///
+/// Use the `gif` and `apng` features to enable decoding.
+pub struct AnimatedImage {
+    debug_name: String,
+    size: [usize; 2],
+    delays: Vec<Duration>,
+    /// Cleared once [`Self::textures`] have been loaded.
+    images: Mutex<Vec<ColorImage>>,
+    /// Lazily loaded when we have an egui context.
+    textures: Mutex<Vec<egui::TextureHandle>>,
+    options: TextureOptions,
+}
+
+impl AnimatedImage {
+    /// Frames shorter than this are shown for [`Self::DEFAULT_FRAME_DELAY`] instead,
+    /// like web browsers do. Many GIFs have a delay of zero and rely on this.
+    pub const MIN_FRAME_DELAY: Duration = Duration::from_millis(11);
+
+    /// See [`Self::MIN_FRAME_DELAY`].
+    pub const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+    /// Create an animation from its frames.
+    ///
+    /// All frames must have the same size.
+    ///
+    /// # Errors
+    /// If there are no frames, or they don't have the same size.
+    pub fn from_frames(
+        debug_name: impl Into<String>,
+        frames: Vec<AnimationFrame>,
+    ) -> Result<Self, String> {
+        let debug_name = debug_name.into();
+        let size = frames
+            .first()
+            .ok_or_else(|| format!("{debug_name:?} has no frames"))?
+            .image
+            .size;
+        if frames.iter().any(|frame| frame.image.size != size) {
+            return Err(format!("The frames of {debug_name:?} have different sizes"));
+        }
+
+        let (images, delays) = frames
+            .into_iter()
+            .map(|AnimationFrame { image, delay }| {
+                let delay = if delay < Self::MIN_FRAME_DELAY {
+                    Self::DEFAULT_FRAME_DELAY
+                } else {
+                    delay
+                };
+                (image, delay)
+            })
+            .unzip();
+
+        Ok(Self {
+            debug_name,
+            size,
+            delays,
+            images: Mutex::new(images),
+            textures: Default::default(),
+            options: Default::default(),
+        })
+    }
+
+    /// Load an animated GIF or APNG.
+    ///
+    /// Images that aren't animated are loaded as an animation with a single frame.
+    ///
+    /// Requires the "gif" and/or "apng" feature.
+    ///
+    /// # Errors
+    /// On invalid image or unsupported image format.
+    #[cfg(any(feature = "gif", feature = "apng"))]
+    pub fn from_image_bytes(
+        debug_name: impl Into<String>,
+        image_bytes: &[u8],
+    ) -> Result<Self, String> {
+        Self::from_frames(debug_name, load_animated_image_bytes(image_bytes)?)
+    }
+
+    /// Set the texture filters to use for the frames.
+    ///
+    /// **Note:** If the textures have already been uploaded to the GPU, this has no effect.
+    pub fn with_options(mut self, options: TextureOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The size of each frame (number of pixels wide/high).
+    pub fn size(&self) -> [usize; 2] {
+        self.size
+    }
+
+    /// The size of each frame (number of pixels wide/high).
+    pub fn size_vec2(&self) -> egui::Vec2 {
+        let [w, h] = self.size();
+        egui::vec2(w as f32, h as f32)
+    }
+
+    /// The debug name of the image, e.g. the file name.
+    pub fn debug_name(&self) -> &str {
+        &self.debug_name
+    }
+
+    /// The number of frames.
+    pub fn frame_count(&self) -> usize {
+        self.delays.len()
+    }
+
+    /// How long to show each frame for.
+    pub fn frame_delays(&self) -> &[Duration] {
+        &self.delays
+    }
+
+    /// How long it takes to play all frames once.
+    pub fn duration(&self) -> Duration {
+        self.delays.iter().sum()
+    }
+
+    /// The frame to show at the given time (in seconds), and how long until the next frame.
+    pub fn frame_at(&self, time: f64) -> (usize, Duration) {
+        let duration = self.duration();
+        if self.delays.len() <= 1 || duration.is_zero() {
+            return (0, Duration::MAX);
+        }
+
+        let mut time_in_frame = Duration::from_secs_f64(time.max(0.0) % duration.as_secs_f64());
+        for (index, &delay) in self.delays.iter().enumerate() {
+            if time_in_frame < delay {
+                return (index, delay - time_in_frame);
+            }
+            time_in_frame -= delay;
+        }
+        // Rounding errors can get us here:
+        (0, self.delays[0])
+    }
+
+    /// The texture id of the frame that should be shown now.
+    ///
+    /// Requests a repaint for when the next frame is due.
+    pub fn texture_id(&self, ctx: &egui::Context) -> egui::TextureId {
+        let (index, time_to_next_frame) = self.frame_at(ctx.input(|i| i.time));
+        if time_to_next_frame != Duration::MAX {
+            ctx.request_repaint_after(time_to_next_frame);
+        }
+
+        let mut textures = self.textures.lock();
+        if textures.is_empty() {
+            let images = std::mem::take(&mut *self.images.lock());
+            *textures = images
+                .into_iter()
+                .enumerate()
+                .map(|(i, image)| {
+                    ctx.load_texture(format!("{}#{i}", self.debug_name), image, self.options)
+                })
+                .collect();
+        }
+        textures[index].id()
+    }
+
+    /// Show the animation with the given maximum size.
+    pub fn show_max_size(&self, ui: &mut egui::Ui, max_size: egui::Vec2) -> egui::Response {
+        let mut desired_size = self.size_vec2();
+        desired_size *= (max_size.x / desired_size.x).min(1.0);
+        desired_size *= (max_size.y / desired_size.y).min(1.0);
+        self.show_size(ui, desired_size)
+    }
+
+    /// Show the animation with the original size (one image pixel = one gui point).
+    pub fn show(&self, ui: &mut egui::Ui) -> egui::Response {
+        self.show_size(ui, self.size_vec2())
+    }
+
+    /// Show the animation with the given scale factor (1.0 = original size).
+    pub fn show_scaled(&self, ui: &mut egui::Ui, scale: f32) -> egui::Response {
+        self.show_size(ui, self.size_vec2() * scale)
+    }
+
+    /// Show the animation with the given size.
+    pub fn show_size(&self, ui: &mut egui::Ui, desired_size: egui::Vec2) -> egui::Response {
+        ui.image((self.texture_id(ui.ctx()), desired_size))
     }
 }
 
@@ -209,6 +414,60 @@ pub fn load_image_bytes(image_bytes: &[u8]) -> Result<egui::ColorImage, String>
     ))
 }
 
+/// Load all frames of an animated GIF or APNG.
+///
+/// Images that aren't animated are loaded as a single frame.
+/// The frames are composited, so each frame is a complete image of the same size.
+///
+/// Requires the "gif" and/or "apng" feature.
+///
+/// # Errors
+/// On invalid image or unsupported image format.
+#[cfg(any(feature = "gif", feature = "apng"))]
+pub fn load_animated_image_bytes(image_bytes: &[u8]) -> Result<Vec<AnimationFrame>, String> {
+    use image::AnimationDecoder as _;
+
+    let format = image::guess_format(image_bytes).map_err(|err| err.to_string())?;
+    let frames = match format {
+        #[cfg(feature = "gif")]
+        image::ImageFormat::Gif => {
+            image::codecs::gif::GifDecoder::new(std::io::Cursor::new(image_bytes))
+                .map_err(|err| err.to_string())?
+                .into_frames()
+        }
+        #[cfg(feature = "apng")]
+        image::ImageFormat::Png => {
+            let decoder = image::codecs::png::PngDecoder::new(std::io::Cursor::new(image_bytes))
+                .map_err(|err| err.to_string())?;
+            if !decoder.is_apng() {
+                return single_frame(image_bytes);
+            }
+            decoder.apng().into_frames()
+        }
+        _ => return single_frame(image_bytes),
+    };
+
+    frames
+        .map(|frame| {
+            let frame = frame.map_err(|err| err.to_string())?;
+            let delay = frame.delay().into();
+            let buffer = frame.into_buffer();
+            let size = [buffer.width() as _, buffer.height() as _];
+            let image =
+                ColorImage::from_rgba_unmultiplied(size, buffer.as_flat_samples().as_slice());
+            Ok(AnimationFrame { image, delay })
+        })
+        .collect()
+}
+
+#[cfg(any(feature = "gif", feature = "apng"))]
+fn single_frame(image_bytes: &[u8]) -> Result<Vec<AnimationFrame>, String> {
+    Ok(vec![AnimationFrame {
+        image: load_image_bytes(image_bytes)?,
+        delay: Duration::ZERO,
+    }])
+}
+
 /// Load an SVG and rasterize it into an egui image.
 ///
 /// Requires the "svg" feature.
@@ -263,3 +522,70 @@ pub fn load_svg_bytes_with_size(
 
     Ok(image)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation(delays_ms: &[u64]) -> Result<AnimatedImage, String> {
+        let frames = delays_ms
+            .iter()
+            .map(|&ms| AnimationFrame {
+                image: ColorImage::new([1, 1], egui::Color32::WHITE),
+                delay: Duration::from_millis(ms),
+            })
+            .collect();
+        AnimatedImage::from_frames("test", frames)
+    }
+
+    #[test]
+    fn frame_at_wraps_around() {
+        let image = animation(&[100, 200]).unwrap();
+        assert_eq!(image.duration(), Duration::from_millis(300));
+
+        let (index, next) = image.frame_at(0.05);
+        assert_eq!(index, 0);
+        assert!((next.as_secs_f64() - 0.05).abs() < 1e-6);
+
+        let (index, next) = image.frame_at(0.15);
+        assert_eq!(index, 1);
+        assert!((next.as_secs_f64() - 0.15).abs() < 1e-6);
+
+        assert_eq!(image.frame_at(0.35).0, 0);
+        assert_eq!(image.frame_at(3.0 + 0.25).0, 1);
+        assert_eq!(
+            image.frame_at(-1.0).0,
+            0,
+            "negative time shows the first frame"
+        );
+    }
+
+    #[test]
+    fn frame_at_without_animation() {
+        assert!(animation(&[]).is_err());
+
+        let image = animation(&[100]).unwrap();
+        assert_eq!(image.frame_at(0.0), (0, Duration::MAX));
+        assert_eq!(image.frame_at(123.4), (0, Duration::MAX));
+    }
+
+    #[test]
+    fn frame_at_rounding_falls_back_to_first_frame() {
+        let image = animation(&[100, 100]).unwrap();
+        // Just short of the full duration, but rounds up to it when converted to a `Duration`:
+        let time = image.duration().as_secs_f64() - 1e-13;
+        assert_eq!(image.frame_at(time), (0, Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn short_delays_use_the_default_delay() {
+        let image = animation(&[0, 50]).unwrap();
+        assert_eq!(
+            image.frame_delays(),
+            &[
+                AnimatedImage::DEFAULT_FRAME_DELAY,
+                Duration::from_millis(50)
+            ]
+        );
+    }
+}
@@ -15,6 +15,7 @@ mod datepicker;
 
 pub mod image;
 mod layout;
+pub mod loaders;
 mod sizing;
 mod strip;
 mod table;
@@ -22,8 +23,9 @@ mod table;
 #[cfg(feature = "chrono")]
 pub use crate::datepicker::DatePickerButton;
 
-pub use crate::image::RetainedImage;
+pub use crate::image::{AnimatedImage, RetainedImage};
 pub(crate) use crate::layout::StripLayout;
+pub use crate::loaders::install_image_loaders;
 pub use crate::sizing::Size;
 pub use crate::strip::*;
 pub use crate::table::*;
@@ -0,0 +1,64 @@
+//! Image loaders for [`egui::load`], e.g. for `file://` URIs, PNG, JPEG, GIF and SVG.
+
+#[cfg(feature = "file")]
+mod file_loader;
+#[cfg(feature = "image")]
+mod image_loader;
+#[cfg(feature = "svg")]
+mod svg_loader;
+
+#[cfg(feature = "file")]
+pub use self::file_loader::FileLoader;
+#[cfg(feature = "image")]
+pub use self::image_loader::ImageCrateLoader;
+#[cfg(feature = "svg")]
+pub use self::svg_loader::SvgLoader;
+
+/// Installs the image loaders of all enabled features.
+///
+/// Call this once at the start of your app, e.g. in the creation closure of `eframe::run_native`:
+///
+/// ```
+/// # let ctx = egui::Context::default();
+/// egui_extras::install_image_loaders(&ctx);
+/// ```
+///
+/// Calling it several times is harmless; a loader is only installed once.
+///
+/// Which loaders are installed depends on the enabled features:
+/// - `file`: loads `file://` URIs from disk, on a background thread.
+/// - `image`: decodes any image format enabled in the `image` crate, e.g. PNG, JPEG and GIF.
+///   You must opt-in to the formats you need with e.g. `image = { version = "0.24", features = ["jpeg", "png"] }`.
+/// - `svg`: rasterizes SVG images, at the size they are shown at.
+///
+/// `bytes://` URIs (see [`egui::Context::include_bytes`]) are always supported by egui itself.
+pub fn install_image_loaders(ctx: &egui::Context) {
+    #[cfg(feature = "file")]
+    if !ctx.is_loader_installed(self::file_loader::FileLoader::ID) {
+        ctx.add_bytes_loader(std::sync::Arc::new(self::file_loader::FileLoader::default()));
+    }
+
+    #[cfg(feature = "image")]
+    if !ctx.is_loader_installed(self::image_loader::ImageCrateLoader::ID) {
+        ctx.add_image_loader(std::sync::Arc::new(
+            self::image_loader::ImageCrateLoader::default(),
+        ));
+    }
+
+    #[cfg(feature = "svg")]
+    if !ctx.is_loader_installed(self::svg_loader::SvgLoader::ID) {
+        ctx.add_image_loader(std::sync::Arc::new(self::svg_loader::SvgLoader::default()));
+    }
+
+    #[cfg(not(any(feature = "file", feature = "image", feature = "svg")))]
+    let _ = ctx;
+}
+
+/// The file extension of the URI, lowercased, ignoring any query or fragment.
+#[cfg(any(feature = "image", feature = "svg"))]
+fn uri_extension(uri: &str) -> Option<String> {
+    let path = uri.split(&['?', '#'][..]).next().unwrap_or(uri);
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (_, extension) = file_name.rsplit_once('.')?;
+    Some(extension.to_ascii_lowercase())
+}
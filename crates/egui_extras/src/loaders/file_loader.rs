@@ -0,0 +1,114 @@
+use std::{collections::HashMap, sync::Arc, task::Poll};
+
+use egui::{
+    load::{Bytes, BytesLoadResult, BytesLoader, BytesPoll, LoadError},
+    mutex::Mutex,
+};
+
+type Entry = Poll<Result<Arc<[u8]>, String>>;
+
+/// Loads `file://` URIs from disk.
+///
+/// Files are read on a background thread, so the ui doesn't freeze while waiting for the disk.
+/// A repaint is requested once the file has been read.
+#[derive(Default)]
+pub struct FileLoader {
+    /// Cache for loaded files
+    cache: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl FileLoader {
+    pub const ID: &'static str = "egui_extras::FileLoader";
+}
+
+const PROTOCOL: &str = "file://";
+
+/// Guess the MIME type from the file extension, so image loaders know what they get.
+fn mime_from_path(path: &str) -> Option<&'static str> {
+    let (_, extension) = path.rsplit_once('.')?;
+    Some(match extension.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "apng" => "image/apng",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        _ => return None,
+    })
+}
+
+impl BytesLoader for FileLoader {
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn load(&self, ctx: &egui::Context, uri: &str) -> BytesLoadResult {
+        // File loader only supports the `file` protocol.
+        let Some(path) = uri.strip_prefix(PROTOCOL) else {
+            return Err(LoadError::NotSupported);
+        };
+
+        let mut cache = self.cache.lock();
+        if let Some(entry) = cache.get(uri).cloned() {
+            match entry {
+                Poll::Ready(Ok(bytes)) => Ok(BytesPoll::Ready {
+                    size: None,
+                    bytes: Bytes::Shared(bytes),
+                    mime: mime_from_path(path).map(ToOwned::to_owned),
+                }),
+                Poll::Ready(Err(err)) => Err(LoadError::Loading(err)),
+                Poll::Pending => Ok(BytesPoll::Pending { size: None }),
+            }
+        } else {
+            cache.insert(uri.to_owned(), Poll::Pending);
+            drop(cache);
+
+            let cache = self.cache.clone();
+            let path = path.to_owned();
+            let thread_uri = uri.to_owned();
+            let ctx = ctx.clone();
+            let spawned = std::thread::Builder::new()
+                .name(format!("egui_extras::FileLoader::load({uri:?})"))
+                .spawn(move || {
+                    let result = std::fs::read(&path)
+                        .map(Arc::<[u8]>::from)
+                        .map_err(|err| format!("Failed to read {path:?}: {err}"));
+                    // Only store the result if the file wasn't forgotten while we were reading it:
+                    if let Some(entry) = cache.lock().get_mut(&thread_uri) {
+                        *entry = Poll::Ready(result);
+                    }
+                    ctx.request_repaint();
+                });
+            if let Err(err) = spawned {
+                let err = format!("Failed to spawn thread to read {uri:?}: {err}");
+                self.cache
+                    .lock()
+                    .insert(uri.to_owned(), Poll::Ready(Err(err.clone())));
+                return Err(LoadError::Loading(err));
+            }
+
+            Ok(BytesPoll::Pending { size: None })
+        }
+    }
+
+    fn forget(&self, uri: &str) {
+        let _ = self.cache.lock().remove(uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache
+            .lock()
+            .values()
+            .map(|entry| match entry {
+                Poll::Ready(Ok(bytes)) => bytes.len(),
+                Poll::Ready(Err(err)) => err.len(),
+                Poll::Pending => 0,
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,90 @@
+use std::{collections::HashMap, sync::Arc};
+
+use egui::{
+    load::{BytesPoll, ImageLoadResult, ImageLoader, ImagePoll, LoadError, SizeHint},
+    mutex::Mutex,
+    ColorImage,
+};
+
+type Entry = Result<Arc<ColorImage>, String>;
+
+/// Decodes images with the [`image`](https://docs.rs/image) crate.
+///
+/// Supports the formats enabled in the `image` crate, e.g. PNG, JPEG and GIF.
+/// Only the first frame of animated images is shown.
+#[derive(Default)]
+pub struct ImageCrateLoader {
+    cache: Mutex<HashMap<String, Entry>>,
+}
+
+impl ImageCrateLoader {
+    pub const ID: &'static str = "egui_extras::ImageCrateLoader";
+}
+
+/// Can the `image` crate decode this, as far as we can tell from the URI or MIME type?
+fn is_supported(uri: &str, mime: Option<&str>) -> bool {
+    if let Some(mime) = mime {
+        if let Some(format) = image::ImageFormat::from_mime_type(mime) {
+            return format.can_read();
+        }
+        if mime != "application/octet-stream" {
+            return false;
+        }
+    }
+
+    // Not all URIs have an extension, e.g. `bytes://icon`, so we give those a try:
+    super::uri_extension(uri).map_or(true, |extension| {
+        image::ImageFormat::from_extension(extension).map_or(false, |format| format.can_read())
+    })
+}
+
+impl ImageLoader for ImageCrateLoader {
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn load(&self, ctx: &egui::Context, uri: &str, _: SizeHint) -> ImageLoadResult {
+        // The size hint is ignored: raster images are always decoded at their original size.
+
+        if let Some(entry) = self.cache.lock().get(uri).cloned() {
+            return match entry {
+                Ok(image) => Ok(ImagePoll::Ready { image }),
+                Err(err) => Err(LoadError::Loading(err)),
+            };
+        }
+
+        match ctx.try_load_bytes(uri)? {
+            BytesPoll::Ready { bytes, mime, .. } => {
+                if !is_supported(uri, mime.as_deref()) {
+                    return Err(LoadError::NotSupported);
+                }
+                let result = crate::image::load_image_bytes(&bytes).map(Arc::new);
+                self.cache.lock().insert(uri.to_owned(), result.clone());
+                match result {
+                    Ok(image) => Ok(ImagePoll::Ready { image }),
+                    Err(err) => Err(LoadError::Loading(err)),
+                }
+            }
+            BytesPoll::Pending { size } => Ok(ImagePoll::Pending { size }),
+        }
+    }
+
+    fn forget(&self, uri: &str) {
+        let _ = self.cache.lock().remove(uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache
+            .lock()
+            .values()
+            .map(|result| match result {
+                Ok(image) => image.pixels.len() * std::mem::size_of::<egui::Color32>(),
+                Err(err) => err.len(),
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,87 @@
+use std::{collections::HashMap, sync::Arc};
+
+use egui::{
+    load::{BytesPoll, ImageLoadResult, ImageLoader, ImagePoll, LoadError, SizeHint},
+    mutex::Mutex,
+    ColorImage,
+};
+
+use crate::image::FitTo;
+
+type Entry = Result<Arc<ColorImage>, String>;
+
+/// Rasterizes SVG images with [`resvg`](https://docs.rs/resvg).
+///
+/// The image is rasterized at the size it is shown at (see [`SizeHint`]), so it stays sharp.
+#[derive(Default)]
+pub struct SvgLoader {
+    cache: Mutex<HashMap<(String, SizeHint), Entry>>,
+}
+
+impl SvgLoader {
+    pub const ID: &'static str = "egui_extras::SvgLoader";
+}
+
+fn is_supported(uri: &str, mime: Option<&str>) -> bool {
+    match mime {
+        Some(mime) => mime.starts_with("image/svg"),
+        None => super::uri_extension(uri).as_deref() == Some("svg"),
+    }
+}
+
+impl ImageLoader for SvgLoader {
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn load(&self, ctx: &egui::Context, uri: &str, size_hint: SizeHint) -> ImageLoadResult {
+        let key = (uri.to_owned(), size_hint);
+
+        if let Some(entry) = self.cache.lock().get(&key).cloned() {
+            return match entry {
+                Ok(image) => Ok(ImagePoll::Ready { image }),
+                Err(err) => Err(LoadError::Loading(err)),
+            };
+        }
+
+        match ctx.try_load_bytes(uri)? {
+            BytesPoll::Ready { bytes, mime, .. } => {
+                if !is_supported(uri, mime.as_deref()) {
+                    return Err(LoadError::NotSupported);
+                }
+                let fit_to = match size_hint {
+                    SizeHint::Original => FitTo::Original,
+                    SizeHint::Width(w) => FitTo::Width(w),
+                    SizeHint::Height(h) => FitTo::Height(h),
+                    SizeHint::Size(w, h) => FitTo::Size(w, h),
+                };
+                let result = crate::image::load_svg_bytes_with_size(&bytes, fit_to).map(Arc::new);
+                self.cache.lock().insert(key, result.clone());
+                match result {
+                    Ok(image) => Ok(ImagePoll::Ready { image }),
+                    Err(err) => Err(LoadError::Loading(err)),
+                }
+            }
+            BytesPoll::Pending { size } => Ok(ImagePoll::Pending { size }),
+        }
+    }
+
+    fn forget(&self, uri: &str) {
+        self.cache.lock().retain(|(u, _), _| u != uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache
+            .lock()
+            .values()
+            .map(|result| match result {
+                Ok(image) => image.pixels.len() * std::mem::size_of::<egui::Color32>(),
+                Err(err) => err.len(),
+            })
+            .sum()
+    }
+}
@@ -40,7 +40,7 @@ fn main() {
                     }
                 });
                 egui::Window::new("NativeTextureDisplay").show(egui_ctx, |ui| {
-                    ui.image(texture_id, image_size);
+                    ui.image((texture_id, image_size));
                 });
             });
 
@@ -2,7 +2,10 @@
 #![allow(semicolon_in_expressions_from_macros)] // glium::program! macro
 
 use egui::{
-    epaint::{textures::TextureFilter, Primitive},
+    epaint::{
+        textures::{TextureFilter, TextureWrapMode},
+        Primitive,
+    },
     TextureOptions,
 };
 
@@ -202,17 +205,23 @@ impl Painter {
                 TextureFilter::Nearest => MinifySamplerFilter::Nearest,
                 TextureFilter::Linear => MinifySamplerFilter::Linear,
             };
+            let wrap_function = match texture.options.wrap_mode {
+                TextureWrapMode::ClampToEdge => SamplerWrapFunction::Clamp,
+                TextureWrapMode::Repeat => SamplerWrapFunction::Repeat,
+                TextureWrapMode::MirroredRepeat => SamplerWrapFunction::Mirror,
+            };
 
             let sampler = texture
                 .glium_texture
                 .sampled()
                 .magnify_filter(mag_filter)
                 .minify_filter(min_filter)
-                .wrap_function(SamplerWrapFunction::Clamp);
+                .wrap_function(wrap_function);
 
             let uniforms = uniform! {
                 u_screen_size: [width_in_points, height_in_points],
                 u_sampler: sampler,
+                u_sdf: texture.sdf,
             };
 
             // egui outputs colors with premultiplied alpha:
@@ -329,7 +338,8 @@ impl Painter {
             let gl_texture =
                 SrgbTexture2d::with_format(facade, glium_image, format, mipmaps).unwrap();
 
-            let user_texture = EguiTexture::new(gl_texture.into(), delta.options);
+            let mut user_texture = EguiTexture::new(gl_texture.into(), delta.options);
+            user_texture.sdf = matches!(&delta.image, egui::ImageData::Font(image) if image.sdf);
             self.textures.insert(tex_id, user_texture);
         }
     }
@@ -369,6 +379,9 @@ impl Painter {
 struct EguiTexture {
     glium_texture: Rc<SrgbTexture2d>,
     options: TextureOptions,
+
+    /// The alpha channel is a signed distance field, see [`egui::FontImage::sdf`].
+    sdf: bool,
 }
 
 impl EguiTexture {
@@ -376,6 +389,7 @@ impl EguiTexture {
         Self {
             glium_texture,
             options,
+            sdf: false,
         }
     }
 }
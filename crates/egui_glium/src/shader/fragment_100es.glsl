@@ -2,6 +2,7 @@
 
 precision mediump float;
 uniform sampler2D u_sampler;
+uniform bool u_sdf;
 varying vec4 v_rgba_gamma; // 0-1 gamma sRGBA
 varying vec2 v_tc;
 
@@ -29,7 +30,21 @@ vec4 linear_from_srgba(vec4 srgba) {
     return vec4(linear_from_srgb(srgba.rgb), srgba.a / 255.0);
 }
 
+// Opacity from a signed distance field, with the edge at 0.5.
+float sdf_coverage(float distance) {
+    // `fwidth` needs an extension in GLSL ES 1.00, so we use a fixed width:
+    float half_width = 0.05;
+    return smoothstep(0.5 - half_width, 0.5 + half_width, distance);
+}
+
 void main() {
+    if (u_sdf) {
+        // The alpha channel is linear, also in sRGB textures:
+        float coverage = sdf_coverage(texture2D(u_sampler, v_tc).a);
+        gl_FragColor = v_rgba_gamma * coverage;
+        return;
+    }
+
     // WebGL doesn't come with sRGBA textures:
     vec4 texture_in_gamma = texture2D(u_sampler, v_tc);
 
@@ -1,6 +1,7 @@
 #version 140
 
 uniform sampler2D u_sampler;
+uniform bool u_sdf;
 in vec4 v_rgba_gamma;
 in vec2 v_tc;
 out vec4 f_color;
@@ -23,7 +24,21 @@ vec4 gamma_from_linear_rgba(vec4 linear_rgba) {
     return vec4(srgb_from_linear(linear_rgba.rgb) / 255.0, linear_rgba.a);
 }
 
+// Opacity from a signed distance field, with the edge at 0.5.
+float sdf_coverage(float distance) {
+    // Smooth over about one physical pixel (smoothstep is undefined for an empty edge):
+    float half_width = max(0.5 * fwidth(distance), 1e-4);
+    return smoothstep(0.5 - half_width, 0.5 + half_width, distance);
+}
+
 void main() {
+    if (u_sdf) {
+        // The alpha channel is linear, also in sRGB textures:
+        float coverage = sdf_coverage(texture(u_sampler, v_tc).a);
+        f_color = v_rgba_gamma * coverage;
+        return;
+    }
+
     // The texture is set up with `SRGB8_ALPHA8`
     vec4 texture_in_gamma = gamma_from_linear_rgba(texture(u_sampler, v_tc));
 
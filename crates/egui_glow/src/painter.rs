@@ -1,7 +1,10 @@
 #![allow(clippy::collapsible_else_if)]
 #![allow(unsafe_code)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use egui::{
     emath::Rect,
@@ -34,6 +37,20 @@ impl TextureFilterExt for egui::TextureFilter {
     }
 }
 
+trait TextureWrapModeExt {
+    fn glow_code(&self) -> u32;
+}
+
+impl TextureWrapModeExt for egui::TextureWrapMode {
+    fn glow_code(&self) -> u32 {
+        match self {
+            egui::TextureWrapMode::ClampToEdge => glow::CLAMP_TO_EDGE,
+            egui::TextureWrapMode::Repeat => glow::REPEAT,
+            egui::TextureWrapMode::MirroredRepeat => glow::MIRRORED_REPEAT,
+        }
+    }
+}
+
 /// An OpenGL painter using [`glow`].
 ///
 /// This is responsible for painting egui and managing egui textures.
@@ -49,6 +66,7 @@ pub struct Painter {
     program: glow::Program,
     u_screen_size: glow::UniformLocation,
     u_sampler: glow::UniformLocation,
+    u_sdf: glow::UniformLocation,
     is_webgl_1: bool,
     vao: crate::vao::VertexArrayObject,
     srgb_textures: bool,
@@ -57,6 +75,9 @@ pub struct Painter {
 
     textures: HashMap<egui::TextureId, glow::Texture>,
 
+    /// Font textures containing signed distance fields, see [`egui::epaint::FontImage::sdf`].
+    sdf_textures: HashSet<egui::TextureId>,
+
     next_native_tex_id: u64,
 
     /// Stores outdated OpenGL textures that are yet to be deleted
@@ -170,6 +191,7 @@ impl Painter {
             gl.delete_shader(frag);
             let u_screen_size = gl.get_uniform_location(program, "u_screen_size").unwrap();
             let u_sampler = gl.get_uniform_location(program, "u_sampler").unwrap();
+            let u_sdf = gl.get_uniform_location(program, "u_sdf").unwrap();
 
             let vbo = gl.create_buffer()?;
 
@@ -216,12 +238,14 @@ impl Painter {
                 program,
                 u_screen_size,
                 u_sampler,
+                u_sdf,
                 is_webgl_1,
                 vao,
                 srgb_textures,
                 vbo,
                 element_array_buffer,
                 textures: Default::default(),
+                sdf_textures: Default::default(),
                 next_native_tex_id: 1 << 32,
                 textures_to_destroy: Vec::new(),
                 destroyed: false,
@@ -443,6 +467,10 @@ impl Painter {
                 );
 
                 self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+                self.gl.uniform_1_i32(
+                    Some(&self.u_sdf),
+                    self.sdf_textures.contains(&mesh.texture_id) as i32,
+                );
             }
 
             unsafe {
@@ -494,6 +522,14 @@ impl Painter {
                     "Mismatch between texture size and texel count"
                 );
 
+                if delta.is_whole() {
+                    if image.sdf {
+                        self.sdf_textures.insert(tex_id);
+                    } else {
+                        self.sdf_textures.remove(&tex_id);
+                    }
+                }
+
                 let data: Vec<u8> = image
                     .srgba_pixels(None)
                     .flat_map(|a| a.to_array())
@@ -535,12 +571,12 @@ impl Painter {
             self.gl.tex_parameter_i32(
                 glow::TEXTURE_2D,
                 glow::TEXTURE_WRAP_S,
-                glow::CLAMP_TO_EDGE as i32,
+                options.wrap_mode.glow_code() as i32,
             );
             self.gl.tex_parameter_i32(
                 glow::TEXTURE_2D,
                 glow::TEXTURE_WRAP_T,
-                glow::CLAMP_TO_EDGE as i32,
+                options.wrap_mode.glow_code() as i32,
             );
             check_for_gl_error!(&self.gl, "tex_parameter");
 
@@ -592,6 +628,7 @@ impl Painter {
     }
 
     pub fn free_texture(&mut self, tex_id: egui::TextureId) {
+        self.sdf_textures.remove(&tex_id);
         if let Some(old_tex) = self.textures.remove(&tex_id) {
             unsafe { self.gl.delete_texture(old_tex) };
         }
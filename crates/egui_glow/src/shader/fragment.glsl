@@ -1,9 +1,21 @@
+#if !defined(GL_ES) || NEW_SHADER_INTERFACE
+    #define HAS_FWIDTH 1
+#elif defined(GL_OES_standard_derivatives)
+    #extension GL_OES_standard_derivatives : enable
+    #define HAS_FWIDTH 1
+#else
+    #define HAS_FWIDTH 0
+#endif
+
 #ifdef GL_ES
     precision mediump float;
 #endif
 
 uniform sampler2D u_sampler;
 
+// The texture is a font atlas of signed distance fields (`FontImage::sdf`).
+uniform bool u_sdf;
+
 #if NEW_SHADER_INTERFACE
     in vec4 v_rgba_in_gamma;
     in vec2 v_tc;
@@ -29,7 +41,25 @@ vec4 srgba_gamma_from_linear(vec4 rgba) {
     return vec4(srgb_gamma_from_linear(rgba.rgb), rgba.a);
 }
 
+// Opacity from a signed distance field, with the edge at 0.5.
+float sdf_coverage(float distance) {
+#if HAS_FWIDTH
+    // Smooth over about one physical pixel (smoothstep is undefined for an empty edge):
+    float half_width = max(0.5 * fwidth(distance), 1e-4);
+#else
+    float half_width = 0.05;
+#endif
+    return smoothstep(0.5 - half_width, 0.5 + half_width, distance);
+}
+
 void main() {
+    if (u_sdf) {
+        // The alpha channel is linear, also in sRGB textures:
+        float coverage = sdf_coverage(texture2D(u_sampler, v_tc).a);
+        gl_FragColor = v_rgba_in_gamma * coverage;
+        return;
+    }
+
 #if SRGB_TEXTURES
     vec4 texture_in_gamma = srgba_gamma_from_linear(texture2D(u_sampler, v_tc));
 #else
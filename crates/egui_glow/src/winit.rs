@@ -53,6 +53,7 @@ impl EguiGlow {
             repaint_after,
             textures_delta,
             shapes,
+            viewports: _, // Viewports are embedded as windows, so this is always empty.
         } = self.egui_ctx.run(raw_input, run_ui);
 
         self.egui_winit
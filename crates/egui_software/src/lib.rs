@@ -0,0 +1,32 @@
+//! A pure Rust CPU rasterizer for the output of [`egui`].
+//!
+//! The main type you want to look at is [`Painter`],
+//! which paints [`egui::ClippedPrimitive`]:s into an [`egui::ColorImage`].
+//!
+//! This is useful for rendering egui without a GPU,
+//! e.g. for screenshots, thumbnails and golden-image tests on CI machines.
+//!
+//! ```
+//! let ctx = egui::Context::default();
+//! let output = ctx.run(Default::default(), |ctx| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.label("Hello world!");
+//!     });
+//! });
+//! let primitives = ctx.tessellate(output.shapes);
+//!
+//! let mut painter = egui_software::Painter::default();
+//! let mut image = egui::ColorImage::new([800, 600], egui::Color32::BLACK);
+//! painter.paint_and_update_textures(&mut image, 1.0, &primitives, &output.textures_delta);
+//! ```
+//!
+//! ## Feature flags
+#![cfg_attr(feature = "document-features", doc = document_features::document_features!())]
+//!
+
+#![allow(clippy::float_cmp)]
+#![allow(clippy::manual_range_contains)]
+#![forbid(unsafe_code)]
+
+pub mod painter;
+pub use painter::Painter;
@@ -0,0 +1,691 @@
+use std::collections::HashMap;
+
+use egui::{
+    emath::{Pos2, Rect, Vec2},
+    epaint::{ImageDelta, Mesh, Primitive, Vertex},
+    Color32, ColorImage, ImageData, TextureFilter, TextureId, TextureOptions, TextureWrapMode,
+    TexturesDelta,
+};
+
+/// A texture uploaded to the [`Painter`].
+///
+/// Stored the same way `egui_glow` stores them: as `sRGBA` premultiplied texels.
+struct Texture {
+    size: [usize; 2],
+    pixels: Vec<Color32>,
+    options: TextureOptions,
+
+    /// The alpha channel is a signed distance field, see [`egui::FontImage::sdf`].
+    sdf: bool,
+}
+
+impl Texture {
+    #[inline]
+    fn texel(&self, x: isize, y: isize) -> [f32; 4] {
+        let x = wrap_texel(x, self.size[0], self.options.wrap_mode);
+        let y = wrap_texel(y, self.size[1], self.options.wrap_mode);
+        self.pixels[y * self.size[0] + x].to_normalized_gamma_f32()
+    }
+
+    /// Sample the texture at the given normalized uv coordinate.
+    fn sample(&self, uv: Pos2, filter: TextureFilter) -> [f32; 4] {
+        let x = uv.x * self.size[0] as f32;
+        let y = uv.y * self.size[1] as f32;
+
+        match filter {
+            TextureFilter::Nearest => self.texel(x.floor() as isize, y.floor() as isize),
+            TextureFilter::Linear => {
+                // Texel centers are at half-integer coordinates:
+                let x = x - 0.5;
+                let y = y - 0.5;
+                let x0 = x.floor();
+                let y0 = y.floor();
+                let tx = x - x0;
+                let ty = y - y0;
+                let (x0, y0) = (x0 as isize, y0 as isize);
+
+                let top = lerp4(self.texel(x0, y0), self.texel(x0 + 1, y0), tx);
+                let bottom = lerp4(self.texel(x0, y0 + 1), self.texel(x0 + 1, y0 + 1), tx);
+                lerp4(top, bottom, ty)
+            }
+        }
+    }
+}
+
+/// Map a texel coordinate outside the texture back into it.
+#[inline]
+fn wrap_texel(i: isize, size: usize, wrap_mode: TextureWrapMode) -> usize {
+    let size = size as isize;
+    let i = match wrap_mode {
+        TextureWrapMode::ClampToEdge => i.clamp(0, size - 1),
+        TextureWrapMode::Repeat => i.rem_euclid(size),
+        TextureWrapMode::MirroredRepeat => {
+            let i = i.rem_euclid(2 * size);
+            if i < size {
+                i
+            } else {
+                2 * size - 1 - i
+            }
+        }
+    };
+    i as usize
+}
+
+/// A CPU painter that rasterizes the triangles egui outputs into a [`ColorImage`].
+///
+/// This is responsible for painting egui and managing egui textures,
+/// just like `egui_glow::Painter`, but without any GPU.
+///
+/// The output closely follows what the GPU backends produce:
+/// * colors are blended with premultiplied alpha in gamma space,
+/// * clip rectangles are rounded to whole physical pixels,
+/// * textures are sampled according to their [`TextureOptions`], including the wrap mode,
+/// * signed distance field fonts get the same smooth edges as in the shaders.
+///
+/// [`egui::PaintCallback`]:s can not be supported, and are ignored.
+#[derive(Default)]
+pub struct Painter {
+    textures: HashMap<TextureId, Texture>,
+}
+
+impl Painter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the texture changes, paint the primitives, and then free the textures.
+    ///
+    /// You are expected to have cleared `target` before calling this,
+    /// e.g. by creating it with [`ColorImage::new`].
+    ///
+    /// The size of `target` is the screen size in physical pixels.
+    pub fn paint_and_update_textures(
+        &mut self,
+        target: &mut ColorImage,
+        pixels_per_point: f32,
+        clipped_primitives: &[egui::ClippedPrimitive],
+        textures_delta: &TexturesDelta,
+    ) {
+        for (id, image_delta) in &textures_delta.set {
+            self.set_texture(*id, image_delta);
+        }
+
+        self.paint_primitives(target, pixels_per_point, clipped_primitives);
+
+        for &id in &textures_delta.free {
+            self.free_texture(id);
+        }
+    }
+
+    /// Paint the primitives on top of what is already in `target`.
+    ///
+    /// The size of `target` is the screen size in physical pixels.
+    pub fn paint_primitives(
+        &mut self,
+        target: &mut ColorImage,
+        pixels_per_point: f32,
+        clipped_primitives: &[egui::ClippedPrimitive],
+    ) {
+        for egui::ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in clipped_primitives
+        {
+            match primitive {
+                Primitive::Mesh(mesh) => {
+                    let clip = ClipRect::new(target.size, pixels_per_point, *clip_rect);
+                    self.paint_mesh(target, pixels_per_point, clip, mesh);
+                }
+                Primitive::Callback(_) => {
+                    // We can't run arbitrary GPU code.
+                }
+            }
+        }
+    }
+
+    fn paint_mesh(
+        &self,
+        target: &mut ColorImage,
+        pixels_per_point: f32,
+        clip: ClipRect,
+        mesh: &Mesh,
+    ) {
+        debug_assert!(mesh.is_valid());
+        if clip.is_empty() {
+            return;
+        }
+        let Some(texture) = self.textures.get(&mesh.texture_id) else {
+            return;
+        };
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [
+                &mesh.vertices[triangle[0] as usize],
+                &mesh.vertices[triangle[1] as usize],
+                &mesh.vertices[triangle[2] as usize],
+            ];
+            paint_triangle(target, pixels_per_point, clip, texture, [a, b, c]);
+        }
+    }
+
+    // ------------------------------------------------------------------------
+
+    pub fn set_texture(&mut self, tex_id: TextureId, delta: &ImageDelta) {
+        let sdf = matches!(&delta.image, ImageData::Font(image) if image.sdf);
+        let pixels: Vec<Color32> = match &delta.image {
+            ImageData::Color(image) => {
+                assert_eq!(
+                    image.width() * image.height(),
+                    image.pixels.len(),
+                    "Mismatch between texture size and texel count"
+                );
+                image.pixels.clone()
+            }
+            ImageData::Font(image) => {
+                assert_eq!(
+                    image.width() * image.height(),
+                    image.pixels.len(),
+                    "Mismatch between texture size and texel count"
+                );
+                image.srgba_pixels(None).collect()
+            }
+        };
+        let size = delta.image.size();
+
+        if let Some([x, y]) = delta.pos {
+            let Some(texture) = self.textures.get_mut(&tex_id) else {
+                debug_assert!(false, "Partial update of unknown texture {tex_id:?}");
+                return;
+            };
+            assert!(
+                x + size[0] <= texture.size[0] && y + size[1] <= texture.size[1],
+                "Partial texture update is outside the bounds of texture {tex_id:?}"
+            );
+            for row in 0..size[1] {
+                let dst = (y + row) * texture.size[0] + x;
+                let src = row * size[0];
+                texture.pixels[dst..dst + size[0]].copy_from_slice(&pixels[src..src + size[0]]);
+            }
+            texture.options = delta.options;
+        } else {
+            self.textures.insert(
+                tex_id,
+                Texture {
+                    size,
+                    pixels,
+                    options: delta.options,
+                    sdf,
+                },
+            );
+        }
+    }
+
+    pub fn free_texture(&mut self, tex_id: TextureId) {
+        self.textures.remove(&tex_id);
+    }
+
+    /// Register a texture of your own, to be referenced with a [`TextureId::User`].
+    ///
+    /// Replaces any previous texture with the same id.
+    pub fn set_user_texture(&mut self, id: u64, image: ColorImage, options: TextureOptions) {
+        self.set_texture(TextureId::User(id), &ImageDelta::full(image, options));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Scissor rectangle in physical pixels, `min` inclusive, `max` exclusive.
+#[derive(Clone, Copy)]
+struct ClipRect {
+    min_x: usize,
+    min_y: usize,
+    max_x: usize,
+    max_y: usize,
+}
+
+impl ClipRect {
+    /// Same rounding and clamping as `egui_glow` and `egui-wgpu`.
+    fn new([width, height]: [usize; 2], pixels_per_point: f32, clip_rect: Rect) -> Self {
+        let clip_min_x = (pixels_per_point * clip_rect.min.x).round() as i64;
+        let clip_min_y = (pixels_per_point * clip_rect.min.y).round() as i64;
+        let clip_max_x = (pixels_per_point * clip_rect.max.x).round() as i64;
+        let clip_max_y = (pixels_per_point * clip_rect.max.y).round() as i64;
+
+        let min_x = clip_min_x.clamp(0, width as i64);
+        let min_y = clip_min_y.clamp(0, height as i64);
+        let max_x = clip_max_x.clamp(min_x, width as i64);
+        let max_y = clip_max_y.clamp(min_y, height as i64);
+
+        Self {
+            min_x: min_x as usize,
+            min_y: min_y as usize,
+            max_x: max_x as usize,
+            max_y: max_y as usize,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.min_x >= self.max_x || self.min_y >= self.max_y
+    }
+}
+
+/// Twice the signed area of the triangle `a, b, p`.
+#[inline]
+fn edge_function(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Does a pixel center lying exactly on the edge `a -> b` belong to this triangle?
+///
+/// Two triangles sharing an edge traverse it in opposite directions,
+/// so exactly one of them will own it. This prevents double-blending along shared edges.
+#[inline]
+fn owns_edge(a: Pos2, b: Pos2) -> bool {
+    let d = b - a;
+    d.y > 0.0 || (d.y == 0.0 && d.x < 0.0)
+}
+
+fn paint_triangle(
+    target: &mut ColorImage,
+    pixels_per_point: f32,
+    clip: ClipRect,
+    texture: &Texture,
+    vertices: [&Vertex; 3],
+) {
+    let [mut va, mut vb, vc] = vertices;
+    let mut a = (pixels_per_point * va.pos.to_vec2()).to_pos2();
+    let mut b = (pixels_per_point * vb.pos.to_vec2()).to_pos2();
+    let c = (pixels_per_point * vc.pos.to_vec2()).to_pos2();
+
+    let mut area = edge_function(a, b, c);
+    if area == 0.0 || !area.is_finite() {
+        return; // Degenerate
+    }
+    if area < 0.0 {
+        // egui is not consistent with its winding order, so we make it so:
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut va, &mut vb);
+        area = -area;
+    }
+
+    // Bounding box of the triangle, in pixel indices, intersected with the clip rect:
+    let min_x = a.x.min(b.x).min(c.x).floor().max(clip.min_x as f32) as usize;
+    let min_y = a.y.min(b.y).min(c.y).floor().max(clip.min_y as f32) as usize;
+    let max_x = (a.x.max(b.x).max(c.x).ceil() as usize).min(clip.max_x);
+    let max_y = (a.y.max(b.y).max(c.y).ceil() as usize).min(clip.max_y);
+    if min_x >= max_x || min_y >= max_y {
+        return;
+    }
+
+    let uv_derivatives = uv_derivatives([a, b, c], [va.uv, vb.uv, vc.uv], area);
+    let filter = texture_filter(texture, uv_derivatives);
+
+    let colors = [
+        va.color.to_normalized_gamma_f32(),
+        vb.color.to_normalized_gamma_f32(),
+        vc.color.to_normalized_gamma_f32(),
+    ];
+    let owns = [owns_edge(b, c), owns_edge(c, a), owns_edge(a, b)];
+    let width = target.size[0];
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let w = [
+                edge_function(b, c, p),
+                edge_function(c, a, p),
+                edge_function(a, b, p),
+            ];
+            let inside = (0..3).all(|i| w[i] > 0.0 || (w[i] == 0.0 && owns[i]));
+            if !inside {
+                continue;
+            }
+
+            let l = [w[0] / area, w[1] / area, w[2] / area];
+            let uv = Pos2::new(
+                l[0] * va.uv.x + l[1] * vb.uv.x + l[2] * vc.uv.x,
+                l[0] * va.uv.y + l[1] * vb.uv.y + l[2] * vc.uv.y,
+            );
+            let texel = if texture.sdf {
+                let coverage = sdf_coverage(texture, uv, uv_derivatives, filter);
+                [coverage; 4]
+            } else {
+                texture.sample(uv, filter)
+            };
+
+            // We multiply the colors in gamma space, just like the GPU backends do.
+            let mut src = [0.0; 4];
+            for i in 0..4 {
+                let vertex_color = l[0] * colors[0][i] + l[1] * colors[1][i] + l[2] * colors[2][i];
+                src[i] = vertex_color * texel[i];
+            }
+
+            let dst = &mut target.pixels[y * width + x];
+            *dst = blend(src, *dst);
+        }
+    }
+}
+
+/// How much the uv coordinate changes from one pixel to the next, horizontally and vertically.
+fn uv_derivatives(pos: [Pos2; 3], uv: [Pos2; 3], area: f32) -> [Vec2; 2] {
+    let [a, b, c] = pos;
+
+    // Screen-space derivatives of the barycentric coordinates:
+    let dl_dx = [(b.y - c.y) / area, (c.y - a.y) / area, (a.y - b.y) / area];
+    let dl_dy = [(c.x - b.x) / area, (a.x - c.x) / area, (b.x - a.x) / area];
+
+    let derivative = |dl: [f32; 3]| {
+        Vec2::new(
+            (0..3).map(|i| dl[i] * uv[i].x).sum(),
+            (0..3).map(|i| dl[i] * uv[i].y).sum(),
+        )
+    };
+    [derivative(dl_dx), derivative(dl_dy)]
+}
+
+/// Pick magnification or minification filter, like the GPU would.
+fn texture_filter(texture: &Texture, [duv_dx, duv_dy]: [Vec2; 2]) -> TextureFilter {
+    let texture_size = Vec2::new(texture.size[0] as f32, texture.size[1] as f32);
+
+    // Texels per pixel:
+    let rho_sq = (duv_dx * texture_size)
+        .length_sq()
+        .max((duv_dy * texture_size).length_sq());
+    if rho_sq > 1.0 {
+        texture.options.minification
+    } else {
+        texture.options.magnification
+    }
+}
+
+/// Opacity from a signed distance field, with the edge at 0.5, smoothed over about one pixel.
+///
+/// The same as `sdf_coverage` in the shaders of `egui_glow` and `egui-wgpu`,
+/// with `fwidth` approximated by sampling the neighboring pixels.
+fn sdf_coverage(
+    texture: &Texture,
+    uv: Pos2,
+    [duv_dx, duv_dy]: [Vec2; 2],
+    filter: TextureFilter,
+) -> f32 {
+    let distance = texture.sample(uv, filter)[3];
+    let dx = texture.sample(uv + duv_dx, filter)[3] - distance;
+    let dy = texture.sample(uv + duv_dy, filter)[3] - distance;
+    let half_width = (0.5 * (dx.abs() + dy.abs())).max(1e-4);
+    smoothstep(0.5 - half_width, 0.5 + half_width, distance)
+}
+
+#[inline]
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Premultiplied alpha blending, with the same blend function as `egui_glow`.
+#[inline]
+fn blend(src: [f32; 4], dst: Color32) -> Color32 {
+    let [dr, dg, db, da] = dst.to_normalized_gamma_f32();
+    let [sr, sg, sb, sa] = src;
+    let r = sr + dr * (1.0 - sa);
+    let g = sg + dg * (1.0 - sa);
+    let b = sb + db * (1.0 - sa);
+    let a = sa * (1.0 - da) + da;
+    Color32::from_rgba_premultiplied(
+        u8_from_f32(r),
+        u8_from_f32(g),
+        u8_from_f32(b),
+        u8_from_f32(a),
+    )
+}
+
+#[inline]
+fn u8_from_f32(f: f32) -> u8 {
+    (f * 255.0 + 0.5).clamp(0.0, 255.0) as u8
+}
+
+#[inline]
+fn lerp4(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{pos2, vec2, ClippedPrimitive};
+
+    fn white_texture_painter() -> Painter {
+        let mut painter = Painter::default();
+        painter.set_texture(
+            TextureId::default(),
+            &ImageDelta::full(ColorImage::new([1, 1], Color32::WHITE), Default::default()),
+        );
+        painter
+    }
+
+    fn rect_primitive(clip_rect: Rect, rect: Rect, color: Color32) -> ClippedPrimitive {
+        let mut mesh = Mesh::default();
+        mesh.add_colored_rect(rect, color);
+        ClippedPrimitive {
+            clip_rect,
+            primitive: Primitive::Mesh(mesh),
+        }
+    }
+
+    #[test]
+    fn fill_rect_exactly() {
+        let mut painter = white_texture_painter();
+        let mut image = ColorImage::new([8, 8], Color32::BLACK);
+        let rect = Rect::from_min_max(pos2(2.0, 2.0), pos2(6.0, 5.0));
+        painter.paint_primitives(
+            &mut image,
+            1.0,
+            &[rect_primitive(Rect::EVERYTHING, rect, Color32::RED)],
+        );
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let inside = (2..6).contains(&x) && (2..5).contains(&y);
+                let expected = if inside { Color32::RED } else { Color32::BLACK };
+                assert_eq!(image[(x, y)], expected, "pixel {x} {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn shared_edges_are_not_blended_twice() {
+        let mut painter = white_texture_painter();
+        let mut image = ColorImage::new([4, 4], Color32::TRANSPARENT);
+        // Edges at half-pixels, going straight through the pixel centers:
+        let rect = Rect::from_min_max(pos2(0.5, 0.5), pos2(3.5, 3.5));
+        let half_white = Color32::from_rgba_premultiplied(128, 128, 128, 128);
+        painter.paint_primitives(
+            &mut image,
+            1.0,
+            &[rect_primitive(Rect::EVERYTHING, rect, half_white)],
+        );
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let c = image[(x, y)];
+                assert!(
+                    c == Color32::TRANSPARENT || c == half_white,
+                    "{c:?} at {x} {y}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn clip_rect_and_pixels_per_point() {
+        let mut painter = white_texture_painter();
+        let mut image = ColorImage::new([10, 10], Color32::BLACK);
+        let clip_rect = Rect::from_min_size(pos2(1.0, 1.0), vec2(2.0, 2.0));
+        painter.paint_primitives(
+            &mut image,
+            2.0,
+            &[rect_primitive(
+                clip_rect,
+                Rect::from_min_size(Pos2::ZERO, vec2(5.0, 5.0)),
+                Color32::WHITE,
+            )],
+        );
+
+        let num_white = image
+            .pixels
+            .iter()
+            .filter(|&&c| c == Color32::WHITE)
+            .count();
+        assert_eq!(num_white, 16);
+        assert_eq!(image[(2, 2)], Color32::WHITE);
+        assert_eq!(image[(6, 6)], Color32::BLACK);
+    }
+
+    #[test]
+    fn texture_filtering() {
+        let mut checker = ColorImage::new([2, 1], Color32::BLACK);
+        checker[(1, 0)] = Color32::WHITE;
+
+        let paint = |options: TextureOptions| {
+            let mut painter = Painter::default();
+            painter.set_user_texture(0, checker.clone(), options);
+            let mut mesh = Mesh::with_texture(TextureId::User(0));
+            mesh.add_rect_with_uv(
+                Rect::from_min_size(Pos2::ZERO, vec2(8.0, 1.0)),
+                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                Color32::WHITE,
+            );
+            let mut image = ColorImage::new([8, 1], Color32::BLACK);
+            painter.paint_primitives(
+                &mut image,
+                1.0,
+                &[ClippedPrimitive {
+                    clip_rect: Rect::EVERYTHING,
+                    primitive: Primitive::Mesh(mesh),
+                }],
+            );
+            image
+        };
+
+        let nearest = paint(TextureOptions::NEAREST);
+        assert_eq!(nearest[(3, 0)], Color32::BLACK);
+        assert_eq!(nearest[(4, 0)], Color32::WHITE);
+
+        let linear = paint(TextureOptions::LINEAR);
+        assert_eq!(linear[(0, 0)], Color32::BLACK);
+        assert_eq!(linear[(7, 0)], Color32::WHITE);
+        let middle = linear[(3, 0)].r();
+        assert!(0 < middle && middle < 255);
+    }
+
+    #[test]
+    fn texture_wrap_modes() {
+        let mut checker = ColorImage::new([2, 1], Color32::BLACK);
+        checker[(1, 0)] = Color32::WHITE;
+
+        // Show the texture twice over, with two pixels per texel:
+        let paint = |options: TextureOptions| {
+            let mut painter = Painter::default();
+            painter.set_user_texture(0, checker.clone(), options);
+            let mut mesh = Mesh::with_texture(TextureId::User(0));
+            mesh.add_rect_with_uv(
+                Rect::from_min_size(Pos2::ZERO, vec2(8.0, 1.0)),
+                Rect::from_min_max(pos2(0.0, 0.0), pos2(2.0, 1.0)),
+                Color32::WHITE,
+            );
+            let mut image = ColorImage::new([8, 1], Color32::BLACK);
+            painter.paint_primitives(
+                &mut image,
+                1.0,
+                &[ClippedPrimitive {
+                    clip_rect: Rect::EVERYTHING,
+                    primitive: Primitive::Mesh(mesh),
+                }],
+            );
+            (0..8)
+                .map(|x| image[(x, 0)] == Color32::WHITE)
+                .collect::<Vec<_>>()
+        };
+
+        let (b, w) = (false, true);
+        assert_eq!(paint(TextureOptions::NEAREST), [b, b, w, w, w, w, w, w]);
+        assert_eq!(
+            paint(TextureOptions::NEAREST_REPEAT),
+            [b, b, w, w, b, b, w, w]
+        );
+        assert_eq!(
+            paint(TextureOptions::NEAREST_MIRRORED_REPEAT),
+            [b, b, w, w, w, w, b, b]
+        );
+    }
+
+    #[test]
+    fn sdf_font_texture() {
+        // The distance goes from 0 (outside) to 1 (inside) over 16 texels:
+        let mut font_image = egui::FontImage::new([16, 1]);
+        for (i, distance) in font_image.pixels.iter_mut().enumerate() {
+            *distance = i as f32 / 15.0;
+        }
+        font_image.sdf = true;
+
+        let mut painter = Painter::default();
+        painter.set_texture(
+            TextureId::User(0),
+            &ImageDelta::full(font_image, TextureOptions::LINEAR),
+        );
+        let mut mesh = Mesh::with_texture(TextureId::User(0));
+        mesh.add_rect_with_uv(
+            Rect::from_min_size(Pos2::ZERO, vec2(16.0, 1.0)),
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+        let mut image = ColorImage::new([16, 1], Color32::BLACK);
+        painter.paint_primitives(
+            &mut image,
+            1.0,
+            &[ClippedPrimitive {
+                clip_rect: Rect::EVERYTHING,
+                primitive: Primitive::Mesh(mesh),
+            }],
+        );
+
+        // A sharp edge in the middle, instead of a gradient:
+        assert!((0..7).all(|x| image[(x, 0)] == Color32::BLACK));
+        assert!((9..16).all(|x| image[(x, 0)] == Color32::WHITE));
+    }
+
+    #[test]
+    fn paint_tessellated_ui() {
+        let ctx = egui::Context::default();
+        let raw_input = egui::RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(200.0, 100.0))),
+            ..Default::default()
+        };
+        let output = ctx.run(raw_input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.label("Hello");
+            });
+        });
+        let primitives = ctx.tessellate(output.shapes);
+        let panel_fill = ctx.style().visuals.panel_fill;
+
+        let screen_size = ctx.screen_rect().size();
+        let size = [screen_size.x as usize, screen_size.y as usize];
+        let mut image = ColorImage::new(size, Color32::TRANSPARENT);
+        let mut painter = Painter::default();
+        painter.paint_and_update_textures(&mut image, 1.0, &primitives, &output.textures_delta);
+
+        // The panel covers everything:
+        assert_eq!(image[(size[0] - 1, size[1] - 1)], panel_fill);
+
+        // …and the text shows up somewhere near the top left:
+        let text_region = image.region(&Rect::from_min_size(Pos2::ZERO, vec2(60.0, 30.0)), None);
+        assert!(text_region.pixels.iter().any(|&c| c != panel_fill));
+    }
+}
@@ -0,0 +1,502 @@
+//! Headless testing of [`egui`] UIs.
+//!
+//! The main type you want to look at is [`Harness`].
+//! It drives an [`egui::Context`] with scripted [`egui::RawInput`],
+//! renders the result on the CPU using [`egui_software`],
+//! and can compare that against golden PNG snapshots (see [`Harness::snapshot`]).
+//!
+//! ```
+//! let mut checked = false;
+//! let mut harness = egui_test::Harness::new_ui(|ui| {
+//!     ui.checkbox(&mut checked, "Check me");
+//! })
+//! .with_size(egui::vec2(200.0, 50.0));
+//!
+//! harness.run();
+//! let image = harness.render();
+//! assert_eq!(image.size, [200, 50]);
+//! ```
+//!
+//! Widgets can be found by their label, role or [`egui::Id`] (using the AccessKit tree egui outputs),
+//! and interacted with by generating the right input events:
+//!
+//! ```
+//! let mut checked = false;
+//! {
+//!     let mut harness = egui_test::Harness::new_ui(|ui| {
+//!         ui.checkbox(&mut checked, "Check me");
+//!     });
+//!     harness.run();
+//!     harness.click(harness.get_by_label("Check me").id());
+//!     harness.run();
+//!     assert_eq!(harness.get_by_label("Check me").is_selected(), Some(true));
+//! }
+//! assert!(checked);
+//! ```
+//!
+//! ## Feature flags
+#![cfg_attr(feature = "document-features", doc = document_features::document_features!())]
+//!
+
+#![allow(clippy::float_cmp)]
+#![allow(clippy::manual_range_contains)]
+#![forbid(unsafe_code)]
+
+mod node;
+mod snapshot;
+
+pub use node::Node;
+pub use snapshot::{SnapshotError, SnapshotOptions};
+
+use egui::{
+    accesskit, ClippedPrimitive, ColorImage, Context, Event, Id, Key, Modifiers, PointerButton,
+    RawInput, TextureId, Vec2,
+};
+
+/// The closure that shows your ui each frame.
+type AppFn<'a> = Box<dyn FnMut(&Context) + 'a>;
+
+/// Drives an egui [`Context`] without any window or GPU.
+///
+/// Each call to [`Self::step`] runs one frame of your ui with the input queued in [`Self::input_mut`],
+/// and [`Self::render`] rasterizes the last frame using [`egui_software::Painter`].
+pub struct Harness<'a> {
+    ctx: Context,
+    app: AppFn<'a>,
+    input: RawInput,
+    size: Vec2,
+    pixels_per_point: f32,
+    max_steps: usize,
+
+    painter: egui_software::Painter,
+    primitives: Vec<ClippedPrimitive>,
+
+    /// Textures freed last frame. Freed at the start of next frame, since [`Self::render`] may still need them.
+    textures_to_free: Vec<TextureId>,
+
+    output: egui::FullOutput,
+
+    /// The widgets of the last frame, in reading order.
+    nodes: Vec<Node>,
+}
+
+impl<'a> Harness<'a> {
+    /// Create a harness that shows the given app each frame.
+    ///
+    /// Nothing is run until you call [`Self::step`] or [`Self::run`].
+    pub fn new(app: impl FnMut(&Context) + 'a) -> Self {
+        let ctx = Context::default();
+        ctx.enable_accesskit();
+        Self {
+            ctx,
+            app: Box::new(app),
+            input: RawInput::default(),
+            size: Vec2::new(800.0, 600.0),
+            pixels_per_point: 1.0,
+            max_steps: 60,
+            painter: egui_software::Painter::default(),
+            primitives: Default::default(),
+            textures_to_free: Default::default(),
+            output: Default::default(),
+            nodes: Default::default(),
+        }
+    }
+
+    /// Create a harness that shows the given ui in an [`egui::CentralPanel`].
+    pub fn new_ui(mut app: impl FnMut(&mut egui::Ui) + 'a) -> Self {
+        Self::new(move |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| app(ui));
+        })
+    }
+
+    /// Size of the screen in points. Default: 800x600.
+    #[inline]
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Physical pixels per point. Default: 1.
+    #[inline]
+    pub fn with_pixels_per_point(mut self, pixels_per_point: f32) -> Self {
+        self.pixels_per_point = pixels_per_point;
+        self
+    }
+
+    /// How many frames [`Self::run`] will step at most before giving up on egui settling down.
+    ///
+    /// Default: 60.
+    #[inline]
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// The [`Context`] used to run the ui.
+    ///
+    /// Use this to e.g. change the style or fonts before the first frame.
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The input for the next frame.
+    ///
+    /// [`RawInput::screen_rect`] and [`RawInput::pixels_per_point`] will be overwritten by the harness.
+    pub fn input_mut(&mut self) -> &mut RawInput {
+        &mut self.input
+    }
+
+    /// Queue an event for the next frame.
+    pub fn push_event(&mut self, event: Event) {
+        self.input.events.push(event);
+    }
+
+    /// The output of the last frame.
+    pub fn output(&self) -> &egui::FullOutput {
+        &self.output
+    }
+
+    /// Run a single frame, consuming the queued input.
+    pub fn step(&mut self) {
+        self.input.screen_rect = Some(egui::Rect::from_min_size(egui::Pos2::ZERO, self.size));
+        self.input.pixels_per_point = Some(self.pixels_per_point);
+
+        for id in self.textures_to_free.drain(..) {
+            self.painter.free_texture(id);
+        }
+
+        let input = self.input.take();
+        let app = &mut self.app;
+        let mut output = self.ctx.run(input, |ctx| app(ctx));
+
+        let textures_delta = std::mem::take(&mut output.textures_delta);
+        for (id, image_delta) in &textures_delta.set {
+            self.painter.set_texture(*id, image_delta);
+        }
+        self.textures_to_free = textures_delta.free;
+
+        self.primitives = self.ctx.tessellate(std::mem::take(&mut output.shapes));
+
+        self.nodes.clear();
+        if let Some(update) = &output.platform_output.accesskit_update {
+            let root_id = egui::accesskit_root_id().accesskit_id();
+            self.nodes.extend(
+                update
+                    .nodes
+                    .iter()
+                    .filter(|(id, _)| *id != root_id)
+                    .map(|(id, node)| Node::new(*id, node.clone(), update.focus == Some(*id))),
+            );
+            self.nodes.sort_by(|a, b| {
+                let (a, b) = (a.rect().min, b.rect().min);
+                a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+            });
+        }
+
+        self.output = output;
+    }
+
+    /// Step frames until egui no longer requests an immediate repaint (e.g. when animations have finished),
+    /// but at most [`Self::with_max_steps`] frames.
+    ///
+    /// Always runs at least one frame.
+    pub fn run(&mut self) {
+        for _ in 0..self.max_steps.max(1) {
+            self.step();
+            if !self.output.repaint_after.is_zero() {
+                break;
+            }
+        }
+    }
+
+    /// Rasterize the last frame.
+    ///
+    /// The returned image is `size * pixels_per_point` physical pixels large.
+    pub fn render(&mut self) -> ColorImage {
+        let size_px = [
+            (self.size.x * self.pixels_per_point).round() as usize,
+            (self.size.y * self.pixels_per_point).round() as usize,
+        ];
+        let mut image = ColorImage::new(size_px, egui::Color32::TRANSPARENT);
+        self.painter
+            .paint_primitives(&mut image, self.pixels_per_point, &self.primitives);
+        image
+    }
+
+    /// Render the last frame and compare it to the snapshot called `name`,
+    /// using the default [`SnapshotOptions`].
+    ///
+    /// See [`Self::try_snapshot_options`].
+    ///
+    /// # Panics
+    /// If the rendered image does not match the snapshot.
+    #[track_caller]
+    pub fn snapshot(&mut self, name: &str) {
+        if let Err(err) = self.try_snapshot(name) {
+            panic!("{err}");
+        }
+    }
+
+    /// Render the last frame and compare it to the snapshot called `name`.
+    ///
+    /// # Panics
+    /// If the rendered image does not match the snapshot.
+    #[track_caller]
+    pub fn snapshot_options(&mut self, name: &str, options: &SnapshotOptions) {
+        if let Err(err) = self.try_snapshot_options(name, options) {
+            panic!("{err}");
+        }
+    }
+
+    /// Render the last frame and compare it to the snapshot called `name`,
+    /// using the default [`SnapshotOptions`].
+    ///
+    /// # Errors
+    /// See [`Self::try_snapshot_options`].
+    pub fn try_snapshot(&mut self, name: &str) -> Result<(), SnapshotError> {
+        self.try_snapshot_options(name, &SnapshotOptions::default())
+    }
+
+    /// Render the last frame and compare it to the snapshot `<output_path>/<name>.png`.
+    ///
+    /// If the `UPDATE_SNAPSHOTS` environment variable is set, the snapshot is (over)written instead.
+    ///
+    /// # Errors
+    /// If the snapshot is missing, can't be read, or does not match.
+    /// On a mismatch `<name>.new.png` and `<name>.diff.png` are written next to the snapshot.
+    pub fn try_snapshot_options(
+        &mut self,
+        name: &str,
+        options: &SnapshotOptions,
+    ) -> Result<(), SnapshotError> {
+        let image = self.render();
+        snapshot::check(&image, name, options)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Querying widgets:
+
+impl<'a> Harness<'a> {
+    /// All widgets shown in the last frame, in reading order (top to bottom, left to right).
+    pub fn query_all(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter()
+    }
+
+    /// All widgets with exactly this label.
+    pub fn query_all_by_label<'s>(&'s self, label: &'s str) -> impl Iterator<Item = &'s Node> {
+        self.query_all()
+            .filter(move |node| node.label() == Some(label))
+    }
+
+    /// The first widget with exactly this label.
+    pub fn query_by_label(&self, label: &str) -> Option<&Node> {
+        self.query_all().find(|node| node.label() == Some(label))
+    }
+
+    /// The only widget with exactly this label.
+    ///
+    /// # Panics
+    /// If there is not exactly one such widget.
+    #[track_caller]
+    pub fn get_by_label(&self, label: &str) -> &Node {
+        self.get_single(
+            |node| node.label() == Some(label),
+            &format!("label {label:?}"),
+        )
+    }
+
+    /// All widgets whose label contains the given text.
+    pub fn query_all_by_label_contains<'s>(
+        &'s self,
+        text: &'s str,
+    ) -> impl Iterator<Item = &'s Node> {
+        self.query_all()
+            .filter(move |node| label_contains(node, text))
+    }
+
+    /// The first widget whose label contains the given text.
+    pub fn query_by_label_contains(&self, text: &str) -> Option<&Node> {
+        self.query_all().find(|node| label_contains(node, text))
+    }
+
+    /// The only widget whose label contains the given text.
+    ///
+    /// # Panics
+    /// If there is not exactly one such widget.
+    #[track_caller]
+    pub fn get_by_label_contains(&self, text: &str) -> &Node {
+        self.get_single(
+            |node| label_contains(node, text),
+            &format!("a label containing {text:?}"),
+        )
+    }
+
+    /// All widgets with the given role, e.g. [`accesskit::Role::Button`].
+    pub fn query_all_by_role(&self, role: accesskit::Role) -> impl Iterator<Item = &Node> {
+        self.query_all().filter(move |node| node.role() == role)
+    }
+
+    /// The first widget with the given role.
+    pub fn query_by_role(&self, role: accesskit::Role) -> Option<&Node> {
+        self.query_all_by_role(role).next()
+    }
+
+    /// The only widget with the given role.
+    ///
+    /// # Panics
+    /// If there is not exactly one such widget.
+    #[track_caller]
+    pub fn get_by_role(&self, role: accesskit::Role) -> &Node {
+        self.get_single(|node| node.role() == role, &format!("role {role:?}"))
+    }
+
+    /// The widget with the given [`Id`].
+    pub fn query_by_id(&self, id: Id) -> Option<&Node> {
+        self.query_all().find(|node| node.id() == id)
+    }
+
+    /// The widget with the given [`Id`].
+    ///
+    /// # Panics
+    /// If there is no such widget.
+    #[track_caller]
+    pub fn get_by_id(&self, id: Id) -> &Node {
+        match self.query_by_id(id) {
+            Some(node) => node,
+            None => panic!("Found no widget with id {id:?}. {}", self.describe_nodes()),
+        }
+    }
+
+    #[track_caller]
+    fn get_single(&self, predicate: impl Fn(&Node) -> bool, what: &str) -> &Node {
+        let mut nodes = self.query_all().filter(|node| predicate(node));
+        let Some(node) = nodes.next() else {
+            panic!("Found no widget with {what}. {}", self.describe_nodes());
+        };
+        if nodes.next().is_some() {
+            panic!("Found more than one widget with {what}");
+        }
+        node
+    }
+
+    fn describe_nodes(&self) -> String {
+        let widgets: Vec<String> = self
+            .query_all()
+            .map(|node| format!("{:?} {:?}", node.role(), node.label().unwrap_or_default()))
+            .collect();
+        format!("Widgets: [{}]", widgets.join(", "))
+    }
+}
+
+fn label_contains(node: &Node, text: &str) -> bool {
+    node.label().map_or(false, |label| label.contains(text))
+}
+
+// ----------------------------------------------------------------------------
+// Simulating input:
+
+/// These queue input events for the next frame, unless otherwise noted.
+///
+/// The widgets are looked up by [`Id`] in the last frame.
+/// Call [`Harness::run`] afterwards to see the effects.
+impl<'a> Harness<'a> {
+    /// Move the pointer to the center of the widget.
+    #[track_caller]
+    pub fn hover(&mut self, id: Id) {
+        let pos = self.get_by_id(id).rect().center();
+        self.push_event(Event::PointerMoved(pos));
+    }
+
+    /// Click the widget with the primary mouse button.
+    #[track_caller]
+    pub fn click(&mut self, id: Id) {
+        self.click_button(id, PointerButton::Primary);
+    }
+
+    /// Click the widget with the given mouse button.
+    #[track_caller]
+    pub fn click_button(&mut self, id: Id, button: PointerButton) {
+        let pos = self.get_by_id(id).rect().center();
+        let modifiers = self.input.modifiers;
+        self.push_event(Event::PointerMoved(pos));
+        for pressed in [true, false] {
+            self.push_event(Event::PointerButton {
+                pos,
+                button,
+                pressed,
+                modifiers,
+            });
+        }
+    }
+
+    /// Drag the widget by `delta` points with the primary mouse button.
+    ///
+    /// Unlike the other methods, this runs three frames (press, move, release),
+    /// since egui needs to see the pointer being held down.
+    #[track_caller]
+    pub fn drag(&mut self, id: Id, delta: Vec2) {
+        let start = self.get_by_id(id).rect().center();
+        let end = start + delta;
+        let modifiers = self.input.modifiers;
+        let button = PointerButton::Primary;
+
+        self.push_event(Event::PointerMoved(start));
+        self.push_event(Event::PointerButton {
+            pos: start,
+            button,
+            pressed: true,
+            modifiers,
+        });
+        self.step();
+
+        self.push_event(Event::PointerMoved(end));
+        self.step();
+
+        self.push_event(Event::PointerButton {
+            pos: end,
+            button,
+            pressed: false,
+            modifiers,
+        });
+        self.step();
+    }
+
+    /// Give the widget keyboard focus.
+    ///
+    /// This uses an AccessKit action, just like a screen reader would.
+    #[track_caller]
+    pub fn focus(&mut self, id: Id) {
+        let target = self.get_by_id(id).id().accesskit_id();
+        self.push_event(Event::AccessKitActionRequest(accesskit::ActionRequest {
+            action: accesskit::Action::Focus,
+            target,
+            data: None,
+        }));
+    }
+
+    /// Press and release a key.
+    pub fn key_press(&mut self, key: Key) {
+        self.key_press_modifiers(Modifiers::NONE, key);
+    }
+
+    /// Press and release a key while holding down the given modifiers.
+    ///
+    /// The modifiers are only set on the key events.
+    /// To hold down modifiers for the whole frame, set [`RawInput::modifiers`] using [`Self::input_mut`].
+    pub fn key_press_modifiers(&mut self, modifiers: Modifiers, key: Key) {
+        for pressed in [true, false] {
+            self.push_event(Event::Key {
+                key,
+                pressed,
+                repeat: false,
+                modifiers,
+            });
+        }
+    }
+
+    /// Type some text into the focused widget.
+    pub fn type_text(&mut self, text: &str) {
+        self.push_event(Event::Text(text.to_owned()));
+    }
+}
@@ -0,0 +1,84 @@
+use egui::{accesskit, Id, Pos2, Rect};
+
+/// A widget shown in the last frame, as found in the AccessKit tree.
+///
+/// Get these using e.g. [`crate::Harness::get_by_label`].
+#[derive(Clone, Debug)]
+pub struct Node {
+    id: Id,
+    node: accesskit::Node,
+    focused: bool,
+}
+
+impl Node {
+    pub(crate) fn new(id: accesskit::NodeId, node: accesskit::Node, focused: bool) -> Self {
+        Self {
+            id: Id::from_accesskit_id(id),
+            node,
+            focused,
+        }
+    }
+
+    /// The [`Id`] of the widget.
+    #[inline]
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// The role of the widget, e.g. [`accesskit::Role::Button`].
+    ///
+    /// This is derived from the [`egui::WidgetType`] of the widget.
+    #[inline]
+    pub fn role(&self) -> accesskit::Role {
+        self.node.role()
+    }
+
+    /// The label of the widget, e.g. the text of a button.
+    #[inline]
+    pub fn label(&self) -> Option<&str> {
+        self.node.name()
+    }
+
+    /// The text value of e.g. a [`egui::TextEdit`].
+    #[inline]
+    pub fn value(&self) -> Option<&str> {
+        self.node.value()
+    }
+
+    /// The value of e.g. a [`egui::Slider`] or [`egui::DragValue`].
+    #[inline]
+    pub fn numeric_value(&self) -> Option<f64> {
+        self.node.numeric_value()
+    }
+
+    /// Is this checkbox, radio button or selectable label selected?
+    ///
+    /// `None` for widgets that can't be selected.
+    pub fn is_selected(&self) -> Option<bool> {
+        self.node
+            .checked_state()
+            .map(|state| state == accesskit::CheckedState::True)
+    }
+
+    /// Does this widget have keyboard focus?
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Where the widget is, in points.
+    pub fn rect(&self) -> Rect {
+        self.node.bounds().map_or(Rect::NOTHING, |bounds| {
+            Rect::from_min_max(
+                Pos2::new(bounds.x0 as f32, bounds.y0 as f32),
+                Pos2::new(bounds.x1 as f32, bounds.y1 as f32),
+            )
+        })
+    }
+
+    /// The underlying AccessKit node.
+    #[inline]
+    pub fn accesskit_node(&self) -> &accesskit::Node {
+        &self.node
+    }
+}
@@ -0,0 +1,233 @@
+use std::path::{Path, PathBuf};
+
+use egui::{Color32, ColorImage};
+
+/// The environment variable that turns on updating of snapshots.
+const UPDATE_ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// How to compare a rendered image against a stored snapshot.
+#[derive(Clone, Debug)]
+pub struct SnapshotOptions {
+    /// How much a single color channel of a pixel may differ (0-255) before the pixel counts as different.
+    ///
+    /// Small differences are expected between platforms, due to floating point differences in the font rasterizer.
+    ///
+    /// Default: 2.
+    pub threshold: u8,
+
+    /// How many pixels may differ before the snapshot fails.
+    ///
+    /// Default: 0.
+    pub max_failed_pixels: usize,
+
+    /// The directory where snapshots are stored.
+    ///
+    /// Default: `tests/snapshots` (relative to the crate being tested).
+    pub output_path: PathBuf,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 2,
+            max_failed_pixels: 0,
+            output_path: PathBuf::from("tests/snapshots"),
+        }
+    }
+}
+
+impl SnapshotOptions {
+    #[inline]
+    pub fn threshold(mut self, threshold: u8) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    #[inline]
+    pub fn max_failed_pixels(mut self, max_failed_pixels: usize) -> Self {
+        self.max_failed_pixels = max_failed_pixels;
+        self
+    }
+
+    #[inline]
+    pub fn output_path(mut self, output_path: impl Into<PathBuf>) -> Self {
+        self.output_path = output_path.into();
+        self
+    }
+}
+
+/// Why a snapshot check failed.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// There is no stored snapshot (or it could not be read).
+    OpenSnapshot {
+        path: PathBuf,
+        err: image::ImageError,
+    },
+
+    /// The rendered image has a different size than the stored snapshot.
+    SizeMismatch {
+        name: String,
+        expected: [usize; 2],
+        actual: [usize; 2],
+    },
+
+    /// Too many pixels differ.
+    Diff {
+        name: String,
+        num_failed_pixels: usize,
+        diff_path: PathBuf,
+    },
+
+    /// Failed to write an image to disk.
+    WriteSnapshot {
+        path: PathBuf,
+        err: image::ImageError,
+    },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OpenSnapshot { path, err } => write!(
+                f,
+                "Failed to read snapshot {path:?}: {err}. Run with {UPDATE_ENV_VAR}=1 to create it."
+            ),
+            Self::SizeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Snapshot {name:?} has size {}x{}, but the rendered image is {}x{}. Run with {UPDATE_ENV_VAR}=1 to update it.",
+                expected[0], expected[1], actual[0], actual[1]
+            ),
+            Self::Diff {
+                name,
+                num_failed_pixels,
+                diff_path,
+            } => write!(
+                f,
+                "Snapshot {name:?} differs in {num_failed_pixels} pixels. See {diff_path:?}. Run with {UPDATE_ENV_VAR}=1 to update it."
+            ),
+            Self::WriteSnapshot { path, err } => {
+                write!(f, "Failed to write snapshot {path:?}: {err}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+// ----------------------------------------------------------------------------
+
+fn should_update_snapshots() -> bool {
+    std::env::var_os(UPDATE_ENV_VAR).map_or(false, |value| value != "0" && !value.is_empty())
+}
+
+pub(crate) fn check(
+    image: &ColorImage,
+    name: &str,
+    options: &SnapshotOptions,
+) -> Result<(), SnapshotError> {
+    let dir = &options.output_path;
+    let path = dir.join(format!("{name}.png"));
+    let new_path = dir.join(format!("{name}.new.png"));
+    let diff_path = dir.join(format!("{name}.diff.png"));
+
+    // Remove leftovers from earlier failures:
+    std::fs::remove_file(&new_path).ok();
+    std::fs::remove_file(&diff_path).ok();
+
+    if should_update_snapshots() {
+        return save_png(image, &path);
+    }
+
+    let expected = match load_png(&path) {
+        Ok(expected) => expected,
+        Err(err) => {
+            save_png(image, &new_path)?;
+            return Err(SnapshotError::OpenSnapshot { path, err });
+        }
+    };
+
+    if expected.size != image.size {
+        save_png(image, &new_path)?;
+        return Err(SnapshotError::SizeMismatch {
+            name: name.to_owned(),
+            expected: expected.size,
+            actual: image.size,
+        });
+    }
+
+    let (num_failed_pixels, diff) = diff_images(&expected, image, options.threshold);
+    if num_failed_pixels > options.max_failed_pixels {
+        save_png(image, &new_path)?;
+        save_png(&diff, &diff_path)?;
+        return Err(SnapshotError::Diff {
+            name: name.to_owned(),
+            num_failed_pixels,
+            diff_path,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the number of pixels that differ by more than `threshold`,
+/// and an image highlighting them in red.
+fn diff_images(expected: &ColorImage, actual: &ColorImage, threshold: u8) -> (usize, ColorImage) {
+    let mut num_failed_pixels = 0;
+    let pixels = expected
+        .pixels
+        .iter()
+        .zip(&actual.pixels)
+        .map(|(&a, &b)| {
+            let max_diff = (0..4)
+                .map(|i| a[i].abs_diff(b[i]))
+                .max()
+                .unwrap_or_default();
+            if max_diff > threshold {
+                num_failed_pixels += 1;
+                Color32::RED
+            } else {
+                // Show the unchanged image faintly, to give context:
+                a.gamma_multiply(0.25)
+            }
+        })
+        .collect();
+    let diff = ColorImage {
+        size: expected.size,
+        pixels,
+    };
+    (num_failed_pixels, diff)
+}
+
+fn load_png(path: &Path) -> Result<ColorImage, image::ImageError> {
+    let image = image::open(path)?.into_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
+
+fn save_png(image: &ColorImage, path: &Path) -> Result<(), SnapshotError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+
+    let rgba: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    image::save_buffer(
+        path,
+        &rgba,
+        image.width() as u32,
+        image.height() as u32,
+        image::ColorType::Rgba8,
+    )
+    .map_err(|err| SnapshotError::WriteSnapshot {
+        path: path.to_owned(),
+        err,
+    })
+}
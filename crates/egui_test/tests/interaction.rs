@@ -0,0 +1,182 @@
+//! Finding widgets and simulating input, without hardcoded coordinates.
+
+use egui::{accesskit::Role, vec2, Key, Modifiers};
+use egui_test::Harness;
+
+#[test]
+fn click_button_by_label() {
+    let mut clicks = 0;
+    {
+        let mut harness = Harness::new_ui(|ui| {
+            ui.label("Some text above, to move the button down");
+            if ui.button("Increment").clicked() {
+                clicks += 1;
+            }
+        });
+        harness.run();
+
+        let button = harness.get_by_label("Increment");
+        assert_eq!(button.role(), Role::Button);
+
+        harness.click(button.id());
+        harness.run();
+        harness.click(harness.get_by_label("Increment").id());
+        harness.run();
+    }
+    assert_eq!(clicks, 2);
+}
+
+#[test]
+fn query_by_role_and_id() {
+    let mut harness = Harness::new_ui(|ui| {
+        ui.checkbox(&mut true, "Checked");
+        ui.add(egui::Slider::new(&mut 0.5, 0.0..=1.0));
+        ui.label("First");
+        ui.label("Second");
+    });
+    harness.run();
+
+    let checkbox = harness.get_by_role(Role::CheckBox);
+    assert_eq!(checkbox.label(), Some("Checked"));
+    assert_eq!(checkbox.is_selected(), Some(true));
+
+    let slider = harness.get_by_role(Role::Slider);
+    assert_eq!(slider.numeric_value(), Some(0.5));
+    assert_eq!(harness.get_by_id(slider.id()).role(), Role::Slider);
+
+    let labels: Vec<_> = harness
+        .query_all_by_role(Role::StaticText)
+        .filter_map(|node| node.label())
+        .collect();
+    assert_eq!(labels, ["First", "Second"]);
+
+    assert!(harness.query_by_label("Missing").is_none());
+    assert!(harness.query_by_label_contains("Sec").is_some());
+}
+
+#[test]
+fn drag_slider() {
+    let mut value = 0.0;
+    {
+        let mut harness = Harness::new_ui(|ui| {
+            ui.add(egui::Slider::new(&mut value, 0.0..=100.0).show_value(false));
+        });
+        harness.run();
+        let slider = harness.get_by_role(Role::Slider).id();
+        harness.drag(slider, vec2(1000.0, 0.0));
+        harness.run();
+    }
+    assert_eq!(value, 100.0);
+}
+
+#[test]
+fn type_into_text_edit() {
+    let mut text = String::new();
+    {
+        let mut harness = Harness::new_ui(|ui| {
+            ui.text_edit_singleline(&mut text);
+        });
+        harness.run();
+
+        let text_edit = harness.get_by_role(Role::TextField).id();
+        harness.focus(text_edit);
+        harness.run();
+        assert!(harness.get_by_id(text_edit).is_focused());
+
+        harness.type_text("Hello worlds");
+        harness.key_press(Key::Backspace);
+        harness.run();
+        assert_eq!(harness.get_by_id(text_edit).value(), Some("Hello world"));
+
+        harness.key_press_modifiers(Modifiers::COMMAND, Key::A);
+        harness.type_text("Bye");
+        harness.run();
+    }
+    assert_eq!(text, "Bye");
+}
+
+#[test]
+fn keyboard_navigation() {
+    let mut harness = Harness::new_ui(|ui| {
+        let _ = ui.button("First");
+        let _ = ui.button("Second");
+    });
+    harness.run();
+
+    harness.key_press(Key::Tab);
+    harness.run();
+    assert!(harness.get_by_label("First").is_focused());
+
+    harness.key_press(Key::Tab);
+    harness.run();
+    assert!(harness.get_by_label("Second").is_focused());
+}
+
+#[test]
+fn modal_blocks_input_below() {
+    let behind_clicks = std::cell::Cell::new(0);
+    let modal_open = std::cell::Cell::new(true);
+    let mut harness = Harness::new(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Behind").clicked() {
+                behind_clicks.set(behind_clicks.get() + 1);
+            }
+        });
+        if modal_open.get() {
+            let modal = egui::Modal::new(egui::Id::new("modal")).show(ctx, |ui| {
+                let _ = ui.button("Inside");
+            });
+            if modal.should_close {
+                modal_open.set(false);
+            }
+        }
+    });
+    harness.run();
+
+    harness.click(harness.get_by_label("Behind").id());
+    harness.run();
+    assert_eq!(behind_clicks.get(), 0);
+
+    // Tabbing cycles through the modal, never reaching the button behind it:
+    let mut inside_was_focused = false;
+    for _ in 0..4 {
+        harness.key_press(Key::Tab);
+        harness.run();
+        assert!(!harness.get_by_label("Behind").is_focused());
+        inside_was_focused |= harness.get_by_label("Inside").is_focused();
+    }
+    assert!(inside_was_focused);
+
+    harness.key_press(Key::Escape);
+    harness.run();
+    assert!(!modal_open.get());
+
+    harness.click(harness.get_by_label("Behind").id());
+    harness.run();
+    assert_eq!(behind_clicks.get(), 1);
+}
+
+#[test]
+fn drag_and_drop_payload() {
+    let dropped = std::cell::Cell::new(None);
+    let mut harness = Harness::new_ui(|ui| {
+        ui.dnd_drag_source(egui::Id::new("source"), 42_u32, |ui| {
+            ui.label("Source");
+        });
+        ui.add_space(32.0);
+        let (_, payload) = ui.dnd_drop_zone::<u32, _>(egui::Frame::group(ui.style()), |ui| {
+            ui.label("Target");
+        });
+        if let Some(payload) = payload {
+            dropped.set(Some(*payload));
+        }
+    });
+    harness.run();
+
+    let source = harness.get_by_label("Source").rect().center();
+    let target = harness.get_by_label("Target").rect().center();
+    harness.drag(egui::Id::new("source"), target - source);
+    harness.run();
+    assert_eq!(dropped.get(), Some(42));
+    assert!(!harness.ctx().memory(|mem| mem.has_dnd_payload()));
+}
@@ -0,0 +1,169 @@
+//! Golden-image snapshots of the built-in egui widgets.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to update the images in `tests/snapshots/`.
+
+use egui::{pos2, vec2, Event, Ui};
+use egui_test::Harness;
+
+fn widget_harness<'a>(app: impl FnMut(&mut Ui) + 'a) -> Harness<'a> {
+    Harness::new_ui(app).with_size(vec2(240.0, 60.0))
+}
+
+fn snapshot_widget(name: &str, app: impl FnMut(&mut Ui)) {
+    let mut harness = widget_harness(app);
+    harness.run();
+    harness.snapshot(name);
+}
+
+#[test]
+fn button() {
+    snapshot_widget("button", |ui| {
+        let _ = ui.button("Click me");
+    });
+}
+
+#[test]
+fn button_hovered() {
+    let mut harness = widget_harness(|ui| {
+        let _ = ui.button("Click me");
+    });
+    harness.run();
+    harness.push_event(Event::PointerMoved(pos2(20.0, 16.0)));
+    harness.run();
+    harness.snapshot("button_hovered");
+}
+
+#[test]
+fn checkbox() {
+    snapshot_widget("checkbox", |ui| {
+        ui.checkbox(&mut false, "Unchecked");
+        ui.checkbox(&mut true, "Checked");
+    });
+}
+
+#[test]
+fn radio() {
+    snapshot_widget("radio", |ui| {
+        ui.horizontal(|ui| {
+            let _ = ui.radio(true, "First");
+            let _ = ui.radio(false, "Second");
+        });
+    });
+}
+
+#[test]
+fn selectable_label() {
+    snapshot_widget("selectable_label", |ui| {
+        ui.horizontal(|ui| {
+            let _ = ui.selectable_label(true, "Selected");
+            let _ = ui.selectable_label(false, "Not selected");
+        });
+    });
+}
+
+#[test]
+fn slider() {
+    snapshot_widget("slider", |ui| {
+        ui.add(egui::Slider::new(&mut 42.0, 0.0..=100.0).text("value"));
+    });
+}
+
+#[test]
+fn drag_value() {
+    snapshot_widget("drag_value", |ui| {
+        ui.add(egui::DragValue::new(&mut 2.5).speed(0.1));
+    });
+}
+
+#[test]
+fn text_edit() {
+    let mut text = String::from("Hello world");
+    snapshot_widget("text_edit", |ui| {
+        ui.text_edit_singleline(&mut text);
+    });
+}
+
+#[test]
+fn combo_box() {
+    snapshot_widget("combo_box", |ui| {
+        egui::ComboBox::from_label("Choose")
+            .selected_text("First")
+            .show_ui(ui, |_ui| {});
+    });
+}
+
+#[test]
+fn collapsing_header() {
+    snapshot_widget("collapsing_header", |ui| {
+        ui.collapsing("Collapsed", |ui| ui.label("Hidden"));
+    });
+}
+
+#[test]
+fn progress_bar() {
+    snapshot_widget("progress_bar", |ui| {
+        ui.add(egui::ProgressBar::new(0.3).show_percentage());
+    });
+}
+
+#[test]
+fn hyperlink() {
+    snapshot_widget("hyperlink", |ui| {
+        ui.hyperlink_to("egui on GitHub", "https://github.com/emilk/egui");
+    });
+}
+
+#[test]
+fn separator_and_labels() {
+    snapshot_widget("separator_and_labels", |ui| {
+        ui.label("Above");
+        ui.separator();
+        ui.heading("Below");
+    });
+}
+
+#[test]
+fn color_edit_button() {
+    snapshot_widget("color_edit_button", |ui| {
+        let mut color = egui::Color32::from_rgb(100, 150, 250);
+        ui.color_edit_button_srgba(&mut color);
+    });
+}
+
+#[test]
+fn high_dpi() {
+    let mut harness = Harness::new_ui(|ui| {
+        ui.label("Sharp text");
+        ui.checkbox(&mut true, "Checked");
+    })
+    .with_size(vec2(120.0, 50.0))
+    .with_pixels_per_point(2.0);
+    harness.run();
+    assert_eq!(harness.render().size, [240, 100]);
+    harness.snapshot("high_dpi");
+}
+
+#[test]
+fn click_changes_rendering() {
+    let mut checked = false;
+    {
+        let mut harness = widget_harness(|ui| {
+            ui.checkbox(&mut checked, "Click me");
+        });
+        harness.run();
+        let unchecked = harness.render();
+
+        for pressed in [true, false] {
+            harness.push_event(Event::PointerButton {
+                pos: pos2(16.0, 16.0),
+                button: egui::PointerButton::Primary,
+                pressed,
+                modifiers: Default::default(),
+            });
+            harness.step();
+        }
+        harness.run();
+        assert!(harness.render() != unchecked);
+    }
+    assert!(checked);
+}
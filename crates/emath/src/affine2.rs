@@ -0,0 +1,219 @@
+use crate::{pos2, vec2, Pos2, Rect, Rot2, Vec2};
+
+/// A 2D affine transform: a linear map (rotation, scale, shear) followed by a translation.
+///
+/// Transforms a position `p` into `x_axis * p.x + y_axis * p.y + translation`.
+///
+/// Unlike [`crate::RectTransform`], this can rotate.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct Affine2 {
+    /// Where the X axis ends up.
+    pub x_axis: Vec2,
+
+    /// Where the Y axis ends up.
+    pub y_axis: Vec2,
+
+    /// Where the origin ends up.
+    pub translation: Vec2,
+}
+
+impl Default for Affine2 {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Affine2 {
+    /// Leaves everything where it is.
+    pub const IDENTITY: Self = Self {
+        x_axis: Vec2::X,
+        y_axis: Vec2::Y,
+        translation: Vec2::ZERO,
+    };
+
+    #[inline]
+    pub fn from_translation(translation: Vec2) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Scale uniformly around the origin.
+    #[inline]
+    pub fn from_scale(scale: f32) -> Self {
+        Self {
+            x_axis: vec2(scale, 0.0),
+            y_axis: vec2(0.0, scale),
+            translation: Vec2::ZERO,
+        }
+    }
+
+    /// Rotate clockwise around the origin by this many radians.
+    #[inline]
+    pub fn from_angle(angle: f32) -> Self {
+        let rot = Rot2::from_angle(angle);
+        Self {
+            x_axis: rot * Vec2::X,
+            y_axis: rot * Vec2::Y,
+            translation: Vec2::ZERO,
+        }
+    }
+
+    /// First scale, then rotate (both around the origin), then translate.
+    pub fn from_scale_angle_translation(scale: f32, angle: f32, translation: Vec2) -> Self {
+        Self::from_translation(translation) * Self::from_angle(angle) * Self::from_scale(scale)
+    }
+
+    /// Scale uniformly and rotate around `pivot`, which stays where it is.
+    pub fn around(pivot: Pos2, scale: f32, angle: f32) -> Self {
+        Self::from_scale_angle_translation(scale, angle, pivot.to_vec2())
+            * Self::from_translation(-pivot.to_vec2())
+    }
+
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// The determinant of the linear part.
+    ///
+    /// Zero means that the transform squashes everything onto a line (or point),
+    /// and negative that it mirrors.
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y
+    }
+
+    /// The average factor by which lengths are scaled.
+    ///
+    /// For a transform made of rotation, translation and uniform scaling, this is the exact scale.
+    #[inline]
+    pub fn scale(&self) -> f32 {
+        self.determinant().abs().sqrt()
+    }
+
+    /// The clockwise rotation of the X axis, in radians.
+    #[inline]
+    pub fn angle(&self) -> f32 {
+        self.x_axis.angle()
+    }
+
+    /// Is this only made of rotation, translation and uniform scaling (no shear, no mirroring)?
+    pub fn is_similarity(&self) -> bool {
+        let epsilon = 1e-5 * self.x_axis.length().max(self.y_axis.length());
+        // The Y axis must be the X axis turned a quarter clockwise:
+        (vec2(-self.x_axis.y, self.x_axis.x) - self.y_axis).length() <= epsilon
+    }
+
+    /// Does this keep axis-aligned rectangles axis-aligned?
+    pub fn is_axis_aligned(&self) -> bool {
+        self.x_axis.y == 0.0 && self.y_axis.x == 0.0
+    }
+
+    /// The transform that undoes this one,
+    /// or `None` if this squashes everything onto a line.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let x_axis = vec2(self.y_axis.y, -self.x_axis.y) / det;
+        let y_axis = vec2(-self.y_axis.x, self.x_axis.x) / det;
+        let linear = Self {
+            x_axis,
+            y_axis,
+            translation: Vec2::ZERO,
+        };
+        Some(Self {
+            translation: -linear.transform_vec(self.translation),
+            ..linear
+        })
+    }
+
+    /// Transforms the position.
+    #[inline]
+    pub fn transform_pos(&self, pos: Pos2) -> Pos2 {
+        pos2(0.0, 0.0) + self.transform_vec(pos.to_vec2()) + self.translation
+    }
+
+    /// Transforms a direction or size, ignoring the translation.
+    #[inline]
+    pub fn transform_vec(&self, vec: Vec2) -> Vec2 {
+        self.x_axis * vec.x + self.y_axis * vec.y
+    }
+
+    /// The smallest axis-aligned rectangle containing the transformed rectangle.
+    pub fn transform_rect_bounds(&self, rect: Rect) -> Rect {
+        if !rect.is_finite() || rect.is_negative() {
+            // e.g. `Rect::EVERYTHING` or `Rect::NOTHING`
+            return rect;
+        }
+        Rect::from_points(&[
+            self.transform_pos(rect.left_top()),
+            self.transform_pos(rect.right_top()),
+            self.transform_pos(rect.left_bottom()),
+            self.transform_pos(rect.right_bottom()),
+        ])
+    }
+}
+
+/// Transforms the position.
+impl std::ops::Mul<Pos2> for Affine2 {
+    type Output = Pos2;
+
+    #[inline]
+    fn mul(self, pos: Pos2) -> Pos2 {
+        self.transform_pos(pos)
+    }
+}
+
+/// Combine two transforms: first apply `rhs`, then `self`.
+impl std::ops::Mul<Affine2> for Affine2 {
+    type Output = Affine2;
+
+    #[inline]
+    fn mul(self, rhs: Affine2) -> Affine2 {
+        Affine2 {
+            x_axis: self.transform_vec(rhs.x_axis),
+            y_axis: self.transform_vec(rhs.y_axis),
+            translation: self.transform_pos(rhs.translation.to_pos2()).to_vec2(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_pos_eq(a: Pos2, b: Pos2) {
+        assert!(a.distance(b) < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_affine2() {
+        let transform = Affine2::around(pos2(10.0, 20.0), 2.0, std::f32::consts::FRAC_PI_2);
+        assert!(transform.is_similarity());
+        assert!((transform.scale() - 2.0).abs() < 1e-6);
+        assert_pos_eq(transform * pos2(10.0, 20.0), pos2(10.0, 20.0));
+        // A quarter turn clockwise turns +X into +Y:
+        assert_pos_eq(transform * pos2(11.0, 20.0), pos2(10.0, 22.0));
+
+        let inverse = transform.inverse().unwrap();
+        for p in [pos2(0.0, 0.0), pos2(3.0, -7.0), pos2(100.0, 50.0)] {
+            assert_pos_eq(inverse * (transform * p), p);
+            assert_pos_eq((transform * inverse) * p, p);
+        }
+
+        let bounds =
+            transform.transform_rect_bounds(Rect::from_min_max(pos2(10.0, 20.0), pos2(14.0, 21.0)));
+        assert_pos_eq(bounds.min, pos2(8.0, 20.0));
+        assert_pos_eq(bounds.max, pos2(10.0, 28.0));
+
+        assert!(Affine2::from_scale(0.0).inverse().is_none());
+    }
+}
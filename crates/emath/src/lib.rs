@@ -26,6 +26,7 @@ use std::ops::{Add, Div, Mul, RangeInclusive, Sub};
 
 // ----------------------------------------------------------------------------
 
+mod affine2;
 pub mod align;
 mod history;
 mod numeric;
@@ -38,6 +39,7 @@ pub mod smart_aim;
 mod vec2;
 
 pub use {
+    affine2::Affine2,
     align::{Align, Align2},
     history::History,
     numeric::*,
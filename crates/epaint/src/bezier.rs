@@ -66,8 +66,9 @@ impl CubicBezierShape {
             let pathshape = PathShape {
                 points,
                 closed: self.closed,
-                fill: self.fill,
+                fill: self.fill.into(),
                 stroke: self.stroke,
+                stroke_alignment: Default::default(),
             };
             pathshapes.push(pathshape);
         }
@@ -425,8 +426,9 @@ impl QuadraticBezierShape {
         PathShape {
             points,
             closed: self.closed,
-            fill: self.fill,
+            fill: self.fill.into(),
             stroke: self.stroke,
+            stroke_alignment: Default::default(),
         }
     }
 
@@ -0,0 +1,986 @@
+//! Paths of lines, curves and arcs, made of several subpaths,
+//! that are filled with a [`FillRule`] so that they can be concave, self-intersecting and have holes.
+
+use crate::*;
+use emath::*;
+
+/// How to decide which parts of a [`ComplexPathShape`] are inside it, and should be filled.
+///
+/// See <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/fill-rule>.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FillRule {
+    /// A point is inside if the path winds around it at all.
+    ///
+    /// Subpaths going in the same direction add up, and ones going in opposite directions make holes.
+    #[default]
+    NonZero,
+
+    /// A point is inside if a ray from it crosses the path an odd number of times.
+    ///
+    /// Any subpath inside another one makes a hole, regardless of its direction.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Is a point that the path winds around `winding` times inside?
+    #[inline]
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            Self::NonZero => winding != 0,
+            Self::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// One step of a [`ComplexPathShape`], like the commands of an SVG path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PathSegment {
+    /// Start a new subpath at this point.
+    MoveTo(Pos2),
+
+    /// A straight line to this point.
+    LineTo(Pos2),
+
+    /// A quadratic [Bézier Curve](https://en.wikipedia.org/wiki/B%C3%A9zier_curve) to `to`.
+    QuadTo { control: Pos2, to: Pos2 },
+
+    /// A cubic [Bézier Curve](https://en.wikipedia.org/wiki/B%C3%A9zier_curve) to `to`.
+    CubicTo {
+        control1: Pos2,
+        control2: Pos2,
+        to: Pos2,
+    },
+
+    /// A part of an ellipse with the given radii, rotated by `x_rotation` radians, to `to`.
+    ///
+    /// Like the SVG `A` command, there are (usually) four such arcs,
+    /// and `large_arc` and `sweep` chooses one of them:
+    /// * `large_arc`: take the arc that is longer than half the ellipse.
+    /// * `sweep`: go clockwise (towards increasing angles).
+    ///
+    /// If the radii are too small to reach `to`, they are scaled up.
+    ArcTo {
+        radius: Vec2,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: Pos2,
+    },
+
+    /// Go back to the start of the current subpath with a straight line, and close it.
+    Close,
+}
+
+impl PathSegment {
+    /// Call the given function for each point and control point of the segment.
+    pub fn for_each_point_mut(&mut self, mut f: impl FnMut(&mut Pos2)) {
+        match self {
+            Self::MoveTo(p) | Self::LineTo(p) => f(p),
+            Self::QuadTo { control, to } => {
+                f(control);
+                f(to);
+            }
+            Self::CubicTo {
+                control1,
+                control2,
+                to,
+            } => {
+                f(control1);
+                f(control2);
+                f(to);
+            }
+            Self::ArcTo { to, .. } => f(to),
+            Self::Close => {}
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Build up a [`ComplexPathShape`] step by step, like an SVG path.
+///
+/// ```
+/// # use epaint::*;
+/// // A square with a round hole:
+/// let shape = PathBuilder::new()
+///     .move_to(pos2(0.0, 0.0))
+///     .line_to(pos2(100.0, 0.0))
+///     .line_to(pos2(100.0, 100.0))
+///     .line_to(pos2(0.0, 100.0))
+///     .close()
+///     .move_to(pos2(25.0, 50.0))
+///     .arc_to(vec2(25.0, 25.0), 0.0, false, true, pos2(75.0, 50.0))
+///     .arc_to(vec2(25.0, 25.0), 0.0, false, true, pos2(25.0, 50.0))
+///     .close()
+///     .fill(Color32::RED, FillRule::EvenOdd);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[must_use = "Call .fill() or .stroke() to make a shape"]
+pub struct PathBuilder {
+    segments: Vec<PathSegment>,
+}
+
+impl PathBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new subpath at this point.
+    #[inline]
+    pub fn move_to(mut self, p: Pos2) -> Self {
+        self.segments.push(PathSegment::MoveTo(p));
+        self
+    }
+
+    /// A straight line to this point.
+    #[inline]
+    pub fn line_to(mut self, p: Pos2) -> Self {
+        self.segments.push(PathSegment::LineTo(p));
+        self
+    }
+
+    /// A quadratic Bézier curve to `to`.
+    #[inline]
+    pub fn quad_to(mut self, control: Pos2, to: Pos2) -> Self {
+        self.segments.push(PathSegment::QuadTo { control, to });
+        self
+    }
+
+    /// A cubic Bézier curve to `to`.
+    #[inline]
+    pub fn cubic_to(mut self, control1: Pos2, control2: Pos2, to: Pos2) -> Self {
+        self.segments.push(PathSegment::CubicTo {
+            control1,
+            control2,
+            to,
+        });
+        self
+    }
+
+    /// An elliptical arc to `to`. See [`PathSegment::ArcTo`].
+    #[inline]
+    pub fn arc_to(
+        mut self,
+        radius: Vec2,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: Pos2,
+    ) -> Self {
+        self.segments.push(PathSegment::ArcTo {
+            radius,
+            x_rotation,
+            large_arc,
+            sweep,
+            to,
+        });
+        self
+    }
+
+    /// Go back to the start of the current subpath, and close it.
+    #[inline]
+    pub fn close(mut self) -> Self {
+        self.segments.push(PathSegment::Close);
+        self
+    }
+
+    /// The segments added so far.
+    #[inline]
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// A filled shape, without an outline.
+    ///
+    /// Open subpaths are filled as if they were closed.
+    pub fn fill(self, fill: impl Into<Fill>, fill_rule: FillRule) -> ComplexPathShape {
+        ComplexPathShape {
+            segments: self.segments,
+            fill: fill.into(),
+            fill_rule,
+            stroke: Default::default(),
+        }
+    }
+
+    /// An outline, without a fill.
+    pub fn stroke(self, stroke: impl Into<Stroke>) -> ComplexPathShape {
+        ComplexPathShape {
+            segments: self.segments,
+            fill: Fill::TRANSPARENT,
+            fill_rule: Default::default(),
+            stroke: stroke.into(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A path made of any number of subpaths of lines, curves and arcs.
+///
+/// Unlike [`PathShape`], the fill can be concave, self-intersecting and have holes.
+/// Which parts are filled is decided by the [`FillRule`].
+///
+/// Use [`PathBuilder`] to create one.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ComplexPathShape {
+    pub segments: Vec<PathSegment>,
+
+    /// A [`Gradient`] fill is relative to the bounding rectangle of the path, see [`Self::bounding_rect`].
+    pub fill: Fill,
+
+    pub fill_rule: FillRule,
+
+    pub stroke: Stroke,
+}
+
+impl ComplexPathShape {
+    /// Add an outline to the shape.
+    #[inline]
+    pub fn with_stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Turn the curves and arcs into line segments,
+    /// so that no point on them is further than `tolerance` from the result.
+    ///
+    /// Returns each subpath as its points, and whether or not it is closed.
+    pub fn flatten(&self, tolerance: f32) -> Vec<(Vec<Pos2>, bool)> {
+        let mut subpaths = vec![];
+        let mut points: Vec<Pos2> = vec![];
+        let mut start = None;
+
+        let push = |points: &mut Vec<Pos2>, p: Pos2| {
+            if points.last() != Some(&p) {
+                points.push(p);
+            }
+        };
+        let finish = |points: &mut Vec<Pos2>, closed: bool, subpaths: &mut Vec<_>| {
+            if closed && points.len() > 2 && points.first() == points.last() {
+                points.pop();
+            }
+            if points.len() >= 2 {
+                subpaths.push((std::mem::take(points), closed));
+            } else {
+                points.clear();
+            }
+        };
+
+        for segment in &self.segments {
+            if !matches!(segment, PathSegment::MoveTo(_) | PathSegment::Close) {
+                // Continue from where the last subpath was closed, or start a new one here:
+                if let (true, Some(start)) = (points.is_empty(), start) {
+                    points.push(start);
+                }
+            }
+            let from = points.last().copied();
+
+            match *segment {
+                PathSegment::MoveTo(p) => {
+                    finish(&mut points, false, &mut subpaths);
+                    start = Some(p);
+                    points.push(p);
+                }
+                PathSegment::LineTo(p) => {
+                    start = start.or(Some(p));
+                    push(&mut points, p);
+                }
+                PathSegment::QuadTo { control, to } => {
+                    let from = from.unwrap_or(control);
+                    start = start.or(Some(from));
+                    let curve = QuadraticBezierShape::from_points_stroke(
+                        [from, control, to],
+                        false,
+                        Color32::TRANSPARENT,
+                        Stroke::NONE,
+                    );
+                    for p in curve.flatten(Some(tolerance)) {
+                        push(&mut points, p);
+                    }
+                }
+                PathSegment::CubicTo {
+                    control1,
+                    control2,
+                    to,
+                } => {
+                    let from = from.unwrap_or(control1);
+                    start = start.or(Some(from));
+                    let curve = CubicBezierShape::from_points_stroke(
+                        [from, control1, control2, to],
+                        false,
+                        Color32::TRANSPARENT,
+                        Stroke::NONE,
+                    );
+                    for p in curve.flatten(Some(tolerance)) {
+                        push(&mut points, p);
+                    }
+                }
+                PathSegment::ArcTo {
+                    radius,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => {
+                    let from = from.unwrap_or(to);
+                    start = start.or(Some(from));
+                    push(&mut points, from);
+                    flatten_arc(
+                        from,
+                        radius,
+                        x_rotation,
+                        large_arc,
+                        sweep,
+                        to,
+                        tolerance,
+                        &mut |p| push(&mut points, p),
+                    );
+                }
+                PathSegment::Close => {
+                    finish(&mut points, true, &mut subpaths);
+                }
+            }
+        }
+        finish(&mut points, false, &mut subpaths);
+
+        subpaths
+    }
+
+    /// The bounding rectangle of the path, without the stroke, to within `tolerance`.
+    pub fn bounding_rect(&self, tolerance: f32) -> Rect {
+        let mut rect = Rect::NOTHING;
+        for (points, _) in self.flatten(tolerance) {
+            for p in points {
+                rect.extend_with(p);
+            }
+        }
+        rect
+    }
+
+    /// The visual bounding rectangle (includes stroke width)
+    pub fn visual_bounding_rect(&self) -> Rect {
+        if self.fill.is_transparent() && self.stroke.is_empty() {
+            return Rect::NOTHING;
+        }
+        let tolerance = 0.5;
+        self.bounding_rect(tolerance)
+            .expand(tolerance + self.stroke.width / 2.0)
+    }
+}
+
+impl From<ComplexPathShape> for Shape {
+    #[inline(always)]
+    fn from(shape: ComplexPathShape) -> Self {
+        Self::ComplexPath(shape)
+    }
+}
+
+/// Flatten an SVG-style elliptical arc, calling `add_point` for every point after `from`.
+///
+/// See <https://www.w3.org/TR/SVG11/implnote.html#ArcImplementationNotes>.
+#[allow(clippy::too_many_arguments)]
+fn flatten_arc(
+    from: Pos2,
+    radius: Vec2,
+    x_rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Pos2,
+    tolerance: f32,
+    add_point: &mut dyn FnMut(Pos2),
+) {
+    let (mut rx, mut ry) = (radius.x.abs(), radius.y.abs());
+    if from == to {
+        return;
+    }
+    if rx == 0.0 || ry == 0.0 {
+        add_point(to);
+        return;
+    }
+
+    let rotation = Rot2::from_angle(x_rotation);
+
+    // The start point, relative to the midpoint between the ends, in the ellipse's own coordinates:
+    let p = rotation.inverse() * ((from - to) / 2.0);
+
+    // Scale up the radii if they are too small to reach:
+    let lambda = (p.x / rx).powi(2) + (p.y / ry).powi(2);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = rx * rx * ry * ry - rx * rx * p.y * p.y - ry * ry * p.x * p.x;
+    let denominator = rx * rx * p.y * p.y + ry * ry * p.x * p.x;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let center_local = coefficient * vec2(rx * p.y / ry, -ry * p.x / rx);
+    let center = from.lerp(to, 0.5) + rotation * center_local;
+
+    let u = vec2((p.x - center_local.x) / rx, (p.y - center_local.y) / ry);
+    let v = vec2((-p.x - center_local.x) / rx, (-p.y - center_local.y) / ry);
+    let angle_between = |a: Vec2, b: Vec2| (a.x * b.y - a.y * b.x).atan2(a.dot(b));
+    let start_angle = angle_between(Vec2::X, u);
+    let mut sweep_angle = angle_between(u, v);
+    if sweep && sweep_angle < 0.0 {
+        sweep_angle += std::f32::consts::TAU;
+    } else if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= std::f32::consts::TAU;
+    }
+
+    let num_segments = arc_segment_count(rx.max(ry), sweep_angle, tolerance);
+
+    for i in 1..num_segments {
+        let angle = start_angle + sweep_angle * i as f32 / num_segments as f32;
+        let (sin, cos) = angle.sin_cos();
+        add_point(center + rotation * vec2(rx * cos, ry * sin));
+    }
+    add_point(to);
+}
+
+/// How many line segments to use for an arc of the given radius and sweep (in radians),
+/// so that the segments stay within `tolerance` of the arc.
+pub(crate) fn arc_segment_count(radius: f32, sweep: f32, tolerance: f32) -> usize {
+    let max_step = std::f32::consts::TAU / 8.0;
+    let step = if radius <= tolerance {
+        max_step
+    } else {
+        (2.0 * (1.0 - tolerance / radius).acos()).min(max_step)
+    };
+    ((sweep.abs() / step).ceil() as usize).clamp(1, 1024)
+}
+
+// ----------------------------------------------------------------------------
+
+/// A non-horizontal edge of a polygon, from top to bottom.
+#[derive(Clone, Copy)]
+struct Edge {
+    top: Pos2,
+    bottom: Pos2,
+
+    /// +1 if the polygon goes downwards along this edge, -1 if it goes upwards.
+    winding: i32,
+
+    /// Which edge of [`polygon_edges`] this is.
+    index: usize,
+}
+
+impl Edge {
+    fn new(a: Pos2, b: Pos2, index: usize) -> Option<Self> {
+        if a.y < b.y {
+            Some(Self {
+                top: a,
+                bottom: b,
+                winding: 1,
+                index,
+            })
+        } else if b.y < a.y {
+            Some(Self {
+                top: b,
+                bottom: a,
+                winding: -1,
+                index,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.top.y) / (self.bottom.y - self.top.y);
+        lerp(self.top.x..=self.bottom.x, t)
+    }
+}
+
+/// A horizontal edge of a polygon, and which sides of it are inside.
+struct HorizontalEdge {
+    y: f32,
+    mid_x: f32,
+    index: usize,
+    inside_above: bool,
+    inside_below: bool,
+}
+
+/// All edges of all the polygons (which are all treated as closed), in order.
+fn polygon_edges<'a>(polygons: &'a [&'a [Pos2]]) -> impl Iterator<Item = (Pos2, Pos2)> + 'a {
+    polygons.iter().flat_map(|points| {
+        let n = points.len();
+        (0..n).map(move |i| (points[i], points[(i + 1) % n]))
+    })
+}
+
+/// Calls `add_span(top_y, bottom_y, left_edge, right_edge)` for each trapezoid
+/// that is inside the polygons.
+///
+/// The polygons are cut into horizontal slabs at every vertex and edge intersection,
+/// and each slab is split into the spans between the edges.
+fn for_each_span(
+    polygons: &[&[Pos2]],
+    fill_rule: FillRule,
+    mut add_span: impl FnMut(f32, f32, &Edge, &Edge),
+) {
+    let mut edges: Vec<Edge> = polygon_edges(polygons)
+        .enumerate()
+        .filter_map(|(index, (a, b))| Edge::new(a, b, index))
+        .collect();
+    if edges.is_empty() {
+        return;
+    }
+    edges.sort_by(|a, b| a.top.y.total_cmp(&b.top.y));
+
+    let mut ys: Vec<f32> = edges
+        .iter()
+        .flat_map(|edge| [edge.top.y, edge.bottom.y])
+        .collect();
+    ys.sort_by(f32::total_cmp);
+    ys.dedup();
+
+    let mut active: Vec<Edge> = vec![];
+    let mut next_edge = 0;
+    let mut split_ys = vec![];
+    let mut crossings: Vec<(f32, i32)> = vec![];
+    let mut order: Vec<usize> = vec![];
+
+    for slab in ys.windows(2) {
+        let (y0, y1) = (slab[0], slab[1]);
+
+        active.retain(|edge| edge.bottom.y > y0);
+        while next_edge < edges.len() && edges[next_edge].top.y <= y0 {
+            active.push(edges[next_edge]);
+            next_edge += 1;
+        }
+
+        // Split the slab where edges cross each other:
+        split_ys.clear();
+        split_ys.push(y0);
+        for (i, a) in active.iter().enumerate() {
+            for b in &active[i + 1..] {
+                let d0 = a.x_at(y0) - b.x_at(y0);
+                let d1 = a.x_at(y1) - b.x_at(y1);
+                if d0 * d1 < 0.0 {
+                    split_ys.push(lerp(y0..=y1, d0 / (d0 - d1)));
+                }
+            }
+        }
+        split_ys.push(y1);
+        split_ys.sort_by(f32::total_cmp);
+        split_ys.dedup();
+
+        for sub_slab in split_ys.windows(2) {
+            let (ya, yb) = (sub_slab[0], sub_slab[1]);
+            let y_mid = 0.5 * (ya + yb);
+
+            crossings.clear();
+            crossings.extend(active.iter().map(|edge| (edge.x_at(y_mid), edge.winding)));
+            order.clear();
+            order.extend(0..active.len());
+            order.sort_by(|&i, &j| crossings[i].0.total_cmp(&crossings[j].0));
+
+            let mut winding = 0;
+            let mut left = None;
+            for &i in &order {
+                let was_inside = fill_rule.is_inside(winding);
+                winding += crossings[i].1;
+                let is_inside = fill_rule.is_inside(winding);
+                if !was_inside && is_inside {
+                    left = Some(&active[i]);
+                } else if was_inside && !is_inside {
+                    if let Some(left) = left.take() {
+                        add_span(ya, yb, left, &active[i]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The outward normal of each edge of [`polygon_edges`] that is on the boundary
+/// between inside and outside, or zero for edges that are not.
+fn outward_normals(polygons: &[&[Pos2]], fill_rule: FillRule) -> Vec<Vec2> {
+    let mut outward: Vec<Vec2> = polygon_edges(polygons)
+        .map(|(a, b)| (b - a).normalized().rot90())
+        .collect();
+
+    let mut horizontal: Vec<HorizontalEdge> = polygon_edges(polygons)
+        .enumerate()
+        .filter(|(_, (a, b))| a.y == b.y && a.x != b.x)
+        .map(|(index, (a, b))| HorizontalEdge {
+            y: a.y,
+            mid_x: 0.5 * (a.x + b.x),
+            index,
+            inside_above: false,
+            inside_below: false,
+        })
+        .collect();
+    horizontal.sort_by(|a, b| a.y.total_cmp(&b.y));
+
+    let mut is_boundary = vec![false; outward.len()];
+    for_each_span(polygons, fill_rule, |ya, yb, left, right| {
+        // The inside is to the right of the left edge, and to the left of the right edge:
+        for (edge, outside_x) in [(left, -1.0), (right, 1.0)] {
+            let normal = &mut outward[edge.index];
+            if normal.x * outside_x < 0.0 {
+                *normal = -*normal;
+            }
+            is_boundary[edge.index] = true;
+        }
+
+        let is_in_span = |y: f32, x: f32| left.x_at(y) <= x && x <= right.x_at(y);
+        for y in [ya, yb] {
+            let first = horizontal.partition_point(|edge| edge.y < y);
+            for edge in horizontal[first..]
+                .iter_mut()
+                .take_while(|edge| edge.y == y)
+            {
+                if is_in_span(y, edge.mid_x) {
+                    if y == ya {
+                        edge.inside_below = true;
+                    } else {
+                        edge.inside_above = true;
+                    }
+                }
+            }
+        }
+    });
+
+    for edge in &horizontal {
+        let outside_y = match (edge.inside_above, edge.inside_below) {
+            (false, true) => -1.0,
+            (true, false) => 1.0,
+            _ => continue,
+        };
+        let normal = &mut outward[edge.index];
+        if normal.y * outside_y < 0.0 {
+            *normal = -*normal;
+        }
+        is_boundary[edge.index] = true;
+    }
+
+    for (normal, is_boundary) in outward.iter_mut().zip(is_boundary) {
+        if !is_boundary {
+            *normal = Vec2::ZERO;
+        }
+    }
+    outward
+}
+
+/// Fill the polygons (which are all treated as closed) with the given fill rule.
+///
+/// Unlike [`tessellator::Path::fill`], this works for any polygon:
+/// concave, self-intersecting, or with holes.
+///
+/// The polygons are cut into horizontal slabs at every vertex and edge intersection,
+/// and the parts of each slab that are inside are filled with trapezoids.
+///
+/// A [`Gradient`] is relative to the bounding rectangle of all the polygons.
+pub(crate) fn fill_polygons(
+    polygons: &[&[Pos2]],
+    fill_rule: FillRule,
+    feathering: f32,
+    fill: &Fill,
+    out: &mut Mesh,
+) {
+    match fill {
+        Fill::Solid(color) => {
+            fill_polygons_with_color(polygons, fill_rule, feathering, *color, out);
+        }
+        Fill::Gradient(gradient) => {
+            if gradient.is_transparent() {
+                return;
+            }
+            let mut rect = Rect::NOTHING;
+            for points in polygons {
+                for &p in *points {
+                    rect.extend_with(p);
+                }
+            }
+            let (first_vertex, first_index) = (out.vertices.len(), out.indices.len());
+            fill_polygons_with_color(polygons, fill_rule, feathering, Color32::WHITE, out);
+            gradient.paint_mesh(rect, out, first_vertex, first_index);
+        }
+    }
+}
+
+fn fill_polygons_with_color(
+    polygons: &[&[Pos2]],
+    fill_rule: FillRule,
+    feathering: f32,
+    color: Color32,
+    out: &mut Mesh,
+) {
+    if color == Color32::TRANSPARENT {
+        return;
+    }
+
+    let fill = |polygons: &[&[Pos2]], out: &mut Mesh| {
+        for_each_span(polygons, fill_rule, |ya, yb, left, right| {
+            let idx = out.vertices.len() as u32;
+            out.colored_vertex(pos2(left.x_at(ya), ya), color);
+            out.colored_vertex(pos2(right.x_at(ya), ya), color);
+            out.colored_vertex(pos2(right.x_at(yb), yb), color);
+            out.colored_vertex(pos2(left.x_at(yb), yb), color);
+            out.add_triangle(idx, idx + 1, idx + 2);
+            out.add_triangle(idx, idx + 2, idx + 3);
+        });
+    };
+
+    if feathering <= 0.0 {
+        fill(polygons, out);
+        return;
+    }
+
+    // Like `Path::fill`: move the boundary in by half the feathering and fill that,
+    // then fade out to half the feathering outside the boundary.
+    // Small gaps or overlaps where edges cross are hidden by the feathering itself.
+    let outward = outward_normals(polygons, fill_rule);
+    let mut inner: Vec<Vec<Pos2>> = Vec::with_capacity(polygons.len());
+    let mut outer: Vec<Vec<Pos2>> = Vec::with_capacity(polygons.len());
+    let mut first_edge = 0;
+    for points in polygons {
+        let n = points.len();
+        let normals = &outward[first_edge..first_edge + n];
+        first_edge += n;
+
+        let (inner_points, outer_points) = (0..n)
+            .map(|i| {
+                let (n0, n1) = (normals[(i + n - 1) % n], normals[i]);
+                let normal = if n0 == Vec2::ZERO || n1 == Vec2::ZERO {
+                    n0 + n1
+                } else {
+                    // miter join
+                    let normal = 0.5 * (n0 + n1);
+                    let length_sq = normal.length_sq();
+                    if length_sq > 0.0 {
+                        normal / length_sq
+                    } else {
+                        n0
+                    }
+                };
+                let offset = 0.5 * feathering * normal;
+                (points[i] - offset, points[i] + offset)
+            })
+            .unzip();
+        inner.push(inner_points);
+        outer.push(outer_points);
+    }
+
+    let inner_polygons: Vec<&[Pos2]> = inner.iter().map(Vec::as_slice).collect();
+    fill(&inner_polygons, out);
+
+    let mut index = 0;
+    for (inner, outer) in inner.iter().zip(&outer) {
+        let n = inner.len();
+        for i in 0..n {
+            let is_boundary = outward[index] != Vec2::ZERO;
+            index += 1;
+            if !is_boundary {
+                continue;
+            }
+            let j = (i + 1) % n;
+            let idx = out.vertices.len() as u32;
+            out.colored_vertex(inner[i], color);
+            out.colored_vertex(inner[j], color);
+            out.colored_vertex(outer[j], Color32::TRANSPARENT);
+            out.colored_vertex(outer[i], Color32::TRANSPARENT);
+            out.add_triangle(idx, idx + 1, idx + 2);
+            out.add_triangle(idx, idx + 2, idx + 3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The area covered by the triangles of the mesh.
+    fn mesh_area(mesh: &Mesh) -> f32 {
+        mesh.indices
+            .chunks(3)
+            .map(|t| {
+                let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[t[i] as usize].pos);
+                let (ab, ac) = (b - a, c - a);
+                0.5 * (ab.x * ac.y - ab.y * ac.x).abs()
+            })
+            .sum()
+    }
+
+    fn square(min: Pos2, size: f32, clockwise: bool) -> Vec<Pos2> {
+        let mut points = vec![
+            min,
+            min + vec2(size, 0.0),
+            min + vec2(size, size),
+            min + vec2(0.0, size),
+        ];
+        if !clockwise {
+            points.reverse();
+        }
+        points
+    }
+
+    #[test]
+    fn test_fill_rules() {
+        let outer = square(pos2(0.0, 0.0), 30.0, true);
+        let inner_cw = square(pos2(10.0, 10.0), 10.0, true);
+        let inner_ccw = square(pos2(10.0, 10.0), 10.0, false);
+
+        for (inner, fill_rule, expected_area) in [
+            (&inner_cw, FillRule::NonZero, 900.0),
+            (&inner_ccw, FillRule::NonZero, 800.0),
+            (&inner_cw, FillRule::EvenOdd, 800.0),
+            (&inner_ccw, FillRule::EvenOdd, 800.0),
+        ] {
+            let mut mesh = Mesh::default();
+            fill_polygons(
+                &[&outer, inner],
+                fill_rule,
+                0.0,
+                &Color32::WHITE.into(),
+                &mut mesh,
+            );
+            assert!(mesh.is_valid());
+            let area = mesh_area(&mesh);
+            assert!((area - expected_area).abs() < 1e-3, "{fill_rule:?}: {area}");
+        }
+    }
+
+    #[test]
+    fn test_fill_concave_and_self_intersecting() {
+        // An L-shape:
+        let l_shape = [
+            pos2(0.0, 0.0),
+            pos2(10.0, 0.0),
+            pos2(10.0, 20.0),
+            pos2(20.0, 20.0),
+            pos2(20.0, 30.0),
+            pos2(0.0, 30.0),
+        ];
+        let mut mesh = Mesh::default();
+        fill_polygons(
+            &[&l_shape],
+            FillRule::NonZero,
+            0.0,
+            &Color32::WHITE.into(),
+            &mut mesh,
+        );
+        assert!((mesh_area(&mesh) - 400.0).abs() < 1e-3);
+
+        // A bow tie, crossing itself in the middle:
+        let bow_tie = [
+            pos2(0.0, 0.0),
+            pos2(10.0, 10.0),
+            pos2(10.0, 0.0),
+            pos2(0.0, 10.0),
+        ];
+        let mut mesh = Mesh::default();
+        fill_polygons(
+            &[&bow_tie],
+            FillRule::EvenOdd,
+            0.0,
+            &Color32::WHITE.into(),
+            &mut mesh,
+        );
+        assert!((mesh_area(&mesh) - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_feathering() {
+        /// The area covered by the mesh, weighted by the alpha of the vertices.
+        fn coverage(mesh: &Mesh) -> f32 {
+            mesh.indices
+                .chunks(3)
+                .map(|t| {
+                    let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[t[i] as usize]);
+                    let (ab, ac) = (b.pos - a.pos, c.pos - a.pos);
+                    let area = 0.5 * (ab.x * ac.y - ab.y * ac.x).abs();
+                    let alpha = (a.color.a() as f32 + b.color.a() as f32 + c.color.a() as f32)
+                        / (3.0 * 255.0);
+                    area * alpha
+                })
+                .sum()
+        }
+
+        let outer = square(pos2(0.0, 0.0), 30.0, true);
+        let hole = square(pos2(10.0, 10.0), 10.0, false);
+        for (polygons, expected_area) in [(vec![&outer[..]], 900.0), (vec![&outer, &hole], 800.0)] {
+            let mut mesh = Mesh::default();
+            fill_polygons(
+                &polygons,
+                FillRule::NonZero,
+                1.0,
+                &Color32::WHITE.into(),
+                &mut mesh,
+            );
+            assert!(mesh.is_valid());
+            // The feathering fades out across the edge, so it neither grows nor shrinks the shape:
+            let area = coverage(&mesh);
+            assert!((area - expected_area).abs() < 1.0, "{area}");
+        }
+    }
+
+    #[test]
+    fn test_gradient_fill() {
+        let l_shape = [
+            pos2(0.0, 0.0),
+            pos2(10.0, 0.0),
+            pos2(10.0, 20.0),
+            pos2(20.0, 20.0),
+            pos2(20.0, 30.0),
+            pos2(0.0, 30.0),
+        ];
+        let gradient = Gradient::horizontal(Color32::RED, Color32::BLUE);
+        let mut mesh = Mesh::default();
+        fill_polygons(
+            &[&l_shape],
+            FillRule::NonZero,
+            0.0,
+            &gradient.into(),
+            &mut mesh,
+        );
+        assert!(mesh.is_valid());
+        assert!((mesh_area(&mesh) - 400.0).abs() < 1e-3);
+
+        // The gradient goes across the bounding rectangle of the whole shape:
+        for vertex in &mesh.vertices {
+            let expected = gradient.color_at(Rect::from_points(&l_shape), vertex.pos);
+            assert_eq!(vertex.color, expected);
+        }
+    }
+
+    #[test]
+    fn test_flatten() {
+        let shape = PathBuilder::new()
+            .move_to(pos2(0.0, 0.0))
+            .line_to(pos2(10.0, 0.0))
+            .quad_to(pos2(20.0, 0.0), pos2(20.0, 10.0))
+            .close()
+            .line_to(pos2(0.0, -10.0))
+            .move_to(pos2(50.0, 0.0))
+            .arc_to(vec2(10.0, 10.0), 0.0, false, true, pos2(70.0, 0.0))
+            .stroke(Stroke::new(1.0, Color32::WHITE));
+
+        let subpaths = shape.flatten(0.1);
+        assert_eq!(subpaths.len(), 3);
+
+        let (points, closed) = &subpaths[0];
+        assert!(*closed);
+        assert_eq!(points[0], pos2(0.0, 0.0));
+        assert_eq!(*points.last().unwrap(), pos2(20.0, 10.0));
+
+        // Continues from the start of the closed subpath:
+        assert_eq!(subpaths[1], (vec![pos2(0.0, 0.0), pos2(0.0, -10.0)], false));
+
+        // A clockwise half circle, going through the top:
+        let (points, closed) = &subpaths[2];
+        assert!(!*closed);
+        assert_eq!(*points.last().unwrap(), pos2(70.0, 0.0));
+        for p in points {
+            assert!((p.distance(pos2(60.0, 0.0)) - 10.0).abs() < 1e-3);
+            assert!(p.y <= 1e-3);
+        }
+    }
+}
@@ -0,0 +1,531 @@
+use super::*;
+
+/// How the colors of a [`Gradient`] are laid out over a shape.
+///
+/// All positions are relative to the bounding rectangle of the filled shape,
+/// so that `(0, 0)` is the top left corner and `(1, 1)` is the bottom right corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GradientKind {
+    /// The color changes along the line from `start` to `end`.
+    Linear { start: Pos2, end: Pos2 },
+
+    /// The color changes with the distance from `center`, reaching the last stop at `radius`.
+    ///
+    /// Since the distance is measured in relative coordinates,
+    /// the gradient is elliptical for shapes that are not square.
+    Radial { center: Pos2, radius: f32 },
+}
+
+/// A fill that smoothly blends between several colors.
+///
+/// Used by the filled shapes, e.g. [`RectShape`] and [`CircleShape`], through [`Fill`].
+///
+/// ```
+/// # use epaint::*;
+/// let gradient = Gradient::vertical(Color32::from_gray(60), Color32::from_gray(40));
+/// let shape = RectShape::filled(Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 20.0)), 4.0, gradient);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Gradient {
+    pub kind: GradientKind,
+
+    /// Colors at positions along the gradient, sorted by position.
+    ///
+    /// Only the first `num_stops` are used, the rest are `(0.0, Color32::TRANSPARENT)`.
+    stops: [(f32, Color32); Gradient::MAX_STOPS],
+
+    num_stops: u8,
+}
+
+impl Gradient {
+    /// The most color stops a gradient can have.
+    pub const MAX_STOPS: usize = 8;
+
+    /// The color changes along the line from `start` to `end`
+    /// (relative to the bounding rectangle of the shape).
+    pub fn linear(start: Pos2, end: Pos2, stops: impl IntoIterator<Item = (f32, Color32)>) -> Self {
+        Self::new(GradientKind::Linear { start, end }, stops)
+    }
+
+    /// The color changes with the distance from `center`
+    /// (relative to the bounding rectangle of the shape).
+    pub fn radial(
+        center: Pos2,
+        radius: f32,
+        stops: impl IntoIterator<Item = (f32, Color32)>,
+    ) -> Self {
+        Self::new(GradientKind::Radial { center, radius }, stops)
+    }
+
+    /// From `top` at the top of the shape to `bottom` at the bottom.
+    pub fn vertical(top: Color32, bottom: Color32) -> Self {
+        Self::linear(pos2(0.0, 0.0), pos2(0.0, 1.0), [(0.0, top), (1.0, bottom)])
+    }
+
+    /// From `left` at the left side of the shape to `right` at the right side.
+    pub fn horizontal(left: Color32, right: Color32) -> Self {
+        Self::linear(pos2(0.0, 0.0), pos2(1.0, 0.0), [(0.0, left), (1.0, right)])
+    }
+
+    /// At most [`Self::MAX_STOPS`] of the `stops` are used.
+    fn new(kind: GradientKind, stops: impl IntoIterator<Item = (f32, Color32)>) -> Self {
+        let mut sorted: Vec<(f32, Color32)> = stops.into_iter().collect();
+        crate::epaint_assert!(
+            sorted.len() <= Self::MAX_STOPS,
+            "A gradient can have at most {} stops",
+            Self::MAX_STOPS
+        );
+        sorted.truncate(Self::MAX_STOPS);
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut stops = [(0.0, Color32::TRANSPARENT); Self::MAX_STOPS];
+        stops[..sorted.len()].copy_from_slice(&sorted);
+        Self {
+            kind,
+            stops,
+            num_stops: sorted.len() as u8,
+        }
+    }
+
+    /// Colors at positions along the gradient, sorted by position.
+    ///
+    /// The start of the gradient is at `0.0` and the end at `1.0`.
+    /// Before the first stop and after the last, the color of that stop is used.
+    pub fn stops(&self) -> &[(f32, Color32)] {
+        &self.stops[..self.num_stops as usize]
+    }
+
+    /// Are all the colors transparent?
+    pub fn is_transparent(&self) -> bool {
+        self.stops()
+            .iter()
+            .all(|(_, color)| *color == Color32::TRANSPARENT)
+    }
+
+    /// Where along the gradient the point `pos` is, for a shape with the bounding rectangle `rect`.
+    ///
+    /// `0.0` is the start of the gradient and `1.0` the end.
+    pub fn position_at(&self, rect: Rect, pos: Pos2) -> f32 {
+        let size = rect.size();
+        let relative = pos2(
+            if size.x > 0.0 {
+                (pos.x - rect.min.x) / size.x
+            } else {
+                0.0
+            },
+            if size.y > 0.0 {
+                (pos.y - rect.min.y) / size.y
+            } else {
+                0.0
+            },
+        );
+
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                let dir = end - start;
+                let length_sq = dir.length_sq();
+                if length_sq > 0.0 {
+                    (relative - start).dot(dir) / length_sq
+                } else {
+                    0.0
+                }
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius > 0.0 {
+                    (relative - center).length() / radius
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// The color at position `t` along the gradient (`0.0` is the start, `1.0` the end).
+    pub fn color_at_position(&self, t: f32) -> Color32 {
+        let stops = self.stops();
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color32::TRANSPARENT,
+        };
+        if t <= first.0 {
+            return first.1;
+        }
+        if last.0 <= t {
+            return last.1;
+        }
+
+        let i = stops.partition_point(|(stop, _)| *stop <= t);
+        let (t0, c0) = stops[i - 1];
+        let (t1, c1) = stops[i];
+        if t1 <= t0 {
+            return c1;
+        }
+        lerp_color(c0, c1, (t - t0) / (t1 - t0))
+    }
+
+    /// The color at `pos`, for a shape with the bounding rectangle `rect`.
+    pub fn color_at(&self, rect: Rect, pos: Pos2) -> Color32 {
+        self.color_at_position(self.position_at(rect, pos))
+    }
+
+    /// Apply a function to the color of each stop.
+    pub fn map_colors(&mut self, mut f: impl FnMut(&mut Color32)) {
+        let num_stops = self.num_stops as usize;
+        for (_, color) in &mut self.stops[..num_stops] {
+            f(color);
+        }
+    }
+
+    /// Recolor the triangles of `mesh` starting at `first_index` with this gradient.
+    ///
+    /// The triangles must only use vertices from `first_vertex` onwards,
+    /// and should have been tessellated as [`Color32::WHITE`],
+    /// so that the alpha of each vertex is its coverage (for feathering).
+    ///
+    /// Triangles are split where needed so that interpolating the vertex colors
+    /// stays close to the true gradient.
+    pub(crate) fn paint_mesh(
+        &self,
+        rect: Rect,
+        mesh: &mut Mesh,
+        first_vertex: usize,
+        first_index: usize,
+    ) {
+        /// Largest allowed color error, in 8-bit color steps.
+        const TOLERANCE: f32 = 2.0;
+
+        let indices = mesh.indices.split_off(first_index);
+        let vertices = mesh.vertices.split_off(first_vertex);
+        let vertex = |index: u32| {
+            let v = vertices[index as usize - first_vertex];
+            GradientVertex {
+                pos: v.pos,
+                uv: v.uv,
+                coverage: v.color.a() as f32 / 255.0,
+                t: self.position_at(rect, v.pos),
+            }
+        };
+
+        for triangle in indices.chunks_exact(3) {
+            let triangle = [
+                vertex(triangle[0]),
+                vertex(triangle[1]),
+                vertex(triangle[2]),
+            ];
+            match self.kind {
+                GradientKind::Linear { .. } => {
+                    // The color is piecewise linear, so splitting the triangle along the stops
+                    // makes interpolating the vertex colors exact.
+                    self.paint_linear_bands(&triangle, mesh);
+                }
+                GradientKind::Radial { .. } => {
+                    self.paint_refined(rect, triangle, TOLERANCE, 0, mesh);
+                }
+            }
+        }
+    }
+
+    fn paint_linear_bands(&self, polygon: &[GradientVertex], mesh: &mut Mesh) {
+        let (min_t, max_t) = polygon
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v.t), hi.max(v.t))
+            });
+
+        let mut band = polygon.to_vec();
+        for &(stop, _) in self.stops() {
+            if min_t < stop && stop < max_t {
+                let (below, above) = split_polygon(&band, stop);
+                self.add_polygon(&below, mesh);
+                band = above;
+            }
+        }
+        self.add_polygon(&band, mesh);
+    }
+
+    fn paint_refined(
+        &self,
+        rect: Rect,
+        triangle: [GradientVertex; 3],
+        tolerance: f32,
+        depth: usize,
+        mesh: &mut Mesh,
+    ) {
+        const MAX_DEPTH: usize = 24;
+        const MIN_EDGE_LENGTH: f32 = 1.0;
+
+        // Split the longest edge in half if the color in its middle is too far off:
+        let (i, length_sq) = (0..3)
+            .map(|i| (i, (triangle[(i + 1) % 3].pos - triangle[i].pos).length_sq()))
+            .fold((0, 0.0), |best, e| if e.1 > best.1 { e } else { best });
+
+        if depth < MAX_DEPTH && MIN_EDGE_LENGTH * MIN_EDGE_LENGTH < length_sq {
+            let [a, b, c] = [triangle[i], triangle[(i + 1) % 3], triangle[(i + 2) % 3]];
+            let error = |p: GradientVertex, q: GradientVertex| {
+                let mid = p.lerp(q, 0.5);
+                let actual = self.color_at(rect, mid.pos);
+                let interpolated = lerp_color(
+                    self.color_at_position(p.t),
+                    self.color_at_position(q.t),
+                    0.5,
+                );
+                color_distance(actual, interpolated)
+            };
+            let max_error = error(a, b).max(error(b, c)).max(error(c, a));
+
+            if tolerance < max_error {
+                let mut mid = a.lerp(b, 0.5);
+                mid.t = self.position_at(rect, mid.pos);
+                self.paint_refined(rect, [a, mid, c], tolerance, depth + 1, mesh);
+                self.paint_refined(rect, [mid, b, c], tolerance, depth + 1, mesh);
+                return;
+            }
+        }
+
+        self.add_polygon(&triangle, mesh);
+    }
+
+    /// Add a convex polygon to the mesh as a triangle fan.
+    fn add_polygon(&self, polygon: &[GradientVertex], mesh: &mut Mesh) {
+        if polygon.len() < 3 {
+            return;
+        }
+        let idx = mesh.vertices.len() as u32;
+        for v in polygon {
+            let mut color = self.color_at_position(v.t);
+            if v.coverage < 1.0 {
+                color = color.linear_multiply(v.coverage);
+            }
+            mesh.vertices.push(Vertex {
+                pos: v.pos,
+                uv: v.uv,
+                color,
+            });
+        }
+        for i in 2..polygon.len() as u32 {
+            mesh.add_triangle(idx, idx + i - 1, idx + i);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to fill a shape: with a single color or with a [`Gradient`].
+///
+/// Anything that takes a fill accepts both a [`Color32`] and a [`Gradient`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Fill {
+    Solid(Color32),
+    Gradient(Gradient),
+}
+
+impl Default for Fill {
+    #[inline]
+    fn default() -> Self {
+        Self::TRANSPARENT
+    }
+}
+
+impl From<Color32> for Fill {
+    #[inline]
+    fn from(color: Color32) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<Gradient> for Fill {
+    #[inline]
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+impl Fill {
+    pub const TRANSPARENT: Self = Self::Solid(Color32::TRANSPARENT);
+
+    /// Is this fill invisible?
+    #[inline]
+    pub fn is_transparent(&self) -> bool {
+        match self {
+            Self::Solid(color) => *color == Color32::TRANSPARENT,
+            Self::Gradient(gradient) => gradient.is_transparent(),
+        }
+    }
+
+    /// The color of a solid fill, or `None` for a gradient.
+    #[inline]
+    pub fn solid(&self) -> Option<Color32> {
+        match self {
+            Self::Solid(color) => Some(*color),
+            Self::Gradient(_) => None,
+        }
+    }
+
+    /// The color at `pos`, for a shape with the bounding rectangle `rect`.
+    pub fn color_at(&self, rect: Rect, pos: Pos2) -> Color32 {
+        match self {
+            Self::Solid(color) => *color,
+            Self::Gradient(gradient) => gradient.color_at(rect, pos),
+        }
+    }
+
+    /// Apply a function to each color of the fill.
+    pub fn map_colors(&mut self, mut f: impl FnMut(&mut Color32)) {
+        match self {
+            Self::Solid(color) => f(color),
+            Self::Gradient(gradient) => gradient.map_colors(f),
+        }
+    }
+
+    /// Multiply all colors with the given factor, e.g. to fade out.
+    #[must_use]
+    pub fn linear_multiply(mut self, factor: f32) -> Self {
+        self.map_colors(|color| *color = color.linear_multiply(factor));
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct GradientVertex {
+    pos: Pos2,
+    uv: Pos2,
+    coverage: f32,
+
+    /// Position along the gradient.
+    t: f32,
+}
+
+impl GradientVertex {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            pos: self.pos.lerp(other.pos, t),
+            uv: self.uv.lerp(other.uv, t),
+            coverage: emath::lerp(self.coverage..=other.coverage, t),
+            t: emath::lerp(self.t..=other.t, t),
+        }
+    }
+}
+
+/// Split a convex polygon into the parts below and above `t`.
+fn split_polygon(polygon: &[GradientVertex], t: f32) -> (Vec<GradientVertex>, Vec<GradientVertex>) {
+    let mut below = vec![];
+    let mut above = vec![];
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        if a.t <= t {
+            below.push(a);
+        }
+        if a.t >= t {
+            above.push(a);
+        }
+        if (a.t < t && t < b.t) || (b.t < t && t < a.t) {
+            let mut cut = a.lerp(b, (t - a.t) / (b.t - a.t));
+            cut.t = t;
+            below.push(cut);
+            above.push(cut);
+        }
+    }
+    (below, above)
+}
+
+/// Interpolate premultiplied colors in gamma space.
+fn lerp_color(a: Color32, b: Color32, t: f32) -> Color32 {
+    let lerp = |a: u8, b: u8| emath::lerp(a as f32..=b as f32, t).round() as u8;
+    Color32::from_rgba_premultiplied(
+        lerp(a.r(), b.r()),
+        lerp(a.g(), b.g()),
+        lerp(a.b(), b.b()),
+        lerp(a.a(), b.a()),
+    )
+}
+
+fn color_distance(a: Color32, b: Color32) -> f32 {
+    let d = |a: u8, b: u8| (a as f32 - b as f32).abs();
+    d(a.r(), b.r())
+        .max(d(a.g(), b.g()))
+        .max(d(a.b(), b.b()))
+        .max(d(a.a(), b.a()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_at_position() {
+        let gradient = Gradient::linear(
+            pos2(0.0, 0.0),
+            pos2(1.0, 0.0),
+            [
+                (1.0, Color32::BLUE),
+                (0.0, Color32::RED),
+                (0.5, Color32::GREEN),
+            ],
+        );
+        assert_eq!(gradient.color_at_position(-1.0), Color32::RED);
+        assert_eq!(gradient.color_at_position(0.5), Color32::GREEN);
+        assert_eq!(gradient.color_at_position(2.0), Color32::BLUE);
+        assert_eq!(
+            gradient.color_at_position(0.75),
+            lerp_color(Color32::GREEN, Color32::BLUE, 0.5)
+        );
+
+        let rect = Rect::from_min_size(pos2(100.0, 0.0), vec2(200.0, 10.0));
+        assert_eq!(gradient.position_at(rect, pos2(150.0, 5.0)), 0.25);
+    }
+
+    #[test]
+    fn test_tessellate_gradient() {
+        let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0));
+        let gradients = [
+            Gradient::linear(
+                pos2(0.0, 0.0),
+                pos2(1.0, 1.0),
+                [
+                    (0.0, Color32::RED),
+                    (0.3, Color32::GREEN),
+                    (1.0, Color32::BLUE),
+                ],
+            ),
+            Gradient::radial(
+                pos2(0.5, 0.5),
+                0.5,
+                [(0.0, Color32::WHITE), (1.0, Color32::BLACK)],
+            ),
+        ];
+
+        for gradient in gradients {
+            let shape = RectShape::filled(rect, 0.0, gradient);
+            let mut mesh = Mesh::default();
+            Tessellator::new(1.0, Default::default(), [1, 1], vec![])
+                .tessellate_rect(&shape, &mut mesh);
+            assert!(mesh.is_valid());
+
+            // Check the middle of each triangle against the gradient:
+            for triangle in mesh.indices.chunks_exact(3) {
+                let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize]);
+                if a.color.a() < 255 || b.color.a() < 255 || c.color.a() < 255 {
+                    continue; // feathering
+                }
+                let center = pos2(
+                    (a.pos.x + b.pos.x + c.pos.x) / 3.0,
+                    (a.pos.y + b.pos.y + c.pos.y) / 3.0,
+                );
+                let interpolated =
+                    lerp_color(lerp_color(a.color, b.color, 0.5), c.color, 1.0 / 3.0);
+                let actual = gradient.color_at(rect, center);
+                assert!(
+                    color_distance(interpolated, actual) <= 16.0,
+                    "{:?}: {:?} != {:?} at {:?}",
+                    gradient.kind,
+                    interpolated,
+                    actual,
+                    center
+                );
+            }
+        }
+    }
+}
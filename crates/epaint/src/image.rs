@@ -238,13 +238,42 @@ pub struct FontImage {
     ///
     /// Often you want to use [`Self::srgba_pixels`] instead.
     pub pixels: Vec<f32>,
+
+    /// Premultiplied `sRGBA` colors of color glyphs, e.g. emoji.
+    ///
+    /// Empty until the first color glyph is added, then one per pixel.
+    /// Where the color is [`Color32::TRANSPARENT`], the coverage in [`Self::pixels`] is used instead.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colors: Vec<Color32>,
+
+    /// The gamma used by [`Self::srgba_pixels`] when it is called with `None`.
+    ///
+    /// `None` means [`Self::DEFAULT_GAMMA`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gamma: Option<f32>,
+
+    /// The glyphs are signed distance fields rather than coverage.
+    ///
+    /// Each value is then `0.5` on the edge of a glyph, larger inside, and smaller outside.
+    /// The renderer should turn this into opacity with a smooth step around `0.5`,
+    /// about one physical pixel wide.
+    ///
+    /// See `TextRasterization::sdf` in [`crate::text`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sdf: bool,
 }
 
 impl FontImage {
+    /// The coverage gamma used unless something else is asked for.
+    pub const DEFAULT_GAMMA: f32 = 0.55; // TODO(emilk): this default coverage gamma is a magic constant, chosen by eye. I don't even know why we need it.
+
     pub fn new(size: [usize; 2]) -> Self {
         Self {
             size,
             pixels: vec![0.0; size[0] * size[1]],
+            colors: Vec::new(),
+            gamma: None,
+            sdf: false,
         }
     }
 
@@ -260,15 +289,25 @@ impl FontImage {
 
     /// Returns the textures as `sRGBA` premultiplied pixels, row by row, top to bottom.
     ///
-    /// `gamma` should normally be set to `None`.
+    /// `gamma` should normally be set to `None`, which uses [`Self::gamma`].
     ///
     /// If you are having problems with text looking skinny and pixelated, try using a low gamma, e.g. `0.4`.
     pub fn srgba_pixels(
         &'_ self,
         gamma: Option<f32>,
     ) -> impl ExactSizeIterator<Item = Color32> + '_ {
-        let gamma = gamma.unwrap_or(0.55); // TODO(emilk): this default coverage gamma is a magic constant, chosen by eye. I don't even know why we need it.
-        self.pixels.iter().map(move |coverage| {
+        // Distances must not be gamma corrected:
+        let gamma = if self.sdf {
+            1.0
+        } else {
+            gamma.or(self.gamma).unwrap_or(Self::DEFAULT_GAMMA)
+        };
+        self.pixels.iter().enumerate().map(move |(i, coverage)| {
+            if let Some(&color) = self.colors.get(i) {
+                if color != Color32::TRANSPARENT {
+                    return color;
+                }
+            }
             let alpha = coverage.powf(gamma);
             // We want to multiply with `vec4(alpha)` in the fragment shader:
             let a = fast_round(alpha * 255.0);
@@ -276,20 +315,43 @@ impl FontImage {
         })
     }
 
+    /// Does this image contain any color glyphs?
+    #[inline]
+    pub fn has_colors(&self) -> bool {
+        !self.colors.is_empty()
+    }
+
+    /// Set the color of a pixel of a color glyph.
+    pub fn set_color(&mut self, (x, y): (usize, usize), color: Color32) {
+        let [w, h] = self.size;
+        assert!(x < w && y < h);
+        if self.colors.is_empty() {
+            self.colors = vec![Color32::TRANSPARENT; w * h];
+        }
+        self.colors[y * w + x] = color;
+    }
+
     /// Clone a sub-region as a new image.
     pub fn region(&self, [x, y]: [usize; 2], [w, h]: [usize; 2]) -> FontImage {
         assert!(x + w <= self.width());
         assert!(y + h <= self.height());
 
         let mut pixels = Vec::with_capacity(w * h);
+        let mut colors = Vec::new();
         for y in y..y + h {
             let offset = y * self.width() + x;
             pixels.extend(&self.pixels[offset..(offset + w)]);
+            if self.has_colors() {
+                colors.extend(&self.colors[offset..(offset + w)]);
+            }
         }
         assert_eq!(pixels.len(), w * h);
         FontImage {
             size: [w, h],
             pixels,
+            colors,
+            gamma: self.gamma,
+            sdf: self.sdf,
         }
     }
 }
@@ -14,6 +14,8 @@
 #![forbid(unsafe_code)]
 
 mod bezier;
+mod complex_path;
+mod gradient;
 pub mod image;
 mod mesh;
 pub mod mutex;
@@ -31,18 +33,20 @@ pub mod util;
 
 pub use {
     bezier::{CubicBezierShape, QuadraticBezierShape},
+    complex_path::{ComplexPathShape, FillRule, PathBuilder, PathSegment},
+    gradient::{Fill, Gradient, GradientKind},
     image::{ColorImage, FontImage, ImageData, ImageDelta},
     mesh::{Mesh, Mesh16, Vertex},
     shadow::Shadow,
     shape::{
-        CircleShape, PaintCallback, PaintCallbackInfo, PathShape, RectShape, Rounding, Shape,
-        TextShape,
+        ArcShape, CircleShape, EllipseShape, ImageShape, NineSlice, PaintCallback,
+        PaintCallbackInfo, PathShape, RectShape, Rounding, Shape, TextShape,
     },
     stats::PaintStats,
-    stroke::Stroke,
+    stroke::{Stroke, StrokeAlignment, StrokeStyle},
     tessellator::{tessellate_shapes, TessellationOptions, Tessellator},
-    text::{FontFamily, FontId, Fonts, Galley},
-    texture_atlas::TextureAtlas,
+    text::{FontFamily, FontId, FontStyle, FontWeight, Fonts, Galley},
+    texture_atlas::{AtlasGroup, TextureAtlas},
     texture_handle::TextureHandle,
     textures::TextureManager,
 };
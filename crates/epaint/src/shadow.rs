@@ -1,87 +1,351 @@
 use super::*;
+use emath::NumExt as _;
 
-/// The color and fuzziness of a fuzzy shape.
-/// Can be used for a rectangular shadow with a soft penumbra.
+/// A blurred shadow of a (rounded) rectangle, like a CSS `box-shadow`.
+///
+/// Can be used for drop shadows of windows and menus, or with [`Self::inset`] for shadows inside a rectangle.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Shadow {
-    /// The shadow extends this much outside the rect.
-    /// The size of the fuzzy penumbra.
-    pub extrusion: f32,
+    /// Move the shadow by this much.
+    pub offset: Vec2,
+
+    /// How blurry the edge of the shadow is.
+    ///
+    /// The shadow fades out over a distance of `blur` on either side of its edge.
+    /// Zero gives a sharp edge.
+    pub blur: f32,
+
+    /// Grow the shadow by this much in all directions before blurring
+    /// (or shrink it, if negative).
+    pub spread: f32,
 
     /// Color of the opaque center of the shadow.
     pub color: Color32,
+
+    /// Paint the shadow inside the rectangle instead of outside of it.
+    ///
+    /// The rectangle is then in shadow everywhere except in a hole
+    /// that is moved by [`Self::offset`] and shrunk by [`Self::spread`].
+    pub inset: bool,
 }
 
 impl Shadow {
     pub const NONE: Self = Self {
-        extrusion: 0.0,
+        offset: Vec2::ZERO,
+        blur: 0.0,
+        spread: 0.0,
         color: Color32::TRANSPARENT,
+        inset: false,
     };
 
     /// Tooltips, menus, …, for dark mode.
     pub fn small_dark() -> Self {
         Self {
-            extrusion: 16.0,
+            offset: vec2(0.0, 4.0),
+            blur: 8.0,
+            spread: 0.0,
             color: Color32::from_black_alpha(96),
+            inset: false,
         }
     }
 
     /// Tooltips, menus, …, for light mode.
     pub fn small_light() -> Self {
         Self {
-            extrusion: 16.0,
+            offset: vec2(0.0, 4.0),
+            blur: 8.0,
+            spread: 0.0,
             color: Color32::from_black_alpha(20),
+            inset: false,
         }
     }
 
     /// Used for egui windows in dark mode.
     pub fn big_dark() -> Self {
         Self {
-            extrusion: 32.0,
+            offset: vec2(0.0, 8.0),
+            blur: 16.0,
+            spread: 0.0,
             color: Color32::from_black_alpha(96),
+            inset: false,
         }
     }
 
     /// Used for egui windows in light mode.
     pub fn big_light() -> Self {
         Self {
-            extrusion: 32.0,
+            offset: vec2(0.0, 8.0),
+            blur: 16.0,
+            spread: 0.0,
             color: Color32::from_black_alpha(16),
+            inset: false,
         }
     }
 
+    /// An inset shadow, painted inside the rectangle.
+    #[inline]
+    pub fn inset(mut self) -> Self {
+        self.inset = true;
+        self
+    }
+
+    /// Nothing to paint?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.color == Color32::TRANSPARENT
+    }
+
+    /// How far outside the rectangle the shadow can reach.
+    pub fn visual_margin(&self) -> f32 {
+        if self.inset || self.is_empty() {
+            0.0
+        } else {
+            (self.offset.x.abs().max(self.offset.y.abs()) + self.spread + self.blur).at_least(0.0)
+        }
+    }
+
+    /// The shadow of the given rectangle, with its corners rounded by `rounding`.
     pub fn tessellate(&self, rect: Rect, rounding: impl Into<Rounding>) -> Mesh {
-        // tessellator.clip_rect = clip_rect; // TODO(emilk): culling
+        let Self {
+            offset,
+            blur,
+            spread,
+            color,
+            inset,
+        } = *self;
 
-        let Self { extrusion, color } = *self;
+        let mut mesh = Mesh::default();
+        if self.is_empty() || rect.is_negative() {
+            return mesh;
+        }
 
-        let rounding: Rounding = rounding.into();
-        let half_ext = 0.5 * extrusion;
+        let rounding = clamp_rounding(rounding.into(), rect);
 
-        let ext_rounding = Rounding {
-            nw: rounding.nw + half_ext,
-            ne: rounding.ne + half_ext,
-            sw: rounding.sw + half_ext,
-            se: rounding.se + half_ext,
+        // The shape of the shadow before blurring, and its rounding:
+        let shadow_rect = if inset {
+            rect.translate(offset).shrink(spread)
+        } else {
+            rect.translate(offset).expand(spread)
+        };
+        let spread = if inset { -spread } else { spread };
+        let adjust = |r: f32| {
+            if r > 0.0 {
+                (r + spread).at_least(0.0)
+            } else {
+                0.0
+            }
+        };
+        let shadow_rounding = Rounding {
+            nw: adjust(rounding.nw),
+            ne: adjust(rounding.ne),
+            sw: adjust(rounding.sw),
+            se: adjust(rounding.se),
         };
 
-        use crate::tessellator::*;
-        let rect = RectShape::filled(rect.expand(half_ext), ext_rounding, color);
-        let pixels_per_point = 1.0; // doesn't matter here
-        let font_tex_size = [1; 2]; // unused size we are not tessellating text.
-        let mut tessellator = Tessellator::new(
-            pixels_per_point,
-            TessellationOptions {
-                feathering: true,
-                feathering_size_in_pixels: extrusion * pixels_per_point,
-                ..Default::default()
-            },
-            font_tex_size,
-            vec![],
-        );
-        let mut mesh = Mesh::default();
-        tessellator.tessellate_rect(&rect, &mut mesh);
+        // Always blur a little, to get an anti-aliased edge:
+        let blur = blur.at_least(0.5);
+
+        // The fraction of the shadow that remains at the signed distance `d` from its edge:
+        let sigma = 0.5 * blur;
+        let coverage = |d: f32| 0.5 * (1.0 - erf(d / (sigma * std::f32::consts::SQRT_2)));
+
+        // Rings from the inside of the edge to the outside:
+        const NUM_RINGS: usize = 8;
+        let mut ring = vec![];
+        for i in 0..=NUM_RINGS {
+            let d = blur * (2.0 * i as f32 / NUM_RINGS as f32 - 1.0);
+            let opacity = if i == 0 {
+                1.0
+            } else if i == NUM_RINGS {
+                0.0
+            } else {
+                coverage(d)
+            };
+            let opacity = if inset { 1.0 - opacity } else { opacity };
+
+            ring.clear();
+            rounded_rect_ring(shadow_rect, shadow_rounding, d, &mut ring);
+            if inset {
+                for p in &mut ring {
+                    *p = clamp_to_rounded_rect(*p, rect, rounding);
+                }
+            }
+            add_ring(&mut mesh, &ring, color.linear_multiply(opacity), i > 0);
+        }
+
+        if inset {
+            // Fill out to the edge of the rectangle:
+            ring.clear();
+            rounded_rect_ring(rect, rounding, 0.0, &mut ring);
+            add_ring(&mut mesh, &ring, color, true);
+        } else {
+            // Fill in the middle:
+            let n = ring.len() as u32;
+            for i in 2..n {
+                mesh.add_triangle(0, i - 1, i);
+            }
+        }
+
         mesh
     }
 }
+
+/// Number of points on each rounded corner of a ring.
+const CORNER_POINTS: usize = 8;
+
+/// Points around the rectangle, grown by `d` (or shrunk, if negative),
+/// with the rounding growing (or shrinking) along with it.
+///
+/// There is always the same number of points, so that rings can be stitched together.
+fn rounded_rect_ring(rect: Rect, rounding: Rounding, d: f32, out: &mut Vec<Pos2>) {
+    // Don't shrink past the center:
+    let d = d.at_least(-0.5 * rect.width().min(rect.height()));
+    let rect = rect.expand(d);
+    let rounding = clamp_rounding(
+        Rounding {
+            nw: (rounding.nw + d).at_least(0.0),
+            ne: (rounding.ne + d).at_least(0.0),
+            sw: (rounding.sw + d).at_least(0.0),
+            se: (rounding.se + d).at_least(0.0),
+        },
+        rect,
+    );
+
+    // Clockwise, starting at the bottom right corner, like `path::rounded_rectangle`:
+    let corners = [
+        (
+            pos2(rect.max.x - rounding.se, rect.max.y - rounding.se),
+            rounding.se,
+        ),
+        (
+            pos2(rect.min.x + rounding.sw, rect.max.y - rounding.sw),
+            rounding.sw,
+        ),
+        (
+            pos2(rect.min.x + rounding.nw, rect.min.y + rounding.nw),
+            rounding.nw,
+        ),
+        (
+            pos2(rect.max.x - rounding.ne, rect.min.y + rounding.ne),
+            rounding.ne,
+        ),
+    ];
+    for (quadrant, (center, radius)) in corners.into_iter().enumerate() {
+        for i in 0..=CORNER_POINTS {
+            let angle =
+                (quadrant as f32 + i as f32 / CORNER_POINTS as f32) * std::f32::consts::FRAC_PI_2;
+            out.push(center + radius * Vec2::angled(angle));
+        }
+    }
+}
+
+/// Add the ring as vertices, and connect them to the previous ring with triangles.
+fn add_ring(mesh: &mut Mesh, ring: &[Pos2], color: Color32, connect_with_previous: bool) {
+    let n = ring.len() as u32;
+    let idx = mesh.vertices.len() as u32;
+    for &pos in ring {
+        mesh.colored_vertex(pos, color);
+    }
+    if connect_with_previous {
+        let prev = idx - n;
+        for i0 in 0..n {
+            let i1 = (i0 + 1) % n;
+            mesh.add_triangle(prev + i0, prev + i1, idx + i0);
+            mesh.add_triangle(prev + i1, idx + i1, idx + i0);
+        }
+    }
+}
+
+fn clamp_rounding(rounding: Rounding, rect: Rect) -> Rounding {
+    let max = 0.5 * rect.width().min(rect.height());
+    rounding.at_least(0.0).at_most(max.at_least(0.0))
+}
+
+/// The closest point to `p` inside the rounded rectangle.
+fn clamp_to_rounded_rect(p: Pos2, rect: Rect, rounding: Rounding) -> Pos2 {
+    let p = rect.clamp(p);
+    let corners = [
+        (
+            pos2(rect.min.x + rounding.nw, rect.min.y + rounding.nw),
+            rounding.nw,
+        ),
+        (
+            pos2(rect.max.x - rounding.ne, rect.min.y + rounding.ne),
+            rounding.ne,
+        ),
+        (
+            pos2(rect.min.x + rounding.sw, rect.max.y - rounding.sw),
+            rounding.sw,
+        ),
+        (
+            pos2(rect.max.x - rounding.se, rect.max.y - rounding.se),
+            rounding.se,
+        ),
+    ];
+    for (center, radius) in corners {
+        let delta = p - center;
+        let in_corner = (delta.x != 0.0
+            && delta.x.signum() == (center.x - rect.center().x).signum())
+            && (delta.y != 0.0 && delta.y.signum() == (center.y - rect.center().y).signum());
+        if in_corner && delta.length() > radius {
+            return center + radius * delta.normalized();
+        }
+    }
+    p
+}
+
+/// The error function, with a maximum error of 1.5e-7.
+///
+/// Abramowitz and Stegun, formula 7.1.26.
+fn erf(x: f32) -> f32 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let y = 1.0
+        - (((((1.061_405_4 * t - 1.453_152_1) * t) + 1.421_413_8) * t - 0.284_496_72) * t
+            + 0.254_829_6)
+            * t
+            * (-x * x).exp();
+    y.copysign(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shadow() {
+        let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 50.0));
+        let shadow = Shadow {
+            offset: vec2(5.0, 10.0),
+            blur: 8.0,
+            spread: 2.0,
+            color: Color32::BLACK,
+            inset: false,
+        };
+        let mesh = shadow.tessellate(rect, 4.0);
+        assert!(mesh.is_valid());
+        let bounds = mesh.calc_bounds();
+        let expected = rect
+            .translate(shadow.offset)
+            .expand(shadow.spread + shadow.blur);
+        assert!((bounds.min - expected.min).length() < 1e-3);
+        assert!((bounds.max - expected.max).length() < 1e-3);
+        assert!(bounds.max.y - rect.max.y <= shadow.visual_margin());
+
+        // Inset shadows stay inside the rectangle:
+        let mesh = shadow.inset().tessellate(rect, 4.0);
+        assert!(mesh.is_valid());
+        for v in &mesh.vertices {
+            assert!(rect.expand(1e-3).contains(v.pos), "{:?}", v.pos);
+        }
+    }
+
+    #[test]
+    fn test_erf() {
+        assert!(erf(0.0).abs() < 1e-6);
+        assert!((erf(1.0) - 0.842_700_8).abs() < 1e-6);
+        assert!((erf(-1.0) + 0.842_700_8).abs() < 1e-6);
+        assert!((erf(3.0) - 1.0).abs() < 1e-4);
+    }
+}
@@ -5,11 +5,11 @@ use std::{any::Any, sync::Arc};
 
 use crate::{
     text::{FontId, Fonts, Galley},
-    Color32, Mesh, Stroke, TextureId,
+    Color32, Fill, Mesh, Stroke, StrokeAlignment, TextureId,
 };
 use emath::*;
 
-pub use crate::{CubicBezierShape, QuadraticBezierShape};
+pub use crate::{ComplexPathShape, CubicBezierShape, QuadraticBezierShape};
 
 /// A paint primitive such as a circle or a piece of text.
 /// Coordinates are all screen space points (not physical pixels).
@@ -31,6 +31,12 @@ pub enum Shape {
     /// Circle with optional outline and fill.
     Circle(CircleShape),
 
+    /// Ellipse with optional outline and fill.
+    Ellipse(EllipseShape),
+
+    /// A part of a circle, either as a curved line or as a pie slice.
+    Arc(ArcShape),
+
     /// A line between two points.
     LineSegment { points: [Pos2; 2], stroke: Stroke },
 
@@ -46,6 +52,9 @@ pub enum Shape {
     /// This needs to be recreated if `pixels_per_point` (dpi scale) changes.
     Text(TextShape),
 
+    /// An image, optionally rotated and/or with nine-slice scaling.
+    Image(ImageShape),
+
     /// A general triangle mesh.
     ///
     /// Can be used to display images.
@@ -57,6 +66,11 @@ pub enum Shape {
     /// A cubic [Bézier Curve](https://en.wikipedia.org/wiki/B%C3%A9zier_curve).
     CubicBezier(CubicBezierShape),
 
+    /// Several subpaths of lines, curves and arcs, with a fill that can be concave and have holes.
+    ///
+    /// Use [`crate::PathBuilder`] to create one.
+    ComplexPath(ComplexPathShape),
+
     /// Backend-specific painting.
     Callback(PaintCallback),
 }
@@ -168,12 +182,20 @@ impl Shape {
         fill: impl Into<Color32>,
         stroke: impl Into<Stroke>,
     ) -> Self {
-        Self::Path(PathShape::convex_polygon(points, fill, stroke))
+        Self::Path(PathShape::convex_polygon(
+            points,
+            Fill::Solid(fill.into()),
+            stroke,
+        ))
     }
 
     #[inline]
     pub fn circle_filled(center: Pos2, radius: f32, fill_color: impl Into<Color32>) -> Self {
-        Self::Circle(CircleShape::filled(center, radius, fill_color))
+        Self::Circle(CircleShape::filled(
+            center,
+            radius,
+            Fill::Solid(fill_color.into()),
+        ))
     }
 
     #[inline]
@@ -181,13 +203,31 @@ impl Shape {
         Self::Circle(CircleShape::stroke(center, radius, stroke))
     }
 
+    #[inline]
+    pub fn ellipse_filled(center: Pos2, radius: Vec2, fill_color: impl Into<Color32>) -> Self {
+        Self::Ellipse(EllipseShape::filled(
+            center,
+            radius,
+            Fill::Solid(fill_color.into()),
+        ))
+    }
+
+    #[inline]
+    pub fn ellipse_stroke(center: Pos2, radius: Vec2, stroke: impl Into<Stroke>) -> Self {
+        Self::Ellipse(EllipseShape::stroke(center, radius, stroke))
+    }
+
     #[inline]
     pub fn rect_filled(
         rect: Rect,
         rounding: impl Into<Rounding>,
         fill_color: impl Into<Color32>,
     ) -> Self {
-        Self::Rect(RectShape::filled(rect, rounding, fill_color))
+        Self::Rect(RectShape::filled(
+            rect,
+            rounding,
+            Fill::Solid(fill_color.into()),
+        ))
     }
 
     #[inline]
@@ -240,9 +280,7 @@ impl Shape {
     ///
     /// `tint` is a color multiplier. Use [`Color32::WHITE`] if you don't want to tint the image.
     pub fn image(texture_id: TextureId, rect: Rect, uv: Rect, tint: Color32) -> Self {
-        let mut mesh = Mesh::with_texture(texture_id);
-        mesh.add_rect_with_uv(rect, uv, tint);
-        Shape::mesh(mesh)
+        Self::Image(ImageShape::new(texture_id, rect, uv, tint))
     }
 
     /// The visual bounding rectangle (includes stroke widths)
@@ -257,6 +295,8 @@ impl Shape {
                 rect
             }
             Self::Circle(circle_shape) => circle_shape.visual_bounding_rect(),
+            Self::Ellipse(ellipse_shape) => ellipse_shape.visual_bounding_rect(),
+            Self::Arc(arc_shape) => arc_shape.visual_bounding_rect(),
             Self::LineSegment { points, stroke } => {
                 if stroke.is_empty() {
                     Rect::NOTHING
@@ -267,9 +307,11 @@ impl Shape {
             Self::Path(path_shape) => path_shape.visual_bounding_rect(),
             Self::Rect(rect_shape) => rect_shape.visual_bounding_rect(),
             Self::Text(text_shape) => text_shape.visual_bounding_rect(),
+            Self::Image(image_shape) => image_shape.visual_bounding_rect(),
             Self::Mesh(mesh) => mesh.calc_bounds(),
             Self::QuadraticBezier(bezier) => bezier.visual_bounding_rect(),
             Self::CubicBezier(bezier) => bezier.visual_bounding_rect(),
+            Self::ComplexPath(path) => path.visual_bounding_rect(),
             Self::Callback(custom) => custom.rect,
         }
     }
@@ -279,13 +321,20 @@ impl Shape {
 impl Shape {
     #[inline(always)]
     pub fn texture_id(&self) -> super::TextureId {
-        if let Shape::Mesh(mesh) = self {
-            mesh.texture_id
-        } else {
-            super::TextureId::default()
+        match self {
+            Shape::Mesh(mesh) => mesh.texture_id,
+            Shape::Image(image_shape) => image_shape.texture_id,
+            _ => super::TextureId::default(),
         }
     }
 
+    /// Transform the shape in-place, e.g. to rotate or zoom it.
+    ///
+    /// See [`crate::shape_transform::transform_shape`].
+    pub fn transform(&mut self, transform: Affine2) {
+        crate::shape_transform::transform_shape(self, transform);
+    }
+
     /// Move the shape by this many points, in-place.
     pub fn translate(&mut self, delta: Vec2) {
         match self {
@@ -298,6 +347,12 @@ impl Shape {
             Shape::Circle(circle_shape) => {
                 circle_shape.center += delta;
             }
+            Shape::Ellipse(ellipse_shape) => {
+                ellipse_shape.center += delta;
+            }
+            Shape::Arc(arc_shape) => {
+                arc_shape.center += delta;
+            }
             Shape::LineSegment { points, .. } => {
                 for p in points {
                     *p += delta;
@@ -314,6 +369,9 @@ impl Shape {
             Shape::Text(text_shape) => {
                 text_shape.pos += delta;
             }
+            Shape::Image(image_shape) => {
+                image_shape.rect = image_shape.rect.translate(delta);
+            }
             Shape::Mesh(mesh) => {
                 mesh.translate(delta);
             }
@@ -327,6 +385,11 @@ impl Shape {
                     *p += delta;
                 }
             }
+            Shape::ComplexPath(path_shape) => {
+                for segment in &mut path_shape.segments {
+                    segment.for_each_point_mut(|p| *p += delta);
+                }
+            }
             Shape::Callback(shape) => {
                 shape.rect = shape.rect.translate(delta);
             }
@@ -342,48 +405,269 @@ impl Shape {
 pub struct CircleShape {
     pub center: Pos2,
     pub radius: f32,
-    pub fill: Color32,
+    pub fill: Fill,
+
     pub stroke: Stroke,
+
+    /// Where the stroke is painted relative to the circumference.
+    pub stroke_alignment: StrokeAlignment,
 }
 
 impl CircleShape {
     #[inline]
-    pub fn filled(center: Pos2, radius: f32, fill_color: impl Into<Color32>) -> Self {
+    pub fn new(
+        center: Pos2,
+        radius: f32,
+        fill: impl Into<Fill>,
+        stroke: impl Into<Stroke>,
+    ) -> Self {
         Self {
             center,
             radius,
-            fill: fill_color.into(),
-            stroke: Default::default(),
+            fill: fill.into(),
+            stroke: stroke.into(),
+            stroke_alignment: Default::default(),
         }
     }
 
+    #[inline]
+    pub fn filled(center: Pos2, radius: f32, fill: impl Into<Fill>) -> Self {
+        Self::new(center, radius, fill, Stroke::NONE)
+    }
+
     #[inline]
     pub fn stroke(center: Pos2, radius: f32, stroke: impl Into<Stroke>) -> Self {
+        Self::new(center, radius, Fill::TRANSPARENT, stroke)
+    }
+
+    /// Paint the stroke inside, centered on, or outside the circumference.
+    #[inline]
+    pub fn with_stroke_alignment(mut self, stroke_alignment: StrokeAlignment) -> Self {
+        self.stroke_alignment = stroke_alignment;
+        self
+    }
+
+    /// The visual bounding rectangle (includes stroke width)
+    pub fn visual_bounding_rect(&self) -> Rect {
+        if self.fill.is_transparent() && self.stroke.is_empty() {
+            Rect::NOTHING
+        } else {
+            let stroke_width = self.stroke_alignment.outer_width(self.stroke.width);
+            Rect::from_center_size(self.center, Vec2::splat(2.0 * (self.radius + stroke_width)))
+        }
+    }
+}
+
+impl From<CircleShape> for Shape {
+    #[inline(always)]
+    fn from(shape: CircleShape) -> Self {
+        Self::Circle(shape)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to paint an ellipse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EllipseShape {
+    pub center: Pos2,
+
+    /// Radius along the x and y axes.
+    pub radius: Vec2,
+
+    pub fill: Fill,
+
+    pub stroke: Stroke,
+
+    /// Where the stroke is painted relative to the outline.
+    pub stroke_alignment: StrokeAlignment,
+}
+
+impl EllipseShape {
+    #[inline]
+    pub fn new(
+        center: Pos2,
+        radius: Vec2,
+        fill: impl Into<Fill>,
+        stroke: impl Into<Stroke>,
+    ) -> Self {
         Self {
             center,
             radius,
-            fill: Default::default(),
+            fill: fill.into(),
             stroke: stroke.into(),
+            stroke_alignment: Default::default(),
         }
     }
 
+    #[inline]
+    pub fn filled(center: Pos2, radius: Vec2, fill: impl Into<Fill>) -> Self {
+        Self::new(center, radius, fill, Stroke::NONE)
+    }
+
+    #[inline]
+    pub fn stroke(center: Pos2, radius: Vec2, stroke: impl Into<Stroke>) -> Self {
+        Self::new(center, radius, Fill::TRANSPARENT, stroke)
+    }
+
+    /// Paint the stroke inside, centered on, or outside the outline.
+    #[inline]
+    pub fn with_stroke_alignment(mut self, stroke_alignment: StrokeAlignment) -> Self {
+        self.stroke_alignment = stroke_alignment;
+        self
+    }
+
     /// The visual bounding rectangle (includes stroke width)
     pub fn visual_bounding_rect(&self) -> Rect {
-        if self.fill == Color32::TRANSPARENT && self.stroke.is_empty() {
+        if self.fill.is_transparent() && self.stroke.is_empty() {
             Rect::NOTHING
         } else {
-            Rect::from_center_size(
-                self.center,
-                Vec2::splat(self.radius * 2.0 + self.stroke.width),
-            )
+            let stroke_width = self.stroke_alignment.outer_width(self.stroke.width);
+            Rect::from_center_size(self.center, 2.0 * (self.radius + Vec2::splat(stroke_width)))
         }
     }
 }
 
-impl From<CircleShape> for Shape {
+impl From<EllipseShape> for Shape {
     #[inline(always)]
-    fn from(shape: CircleShape) -> Self {
-        Self::Circle(shape)
+    fn from(shape: EllipseShape) -> Self {
+        Self::Ellipse(shape)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to paint a part of a circle.
+///
+/// Angles are in radians, with zero pointing right (+X) and positive angles going clockwise (towards +Y).
+/// The arc goes from `start_angle` to `end_angle`, which may be smaller than `start_angle`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ArcShape {
+    pub center: Pos2,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+
+    /// If true, the arc is closed through the center into a pie slice (sector).
+    /// This is required if `fill` is not transparent.
+    pub closed: bool,
+
+    /// A [`Gradient`](crate::Gradient) fill is relative to [`Self::bounding_rect`].
+    pub fill: Fill,
+
+    pub stroke: Stroke,
+
+    /// Where the stroke is painted relative to the outline of a pie slice or full circle.
+    ///
+    /// The stroke of an open arc is always centered on it.
+    pub stroke_alignment: StrokeAlignment,
+}
+
+impl ArcShape {
+    /// A curved line along the circle.
+    #[inline]
+    pub fn stroke(
+        center: Pos2,
+        radius: f32,
+        angles: RangeInclusive<f32>,
+        stroke: impl Into<Stroke>,
+    ) -> Self {
+        Self {
+            center,
+            radius,
+            start_angle: *angles.start(),
+            end_angle: *angles.end(),
+            closed: false,
+            fill: Fill::TRANSPARENT,
+            stroke: stroke.into(),
+            stroke_alignment: Default::default(),
+        }
+    }
+
+    /// A pie slice, with a fill and optional stroke.
+    #[inline]
+    pub fn pie(
+        center: Pos2,
+        radius: f32,
+        angles: RangeInclusive<f32>,
+        fill: impl Into<Fill>,
+        stroke: impl Into<Stroke>,
+    ) -> Self {
+        Self {
+            center,
+            radius,
+            start_angle: *angles.start(),
+            end_angle: *angles.end(),
+            closed: true,
+            fill: fill.into(),
+            stroke: stroke.into(),
+            stroke_alignment: Default::default(),
+        }
+    }
+
+    /// Paint the stroke of a pie slice or full circle inside, centered on, or outside the outline.
+    #[inline]
+    pub fn with_stroke_alignment(mut self, stroke_alignment: StrokeAlignment) -> Self {
+        self.stroke_alignment = stroke_alignment;
+        self
+    }
+
+    /// Is the outline closed, i.e. is this a pie slice or a full circle?
+    #[inline]
+    fn is_outline_closed(&self) -> bool {
+        self.closed || (self.end_angle - self.start_angle).abs() >= std::f32::consts::TAU
+    }
+
+    /// The visual bounding rectangle (includes stroke width)
+    pub fn visual_bounding_rect(&self) -> Rect {
+        if self.fill.is_transparent() && self.stroke.is_empty() {
+            return Rect::NOTHING;
+        }
+
+        let stroke_width = if self.is_outline_closed() {
+            self.stroke_alignment.outer_width(self.stroke.width)
+        } else {
+            self.stroke.width / 2.0
+        };
+        self.bounding_rect().expand(stroke_width)
+    }
+
+    /// The bounding rectangle of the arc, or of the pie slice if it is closed, without the stroke.
+    pub fn bounding_rect(&self) -> Rect {
+        let (min_angle, max_angle) = if self.start_angle <= self.end_angle {
+            (self.start_angle, self.end_angle)
+        } else {
+            (self.end_angle, self.start_angle)
+        };
+
+        let mut rect = Rect::NOTHING;
+        let mut add_angle = |angle: f32| {
+            rect.extend_with(self.center + self.radius * Vec2::angled(angle));
+        };
+        add_angle(min_angle);
+        add_angle(max_angle);
+
+        // Add the extreme points along the axes:
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let mut angle = (min_angle / quarter).ceil() * quarter;
+        while angle < max_angle.min(min_angle + std::f32::consts::TAU) {
+            add_angle(angle);
+            angle += quarter;
+        }
+
+        if self.closed {
+            rect.extend_with(self.center);
+        }
+        rect
+    }
+}
+
+impl From<ArcShape> for Shape {
+    #[inline(always)]
+    fn from(shape: ArcShape) -> Self {
+        Self::Arc(shape)
     }
 }
 
@@ -401,10 +685,13 @@ pub struct PathShape {
     pub closed: bool,
 
     /// Fill is only supported for convex polygons.
-    pub fill: Color32,
+    pub fill: Fill,
 
     /// Color and thickness of the line.
     pub stroke: Stroke,
+
+    /// Where the stroke is painted relative to the points, if the path is closed.
+    pub stroke_alignment: StrokeAlignment,
 }
 
 impl PathShape {
@@ -418,6 +705,7 @@ impl PathShape {
             closed: false,
             fill: Default::default(),
             stroke: stroke.into(),
+            stroke_alignment: Default::default(),
         }
     }
 
@@ -429,6 +717,7 @@ impl PathShape {
             closed: true,
             fill: Default::default(),
             stroke: stroke.into(),
+            stroke_alignment: Default::default(),
         }
     }
 
@@ -438,7 +727,7 @@ impl PathShape {
     #[inline]
     pub fn convex_polygon(
         points: Vec<Pos2>,
-        fill: impl Into<Color32>,
+        fill: impl Into<Fill>,
         stroke: impl Into<Stroke>,
     ) -> Self {
         PathShape {
@@ -446,14 +735,25 @@ impl PathShape {
             closed: true,
             fill: fill.into(),
             stroke: stroke.into(),
+            stroke_alignment: Default::default(),
         }
     }
 
+    /// Paint the stroke inside, centered on, or outside the outline, if the path is closed.
+    #[inline]
+    pub fn with_stroke_alignment(mut self, stroke_alignment: StrokeAlignment) -> Self {
+        self.stroke_alignment = stroke_alignment;
+        self
+    }
+
     /// The visual bounding rectangle (includes stroke width)
     #[inline]
     pub fn visual_bounding_rect(&self) -> Rect {
-        if self.fill == Color32::TRANSPARENT && self.stroke.is_empty() {
+        if self.fill.is_transparent() && self.stroke.is_empty() {
             Rect::NOTHING
+        } else if self.closed {
+            let stroke_width = self.stroke_alignment.outer_width(self.stroke.width);
+            Rect::from_points(&self.points).expand(stroke_width)
         } else {
             Rect::from_points(&self.points).expand(self.stroke.width / 2.0)
         }
@@ -479,44 +779,59 @@ pub struct RectShape {
     pub rounding: Rounding,
 
     /// How to fill the rectangle.
-    pub fill: Color32,
+    pub fill: Fill,
 
     /// The thickness and color of the outline.
     pub stroke: Stroke,
+
+    /// Where the stroke is painted relative to the edge of the rectangle.
+    pub stroke_alignment: StrokeAlignment,
 }
 
 impl RectShape {
     #[inline]
-    pub fn filled(
+    pub fn new(
         rect: Rect,
         rounding: impl Into<Rounding>,
-        fill_color: impl Into<Color32>,
+        fill: impl Into<Fill>,
+        stroke: impl Into<Stroke>,
     ) -> Self {
         Self {
             rect,
             rounding: rounding.into(),
-            fill: fill_color.into(),
-            stroke: Default::default(),
+            fill: fill.into(),
+            stroke: stroke.into(),
+            stroke_alignment: Default::default(),
         }
     }
 
+    #[inline]
+    pub fn filled(rect: Rect, rounding: impl Into<Rounding>, fill: impl Into<Fill>) -> Self {
+        Self::new(rect, rounding, fill, Stroke::NONE)
+    }
+
     #[inline]
     pub fn stroke(rect: Rect, rounding: impl Into<Rounding>, stroke: impl Into<Stroke>) -> Self {
-        Self {
-            rect,
-            rounding: rounding.into(),
-            fill: Default::default(),
-            stroke: stroke.into(),
-        }
+        Self::new(rect, rounding, Fill::TRANSPARENT, stroke)
+    }
+
+    /// Paint the stroke inside, centered on, or outside the edge of the rectangle.
+    ///
+    /// With [`StrokeAlignment::Inside`] nothing is painted outside [`Self::rect`].
+    #[inline]
+    pub fn with_stroke_alignment(mut self, stroke_alignment: StrokeAlignment) -> Self {
+        self.stroke_alignment = stroke_alignment;
+        self
     }
 
     /// The visual bounding rectangle (includes stroke width)
     #[inline]
     pub fn visual_bounding_rect(&self) -> Rect {
-        if self.fill == Color32::TRANSPARENT && self.stroke.is_empty() {
+        if self.fill.is_transparent() && self.stroke.is_empty() {
             Rect::NOTHING
         } else {
-            self.rect.expand(self.stroke.width / 2.0)
+            self.rect
+                .expand(self.stroke_alignment.outer_width(self.stroke.width))
         }
     }
 }
@@ -670,6 +985,202 @@ impl From<TextShape> for Shape {
 
 // ----------------------------------------------------------------------------
 
+/// How to paint an image: a rectangle filled with (a part of) a texture.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ImageShape {
+    pub texture_id: TextureId,
+
+    /// Where to paint the image, before it is rotated.
+    pub rect: Rect,
+
+    /// Which part of the texture to show, with (0,0) in the top-left corner and (1,1) in the bottom-right.
+    ///
+    /// Go outside the 0-1 range to tile or mirror the texture,
+    /// together with a [`crate::textures::TextureWrapMode`] other than `ClampToEdge`.
+    pub uv: Rect,
+
+    /// Multiply the texture with this color. [`Color32::WHITE`] means no tint.
+    pub tint: Color32,
+
+    /// Rotate the image by this many radians clockwise, around [`Self::rotation_origin`].
+    pub angle: f32,
+
+    /// The pivot of the rotation, normalized within [`Self::rect`]:
+    /// (0,0) is the top-left corner and (1,1) the bottom-right.
+    pub rotation_origin: Vec2,
+
+    /// If set, the borders of the image keep their size instead of being stretched.
+    pub nine_slice: Option<NineSlice>,
+}
+
+impl ImageShape {
+    #[inline]
+    pub fn new(texture_id: TextureId, rect: Rect, uv: Rect, tint: Color32) -> Self {
+        Self {
+            texture_id,
+            rect,
+            uv,
+            tint,
+            angle: 0.0,
+            rotation_origin: Vec2::splat(0.5),
+            nine_slice: None,
+        }
+    }
+
+    /// Rotate the image clockwise by `angle` radians around `origin`,
+    /// which is normalized within the rectangle (`Vec2::splat(0.5)` is the center).
+    #[inline]
+    pub fn with_rotation(mut self, angle: f32, origin: Vec2) -> Self {
+        self.angle = angle;
+        self.rotation_origin = origin;
+        self
+    }
+
+    #[inline]
+    pub fn with_nine_slice(mut self, nine_slice: NineSlice) -> Self {
+        self.nine_slice = Some(nine_slice);
+        self
+    }
+
+    /// The point the image is rotated around.
+    #[inline]
+    pub fn pivot(&self) -> Pos2 {
+        self.rect.min + self.rotation_origin * self.rect.size()
+    }
+
+    /// The visual bounding rectangle, taking rotation into account.
+    pub fn visual_bounding_rect(&self) -> Rect {
+        if self.angle == 0.0 {
+            self.rect
+        } else {
+            let rot = Rot2::from_angle(self.angle);
+            let pivot = self.pivot();
+            let corners = [
+                self.rect.left_top(),
+                self.rect.right_top(),
+                self.rect.left_bottom(),
+                self.rect.right_bottom(),
+            ];
+            Rect::from_points(&corners.map(|p| pivot + rot * (p - pivot)))
+        }
+    }
+
+    /// The textured triangles that paint this image.
+    pub fn to_mesh(&self) -> Mesh {
+        let Self {
+            texture_id,
+            rect,
+            uv,
+            tint,
+            angle,
+            nine_slice,
+            ..
+        } = *self;
+
+        let mut mesh = Mesh::with_texture(texture_id);
+        if let Some(nine_slice) = nine_slice {
+            let (xs, us) = nine_slice.slice(rect, uv, 0);
+            let (ys, vs) = nine_slice.slice(rect, uv, 1);
+            add_grid(&mut mesh, &xs, &ys, &us, &vs, tint);
+        } else {
+            mesh.add_rect_with_uv(rect, uv, tint);
+        }
+
+        if angle != 0.0 {
+            mesh.rotate(Rot2::from_angle(angle), self.pivot());
+        }
+        mesh
+    }
+}
+
+impl From<ImageShape> for Shape {
+    #[inline(always)]
+    fn from(shape: ImageShape) -> Self {
+        Self::Image(shape)
+    }
+}
+
+/// Nine-slice scaling of an [`ImageShape`], for skinning panels and buttons with a texture.
+///
+/// The image is cut into a 3x3 grid.
+/// The corners keep their size, the edges are only stretched along their length,
+/// and the center is stretched to fill the rest.
+///
+/// If the image is smaller than its borders, the borders shrink proportionally.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NineSlice {
+    /// The part of the texture that is stretched to fill the center,
+    /// in the same coordinates as [`ImageShape::uv`].
+    pub center_uv: Rect,
+
+    /// The width of the left border and the height of the top border, in points.
+    pub border_min: Vec2,
+
+    /// The width of the right border and the height of the bottom border, in points.
+    pub border_max: Vec2,
+}
+
+impl NineSlice {
+    /// Borders that are `border` texels wide on every side of `uv`,
+    /// in a texture that is `texture_size` texels large, painted at one point per texel.
+    pub fn from_border(uv: Rect, texture_size: Vec2, border: f32) -> Self {
+        let uv_border = Vec2::splat(border) / texture_size;
+        Self {
+            center_uv: Rect::from_min_max(uv.min + uv_border, uv.max - uv_border),
+            border_min: Vec2::splat(border),
+            border_max: Vec2::splat(border),
+        }
+    }
+
+    /// The positions and texture coordinates of the grid lines along one axis (0 = x, 1 = y).
+    fn slice(&self, rect: Rect, uv: Rect, axis: usize) -> ([f32; 4], [f32; 4]) {
+        let (min, max) = (rect.min[axis], rect.max[axis]);
+        let (mut border_min, mut border_max) = (self.border_min[axis], self.border_max[axis]);
+        let border_sum = border_min + border_max;
+        if border_sum > max - min && border_sum > 0.0 {
+            let shrink = (max - min).max(0.0) / border_sum;
+            border_min *= shrink;
+            border_max *= shrink;
+        }
+        (
+            [min, min + border_min, max - border_max, max],
+            [
+                uv.min[axis],
+                self.center_uv.min[axis],
+                self.center_uv.max[axis],
+                uv.max[axis],
+            ],
+        )
+    }
+}
+
+/// Add a grid of textured quads, with corners at `xs` × `ys`.
+fn add_grid(mesh: &mut Mesh, xs: &[f32], ys: &[f32], us: &[f32], vs: &[f32], color: Color32) {
+    let idx = mesh.vertices.len() as u32;
+    let columns = xs.len() as u32;
+    for (&y, &v) in ys.iter().zip(vs) {
+        for (&x, &u) in xs.iter().zip(us) {
+            mesh.vertices.push(crate::Vertex {
+                pos: pos2(x, y),
+                uv: pos2(u, v),
+                color,
+            });
+        }
+    }
+    for row in 0..ys.len() as u32 - 1 {
+        for column in 0..columns - 1 {
+            let top_left = idx + row * columns + column;
+            let bottom_left = top_left + columns;
+            mesh.add_triangle(top_left, top_left + 1, bottom_left);
+            mesh.add_triangle(bottom_left, top_left + 1, bottom_left + 1);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Creates equally spaced filled circles from a line.
 fn points_from_line(
     path: &[Pos2],
@@ -1,4 +1,6 @@
+use crate::complex_path::arc_segment_count;
 use crate::*;
+use emath::*;
 
 pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
     #![allow(clippy::match_same_arms)]
@@ -10,18 +12,26 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
             }
         }
         Shape::Circle(circle_shape) => {
-            adjust_color(&mut circle_shape.fill);
+            circle_shape.fill.map_colors(adjust_color);
             adjust_color(&mut circle_shape.stroke.color);
         }
+        Shape::Ellipse(ellipse_shape) => {
+            ellipse_shape.fill.map_colors(adjust_color);
+            adjust_color(&mut ellipse_shape.stroke.color);
+        }
+        Shape::Arc(arc_shape) => {
+            arc_shape.fill.map_colors(adjust_color);
+            adjust_color(&mut arc_shape.stroke.color);
+        }
         Shape::LineSegment { stroke, .. } => {
             adjust_color(&mut stroke.color);
         }
         Shape::Path(path_shape) => {
-            adjust_color(&mut path_shape.fill);
+            path_shape.fill.map_colors(adjust_color);
             adjust_color(&mut path_shape.stroke.color);
         }
         Shape::Rect(rect_shape) => {
-            adjust_color(&mut rect_shape.fill);
+            rect_shape.fill.map_colors(adjust_color);
             adjust_color(&mut rect_shape.stroke.color);
         }
         Shape::Text(text_shape) => {
@@ -38,6 +48,9 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
                 }
             }
         }
+        Shape::Image(image_shape) => {
+            adjust_color(&mut image_shape.tint);
+        }
         Shape::Mesh(mesh) => {
             for v in &mut mesh.vertices {
                 adjust_color(&mut v.color);
@@ -51,8 +64,372 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
             adjust_color(&mut bezier.fill);
             adjust_color(&mut bezier.stroke.color);
         }
+        Shape::ComplexPath(path_shape) => {
+            path_shape.fill.map_colors(adjust_color);
+            adjust_color(&mut path_shape.stroke.color);
+        }
         Shape::Callback(_) => {
             // Can't tint user callback code
         }
     }
 }
+
+/// Transform the shape in-place, e.g. to rotate or zoom it.
+///
+/// Shapes that can't represent the result are converted into ones that can,
+/// e.g. a rotated [`RectShape`] becomes a [`PathShape`].
+///
+/// Stroke widths are scaled by [`Affine2::scale`].
+/// Text is moved, rotated and scaled, but not re-laid out, so it gets blurry when scaled up a lot.
+/// Paint callbacks get the bounding rectangle of their transformed rectangle.
+pub fn transform_shape(shape: &mut Shape, transform: Affine2) {
+    if transform.is_identity() {
+        return;
+    }
+
+    let scale = transform.scale();
+    let similarity = transform.is_similarity();
+
+    match shape {
+        Shape::Noop => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                transform_shape(shape, transform);
+            }
+        }
+        Shape::Circle(circle_shape) => {
+            if similarity {
+                let bounds = |circle: &CircleShape| {
+                    Rect::from_center_size(circle.center, Vec2::splat(2.0 * circle.radius))
+                };
+                let old_rect = bounds(circle_shape);
+                circle_shape.center = transform * circle_shape.center;
+                circle_shape.radius *= scale;
+                scale_stroke(&mut circle_shape.stroke, scale);
+                let new_rect = bounds(circle_shape);
+                transform_fill(&mut circle_shape.fill, transform, old_rect, new_rect);
+            } else {
+                let CircleShape {
+                    center,
+                    radius,
+                    fill,
+                    stroke,
+                    stroke_alignment,
+                } = *circle_shape;
+                let points = ellipse_points(center, Vec2::splat(radius), transform);
+                *shape = Shape::Path(PathShape {
+                    points,
+                    closed: true,
+                    fill,
+                    stroke,
+                    stroke_alignment,
+                });
+                transform_shape(shape, transform);
+            }
+        }
+        Shape::Ellipse(ellipse_shape) => {
+            if transform.is_axis_aligned() {
+                let old_rect =
+                    Rect::from_center_size(ellipse_shape.center, 2.0 * ellipse_shape.radius);
+                ellipse_shape.center = transform * ellipse_shape.center;
+                ellipse_shape.radius =
+                    ellipse_shape.radius * vec2(transform.x_axis.x.abs(), transform.y_axis.y.abs());
+                scale_stroke(&mut ellipse_shape.stroke, scale);
+                let new_rect =
+                    Rect::from_center_size(ellipse_shape.center, 2.0 * ellipse_shape.radius);
+                transform_fill(&mut ellipse_shape.fill, transform, old_rect, new_rect);
+            } else {
+                let EllipseShape {
+                    center,
+                    radius,
+                    fill,
+                    stroke,
+                    stroke_alignment,
+                } = *ellipse_shape;
+                *shape = Shape::Path(PathShape {
+                    points: ellipse_points(center, radius, transform),
+                    closed: true,
+                    fill,
+                    stroke,
+                    stroke_alignment,
+                });
+                transform_shape(shape, transform);
+            }
+        }
+        Shape::Arc(arc_shape) => {
+            if similarity {
+                let old_rect = arc_shape.bounding_rect();
+                let angle = transform.angle();
+                arc_shape.center = transform * arc_shape.center;
+                arc_shape.radius *= scale;
+                arc_shape.start_angle += angle;
+                arc_shape.end_angle += angle;
+                scale_stroke(&mut arc_shape.stroke, scale);
+                let new_rect = arc_shape.bounding_rect();
+                transform_fill(&mut arc_shape.fill, transform, old_rect, new_rect);
+            } else {
+                // A pie slice can be concave, so we need a complex path to fill it:
+                let ArcShape {
+                    center,
+                    radius,
+                    start_angle,
+                    end_angle,
+                    closed,
+                    fill,
+                    stroke,
+                    stroke_alignment,
+                } = *arc_shape;
+                let num_segments = arc_segment_count(
+                    transformed_radius(Vec2::splat(radius), transform),
+                    end_angle - start_angle,
+                    ARC_TOLERANCE,
+                );
+                let mut points: Vec<Pos2> = (0..=num_segments)
+                    .map(|i| {
+                        let angle = lerp(start_angle..=end_angle, i as f32 / num_segments as f32);
+                        center + radius * Vec2::angled(angle)
+                    })
+                    .collect();
+                if closed {
+                    points.push(center);
+                }
+
+                let mut path = PathBuilder::new().move_to(points[0]);
+                for &p in &points[1..] {
+                    path = path.line_to(p);
+                }
+                if closed {
+                    path = path.close();
+                }
+                let fill_shape = path.fill(fill, FillRule::NonZero);
+                *shape = if closed && stroke_alignment != StrokeAlignment::Center {
+                    // Complex paths have no stroke alignment, so the outline is a separate path:
+                    Shape::Vec(vec![
+                        fill_shape.into(),
+                        Shape::Path(PathShape {
+                            points,
+                            closed: true,
+                            fill: Fill::TRANSPARENT,
+                            stroke,
+                            stroke_alignment,
+                        }),
+                    ])
+                } else {
+                    fill_shape.with_stroke(stroke).into()
+                };
+                transform_shape(shape, transform);
+            }
+        }
+        Shape::LineSegment { points, stroke } => {
+            for p in points {
+                *p = transform * *p;
+            }
+            scale_stroke(stroke, scale);
+        }
+        Shape::Path(path_shape) => {
+            let old_rect = Rect::from_points(&path_shape.points);
+            for p in &mut path_shape.points {
+                *p = transform * *p;
+            }
+            scale_stroke(&mut path_shape.stroke, scale);
+            let new_rect = Rect::from_points(&path_shape.points);
+            transform_fill(&mut path_shape.fill, transform, old_rect, new_rect);
+        }
+        Shape::Rect(rect_shape) => {
+            if transform.is_axis_aligned() {
+                let old_rect = rect_shape.rect;
+                rect_shape.rect = transform.transform_rect_bounds(rect_shape.rect);
+                transform_fill(&mut rect_shape.fill, transform, old_rect, rect_shape.rect);
+                rect_shape.rounding = Rounding {
+                    nw: rect_shape.rounding.nw * scale,
+                    ne: rect_shape.rounding.ne * scale,
+                    sw: rect_shape.rounding.sw * scale,
+                    se: rect_shape.rounding.se * scale,
+                };
+                scale_stroke(&mut rect_shape.stroke, scale);
+            } else {
+                let RectShape {
+                    rect,
+                    rounding,
+                    fill,
+                    stroke,
+                    stroke_alignment,
+                } = *rect_shape;
+                let mut points = vec![];
+                crate::tessellator::path::rounded_rectangle(&mut points, rect, rounding);
+                *shape = Shape::Path(PathShape {
+                    points,
+                    closed: true,
+                    fill,
+                    stroke,
+                    stroke_alignment,
+                });
+                transform_shape(shape, transform);
+            }
+        }
+        Shape::Text(text_shape) => {
+            text_shape.pos = transform * text_shape.pos;
+            text_shape.angle += transform.angle();
+            scale_stroke(&mut text_shape.underline, scale);
+
+            if scale != 1.0 && !text_shape.galley.is_empty() {
+                let scale_pos = |pos: Pos2| (scale * pos.to_vec2()).to_pos2();
+                let scale_rect =
+                    |rect: Rect| Rect::from_min_max(scale_pos(rect.min), scale_pos(rect.max));
+                let galley = std::sync::Arc::make_mut(&mut text_shape.galley);
+                galley.rect = scale_rect(galley.rect);
+                galley.mesh_bounds = scale_rect(galley.mesh_bounds);
+                for row in &mut galley.rows {
+                    row.rect = scale_rect(row.rect);
+                    row.visuals.mesh_bounds = scale_rect(row.visuals.mesh_bounds);
+                    for vertex in &mut row.visuals.mesh.vertices {
+                        vertex.pos = scale_pos(vertex.pos);
+                    }
+                }
+            }
+        }
+        Shape::Image(image_shape) => {
+            if similarity {
+                let size = scale * image_shape.rect.size();
+                let pivot = transform * image_shape.pivot();
+                image_shape.rect =
+                    Rect::from_min_size(pivot - image_shape.rotation_origin * size, size);
+                image_shape.angle += transform.angle();
+                if let Some(nine_slice) = &mut image_shape.nine_slice {
+                    nine_slice.border_min *= scale;
+                    nine_slice.border_max *= scale;
+                }
+            } else {
+                *shape = Shape::Mesh(image_shape.to_mesh());
+                transform_shape(shape, transform);
+            }
+        }
+        Shape::Mesh(mesh) => {
+            for v in &mut mesh.vertices {
+                v.pos = transform * v.pos;
+            }
+        }
+        Shape::QuadraticBezier(bezier_shape) => {
+            for p in &mut bezier_shape.points {
+                *p = transform * *p;
+            }
+            scale_stroke(&mut bezier_shape.stroke, scale);
+        }
+        Shape::CubicBezier(bezier_shape) => {
+            for p in &mut bezier_shape.points {
+                *p = transform * *p;
+            }
+            scale_stroke(&mut bezier_shape.stroke, scale);
+        }
+        Shape::ComplexPath(path_shape) => {
+            let old_rect = path_shape.bounding_rect(ARC_TOLERANCE);
+            let angle = transform.angle();
+            for segment in &mut path_shape.segments {
+                segment.for_each_point_mut(|p| *p = transform * *p);
+                // Exact for similarities, an approximation otherwise:
+                if let PathSegment::ArcTo {
+                    radius, x_rotation, ..
+                } = segment
+                {
+                    *radius *= scale;
+                    *x_rotation += angle;
+                }
+            }
+            scale_stroke(&mut path_shape.stroke, scale);
+            let new_rect = path_shape.bounding_rect(ARC_TOLERANCE);
+            transform_fill(&mut path_shape.fill, transform, old_rect, new_rect);
+        }
+        Shape::Callback(callback) => {
+            callback.rect = transform.transform_rect_bounds(callback.rect);
+        }
+    }
+}
+
+fn scale_stroke(stroke: &mut Stroke, scale: f32) {
+    stroke.width *= scale;
+    match &mut stroke.style {
+        StrokeStyle::Solid => {}
+        StrokeStyle::Dashed {
+            length,
+            gap,
+            offset,
+        } => {
+            *length *= scale;
+            *gap *= scale;
+            *offset *= scale;
+        }
+        StrokeStyle::Dotted { spacing, offset } => {
+            *spacing *= scale;
+            *offset *= scale;
+        }
+    }
+}
+
+/// Move the positions of a gradient fill along with its shape.
+///
+/// Gradient positions are relative to the bounding rectangle of the shape,
+/// which `transform` moves from `old_rect` to `new_rect`.
+/// This is exact for similarities, and an approximation otherwise.
+fn transform_fill(fill: &mut Fill, transform: Affine2, old_rect: Rect, new_rect: Rect) {
+    if let Fill::Gradient(gradient) = fill {
+        let transform_pos = |relative: Pos2| {
+            let pos = transform * old_rect.lerp_inside(relative.to_vec2());
+            let size = new_rect.size();
+            pos2(
+                if size.x > 0.0 {
+                    (pos.x - new_rect.min.x) / size.x
+                } else {
+                    0.0
+                },
+                if size.y > 0.0 {
+                    (pos.y - new_rect.min.y) / size.y
+                } else {
+                    0.0
+                },
+            )
+        };
+
+        match &mut gradient.kind {
+            GradientKind::Linear { start, end } => {
+                *start = transform_pos(*start);
+                *end = transform_pos(*end);
+            }
+            GradientKind::Radial { center, radius } => {
+                let old_center = *center;
+                *center = transform_pos(old_center);
+                // The radius along each axis, averaged:
+                let radius_x = (transform_pos(old_center + vec2(*radius, 0.0)) - *center).length();
+                let radius_y = (transform_pos(old_center + vec2(0.0, *radius)) - *center).length();
+                *radius = 0.5 * (radius_x + radius_y);
+            }
+        }
+    }
+}
+
+/// How far the points of [`ellipse_points`] and of transformed arcs may be from the true curve,
+/// after the transform.
+const ARC_TOLERANCE: f32 = 0.1;
+
+/// Points around an ellipse, for when it can't stay an [`EllipseShape`].
+///
+/// There are enough points for the ellipse to look smooth after `transform`.
+fn ellipse_points(center: Pos2, radius: Vec2, transform: Affine2) -> Vec<Pos2> {
+    let num_points = arc_segment_count(
+        transformed_radius(radius, transform),
+        std::f32::consts::TAU,
+        ARC_TOLERANCE,
+    );
+    (0..num_points)
+        .map(|i| {
+            let angle = i as f32 / num_points as f32 * std::f32::consts::TAU;
+            center + radius * Vec2::angled(angle)
+        })
+        .collect()
+}
+
+/// The largest radius of an ellipse after `transform`, roughly.
+fn transformed_radius(radius: Vec2, transform: Affine2) -> f32 {
+    let x = transform.transform_vec(vec2(radius.x, 0.0)).length();
+    let y = transform.transform_vec(vec2(0.0, radius.y)).length();
+    x.max(y)
+}
@@ -157,6 +157,25 @@ impl AllocInfo {
     }
 }
 
+/// Statistics about the font atlas, see [`crate::TextureAtlas::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FontAtlasStats {
+    /// Size of the font texture, in texels.
+    pub size: [usize; 2],
+
+    /// How full the atlas is, in the range 0-1.
+    ///
+    /// When this gets high, the fonts that haven't been used recently are evicted,
+    /// and if that isn't enough the whole atlas is recreated.
+    pub fill_ratio: f32,
+
+    /// How many times the glyphs of a font (at one size) were evicted to make room.
+    pub num_evictions: usize,
+
+    /// How many times the whole atlas was thrown away and recreated.
+    pub num_resets: usize,
+}
+
 /// Collected allocation statistics for shapes and meshes.
 #[derive(Clone, Copy, Default)]
 pub struct PaintStats {
@@ -174,6 +193,8 @@ pub struct PaintStats {
     pub clipped_primitives: AllocInfo,
     pub vertices: AllocInfo,
     pub indices: AllocInfo,
+
+    pub font_atlas: FontAtlasStats,
 }
 
 impl PaintStats {
@@ -201,13 +222,19 @@ impl PaintStats {
             }
             Shape::Noop
             | Shape::Circle { .. }
+            | Shape::Ellipse(_)
+            | Shape::Arc(_)
             | Shape::LineSegment { .. }
             | Shape::Rect { .. }
+            | Shape::Image(_)
             | Shape::CubicBezier(_)
             | Shape::QuadraticBezier(_) => {}
             Shape::Path(path_shape) => {
                 self.shape_path += AllocInfo::from_slice(&path_shape.points);
             }
+            Shape::ComplexPath(path_shape) => {
+                self.shape_path += AllocInfo::from_slice(&path_shape.segments);
+            }
             Shape::Text(text_shape) => {
                 self.shape_text += AllocInfo::from_galley(&text_shape.galley);
 
@@ -238,6 +265,11 @@ impl PaintStats {
         }
         self
     }
+
+    pub fn with_font_atlas(mut self, font_atlas: FontAtlasStats) -> Self {
+        self.font_atlas = font_atlas;
+        self
+    }
 }
 
 fn megabytes(size: usize) -> String {
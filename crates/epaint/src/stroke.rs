@@ -10,6 +10,10 @@ use super::*;
 pub struct Stroke {
     pub width: f32,
     pub color: Color32,
+
+    /// Solid, dashed or dotted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: StrokeStyle,
 }
 
 impl Stroke {
@@ -17,6 +21,7 @@ impl Stroke {
     pub const NONE: Stroke = Stroke {
         width: 0.0,
         color: Color32::TRANSPARENT,
+        style: StrokeStyle::Solid,
     };
 
     #[deprecated = "Use Stroke::NONE instead"]
@@ -30,9 +35,50 @@ impl Stroke {
         Self {
             width: width.into(),
             color: color.into(),
+            style: StrokeStyle::Solid,
         }
     }
 
+    /// Dashes of `length` points, separated by gaps of `gap` points.
+    ///
+    /// ```
+    /// # use epaint::*;
+    /// let focus_ring = Stroke::new(1.0, Color32::WHITE).dashed(4.0, 2.0);
+    /// ```
+    #[inline]
+    pub fn dashed(mut self, length: f32, gap: f32) -> Self {
+        self.style = StrokeStyle::Dashed {
+            length,
+            gap,
+            offset: 0.0,
+        };
+        self
+    }
+
+    /// Round dots with a diameter of the stroke width, `spacing` points apart (center to center).
+    #[inline]
+    pub fn dotted(mut self, spacing: f32) -> Self {
+        self.style = StrokeStyle::Dotted {
+            spacing,
+            offset: 0.0,
+        };
+        self
+    }
+
+    /// How far into the dash pattern the stroke starts.
+    ///
+    /// Animate this to get "marching ants". Has no effect on solid strokes.
+    #[inline]
+    pub fn with_dash_offset(mut self, dash_offset: f32) -> Self {
+        match &mut self.style {
+            StrokeStyle::Solid => {}
+            StrokeStyle::Dashed { offset, .. } | StrokeStyle::Dotted { offset, .. } => {
+                *offset = dash_offset;
+            }
+        }
+        self
+    }
+
     /// True if width is zero or color is transparent
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -53,8 +99,83 @@ where
 impl std::hash::Hash for Stroke {
     #[inline(always)]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let Self { width, color } = *self;
+        let Self {
+            width,
+            color,
+            style,
+        } = *self;
         crate::f32_hash(state, width);
         color.hash(state);
+        style.hash(state);
+    }
+}
+
+/// Whether a [`Stroke`] is a solid line, or broken up into dashes or dots.
+///
+/// The pattern follows the path being stroked, including around corners.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum StrokeStyle {
+    #[default]
+    Solid,
+
+    /// Dashes of `length` points separated by gaps of `gap` points.
+    ///
+    /// The pattern is started `offset` points in.
+    Dashed { length: f32, gap: f32, offset: f32 },
+
+    /// Round dots `spacing` points apart (center to center).
+    ///
+    /// The pattern is started `offset` points in.
+    Dotted { spacing: f32, offset: f32 },
+}
+
+impl std::hash::Hash for StrokeStyle {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            Self::Solid => {}
+            Self::Dashed {
+                length,
+                gap,
+                offset,
+            } => {
+                crate::f32_hash(state, length);
+                crate::f32_hash(state, gap);
+                crate::f32_hash(state, offset);
+            }
+            Self::Dotted { spacing, offset } => {
+                crate::f32_hash(state, spacing);
+                crate::f32_hash(state, offset);
+            }
+        }
+    }
+}
+
+/// Where the stroke of a closed shape (like [`crate::RectShape`]) is painted, relative to its outline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum StrokeAlignment {
+    /// Half inside and half outside the outline.
+    #[default]
+    Center,
+
+    /// Entirely inside the outline, so that the shape never grows because of its stroke.
+    Inside,
+
+    /// Entirely outside the outline, so that the stroke never covers the fill.
+    Outside,
+}
+
+impl StrokeAlignment {
+    /// How far outside the outline a stroke of the given width reaches.
+    #[inline]
+    pub fn outer_width(self, stroke_width: f32) -> f32 {
+        match self {
+            Self::Center => stroke_width / 2.0,
+            Self::Inside => 0.0,
+            Self::Outside => stroke_width,
+        }
     }
 }
@@ -494,11 +494,40 @@ impl Path {
     pub fn fill(&mut self, feathering: f32, color: Color32, out: &mut Mesh) {
         fill_closed_path(feathering, &mut self.0, color, out);
     }
+
+    /// Move each point of a closed path `distance` points outwards along its normal
+    /// (or inwards, if `distance` is negative).
+    pub fn offset_closed(&mut self, distance: f32) {
+        let distance = if cw_signed_area(&self.0) < 0.0 {
+            -distance
+        } else {
+            distance
+        };
+        for point in &mut self.0 {
+            point.pos += distance * point.normal;
+        }
+    }
+
+    /// Like [`Self::fill`], but with a gradient.
+    ///
+    /// `rect` is the bounding rectangle of the shape, which the gradient is relative to.
+    pub fn fill_gradient(
+        &mut self,
+        feathering: f32,
+        gradient: &Gradient,
+        rect: Rect,
+        out: &mut Mesh,
+    ) {
+        let (first_vertex, first_index) = (out.vertices.len(), out.indices.len());
+        fill_closed_path(feathering, &mut self.0, Color32::WHITE, out);
+        gradient.paint_mesh(rect, out, first_vertex, first_index);
+    }
 }
 
 pub mod path {
     //! Helpers for constructing paths
     use crate::shape::Rounding;
+    use crate::StrokeAlignment;
     use emath::*;
 
     /// overwrites existing points
@@ -582,6 +611,42 @@ pub mod path {
         let max_cr = half_width.min(half_height);
         rounding.at_most(max_cr).at_least(0.0)
     }
+
+    /// The rectangle and rounding to stroke along, so that a stroke of the given width
+    /// ends up inside, centered on, or outside the edge of `rect`.
+    ///
+    /// Rounded corners keep their centers, so the stroke follows the rounding,
+    /// while sharp corners stay sharp.
+    pub fn aligned_stroke_rect(
+        rect: Rect,
+        rounding: Rounding,
+        stroke_width: f32,
+        alignment: StrokeAlignment,
+    ) -> (Rect, Rounding) {
+        let rounding = clamp_radius(rounding, rect);
+        let offset = match alignment {
+            StrokeAlignment::Center => return (rect, rounding),
+            StrokeAlignment::Inside => {
+                // Don't shrink past the center:
+                -(0.5 * stroke_width).min(0.5 * rect.width().min(rect.height()))
+            }
+            StrokeAlignment::Outside => 0.5 * stroke_width,
+        };
+        let adjust = |r: f32| {
+            if r > 0.0 {
+                (r + offset).at_least(0.0)
+            } else {
+                0.0
+            }
+        };
+        let rounding = Rounding {
+            nw: adjust(rounding.nw),
+            ne: adjust(rounding.ne),
+            sw: adjust(rounding.sw),
+            se: adjust(rounding.se),
+        };
+        (rect.expand(offset), rounding)
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -730,6 +795,133 @@ fn fill_closed_path(feathering: f32, path: &mut [PathPoint], color: Color32, out
     }
 }
 
+/// Tessellate the given path as a dashed or dotted stroke,
+/// by cutting it into pieces that are stroked (or filled) one by one.
+fn stroke_dashed_path(
+    feathering: f32,
+    path: &[PathPoint],
+    path_type: PathType,
+    stroke: Stroke,
+    out: &mut Mesh,
+) {
+    let solid = Stroke {
+        style: StrokeStyle::Solid,
+        ..stroke
+    };
+    let (dash_length, period, offset) = match stroke.style {
+        StrokeStyle::Solid => (0.0, 0.0, 0.0),
+        StrokeStyle::Dashed {
+            length,
+            gap,
+            offset,
+        } => (length, length + gap, offset),
+        StrokeStyle::Dotted { spacing, offset } => (0.0, spacing, offset),
+    };
+    let dotted = matches!(stroke.style, StrokeStyle::Dotted { .. });
+    if !(period > 0.0 && period.is_finite() && dash_length < period)
+        || (!dotted && dash_length <= 0.0)
+    {
+        // Not a valid pattern
+        stroke_path(feathering, path, path_type, solid, out);
+        return;
+    }
+
+    let mut dot = Path::default();
+    let mut add_dot = |pos: Pos2, out: &mut Mesh| {
+        dot.clear();
+        dot.add_circle(pos, stroke.width / 2.0);
+        dot.fill(feathering, stroke.color, out);
+    };
+
+    let num_segments = match path_type {
+        PathType::Open => path.len() - 1,
+        PathType::Closed => path.len(),
+    };
+
+    // How far we are into the current period of the pattern:
+    let mut phase = offset.rem_euclid(period);
+    let mut dash: Vec<PathPoint> = vec![];
+    if phase < dash_length {
+        let total_length: f32 = (0..num_segments)
+            .map(|i| path[i].pos.distance(path[(i + 1) % path.len()].pos))
+            .sum();
+        let end_phase = (offset + total_length).rem_euclid(period);
+        let wraps_around =
+            path_type == PathType::Closed && 0.0 < end_phase && end_phase < dash_length;
+        if wraps_around {
+            // Use the corner normal so it meets the last dash seamlessly.
+            dash.push(path[0].clone());
+        } else {
+            // The first points may coincide, so use the first segment that has a direction:
+            let normal = (0..num_segments)
+                .map(|i| path[(i + 1) % path.len()].pos - path[i].pos)
+                .find(|dir| *dir != Vec2::ZERO)
+                .map_or(path[0].normal, |dir| dir.normalized().rot90());
+            dash.push(PathPoint {
+                pos: path[0].pos,
+                normal,
+            });
+        }
+    } else if dotted && phase == 0.0 {
+        add_dot(path[0].pos, out);
+    }
+    for i in 0..num_segments {
+        let (a, b) = (&path[i], &path[(i + 1) % path.len()]);
+        let closing = path_type == PathType::Closed && i + 1 == num_segments;
+        let segment_length = a.pos.distance(b.pos);
+        if segment_length <= 0.0 {
+            continue;
+        }
+        let dir = (b.pos - a.pos) / segment_length;
+        let normal = dir.rot90();
+
+        let mut s = 0.0; // How far along the segment we are
+        loop {
+            let in_dash = phase < dash_length;
+            let to_next = if in_dash {
+                dash_length - phase
+            } else {
+                period - phase
+            };
+            let remaining = segment_length - s;
+            if remaining < to_next || (closing && !in_dash && remaining == to_next) {
+                // (The start of a closed path already got its dot.)
+                // The segment ends before the next change in the pattern.
+                phase += remaining;
+                if in_dash {
+                    if dash.last().map_or(false, |p| p.pos == b.pos) {
+                        dash.pop(); // The dash started right at the corner
+                    }
+                    dash.push(b.clone());
+                }
+                break;
+            }
+
+            s += to_next;
+            let pos = a.pos + s * dir;
+            if in_dash {
+                // End of dash:
+                phase = dash_length;
+                dash.push(PathPoint { pos, normal });
+                stroke_path(feathering, &dash, PathType::Open, solid, out);
+                dash.clear();
+            } else {
+                // Start of a new period:
+                phase = 0.0;
+                if dotted {
+                    add_dot(pos, out);
+                } else {
+                    dash.push(PathPoint { pos, normal });
+                }
+            }
+        }
+    }
+
+    if dash.len() >= 2 {
+        stroke_path(feathering, &dash, PathType::Open, solid, out);
+    }
+}
+
 /// Tessellate the given path as a stroke with thickness.
 fn stroke_path(
     feathering: f32,
@@ -744,6 +936,11 @@ fn stroke_path(
         return;
     }
 
+    if stroke.style != StrokeStyle::Solid {
+        stroke_dashed_path(feathering, path, path_type, stroke, out);
+        return;
+    }
+
     let idx = out.vertices.len() as u32;
 
     if feathering > 0.0 {
@@ -1104,6 +1301,12 @@ impl Tessellator {
             Shape::Circle(circle) => {
                 self.tessellate_circle(circle, out);
             }
+            Shape::Ellipse(ellipse) => {
+                self.tessellate_ellipse(ellipse, out);
+            }
+            Shape::Arc(arc) => {
+                self.tessellate_arc(arc, out);
+            }
             Shape::Mesh(mesh) => {
                 if !mesh.is_valid() {
                     crate::epaint_assert!(false, "Invalid Mesh in Shape::Mesh");
@@ -1117,6 +1320,9 @@ impl Tessellator {
                 }
                 out.append(mesh);
             }
+            Shape::Image(image_shape) => {
+                self.tessellate_image(&image_shape, out);
+            }
             Shape::LineSegment { points, stroke } => self.tessellate_line(points, stroke, out),
             Shape::Path(path_shape) => {
                 self.tessellate_path(&path_shape, out);
@@ -1138,6 +1344,7 @@ impl Tessellator {
                 self.tessellate_quadratic_bezier(quadratic_shape, out);
             }
             Shape::CubicBezier(cubic_shape) => self.tessellate_cubic_bezier(cubic_shape, out),
+            Shape::ComplexPath(path_shape) => self.tessellate_complex_path(&path_shape, out),
             Shape::Callback(_) => {
                 panic!("Shape::Callback passed to Tessellator");
             }
@@ -1154,6 +1361,7 @@ impl Tessellator {
             radius,
             mut fill,
             stroke,
+            stroke_alignment,
         } = shape;
 
         if radius <= 0.0 {
@@ -1169,25 +1377,27 @@ impl Tessellator {
             return;
         }
 
-        if self.options.prerasterized_discs && fill != Color32::TRANSPARENT {
-            let radius_px = radius * self.pixels_per_point;
-            // strike the right balance between some circles becoming too blurry, and some too sharp.
-            let cutoff_radius = radius_px * 2.0_f32.powf(0.25);
-
-            // Find the right disc radius for a crisp edge:
-            // TODO(emilk): perhaps we can do something faster than this linear search.
-            for disc in &self.prepared_discs {
-                if cutoff_radius <= disc.r {
-                    let side = radius_px * disc.w / (self.pixels_per_point * disc.r);
-                    let rect = Rect::from_center_size(center, Vec2::splat(side));
-                    out.add_rect_with_uv(rect, disc.uv, fill);
-
-                    if stroke.is_empty() {
-                        return; // we are done
-                    } else {
-                        // we still need to do the stroke
-                        fill = Color32::TRANSPARENT; // don't fill again below
-                        break;
+        if let Fill::Solid(color) = fill {
+            if self.options.prerasterized_discs && color != Color32::TRANSPARENT {
+                let radius_px = radius * self.pixels_per_point;
+                // strike the right balance between some circles becoming too blurry, and some too sharp.
+                let cutoff_radius = radius_px * 2.0_f32.powf(0.25);
+
+                // Find the right disc radius for a crisp edge:
+                // TODO(emilk): perhaps we can do something faster than this linear search.
+                for disc in &self.prepared_discs {
+                    if cutoff_radius <= disc.r {
+                        let side = radius_px * disc.w / (self.pixels_per_point * disc.r);
+                        let rect = Rect::from_center_size(center, Vec2::splat(side));
+                        out.add_rect_with_uv(rect, disc.uv, color);
+
+                        if stroke.is_empty() {
+                            return; // we are done
+                        } else {
+                            // we still need to do the stroke
+                            fill = Fill::TRANSPARENT; // don't fill again below
+                            break;
+                        }
                     }
                 }
             }
@@ -1195,7 +1405,168 @@ impl Tessellator {
 
         self.scratchpad_path.clear();
         self.scratchpad_path.add_circle(center, radius);
-        self.scratchpad_path.fill(self.feathering, fill, out);
+        match &fill {
+            Fill::Solid(color) => self.scratchpad_path.fill(self.feathering, *color, out),
+            Fill::Gradient(gradient) => {
+                let rect = Rect::from_center_size(center, Vec2::splat(2.0 * radius));
+                self.scratchpad_path
+                    .fill_gradient(self.feathering, gradient, rect, out);
+            }
+        }
+        if stroke_alignment != StrokeAlignment::Center && !stroke.is_empty() {
+            let stroke_radius = match stroke_alignment {
+                StrokeAlignment::Center => radius,
+                StrokeAlignment::Inside => (radius - 0.5 * stroke.width).at_least(0.0),
+                StrokeAlignment::Outside => radius + 0.5 * stroke.width,
+            };
+            self.scratchpad_path.clear();
+            self.scratchpad_path.add_circle(center, stroke_radius);
+        }
+        self.scratchpad_path
+            .stroke_closed(self.feathering, stroke, out);
+    }
+
+    /// Tessellate a single [`EllipseShape`] into a [`Mesh`].
+    ///
+    /// * `shape`: the ellipse to tessellate.
+    /// * `out`: triangles are appended to this.
+    pub fn tessellate_ellipse(&mut self, shape: EllipseShape, out: &mut Mesh) {
+        let EllipseShape {
+            center,
+            radius,
+            fill,
+            stroke,
+            stroke_alignment,
+        } = shape;
+
+        if radius.x <= 0.0 || radius.y <= 0.0 {
+            return;
+        }
+
+        if self.options.coarse_tessellation_culling
+            && !shape.visual_bounding_rect().intersects(self.clip_rect)
+        {
+            return;
+        }
+
+        let num_segments = self.arc_segment_count(radius.max_elem(), std::f32::consts::TAU);
+        // Symmetric around both axes, so that we hit the extremes exactly:
+        let num_segments = (num_segments + 3) / 4 * 4;
+
+        self.scratchpad_points.clear();
+        self.scratchpad_points.extend((0..num_segments).map(|i| {
+            let angle = i as f32 / num_segments as f32 * std::f32::consts::TAU;
+            let (sin, cos) = angle.sin_cos();
+            center + vec2(radius.x * cos, radius.y * sin)
+        }));
+
+        self.scratchpad_path.clear();
+        self.scratchpad_path.add_line_loop(&self.scratchpad_points);
+        let rect = Rect::from_center_size(center, 2.0 * radius);
+        self.fill_and_stroke_closed_path(rect, &fill, stroke, stroke_alignment, out);
+    }
+
+    /// Tessellate a single [`ArcShape`] into a [`Mesh`].
+    ///
+    /// * `shape`: the arc or pie slice to tessellate.
+    /// * `out`: triangles are appended to this.
+    pub fn tessellate_arc(&mut self, shape: ArcShape, out: &mut Mesh) {
+        let ArcShape {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            closed,
+            fill,
+            stroke,
+            stroke_alignment,
+        } = shape;
+
+        if radius <= 0.0 || start_angle == end_angle {
+            return;
+        }
+
+        if self.options.coarse_tessellation_culling
+            && !shape.visual_bounding_rect().intersects(self.clip_rect)
+        {
+            return;
+        }
+
+        let sweep = (end_angle - start_angle).clamp(-std::f32::consts::TAU, std::f32::consts::TAU);
+        let full_circle = sweep.abs() == std::f32::consts::TAU;
+        let num_segments = self.arc_segment_count(radius, sweep.abs());
+
+        // Always go clockwise, so that pie slices are filled correctly:
+        let (first_angle, sweep) = if sweep < 0.0 {
+            (start_angle + sweep, -sweep)
+        } else {
+            (start_angle, sweep)
+        };
+
+        self.scratchpad_points.clear();
+        if closed && !full_circle {
+            self.scratchpad_points.push(center);
+        }
+        let num_points = if full_circle {
+            num_segments
+        } else {
+            num_segments + 1
+        };
+        self.scratchpad_points.extend((0..num_points).map(|i| {
+            let angle = first_angle + i as f32 / num_segments as f32 * sweep;
+            center + radius * Vec2::angled(angle)
+        }));
+
+        self.scratchpad_path.clear();
+        if closed || full_circle {
+            self.scratchpad_path.add_line_loop(&self.scratchpad_points);
+            let fill = if closed { fill } else { Fill::TRANSPARENT };
+            let rect = shape.bounding_rect();
+            self.fill_and_stroke_closed_path(rect, &fill, stroke, stroke_alignment, out);
+        } else {
+            crate::epaint_assert!(
+                fill.is_transparent(),
+                "You asked to fill an arc that is not closed. That is not supported."
+            );
+            self.scratchpad_path
+                .add_open_points(&self.scratchpad_points);
+            self.scratchpad_path
+                .stroke_open(self.feathering, stroke, out);
+        }
+    }
+
+    /// How many line segments to use for an arc of the given radius and sweep (in radians),
+    /// so that it looks smooth at the current `pixels_per_point`.
+    fn arc_segment_count(&self, radius: f32, sweep: f32) -> usize {
+        // The largest allowed distance between the arc and its segments.
+        // Feathering blurs the edge anyway, so we can be less precise when it is wide.
+        let tolerance = (0.25 / self.pixels_per_point).max(0.5 * self.feathering);
+        crate::complex_path::arc_segment_count(radius, sweep, tolerance)
+    }
+
+    /// Fill and stroke the closed outline in `scratchpad_path`.
+    ///
+    /// A gradient `fill` is relative to `rect`.
+    fn fill_and_stroke_closed_path(
+        &mut self,
+        rect: Rect,
+        fill: &Fill,
+        stroke: Stroke,
+        stroke_alignment: StrokeAlignment,
+        out: &mut Mesh,
+    ) {
+        match fill {
+            Fill::Solid(color) => self.scratchpad_path.fill(self.feathering, *color, out),
+            Fill::Gradient(gradient) => {
+                self.scratchpad_path
+                    .fill_gradient(self.feathering, gradient, rect, out);
+            }
+        }
+        match stroke_alignment {
+            StrokeAlignment::Center => {}
+            StrokeAlignment::Inside => self.scratchpad_path.offset_closed(-0.5 * stroke.width),
+            StrokeAlignment::Outside => self.scratchpad_path.offset_closed(0.5 * stroke.width),
+        }
         self.scratchpad_path
             .stroke_closed(self.feathering, stroke, out);
     }
@@ -1262,6 +1633,7 @@ impl Tessellator {
             closed,
             fill,
             stroke,
+            stroke_alignment,
         } = path_shape;
 
         self.scratchpad_path.clear();
@@ -1271,12 +1643,26 @@ impl Tessellator {
             self.scratchpad_path.add_open_points(points);
         }
 
-        if *fill != Color32::TRANSPARENT {
+        if !fill.is_transparent() {
             crate::epaint_assert!(
                 closed,
                 "You asked to fill a path that is not closed. That makes no sense."
             );
-            self.scratchpad_path.fill(self.feathering, *fill, out);
+            match fill {
+                Fill::Solid(color) => self.scratchpad_path.fill(self.feathering, *color, out),
+                Fill::Gradient(gradient) => {
+                    let rect = Rect::from_points(points);
+                    self.scratchpad_path
+                        .fill_gradient(self.feathering, gradient, rect, out);
+                }
+            }
+        }
+        if *closed {
+            match stroke_alignment {
+                StrokeAlignment::Center => {}
+                StrokeAlignment::Inside => self.scratchpad_path.offset_closed(-0.5 * stroke.width),
+                StrokeAlignment::Outside => self.scratchpad_path.offset_closed(0.5 * stroke.width),
+            }
         }
         let typ = if *closed {
             PathType::Closed
@@ -1297,6 +1683,7 @@ impl Tessellator {
             rounding,
             fill,
             stroke,
+            stroke_alignment,
         } = *rect;
 
         if self.options.coarse_tessellation_culling
@@ -1316,6 +1703,7 @@ impl Tessellator {
         if rect.width() < self.feathering {
             // Very thin - approximate by a vertical line-segment:
             let line = [rect.center_top(), rect.center_bottom()];
+            let fill = fill.color_at(rect, rect.center()); // too thin to show a gradient
             if fill != Color32::TRANSPARENT {
                 self.tessellate_line(line, Stroke::new(rect.width(), fill), out);
             }
@@ -1326,6 +1714,7 @@ impl Tessellator {
         } else if rect.height() < self.feathering {
             // Very thin - approximate by a horizontal line-segment:
             let line = [rect.left_center(), rect.right_center()];
+            let fill = fill.color_at(rect, rect.center()); // too thin to show a gradient
             if fill != Color32::TRANSPARENT {
                 self.tessellate_line(line, Stroke::new(rect.height(), fill), out);
             }
@@ -1338,11 +1727,40 @@ impl Tessellator {
             path.clear();
             path::rounded_rectangle(&mut self.scratchpad_points, rect, rounding);
             path.add_line_loop(&self.scratchpad_points);
-            path.fill(self.feathering, fill, out);
+            match &fill {
+                Fill::Solid(color) => path.fill(self.feathering, *color, out),
+                Fill::Gradient(gradient) => {
+                    path.fill_gradient(self.feathering, gradient, rect, out);
+                }
+            }
+            if stroke_alignment != StrokeAlignment::Center && !stroke.is_empty() {
+                let (stroke_rect, stroke_rounding) =
+                    path::aligned_stroke_rect(rect, rounding, stroke.width, stroke_alignment);
+                path.clear();
+                path::rounded_rectangle(&mut self.scratchpad_points, stroke_rect, stroke_rounding);
+                path.add_line_loop(&self.scratchpad_points);
+            }
             path.stroke_closed(self.feathering, stroke, out);
         }
     }
 
+    /// Tessellate a single [`ImageShape`] into a [`Mesh`].
+    /// * `image_shape`: the image to tessellate.
+    /// * `out`: triangles are appended to this.
+    pub fn tessellate_image(&mut self, image_shape: &ImageShape, out: &mut Mesh) {
+        if image_shape.tint == Color32::TRANSPARENT {
+            return;
+        }
+        if self.options.coarse_tessellation_culling
+            && !self
+                .clip_rect
+                .intersects(image_shape.visual_bounding_rect())
+        {
+            return;
+        }
+        out.append(image_shape.to_mesh());
+    }
+
     /// Tessellate a single [`TextShape`] into a [`Mesh`].
     /// * `text_shape`: the text to tessellate.
     /// * `out`: triangles are appended to this.
@@ -1420,6 +1838,8 @@ impl Tessellator {
                         if let Some(override_text_color) = override_text_color {
                             if row.visuals.glyph_vertex_range.contains(&i) {
                                 color = *override_text_color;
+                            } else if row.visuals.color_glyph_vertex_range.contains(&i) {
+                                color = Color32::from_white_alpha(override_text_color.a());
                             }
                         }
 
@@ -1503,6 +1923,56 @@ impl Tessellator {
         }
     }
 
+    /// Tessellate a single [`ComplexPathShape`] into a [`Mesh`].
+    ///
+    /// * `path_shape`: the shape to tessellate.
+    /// * `out`: triangles are appended to this.
+    pub fn tessellate_complex_path(&mut self, path_shape: &ComplexPathShape, out: &mut Mesh) {
+        let ComplexPathShape {
+            segments: _,
+            ref fill,
+            fill_rule,
+            stroke,
+        } = *path_shape;
+
+        if fill.is_transparent() && stroke.is_empty() {
+            return;
+        }
+
+        let subpaths = path_shape.flatten(self.options.bezier_tolerance);
+
+        if self.options.coarse_tessellation_culling {
+            let mut bounds = Rect::NOTHING;
+            for (points, _) in &subpaths {
+                for &p in points {
+                    bounds.extend_with(p);
+                }
+            }
+            if !bounds.expand(stroke.width).intersects(self.clip_rect) {
+                return;
+            }
+        }
+
+        let polygons: Vec<&[Pos2]> = subpaths
+            .iter()
+            .map(|(points, _)| points.as_slice())
+            .collect();
+        crate::complex_path::fill_polygons(&polygons, fill_rule, self.feathering, fill, out);
+
+        for (points, closed) in &subpaths {
+            self.scratchpad_path.clear();
+            if *closed {
+                self.scratchpad_path.add_line_loop(points);
+                self.scratchpad_path
+                    .stroke_closed(self.feathering, stroke, out);
+            } else {
+                self.scratchpad_path.add_open_points(points);
+                self.scratchpad_path
+                    .stroke_open(self.feathering, stroke, out);
+            }
+        }
+    }
+
     fn tessellate_bezier_complete(
         &mut self,
         points: &[Pos2],
@@ -1655,3 +2125,223 @@ fn test_tessellator() {
     );
     assert_eq!(primitives.len(), 2);
 }
+
+#[test]
+fn test_dashed_stroke() {
+    use crate::*;
+
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0));
+    let mut tessellator = Tessellator::new(1.0, options, [1, 1], vec![]);
+
+    // 400 points around, so 20 dashes without any crossing a corner:
+    let mut mesh = Mesh::default();
+    let stroke = Stroke::new(2.0, Color32::WHITE).dashed(10.0, 10.0);
+    tessellator.tessellate_rect(&RectShape::stroke(rect, 0.0, stroke), &mut mesh);
+    assert_eq!(mesh.vertices.len(), 20 * 4);
+
+    // Dots are small circles with 9 vertices each:
+    let mut mesh = Mesh::default();
+    let stroke = Stroke::new(2.0, Color32::WHITE).dotted(20.0);
+    tessellator.tessellate_rect(&RectShape::stroke(rect, 0.0, stroke), &mut mesh);
+    assert_eq!(mesh.vertices.len(), 20 * 9);
+
+    // Shifting the pattern makes three dashes go around corners,
+    // and splits the one at the start of the path in two:
+    let mut mesh = Mesh::default();
+    let stroke = Stroke::new(2.0, Color32::WHITE)
+        .dashed(10.0, 10.0)
+        .with_dash_offset(5.0);
+    tessellator.tessellate_rect(&RectShape::stroke(rect, 0.0, stroke), &mut mesh);
+    assert_eq!(mesh.vertices.len(), 18 * 4 + 3 * 6);
+
+    // A repeated first point doesn't pinch the start of the first dash:
+    let mut mesh = Mesh::default();
+    let stroke = Stroke::new(2.0, Color32::WHITE).dashed(10.0, 10.0);
+    let points = vec![pos2(0.0, 0.0), pos2(0.0, 0.0), pos2(100.0, 0.0)];
+    tessellator.tessellate_path(&PathShape::line(points, stroke), &mut mesh);
+    assert_eq!(mesh.vertices.len(), 5 * 4);
+    assert_eq!(mesh.vertices[0].pos.distance(mesh.vertices[1].pos), 2.0);
+}
+
+#[test]
+fn test_stroke_alignment() {
+    use crate::*;
+
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let rect = Rect::from_min_size(pos2(10.0, 10.0), vec2(100.0, 50.0));
+    let stroke = Stroke::new(4.0, Color32::WHITE);
+    let mut tessellator = Tessellator::new(1.0, options, [1, 1], vec![]);
+
+    for (alignment, expected_bounds) in [
+        (StrokeAlignment::Inside, rect),
+        (StrokeAlignment::Center, rect.expand(2.0)),
+        (StrokeAlignment::Outside, rect.expand(4.0)),
+    ] {
+        for rounding in [0.0, 8.0] {
+            let shape = RectShape::stroke(rect, rounding, stroke).with_stroke_alignment(alignment);
+            assert_eq!(shape.visual_bounding_rect(), expected_bounds);
+
+            let mut mesh = Mesh::default();
+            tessellator.tessellate_rect(&shape, &mut mesh);
+            let bounds = mesh.calc_bounds();
+            assert!(
+                (bounds.min - expected_bounds.min).length() < 1e-3
+                    && (bounds.max - expected_bounds.max).length() < 1e-3,
+                "{:?} with rounding {}: {:?}",
+                alignment,
+                rounding,
+                bounds
+            );
+        }
+    }
+}
+
+#[test]
+fn test_tessellate_arc_and_ellipse() {
+    use crate::*;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_rect_eq(a: Rect, b: Rect) {
+        assert!(
+            (a.min - b.min).length() < 1e-3 && (a.max - b.max).length() < 1e-3,
+            "{a:?} != {b:?}"
+        );
+    }
+
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let mut tessellator = Tessellator::new(1.0, options, [1, 1], vec![]);
+
+    let ellipse = EllipseShape::filled(pos2(50.0, 50.0), vec2(40.0, 20.0), Color32::WHITE);
+    let mut mesh = Mesh::default();
+    tessellator.tessellate_ellipse(ellipse, &mut mesh);
+    assert!(mesh.is_valid());
+    let bounds = mesh.calc_bounds();
+    let ellipse_rect = Rect::from_min_max(pos2(10.0, 30.0), pos2(90.0, 70.0));
+    assert_rect_eq(bounds, ellipse_rect);
+
+    // Gradients and aligned strokes, like for circles:
+    let ellipse = EllipseShape::new(
+        pos2(50.0, 50.0),
+        vec2(40.0, 20.0),
+        Gradient::horizontal(Color32::RED, Color32::BLUE),
+        (4.0, Color32::WHITE),
+    )
+    .with_stroke_alignment(StrokeAlignment::Inside);
+    assert_rect_eq(ellipse.visual_bounding_rect(), ellipse_rect);
+    let mut mesh = Mesh::default();
+    tessellator.tessellate_ellipse(ellipse, &mut mesh);
+    assert!(mesh.is_valid());
+    assert_rect_eq(mesh.calc_bounds(), ellipse_rect);
+    for color in [Color32::RED, Color32::BLUE] {
+        assert!(mesh.vertices.iter().any(|v| v.color == color));
+    }
+
+    // A pie slice of three quarters, going counter-clockwise:
+    let pie = ArcShape::pie(
+        pos2(0.0, 0.0),
+        10.0,
+        FRAC_PI_2..=-PI,
+        Color32::WHITE,
+        Stroke::NONE,
+    );
+    assert_rect_eq(
+        pie.visual_bounding_rect(),
+        Rect::from_min_max(pos2(-10.0, -10.0), pos2(10.0, 10.0)),
+    );
+    let outlined_pie = ArcShape {
+        stroke: Stroke::new(2.0, Color32::WHITE),
+        ..pie
+    };
+    assert_eq!(
+        outlined_pie
+            .with_stroke_alignment(StrokeAlignment::Inside)
+            .visual_bounding_rect(),
+        pie.bounding_rect()
+    );
+    let mut mesh = Mesh::default();
+    tessellator.tessellate_arc(pie, &mut mesh);
+    assert!(mesh.is_valid());
+    let area: f32 = mesh
+        .indices
+        .chunks(3)
+        .map(|t| {
+            let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[t[i] as usize].pos);
+            let (ab, ac) = (b - a, c - a);
+            0.5 * (ab.x * ac.y - ab.y * ac.x).abs()
+        })
+        .sum();
+    let expected_area = 0.75 * PI * 10.0 * 10.0;
+    assert!(
+        (area - expected_area).abs() < 0.05 * expected_area,
+        "{area}"
+    );
+
+    // A quarter arc, without the center:
+    let arc = ArcShape::stroke(pos2(0.0, 0.0), 10.0, 0.0..=FRAC_PI_2, (2.0, Color32::WHITE));
+    assert_rect_eq(
+        arc.visual_bounding_rect(),
+        Rect::from_min_max(pos2(-1.0, -1.0), pos2(11.0, 11.0)),
+    );
+
+    // More segments on a high-dpi screen:
+    let mut low_dpi_mesh = Mesh::default();
+    tessellator.tessellate_arc(arc, &mut low_dpi_mesh);
+    let mut tessellator = Tessellator::new(4.0, options, [1, 1], vec![]);
+    let mut high_dpi_mesh = Mesh::default();
+    tessellator.tessellate_arc(arc, &mut high_dpi_mesh);
+    assert!(high_dpi_mesh.vertices.len() > low_dpi_mesh.vertices.len());
+}
+
+#[test]
+fn test_tessellate_image() {
+    use crate::*;
+
+    let mut tessellator = Tessellator::new(1.0, Default::default(), [1, 1], vec![]);
+    let texture_id = TextureId::User(1);
+    let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+    let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 50.0));
+
+    // An 8 texel border on a 32x32 texture:
+    let nine_slice = NineSlice::from_border(uv, vec2(32.0, 32.0), 8.0);
+    let image = ImageShape::new(texture_id, rect, uv, Color32::WHITE).with_nine_slice(nine_slice);
+    let mut mesh = Mesh::default();
+    tessellator.tessellate_image(&image, &mut mesh);
+    assert_eq!(mesh.texture_id, texture_id);
+    assert_eq!(mesh.vertices.len(), 16);
+    assert_eq!(mesh.indices.len(), 9 * 6);
+    assert_eq!(mesh.vertices[5].pos, pos2(8.0, 8.0));
+    assert_eq!(mesh.vertices[5].uv, pos2(0.25, 0.25));
+    assert_eq!(mesh.vertices[10].pos, pos2(92.0, 42.0));
+    assert_eq!(mesh.vertices[10].uv, pos2(0.75, 0.75));
+
+    // The borders shrink when the image is smaller than them:
+    let small = ImageShape {
+        rect: Rect::from_min_size(pos2(0.0, 0.0), vec2(10.0, 10.0)),
+        ..image
+    };
+    let mesh = small.to_mesh();
+    assert_eq!(mesh.vertices[5].pos, pos2(5.0, 5.0));
+    assert_eq!(mesh.vertices[10].pos, pos2(5.0, 5.0));
+
+    // A quarter turn around the center:
+    let rotated = ImageShape::new(texture_id, rect, uv, Color32::WHITE)
+        .with_rotation(std::f32::consts::FRAC_PI_2, Vec2::splat(0.5));
+    let expected = Rect::from_center_size(rect.center(), vec2(50.0, 100.0));
+    for bounds in [
+        rotated.visual_bounding_rect(),
+        rotated.to_mesh().calc_bounds(),
+    ] {
+        assert!((bounds.min - expected.min).length() < 1e-3, "{bounds:?}");
+        assert!((bounds.max - expected.max).length() < 1e-3, "{bounds:?}");
+    }
+}
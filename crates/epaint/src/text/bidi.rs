@@ -0,0 +1,182 @@
+//! Bidirectional text, e.g. Hebrew or Arabic mixed with English.
+//!
+//! The embedding levels are resolved with the [Unicode Bidirectional Algorithm](https://unicode.org/reports/tr9/)
+//! of the [`unicode_bidi`] crate, and each row is then reordered visually.
+//!
+//! Glyphs stay in logical order in each [`Row`](super::Row), so cursors and selections work on the logical text.
+//! Only the positions of the glyphs are changed.
+
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo};
+
+use super::Glyph;
+
+/// Is this a strong right-to-left character, e.g. a Hebrew or Arabic letter?
+#[cfg(feature = "shaping")]
+pub(crate) fn is_rtl_char(c: char) -> bool {
+    matches!(bidi_class(c), BidiClass::R | BidiClass::AL)
+}
+
+/// Resolve the embedding levels of the glyphs of a paragraph, and store them in [`Glyph::bidi_level`].
+///
+/// Returns the paragraph embedding level, or `None` if the paragraph has no right-to-left characters,
+/// in which case all levels are left at zero.
+pub(crate) fn resolve_levels(glyphs: &mut [Glyph]) -> Option<u8> {
+    use BidiClass::{AL, AN, FSI, R, RLE, RLI, RLO};
+
+    let may_have_rtl = glyphs
+        .iter()
+        .any(|glyph| matches!(bidi_class(glyph.chr), R | AL | AN | RLE | RLO | RLI | FSI));
+    if !may_have_rtl {
+        return None;
+    }
+
+    let text: String = glyphs.iter().map(|glyph| glyph.chr).collect();
+    let info = BidiInfo::new(&text, None);
+    if !info.has_rtl() {
+        return None;
+    }
+    // `levels` has one entry per byte:
+    for (glyph, (byte_index, _)) in glyphs.iter_mut().zip(text.char_indices()) {
+        glyph.bidi_level = info.levels[byte_index].number();
+    }
+
+    Some(info.paragraphs.first()?.level.number())
+}
+
+/// Rule L1: whitespace at the end of a row gets the paragraph level,
+/// so that it ends up at the end of the row in the paragraph direction.
+pub(crate) fn reset_trailing_whitespace(glyphs: &mut [Glyph], base_level: u8) {
+    use BidiClass::{B, BN, FSI, LRE, LRI, LRO, PDF, PDI, RLE, RLI, RLO, S, WS};
+
+    for glyph in glyphs.iter_mut().rev() {
+        let is_whitespace = matches!(
+            bidi_class(glyph.chr),
+            WS | S | B | BN | FSI | LRI | RLI | PDI | LRE | RLE | LRO | RLO | PDF
+        );
+        if !is_whitespace && !super::font::invisible_char(glyph.chr) {
+            break;
+        }
+        glyph.bidi_level = base_level;
+    }
+}
+
+/// Rule L2: reverse each sequence of glyphs at or above each odd level, from the highest level down.
+///
+/// The glyphs stay in logical order; their x positions are mirrored within the span of each reversed sequence,
+/// which also keeps any extra spacing from justification.
+pub(crate) fn reorder_row(glyphs: &mut [Glyph]) {
+    let Some(max_level) = glyphs.iter().map(|glyph| glyph.bidi_level).max() else {
+        return;
+    };
+    let Some(min_odd_level) = glyphs
+        .iter()
+        .map(|glyph| glyph.bidi_level)
+        .filter(|level| level % 2 == 1)
+        .min()
+    else {
+        return;
+    };
+
+    for level in (min_odd_level..=max_level).rev() {
+        let mut i = 0;
+        while i < glyphs.len() {
+            if glyphs[i].bidi_level >= level {
+                let start = i;
+                while i < glyphs.len() && glyphs[i].bidi_level >= level {
+                    i += 1;
+                }
+                mirror(&mut glyphs[start..i]);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+fn mirror(glyphs: &mut [Glyph]) {
+    let min_x = glyphs
+        .iter()
+        .map(|glyph| glyph.pos.x)
+        .fold(f32::INFINITY, f32::min);
+    let max_x = glyphs
+        .iter()
+        .map(|glyph| glyph.max_x())
+        .fold(f32::NEG_INFINITY, f32::max);
+    for glyph in glyphs {
+        glyph.pos.x = min_x + max_x - glyph.max_x();
+    }
+}
+
+/// Rule L4: brackets in right-to-left text are shown mirrored, so that `(` still opens.
+pub(crate) fn mirrored_char(c: char) -> Option<char> {
+    Some(match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        '‹' => '›',
+        '›' => '‹',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use emath::{pos2, vec2};
+
+    fn glyphs(text: &str) -> Vec<Glyph> {
+        text.chars()
+            .enumerate()
+            .map(|(i, chr)| Glyph {
+                chr,
+                pos: pos2(i as f32, 0.0),
+                ascent: 0.0,
+                size: vec2(1.0, 1.0),
+                uv_rect: Default::default(),
+                section_index: 0,
+                bidi_level: 0,
+            })
+            .collect()
+    }
+
+    /// The text in visual order, left to right.
+    fn visual(glyphs: &[Glyph]) -> String {
+        let mut glyphs = glyphs.to_vec();
+        glyphs.sort_by(|a, b| a.pos.x.total_cmp(&b.pos.x));
+        glyphs.iter().map(|glyph| glyph.chr).collect()
+    }
+
+    fn layout(text: &str) -> String {
+        let mut glyphs = glyphs(text);
+        if let Some(base) = resolve_levels(&mut glyphs) {
+            reset_trailing_whitespace(&mut glyphs, base);
+        }
+        reorder_row(&mut glyphs);
+        visual(&glyphs)
+    }
+
+    #[test]
+    fn test_bidi_reorder() {
+        assert_eq!(layout("hello world"), "hello world");
+        assert_eq!(layout("שלום"), "םולש");
+        assert_eq!(layout("abc שלום def"), "abc םולש def");
+        assert_eq!(layout("שלום abc עולם"), "םלוע abc םולש");
+        // Numbers stay left-to-right, even in right-to-left text:
+        assert_eq!(layout("שלום 1,234 עולם"), "םלוע 1,234 םולש");
+        // Trailing whitespace stays at the end in the paragraph direction:
+        assert_eq!(layout("abc שלום "), "abc םולש ");
+        assert_eq!(layout("שלום "), " םולש");
+        // Explicit overrides are heeded:
+        assert_eq!(
+            layout("abc \u{202E}def\u{202C} ghi"),
+            "abc \u{202E}\u{202C}fed ghi"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Color glyphs, e.g. emoji.
+//!
+//! Supports the `COLR` table (layers of outlines, each with its own color),
+//! and the `CBDT` and `sbix` tables (PNG images).
+
+use ab_glyph::{Font as _, ScaleFont as _};
+use emath::{vec2, Vec2};
+
+use super::FontData;
+use crate::Color32;
+
+/// A rasterized color glyph.
+pub(crate) struct ColorGlyph {
+    /// width, height in pixels.
+    pub size: [usize; 2],
+
+    /// Premultiplied `sRGBA`, row by row, top to bottom.
+    pub pixels: Vec<Color32>,
+
+    /// Where the top left corner goes, relative to the pen position on the baseline.
+    /// Positive y is down.
+    ///
+    /// Unit: pixels.
+    pub offset: Vec2,
+}
+
+/// Rasterize a color glyph, at a scale where the font height is `scale_in_pixels`.
+///
+/// Returns `None` if this is not a color glyph.
+pub(crate) fn rasterize(
+    font_data: &FontData,
+    font: &ab_glyph::FontArc,
+    glyph_id: ab_glyph::GlyphId,
+    scale_in_pixels: f32,
+) -> Option<ColorGlyph> {
+    // Parsing the face is cheap, and we only do it once per glyph.
+    let face = ttf_parser::Face::parse(&font_data.font, font_data.index).ok()?;
+    let id = ttf_parser::GlyphId(glyph_id.0);
+
+    if face.is_color_glyph(id) {
+        rasterize_layers(&face, font, id, scale_in_pixels)
+    } else {
+        let scaled = font.as_scaled(scale_in_pixels);
+        let pixels_per_em = scaled.h_scale_factor() * face.units_per_em() as f32;
+        rasterize_bitmap(&face, id, pixels_per_em)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// COLR
+
+/// Collects the layers of a `COLR` glyph.
+///
+/// Only solid colors are supported.
+/// Gradients use the color of their first stop, and transforms and clips are ignored.
+struct Layers {
+    outline: Option<ttf_parser::GlyphId>,
+    layers: Vec<(ttf_parser::GlyphId, Color32)>,
+}
+
+impl<'a> ttf_parser::colr::Painter<'a> for Layers {
+    fn outline_glyph(&mut self, glyph_id: ttf_parser::GlyphId) {
+        self.outline = Some(glyph_id);
+    }
+
+    fn paint(&mut self, paint: ttf_parser::colr::Paint<'a>) {
+        use ttf_parser::colr::Paint;
+        let color = match paint {
+            Paint::Solid(color) => Some(color),
+            Paint::LinearGradient(gradient) => gradient.stops(PALETTE, &[]).next().map(|s| s.color),
+            Paint::RadialGradient(gradient) => gradient.stops(PALETTE, &[]).next().map(|s| s.color),
+            Paint::SweepGradient(gradient) => gradient.stops(PALETTE, &[]).next().map(|s| s.color),
+        };
+        if let (Some(outline), Some(color)) = (self.outline, color) {
+            let color =
+                Color32::from_rgba_unmultiplied(color.red, color.green, color.blue, color.alpha);
+            self.layers.push((outline, color));
+        }
+    }
+
+    fn push_clip(&mut self) {}
+
+    fn push_clip_box(&mut self, _clipbox: ttf_parser::colr::ClipBox) {}
+
+    fn pop_clip(&mut self) {}
+
+    fn push_layer(&mut self, _mode: ttf_parser::colr::CompositeMode) {}
+
+    fn pop_layer(&mut self) {}
+
+    fn push_transform(&mut self, _transform: ttf_parser::Transform) {}
+
+    fn pop_transform(&mut self) {}
+}
+
+/// We always use the first color palette.
+const PALETTE: u16 = 0;
+
+fn rasterize_layers(
+    face: &ttf_parser::Face<'_>,
+    font: &ab_glyph::FontArc,
+    id: ttf_parser::GlyphId,
+    scale_in_pixels: f32,
+) -> Option<ColorGlyph> {
+    let mut layers = Layers {
+        outline: None,
+        layers: Vec::new(),
+    };
+    // Glyphs are cached independently of the text color, so layers in the
+    // "foreground color" get a gray that is readable on both light and dark backgrounds.
+    let foreground = ttf_parser::RgbaColor::new(128, 128, 128, 255);
+    face.paint_color_glyph(id, PALETTE, foreground, &mut layers)?;
+
+    let outlined: Vec<(ab_glyph::OutlinedGlyph, Color32)> = layers
+        .layers
+        .iter()
+        .filter_map(|&(layer_id, color)| {
+            let glyph = ab_glyph::GlyphId(layer_id.0)
+                .with_scale_and_position(scale_in_pixels, ab_glyph::point(0.0, 0.0));
+            Some((font.outline_glyph(glyph)?, color))
+        })
+        .collect();
+
+    let bb = outlined
+        .iter()
+        .map(|(glyph, _)| glyph.px_bounds())
+        .reduce(|a, b| ab_glyph::Rect {
+            min: ab_glyph::point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+            max: ab_glyph::point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+        })?;
+
+    let [w, h] = [bb.width() as usize, bb.height() as usize];
+    let mut pixels = vec![Color32::TRANSPARENT; w * h];
+    for (glyph, color) in &outlined {
+        let glyph_bb = glyph.px_bounds();
+        let dx = (glyph_bb.min.x - bb.min.x) as usize;
+        let dy = (glyph_bb.min.y - bb.min.y) as usize;
+        glyph.draw(|x, y, v| {
+            let (x, y) = (dx + x as usize, dy + y as usize);
+            if v > 0.0 && x < w && y < h {
+                let pixel = &mut pixels[y * w + x];
+                *pixel = blend_over(*pixel, color.gamma_multiply(v.min(1.0)));
+            }
+        });
+    }
+
+    Some(ColorGlyph {
+        size: [w, h],
+        pixels,
+        offset: vec2(bb.min.x, bb.min.y),
+    })
+}
+
+/// Paint the premultiplied color `src` on top of `dst`.
+fn blend_over(dst: Color32, src: Color32) -> Color32 {
+    let keep = 255 - src.a() as u32;
+    let blend = |s: u8, d: u8| s.saturating_add(((d as u32 * keep + 127) / 255) as u8);
+    Color32::from_rgba_premultiplied(
+        blend(src.r(), dst.r()),
+        blend(src.g(), dst.g()),
+        blend(src.b(), dst.b()),
+        blend(src.a(), dst.a()),
+    )
+}
+
+// ----------------------------------------------------------------------------
+// CBDT and sbix
+
+fn rasterize_bitmap(
+    face: &ttf_parser::Face<'_>,
+    id: ttf_parser::GlyphId,
+    pixels_per_em: f32,
+) -> Option<ColorGlyph> {
+    let image = face.glyph_raster_image(id, pixels_per_em.round() as u16)?;
+    if image.format != ttf_parser::RasterImageFormat::PNG || image.pixels_per_em == 0 {
+        return None;
+    }
+    let (src_size, src) = decode_png(image.data)?;
+
+    let scale = pixels_per_em / image.pixels_per_em as f32;
+    let size = [
+        ((src_size[0] as f32 * scale).round() as usize).max(1),
+        ((src_size[1] as f32 * scale).round() as usize).max(1),
+    ];
+    // `x` and `y` are the bottom left corner of the image, with positive y up:
+    let offset = vec2(
+        image.x as f32 * scale,
+        -(image.y as f32 + src_size[1] as f32) * scale,
+    );
+
+    Some(ColorGlyph {
+        size,
+        pixels: resize(&src, src_size, size),
+        offset,
+    })
+}
+
+/// Decode a PNG into premultiplied colors.
+fn decode_png(data: &[u8]) -> Option<([usize; 2], Vec<Color32>)> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).ok()?;
+    let bytes = &buf[..info.buffer_size()];
+
+    let pixels: Vec<Color32> = match info.color_type {
+        png::ColorType::Rgba => bytes
+            .chunks_exact(4)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
+            .collect(),
+        png::ColorType::Rgb => bytes
+            .chunks_exact(3)
+            .map(|p| Color32::from_rgb(p[0], p[1], p[2]))
+            .collect(),
+        png::ColorType::GrayscaleAlpha => bytes
+            .chunks_exact(2)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[0], p[0], p[1]))
+            .collect(),
+        png::ColorType::Grayscale => bytes.iter().map(|&l| Color32::from_gray(l)).collect(),
+        png::ColorType::Indexed => return None, // `EXPAND` turns these into `Rgb` or `Rgba`
+    };
+
+    let size = [info.width as usize, info.height as usize];
+    (pixels.len() == size[0] * size[1]).then_some((size, pixels))
+}
+
+/// Resize an image of premultiplied colors,
+/// averaging all the source pixels covered by each target pixel.
+fn resize(src: &[Color32], [src_w, src_h]: [usize; 2], [w, h]: [usize; 2]) -> Vec<Color32> {
+    let mut pixels = Vec::with_capacity(w * h);
+    for y in 0..h {
+        let y0 = y * src_h / h;
+        let y1 = ((y + 1) * src_h / h).clamp(y0 + 1, src_h);
+        for x in 0..w {
+            let x0 = x * src_w / w;
+            let x1 = ((x + 1) * src_w / w).clamp(x0 + 1, src_w);
+
+            let mut sum = [0_u32; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let color = src[sy * src_w + sx];
+                    for (sum, c) in sum.iter_mut().zip(color.to_array()) {
+                        *sum += c as u32;
+                    }
+                }
+            }
+
+            let n = ((y1 - y0) * (x1 - x0)) as u32;
+            let [r, g, b, a] = sum.map(|sum| ((sum + n / 2) / n) as u8);
+            pixels.push(Color32::from_rgba_premultiplied(r, g, b, a));
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize() {
+        let red = Color32::from_rgb(255, 0, 0);
+        let src = [red, Color32::TRANSPARENT, red, Color32::TRANSPARENT];
+        assert_eq!(
+            resize(&src, [2, 2], [1, 1]),
+            [Color32::from_rgba_premultiplied(128, 0, 0, 128)]
+        );
+        assert_eq!(resize(&src, [2, 2], [4, 4]).len(), 16);
+        assert_eq!(blend_over(red, Color32::TRANSPARENT), red);
+        assert_eq!(blend_over(Color32::TRANSPARENT, red), red);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     mutex::{Mutex, RwLock},
     text::FontTweak,
-    TextureAtlas,
+    AtlasGroup, TextureAtlas,
 };
 use emath::{vec2, Vec2};
 use std::collections::BTreeSet;
@@ -24,6 +24,20 @@ pub struct UvRect {
 
     /// Bottom right corner (exclusive).
     pub max: [u16; 2],
+
+    /// Is this a color glyph (e.g. an emoji), stored in [`FontImage::colors`](crate::FontImage::colors)?
+    ///
+    /// Color glyphs are not tinted by the text color.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_color: bool,
+
+    /// How many copies of the glyph are stored side by side in the texture, for subpixel positioning.
+    ///
+    /// Copy `i` is shifted `i / subpixel_bins` pixels to the right, and starts at
+    /// `min[0] + i * (max[0] - min[0])`.
+    /// `0` or `1` means there is only one copy.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub subpixel_bins: u8,
 }
 
 impl UvRect {
@@ -71,6 +85,50 @@ impl Default for GlyphInfo {
     }
 }
 
+/// A glyph positioned by text shaping, see [`FontImpl::shape`].
+#[cfg(feature = "shaping")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ShapedGlyph {
+    pub id: ab_glyph::GlyphId,
+
+    /// Byte offset of the first character of the cluster this glyph belongs to.
+    ///
+    /// A cluster is a group of characters that are shaped together, e.g. a ligature or a letter with combining marks.
+    pub cluster: usize,
+
+    /// Unit: pixels.
+    pub advance: f32,
+
+    /// Where to draw the glyph, relative to the pen position.
+    /// Positive y is down.
+    ///
+    /// Unit: pixels.
+    pub offset: Vec2,
+}
+
+/// Glyph ids and offsets in whole pixels.
+type ClusterKey = Vec<(u16, [i32; 2])>;
+
+#[cfg(feature = "shaping")]
+type ShapingFaceRef<'a> = rustybuzz::Face<'a>;
+
+#[cfg(feature = "shaping")]
+self_cell::self_cell!(
+    /// A font file, parsed once for shaping with [`rustybuzz`].
+    struct ShapingFace {
+        owner: Arc<crate::text::FontData>,
+
+        #[not_covariant]
+        dependent: ShapingFaceRef,
+    }
+);
+
+/// Signed distance fields of glyphs and clusters of glyphs, shared by all sizes of a font.
+///
+/// Keyed by glyph ids and offsets in pixels at [`super::sdf::SDF_SCALE`].
+/// The [`UvRect::offset`] and [`UvRect::size`] are in pixels at [`super::sdf::SDF_SCALE`].
+pub(crate) type SdfGlyphCache = RwLock<ahash::HashMap<ClusterKey, UvRect>>;
+
 // ----------------------------------------------------------------------------
 
 /// A specific font with a size.
@@ -85,8 +143,30 @@ pub struct FontImpl {
     y_offset: f32,
     ascent: f32,
     pixels_per_point: f32,
+
+    /// See [`UvRect::subpixel_bins`].
+    subpixel_bins: u8,
+
+    /// Rasterize signed distance fields instead of coverage, see [`Self::with_sdf_glyphs`].
+    sdf_glyphs: Option<Arc<SdfGlyphCache>>,
+
     glyph_info_cache: RwLock<ahash::HashMap<char, GlyphInfo>>, // TODO(emilk): standard Mutex
     atlas: Arc<Mutex<TextureAtlas>>,
+
+    /// The glyphs of this font are freed together, when the font is no longer used.
+    atlas_group: AtlasGroup,
+
+    /// The raw font file, needed for shaping and color glyphs.
+    #[cfg(any(feature = "shaping", feature = "color_emoji"))]
+    font_data: Option<Arc<crate::text::FontData>>,
+
+    /// The font file parsed for shaping, or `None` if it can't be used for shaping.
+    #[cfg(feature = "shaping")]
+    shaping_face: Option<ShapingFace>,
+
+    /// Shaped clusters of glyphs, keyed by glyph id and offset in whole pixels.
+    #[cfg(feature = "shaping")]
+    cluster_cache: RwLock<ahash::HashMap<ClusterKey, UvRect>>,
 }
 
 impl FontImpl {
@@ -130,6 +210,8 @@ impl FontImpl {
         // Round to closest pixel:
         let y_offset = (y_offset_points * pixels_per_point).round() / pixels_per_point;
 
+        let atlas_group = atlas.lock().new_group();
+
         Self {
             name,
             ab_glyph_font,
@@ -138,11 +220,55 @@ impl FontImpl {
             y_offset,
             ascent: ascent + baseline_offset,
             pixels_per_point,
+            subpixel_bins: 1,
+            sdf_glyphs: None,
             glyph_info_cache: Default::default(),
             atlas,
+            atlas_group,
+            #[cfg(any(feature = "shaping", feature = "color_emoji"))]
+            font_data: None,
+            #[cfg(feature = "shaping")]
+            shaping_face: None,
+            #[cfg(feature = "shaping")]
+            cluster_cache: Default::default(),
         }
     }
 
+    /// Enable shaping of text with this font (see [`Self::shape`]),
+    /// and color glyphs (with the `color_emoji` feature).
+    ///
+    /// `font_data` must be the font file that this font was created from.
+    #[cfg(any(feature = "shaping", feature = "color_emoji"))]
+    pub fn with_font_data(mut self, font_data: Arc<crate::text::FontData>) -> Self {
+        #[cfg(feature = "shaping")]
+        {
+            self.shaping_face = ShapingFace::try_new(font_data.clone(), |font_data| {
+                rustybuzz::Face::from_slice(&font_data.font, font_data.index).ok_or(())
+            })
+            .ok();
+        }
+        self.font_data = Some(font_data);
+        self
+    }
+
+    /// Rasterize each glyph this many times, at different horizontal subpixel offsets.
+    ///
+    /// See [`UvRect::subpixel_bins`].
+    pub fn with_subpixel_bins(mut self, subpixel_bins: u8) -> Self {
+        self.subpixel_bins = subpixel_bins.max(1);
+        self
+    }
+
+    /// Rasterize glyphs as signed distance fields, at a fixed size,
+    /// into a cache that is shared by all sizes of this font.
+    ///
+    /// See [`crate::text::TextRasterization::sdf`].
+    pub(crate) fn with_sdf_glyphs(mut self, sdf_glyphs: Arc<SdfGlyphCache>) -> Self {
+        self.sdf_glyphs = Some(sdf_glyphs);
+        self.subpixel_bins = 1;
+        self
+    }
+
     /// Code points that will always be replaced by the replacement character.
     ///
     /// See also [`invisible_char`].
@@ -257,46 +383,351 @@ impl FontImpl {
         self.pixels_per_point
     }
 
-    fn allocate_glyph(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
-        assert!(glyph_id.0 != 0);
-        use ab_glyph::{Font as _, ScaleFont};
+    /// The glyphs of this font are in this group of the font atlas.
+    #[inline(always)]
+    pub(crate) fn atlas_group(&self) -> AtlasGroup {
+        self.atlas_group
+    }
 
-        let glyph = glyph_id.with_scale_and_position(
-            self.scale_in_pixels as f32,
-            ab_glyph::Point { x: 0.0, y: 0.0 },
-        );
+    /// Are glyphs positioned with subpixel precision? See [`Self::with_subpixel_bins`].
+    #[inline(always)]
+    pub fn subpixel_positioning(&self) -> bool {
+        self.subpixel_bins > 1
+    }
+
+    /// Distance from the top of a row to the baseline. In points.
+    #[inline(always)]
+    pub fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    /// Shape a piece of text with [`rustybuzz`], applying ligatures, contextual forms, mark positioning and kerning.
+    ///
+    /// The glyphs are returned in logical order, with their clusters in increasing order.
+    /// Returns `None` if the font can't be used for shaping.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn shape(&self, text: &str) -> Option<Vec<ShapedGlyph>> {
+        use ab_glyph::{Font as _, ScaleFont as _};
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.guess_segment_properties();
+        // Runs are laid out left-to-right in logical order. Joining still follows the script.
+        buffer.set_direction(rustybuzz::Direction::LeftToRight);
+        let output = self
+            .shaping_face
+            .as_ref()?
+            .with_dependent(|_, face| rustybuzz::shape(face, &[], buffer));
+
+        let scaled = self.ab_glyph_font.as_scaled(self.scale_in_pixels as f32);
+        let (h_scale, v_scale) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+
+        Some(
+            output
+                .glyph_infos()
+                .iter()
+                .zip(output.glyph_positions())
+                .map(|(info, pos)| ShapedGlyph {
+                    id: ab_glyph::GlyphId(info.glyph_id as u16),
+                    cluster: info.cluster as usize,
+                    advance: pos.x_advance as f32 * h_scale,
+                    offset: vec2(
+                        pos.x_offset as f32 * h_scale,
+                        -pos.y_offset as f32 * v_scale,
+                    ),
+                })
+                .collect(),
+        )
+    }
+
+    /// Rasterize a cluster of shaped glyphs (e.g. a letter and its combining marks) together,
+    /// so it can be shown as one glyph.
+    ///
+    /// `glyphs` are glyph ids and offsets in pixels, relative to the start of the cluster.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn cluster_uv_rect(&self, glyphs: &[(ab_glyph::GlyphId, Vec2)]) -> UvRect {
+        let key: ClusterKey = glyphs
+            .iter()
+            .map(|(id, offset)| (id.0, [offset.x.round() as i32, offset.y.round() as i32]))
+            .collect();
+
+        if let Some(uv_rect) = self.cluster_cache.read().get(&key) {
+            return *uv_rect;
+        }
+
+        let glyphs: Vec<(ab_glyph::GlyphId, Vec2)> = key
+            .iter()
+            .filter(|(id, _)| *id != 0)
+            .map(|&(id, [x, y])| (ab_glyph::GlyphId(id), vec2(x as f32, y as f32)))
+            .collect();
+        let color_uv_rect = match glyphs[..] {
+            [(glyph_id, offset)] => self.allocate_color_glyph(glyph_id).map(|uv_rect| UvRect {
+                offset: uv_rect.offset + offset / self.pixels_per_point,
+                ..uv_rect
+            }),
+            _ => None,
+        };
+        let uv_rect = color_uv_rect.unwrap_or_else(|| self.allocate_glyphs(&glyphs));
+        self.cluster_cache.write().insert(key, uv_rect);
+        uv_rect
+    }
+
+    /// Rasterize several glyphs, at the given offsets in pixels, into one region of the atlas.
+    ///
+    /// With subpixel positioning, this is done once for each subpixel offset,
+    /// and the copies are put side by side (see [`UvRect::subpixel_bins`]).
+    fn allocate_glyphs(&self, glyphs: &[(ab_glyph::GlyphId, Vec2)]) -> UvRect {
+        use ab_glyph::Font as _;
 
-        let uv_rect = self.ab_glyph_font.outline_glyph(glyph).map(|glyph| {
-            let bb = glyph.px_bounds();
-            let glyph_width = bb.width() as usize;
-            let glyph_height = bb.height() as usize;
-            if glyph_width == 0 || glyph_height == 0 {
-                UvRect::default()
-            } else {
-                let atlas = &mut self.atlas.lock();
-                let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+        if let Some(sdf_glyphs) = &self.sdf_glyphs {
+            let sdf_scale = super::sdf::SDF_SCALE / self.scale_in_pixels as f32;
+            let key: ClusterKey = glyphs
+                .iter()
+                .map(|&(glyph_id, offset)| {
+                    let offset = offset * sdf_scale;
+                    (
+                        glyph_id.0,
+                        [offset.x.round() as i32, offset.y.round() as i32],
+                    )
+                })
+                .collect();
+            let cached = sdf_glyphs.read().get(&key).copied();
+            let uv_rect = cached.unwrap_or_else(|| {
+                let glyphs: Vec<_> = key
+                    .iter()
+                    .map(|&(id, [x, y])| (ab_glyph::GlyphId(id), vec2(x as f32, y as f32)))
+                    .collect();
+                let uv_rect = self.allocate_sdf_glyphs(&glyphs);
+                sdf_glyphs.write().insert(key, uv_rect);
+                uv_rect
+            });
+            return self.sdf_to_this_size(uv_rect);
+        }
+
+        let bins = self.subpixel_bins.max(1);
+        let outlined: Vec<Vec<ab_glyph::OutlinedGlyph>> = (0..bins)
+            .map(|bin| {
+                let subpixel_offset = bin as f32 / bins as f32;
+                glyphs
+                    .iter()
+                    .filter_map(|&(glyph_id, offset)| {
+                        let glyph = glyph_id.with_scale_and_position(
+                            self.scale_in_pixels as f32,
+                            ab_glyph::Point {
+                                x: offset.x + subpixel_offset,
+                                y: offset.y,
+                            },
+                        );
+                        self.ab_glyph_font.outline_glyph(glyph)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // The same bounding box for all copies, so they can share `UvRect::offset` and `UvRect::size`:
+        let Some(bb) = outlined
+            .iter()
+            .flatten()
+            .map(|glyph| glyph.px_bounds())
+            .reduce(|a, b| ab_glyph::Rect {
+                min: ab_glyph::point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+                max: ab_glyph::point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+            })
+        else {
+            return UvRect::default();
+        };
+
+        let glyph_width = bb.width() as usize;
+        let glyph_height = bb.height() as usize;
+        if glyph_width == 0 || glyph_height == 0 {
+            return UvRect::default();
+        }
+
+        let atlas = &mut self.atlas.lock();
+        let (glyph_pos, image) = atlas.allocate_in_group(
+            self.atlas_group,
+            (glyph_width * bins as usize, glyph_height),
+        );
+        for (bin, outlined) in outlined.iter().enumerate() {
+            let bin_x = glyph_pos.0 + bin * glyph_width;
+            for glyph in outlined {
+                let glyph_bb = glyph.px_bounds();
+                let dx = (glyph_bb.min.x - bb.min.x) as usize;
+                let dy = (glyph_bb.min.y - bb.min.y) as usize;
                 glyph.draw(|x, y, v| {
-                    if v > 0.0 {
-                        let px = glyph_pos.0 + x as usize;
-                        let py = glyph_pos.1 + y as usize;
-                        image[(px, py)] = v;
+                    let px = bin_x + dx + x as usize;
+                    let py = glyph_pos.1 + dy + y as usize;
+                    if v > 0.0 && px < bin_x + glyph_width && py < glyph_pos.1 + glyph_height {
+                        // Overlapping glyphs (e.g. a letter and its accent) are blended:
+                        let old = image[(px, py)];
+                        image[(px, py)] = old + v - old * v;
                     }
                 });
+            }
+        }
+
+        let offset_in_pixels = vec2(bb.min.x, bb.min.y);
+        let offset = offset_in_pixels / self.pixels_per_point + self.y_offset * Vec2::Y;
+        UvRect {
+            offset,
+            size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [
+                (glyph_pos.0 + glyph_width) as u16,
+                (glyph_pos.1 + glyph_height) as u16,
+            ],
+            is_color: false,
+            subpixel_bins: bins,
+        }
+    }
+
+    /// Rasterize several glyphs as one signed distance field, at [`super::sdf::SDF_SCALE`].
+    ///
+    /// The offsets, and the returned [`UvRect`], are in pixels at that scale.
+    fn allocate_sdf_glyphs(&self, glyphs: &[(ab_glyph::GlyphId, Vec2)]) -> UvRect {
+        use super::sdf::{sdf_from_coverage, SDF_SCALE, SDF_SPREAD};
+        use ab_glyph::Font as _;
+
+        let outlined: Vec<ab_glyph::OutlinedGlyph> = glyphs
+            .iter()
+            .filter_map(|&(glyph_id, offset)| {
+                let glyph = glyph_id.with_scale_and_position(
+                    SDF_SCALE,
+                    ab_glyph::Point {
+                        x: offset.x,
+                        y: offset.y,
+                    },
+                );
+                self.ab_glyph_font.outline_glyph(glyph)
+            })
+            .collect();
+
+        let Some(bb) = outlined
+            .iter()
+            .map(|glyph| glyph.px_bounds())
+            .reduce(|a, b| ab_glyph::Rect {
+                min: ab_glyph::point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+                max: ab_glyph::point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+            })
+        else {
+            return UvRect::default();
+        };
+        if bb.width() < 1.0 || bb.height() < 1.0 {
+            return UvRect::default();
+        }
+
+        // Leave room for the distances outside of the glyph:
+        let glyph_width = bb.width() as usize + 2 * SDF_SPREAD;
+        let glyph_height = bb.height() as usize + 2 * SDF_SPREAD;
+
+        let mut coverage = vec![0.0; glyph_width * glyph_height];
+        for glyph in &outlined {
+            let glyph_bb = glyph.px_bounds();
+            let dx = (glyph_bb.min.x - bb.min.x) as usize + SDF_SPREAD;
+            let dy = (glyph_bb.min.y - bb.min.y) as usize + SDF_SPREAD;
+            glyph.draw(|x, y, v| {
+                let (x, y) = (dx + x as usize, dy + y as usize);
+                if v > 0.0 && x < glyph_width && y < glyph_height {
+                    let old: f32 = coverage[y * glyph_width + x];
+                    coverage[y * glyph_width + x] = old + v - old * v;
+                }
+            });
+        }
+        let distances = sdf_from_coverage(&coverage, [glyph_width, glyph_height]);
+
+        // Shared by all sizes of the font, so never freed:
+        let atlas = &mut self.atlas.lock();
+        let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+        for y in 0..glyph_height {
+            for x in 0..glyph_width {
+                image[(glyph_pos.0 + x, glyph_pos.1 + y)] = distances[y * glyph_width + x];
+            }
+        }
+
+        UvRect {
+            offset: vec2(bb.min.x, bb.min.y) - Vec2::splat(SDF_SPREAD as f32),
+            size: vec2(glyph_width as f32, glyph_height as f32),
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [
+                (glyph_pos.0 + glyph_width) as u16,
+                (glyph_pos.1 + glyph_height) as u16,
+            ],
+            is_color: false,
+            subpixel_bins: 1,
+        }
+    }
+
+    /// Scale a [`UvRect`] from [`Self::allocate_sdf_glyphs`] to the size of this font.
+    fn sdf_to_this_size(&self, uv_rect: UvRect) -> UvRect {
+        if uv_rect.is_nothing() {
+            return uv_rect;
+        }
+        let scale = self.scale_in_pixels as f32 / super::sdf::SDF_SCALE / self.pixels_per_point;
+        UvRect {
+            offset: uv_rect.offset * scale + self.y_offset * Vec2::Y,
+            size: uv_rect.size * scale,
+            ..uv_rect
+        }
+    }
+
+    /// Rasterize a color glyph (e.g. an emoji) into the atlas.
+    ///
+    /// Returns `None` if this is not a color glyph, in which case the outline should be used.
+    #[cfg(feature = "color_emoji")]
+    fn allocate_color_glyph(&self, glyph_id: ab_glyph::GlyphId) -> Option<UvRect> {
+        if self.sdf_glyphs.is_some() {
+            return None; // Signed distance fields can't have colors, so we use the outline.
+        }
+        let font_data = self.font_data.as_ref()?;
+        let glyph = super::color_glyph::rasterize(
+            font_data,
+            &self.ab_glyph_font,
+            glyph_id,
+            self.scale_in_pixels as f32,
+        )?;
+        let [glyph_width, glyph_height] = glyph.size;
+        if glyph_width == 0 || glyph_height == 0 {
+            return None;
+        }
 
-                let offset_in_pixels = vec2(bb.min.x, bb.min.y);
-                let offset = offset_in_pixels / self.pixels_per_point + self.y_offset * Vec2::Y;
-                UvRect {
-                    offset,
-                    size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
-                    min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
-                    max: [
-                        (glyph_pos.0 + glyph_width) as u16,
-                        (glyph_pos.1 + glyph_height) as u16,
-                    ],
+        let atlas = &mut self.atlas.lock();
+        let (glyph_pos, image) =
+            atlas.allocate_in_group(self.atlas_group, (glyph_width, glyph_height));
+        for y in 0..glyph_height {
+            for x in 0..glyph_width {
+                let color = glyph.pixels[y * glyph_width + x];
+                if color != crate::Color32::TRANSPARENT {
+                    image.set_color((glyph_pos.0 + x, glyph_pos.1 + y), color);
                 }
             }
-        });
-        let uv_rect = uv_rect.unwrap_or_default();
+        }
+
+        Some(UvRect {
+            offset: glyph.offset / self.pixels_per_point + self.y_offset * Vec2::Y,
+            size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [
+                (glyph_pos.0 + glyph_width) as u16,
+                (glyph_pos.1 + glyph_height) as u16,
+            ],
+            is_color: true,
+            subpixel_bins: 1,
+        })
+    }
+
+    #[cfg(not(feature = "color_emoji"))]
+    #[allow(clippy::unused_self)]
+    fn allocate_color_glyph(&self, _glyph_id: ab_glyph::GlyphId) -> Option<UvRect> {
+        None
+    }
+
+    fn allocate_glyph(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
+        assert!(glyph_id.0 != 0);
+        use ab_glyph::{Font as _, ScaleFont};
+
+        let uv_rect = self
+            .allocate_color_glyph(glyph_id)
+            .unwrap_or_else(|| self.allocate_glyphs(&[(glyph_id, Vec2::ZERO)]));
 
         let advance_width_in_points = self
             .ab_glyph_font
@@ -314,7 +745,7 @@ impl FontImpl {
     }
 }
 
-type FontIndex = usize;
+pub(crate) type FontIndex = usize;
 
 // TODO(emilk): rename?
 /// Wrapper over multiple [`FontImpl`] (e.g. a primary + fallbacks for emojis)
@@ -326,6 +757,20 @@ pub struct Font {
     pixels_per_point: f32,
     row_height: f32,
     glyph_info_cache: ahash::HashMap<char, (FontIndex, GlyphInfo)>,
+
+    /// Don't round glyph positions to whole pixels, see [`FontImpl::subpixel_positioning`].
+    subpixel_positioning: bool,
+
+    /// Slant the glyphs, because we don't have an italic face.
+    synthetic_italics: bool,
+
+    /// Where to load fallback fonts from the system, and at what size (in points) and weight.
+    #[cfg(feature = "system_fonts")]
+    system_fallback: Option<(
+        Arc<Mutex<super::fonts::FontImplCache>>,
+        f32,
+        super::FontWeight,
+    )>,
 }
 
 impl Font {
@@ -338,11 +783,16 @@ impl Font {
                 pixels_per_point: 1.0,
                 row_height: 0.0,
                 glyph_info_cache: Default::default(),
+                subpixel_positioning: false,
+                synthetic_italics: false,
+                #[cfg(feature = "system_fonts")]
+                system_fallback: None,
             };
         }
 
         let pixels_per_point = fonts[0].pixels_per_point();
         let row_height = fonts[0].row_height();
+        let subpixel_positioning = fonts[0].subpixel_positioning();
 
         let mut slf = Self {
             fonts,
@@ -351,6 +801,10 @@ impl Font {
             pixels_per_point,
             row_height,
             glyph_info_cache: Default::default(),
+            subpixel_positioning,
+            synthetic_italics: false,
+            #[cfg(feature = "system_fonts")]
+            system_fallback: None,
         };
 
         const PRIMARY_REPLACEMENT_CHAR: char = '◻'; // white medium square
@@ -370,6 +824,29 @@ impl Font {
         slf
     }
 
+    /// Look for characters that none of the fonts have in the fonts installed on the system.
+    #[cfg(feature = "system_fonts")]
+    pub(crate) fn with_system_fallback(
+        mut self,
+        font_impl_cache: Arc<Mutex<super::fonts::FontImplCache>>,
+        scale_in_points: f32,
+        weight: super::FontWeight,
+    ) -> Self {
+        self.system_fallback = Some((font_impl_cache, scale_in_points, weight));
+        self
+    }
+
+    /// Slant the glyphs, e.g. because italics were asked for but there is no italic face.
+    pub(crate) fn with_synthetic_italics(mut self, synthetic_italics: bool) -> Self {
+        self.synthetic_italics = synthetic_italics;
+        self
+    }
+
+    /// Should the glyphs be slanted, because there is no italic face?
+    pub(crate) fn synthetic_italics(&self) -> bool {
+        self.synthetic_italics
+    }
+
     pub fn preload_characters(&mut self, s: &str) {
         for c in s.chars() {
             self.glyph_info(c);
@@ -403,6 +880,16 @@ impl Font {
         (point * self.pixels_per_point).round() / self.pixels_per_point
     }
 
+    /// Round the x position of a glyph to a whole pixel, unless glyphs are positioned with subpixel precision.
+    #[inline(always)]
+    pub(crate) fn round_glyph_x(&self, x: f32) -> f32 {
+        if self.subpixel_positioning {
+            x
+        } else {
+            self.round_to_pixel(x)
+        }
+    }
+
     /// Height of one row of text. In points
     #[inline(always)]
     pub fn row_height(&self) -> f32 {
@@ -437,12 +924,20 @@ impl Font {
             return *font_index_glyph_info;
         }
 
-        let font_index_glyph_info = self.glyph_info_no_cache_or_fallback(c);
+        let font_index_glyph_info = self
+            .glyph_info_no_cache_or_fallback(c)
+            .or_else(|| self.glyph_info_from_system_fonts(c));
         let font_index_glyph_info = font_index_glyph_info.unwrap_or(self.replacement_glyph);
         self.glyph_info_cache.insert(c, font_index_glyph_info);
         font_index_glyph_info
     }
 
+    /// The fonts used, including any fallback fonts loaded so far.
+    #[inline]
+    pub(crate) fn font_impls(&self) -> &[Arc<FontImpl>] {
+        &self.fonts
+    }
+
     #[inline]
     pub(crate) fn glyph_info_and_font_impl(&mut self, c: char) -> (Option<&FontImpl>, GlyphInfo) {
         if self.fonts.is_empty() {
@@ -453,6 +948,53 @@ impl Font {
         (Some(font_impl), glyph_info)
     }
 
+    /// Which of the fonts to shape this character with.
+    ///
+    /// `None` for characters that are laid out one at a time,
+    /// e.g. tabs and characters that no font supports.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn shaping_font_index(&mut self, c: char) -> Option<FontIndex> {
+        if c == '\t' || c == '\u{2009}' {
+            return None; // These have special widths, see `FontImpl::glyph_info`.
+        }
+        let font_index_glyph_info = self.glyph_info(c);
+        if font_index_glyph_info == self.replacement_glyph {
+            None
+        } else {
+            Some(font_index_glyph_info.0)
+        }
+    }
+
+    #[cfg(feature = "shaping")]
+    pub(crate) fn font_impl(&self, font_index: FontIndex) -> &Arc<FontImpl> {
+        &self.fonts[font_index]
+    }
+
+    /// Load a font with this character from the system, and add it as the last fallback.
+    #[cfg(feature = "system_fonts")]
+    fn glyph_info_from_system_fonts(&mut self, c: char) -> Option<(FontIndex, GlyphInfo)> {
+        if c.is_control() {
+            return None; // e.g. `\n`, which should be the replacement character.
+        }
+        let (font_impl_cache, scale_in_points, weight) = self.system_fallback.as_ref()?;
+        let font_impl = font_impl_cache
+            .lock()
+            .system_font_impl(*scale_in_points, *weight, c)?;
+        let glyph_info = font_impl.glyph_info(c)?;
+
+        let font_index = self.fonts.len();
+        self.fonts.push(font_impl);
+        self.characters = None;
+        self.glyph_info_cache.insert(c, (font_index, glyph_info));
+        Some((font_index, glyph_info))
+    }
+
+    #[cfg(not(feature = "system_fonts"))]
+    #[allow(clippy::unused_self)]
+    fn glyph_info_from_system_fonts(&mut self, _c: char) -> Option<(FontIndex, GlyphInfo)> {
+        None
+    }
+
     fn glyph_info_no_cache_or_fallback(&mut self, c: char) -> Option<(FontIndex, GlyphInfo)> {
         for (font_index, font_impl) in self.fonts.iter().enumerate() {
             if let Some(glyph_info) = font_impl.glyph_info(c) {
@@ -468,7 +1010,7 @@ impl Font {
 ///
 /// See also [`FontImpl::ignore_character`].
 #[inline]
-fn invisible_char(c: char) -> bool {
+pub(crate) fn invisible_char(c: char) -> bool {
     if c == '\r' {
         // A character most vile and pernicious. Don't display it.
         return true;
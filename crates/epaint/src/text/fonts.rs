@@ -6,10 +6,13 @@ use crate::{
         font::{Font, FontImpl},
         Galley, LayoutJob,
     },
-    TextureAtlas,
+    AtlasGroup, TextureAtlas,
 };
 use emath::NumExt as _;
 
+#[cfg(feature = "system_fonts")]
+use crate::text::SystemFonts;
+
 // ----------------------------------------------------------------------------
 
 /// How to select a sized font.
@@ -21,7 +24,20 @@ pub struct FontId {
 
     /// What font family to use.
     pub family: FontFamily,
-    // TODO(emilk): weight (bold), italics, …
+
+    /// How bold the text is.
+    ///
+    /// Picks a face of the right weight from [`FontDefinitions::faces`],
+    /// or sets the weight axis (`wght`) of variable fonts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weight: FontWeight,
+
+    /// Normal or italic.
+    ///
+    /// Picks an italic face from [`FontDefinitions::faces`] if there is one,
+    /// else the glyphs are slanted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: FontStyle,
 }
 
 impl Default for FontId {
@@ -30,6 +46,8 @@ impl Default for FontId {
         Self {
             size: 14.0,
             family: FontFamily::Proportional,
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
         }
     }
 }
@@ -37,7 +55,12 @@ impl Default for FontId {
 impl FontId {
     #[inline]
     pub const fn new(size: f32, family: FontFamily) -> Self {
-        Self { size, family }
+        Self {
+            size,
+            family,
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
+        }
     }
 
     #[inline]
@@ -49,15 +72,110 @@ impl FontId {
     pub const fn monospace(size: f32) -> Self {
         Self::new(size, FontFamily::Monospace)
     }
+
+    #[inline]
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    #[inline]
+    pub fn style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Use [`FontWeight::BOLD`].
+    #[inline]
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    /// Use [`FontStyle::Italic`].
+    #[inline]
+    pub fn italic(self) -> Self {
+        self.style(FontStyle::Italic)
+    }
 }
 
 #[allow(clippy::derive_hash_xor_eq)]
 impl std::hash::Hash for FontId {
     #[inline(always)]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let Self { size, family } = self;
+        let Self {
+            size,
+            family,
+            weight,
+            style,
+        } = self;
         crate::f32_hash(state, *size);
         family.hash(state);
+        weight.hash(state);
+        style.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How thick the strokes of a font are, from 100 (thin) to 900 (black).
+///
+/// Uses the same numbers as CSS and the `OS/2` table of fonts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const EXTRA_LIGHT: Self = Self(200);
+    pub const LIGHT: Self = Self(300);
+    pub const NORMAL: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMI_BOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const EXTRA_BOLD: Self = Self(800);
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    #[inline]
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// Normal or italic text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FontStyle {
+    #[default]
+    Normal,
+
+    /// Slanted, often with different letter shapes.
+    Italic,
+}
+
+/// The value of an axis of a variable font, e.g. width (`wdth`).
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/dvaraxisreg>.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontVariation {
+    /// E.g. `*b"wdth"`.
+    pub tag: [u8; 4],
+
+    pub value: f32,
+}
+
+impl FontVariation {
+    #[inline]
+    pub const fn new(tag: [u8; 4], value: f32) -> Self {
+        Self { tag, value }
+    }
+
+    /// The width axis (`wdth`), in percent of the normal width.
+    #[inline]
+    pub const fn width(percent: f32) -> Self {
+        Self::new(*b"wdth", percent)
     }
 }
 
@@ -117,6 +235,20 @@ pub struct FontData {
 
     /// Extra scale and vertical tweak to apply to all text of this font.
     pub tweak: FontTweak,
+
+    /// The weight of this face, used to pick between the [`FontDefinitions::faces`] of a font.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weight: FontWeight,
+
+    /// The style of this face, used to pick between the [`FontDefinitions::faces`] of a font.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: FontStyle,
+
+    /// Values for the axes of a variable font, e.g. [`FontVariation::width`].
+    ///
+    /// The weight axis (`wght`) is set from [`FontId::weight`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variations: Vec<FontVariation>,
 }
 
 impl FontData {
@@ -125,6 +257,9 @@ impl FontData {
             font: std::borrow::Cow::Borrowed(font),
             index: 0,
             tweak: Default::default(),
+            weight: Default::default(),
+            style: Default::default(),
+            variations: Default::default(),
         }
     }
 
@@ -133,12 +268,31 @@ impl FontData {
             font: std::borrow::Cow::Owned(font),
             index: 0,
             tweak: Default::default(),
+            weight: Default::default(),
+            style: Default::default(),
+            variations: Default::default(),
         }
     }
 
     pub fn tweak(self, tweak: FontTweak) -> Self {
         Self { tweak, ..self }
     }
+
+    /// The weight of this face, e.g. [`FontWeight::BOLD`] for a bold font.
+    pub fn weight(self, weight: FontWeight) -> Self {
+        Self { weight, ..self }
+    }
+
+    /// The style of this face, e.g. [`FontStyle::Italic`] for an italic font.
+    pub fn style(self, style: FontStyle) -> Self {
+        Self { style, ..self }
+    }
+
+    /// Set an axis of a variable font, e.g. [`FontVariation::width`].
+    pub fn variation(mut self, variation: FontVariation) -> Self {
+        self.variations.push(variation);
+        self
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -189,20 +343,110 @@ impl Default for FontTweak {
 
 // ----------------------------------------------------------------------------
 
-fn ab_glyph_font_from_font_data(name: &str, data: &FontData) -> ab_glyph::FontArc {
+/// How glyphs are rasterized and positioned, see [`Fonts::set_rasterization`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TextRasterization {
+    /// Position glyphs at fractions of a physical pixel, instead of rounding them to whole pixels.
+    ///
+    /// This keeps the spacing between letters even at fractional scales,
+    /// at the cost of rasterizing each glyph [`Self::SUBPIXEL_BINS`] times,
+    /// using that much more of the font atlas.
+    ///
+    /// Default: `false`.
+    pub subpixel_positioning: bool,
+
+    /// The gamma used to turn the coverage of a glyph into opacity.
+    ///
+    /// Lower values make text bolder and give it more contrast, higher values make it thinner.
+    ///
+    /// Default: [`FontImage::DEFAULT_GAMMA`](crate::FontImage::DEFAULT_GAMMA).
+    pub gamma: f32,
+
+    /// Store glyphs as signed distance fields, rasterized once and then scaled to every size.
+    ///
+    /// This avoids rasterizing glyphs again for every new font size, e.g. when zooming,
+    /// and keeps text crisp when it is scaled up.
+    /// Small text looks a bit softer than with normal rasterization.
+    ///
+    /// This requires a renderer that supports it (see [`FontImage::sdf`](crate::FontImage::sdf)),
+    /// like `egui_glow` and `egui-wgpu`.
+    /// [`Self::subpixel_positioning`] and [`Self::gamma`] are ignored,
+    /// and color glyphs (emoji) are drawn with their outlines.
+    ///
+    /// Default: `false`.
+    pub sdf: bool,
+}
+
+impl TextRasterization {
+    /// Number of horizontal positions within a pixel that glyphs are rasterized at,
+    /// with [`Self::subpixel_positioning`].
+    pub const SUBPIXEL_BINS: u8 = 4;
+
+    fn subpixel_bins(&self) -> u8 {
+        if self.subpixel_positioning && !self.sdf {
+            Self::SUBPIXEL_BINS
+        } else {
+            1
+        }
+    }
+}
+
+impl Default for TextRasterization {
+    fn default() -> Self {
+        Self {
+            subpixel_positioning: false,
+            gamma: crate::FontImage::DEFAULT_GAMMA,
+            sdf: false,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Load a font, with the [`FontData::variations`] applied,
+/// and the weight axis set to `weight` (if any).
+fn ab_glyph_font_from_font_data(
+    name: &str,
+    data: &FontData,
+    weight: Option<FontWeight>,
+) -> ab_glyph::FontArc {
+    fn set_variations<F: ab_glyph::VariableFont>(
+        mut font: F,
+        data: &FontData,
+        weight: Option<FontWeight>,
+    ) -> F {
+        for variation in &data.variations {
+            font.set_variation(&variation.tag, variation.value);
+        }
+        if let Some(weight) = weight {
+            font.set_variation(b"wght", weight.0 as f32);
+        }
+        font
+    }
+
     match &data.font {
         std::borrow::Cow::Borrowed(bytes) => {
             ab_glyph::FontRef::try_from_slice_and_index(bytes, data.index)
-                .map(ab_glyph::FontArc::from)
+                .map(|font| ab_glyph::FontArc::from(set_variations(font, data, weight)))
         }
         std::borrow::Cow::Owned(bytes) => {
             ab_glyph::FontVec::try_from_vec_and_index(bytes.clone(), data.index)
-                .map(ab_glyph::FontArc::from)
+                .map(|font| ab_glyph::FontArc::from(set_variations(font, data, weight)))
         }
     }
     .unwrap_or_else(|err| panic!("Error parsing {:?} TTF/OTF font file: {}", name, err))
 }
 
+/// Does this font have a weight axis (`wght`)?
+fn has_weight_axis(data: &FontData) -> bool {
+    use ab_glyph::VariableFont as _;
+    ab_glyph::FontRef::try_from_slice_and_index(&data.font, data.index).map_or(false, |font| {
+        font.variations().iter().any(|axis| &axis.tag == b"wght")
+    })
+}
+
 /// Describes the font data and the sizes to use.
 ///
 /// Often you would start with [`FontDefinitions::default()`] and then add/change the contents.
@@ -245,6 +489,24 @@ pub struct FontDefinitions {
     /// the first font and then move to the second, and so on.
     /// So the first font is the primary, and then comes a list of fallbacks in order of priority.
     pub families: BTreeMap<FontFamily, Vec<String>>,
+
+    /// Other faces of the fonts in [`Self::families`], e.g. bold and italic.
+    ///
+    /// Maps a key of [`Self::font_data`] to other keys of [`Self::font_data`].
+    /// When text asks for a [`FontWeight`] or [`FontStyle`], each font of the family is replaced
+    /// by the closest match of itself and its faces, going by [`FontData::style`] and [`FontData::weight`].
+    ///
+    /// ```
+    /// # use epaint::text::{FontData, FontDefinitions, FontWeight};
+    /// # let bold_font_bytes: &'static [u8] = include_bytes!("../../fonts/Ubuntu-Light.ttf");
+    /// let mut fonts = FontDefinitions::default();
+    /// fonts.font_data.insert(
+    ///     "Ubuntu-Bold".to_owned(),
+    ///     FontData::from_static(bold_font_bytes).weight(FontWeight::BOLD),
+    /// );
+    /// fonts.faces.insert("Ubuntu-Light".to_owned(), vec!["Ubuntu-Bold".to_owned()]);
+    /// ```
+    pub faces: BTreeMap<String, Vec<String>>,
 }
 
 impl Default for FontDefinitions {
@@ -319,6 +581,7 @@ impl Default for FontDefinitions {
         Self {
             font_data,
             families,
+            faces: Default::default(),
         }
     }
 }
@@ -333,8 +596,24 @@ impl FontDefinitions {
         Self {
             font_data: Default::default(),
             families,
+            faces: Default::default(),
         }
     }
+
+    /// The face of a font that best matches the weight and style, see [`Self::faces`].
+    pub fn resolve_face<'a>(
+        &'a self,
+        font_name: &'a str,
+        weight: FontWeight,
+        style: FontStyle,
+    ) -> &'a str {
+        let faces = self.faces.get(font_name).into_iter().flatten();
+        std::iter::once(font_name)
+            .chain(faces.map(|face| face.as_str()))
+            .filter_map(|name| Some((name, self.font_data.get(name)?)))
+            .min_by_key(|(_, data)| (data.style != style, data.weight.0.abs_diff(weight.0)))
+            .map_or(font_name, |(name, _)| name)
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -381,22 +660,66 @@ impl Fonts {
         let pixels_per_point_changed =
             (fonts_and_cache.fonts.pixels_per_point - pixels_per_point).abs() > 1e-3;
         let max_texture_side_changed = fonts_and_cache.fonts.max_texture_side != max_texture_side;
-        let font_atlas_almost_full = fonts_and_cache.fonts.atlas.lock().fill_ratio() > 0.8;
+
+        if !pixels_per_point_changed && !max_texture_side_changed {
+            // Make room for new glyphs by freeing the fonts that are no longer used:
+            fonts_and_cache.fonts.evict_unused_fonts();
+        }
+
+        let font_atlas_almost_full =
+            fonts_and_cache.fonts.atlas.lock().fill_ratio() > FontsImpl::MAX_FILL_RATIO;
         let needs_recreate =
             pixels_per_point_changed || max_texture_side_changed || font_atlas_almost_full;
 
         if needs_recreate {
             let definitions = fonts_and_cache.fonts.definitions.clone();
-
+            let rasterization = fonts_and_cache.fonts.rasterization;
+
+            let fonts = FontsImpl::new_with_rasterization(
+                pixels_per_point,
+                max_texture_side,
+                definitions,
+                rasterization,
+            );
+            fonts.replaces(&fonts_and_cache.fonts);
             *fonts_and_cache = FontsAndCache {
-                fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
+                fonts,
                 galley_cache: Default::default(),
             };
         }
 
+        fonts_and_cache.fonts.generation = fonts_and_cache.fonts.generation.wrapping_add(1);
         fonts_and_cache.galley_cache.flush_cache();
     }
 
+    /// Change how text is rasterized and positioned.
+    ///
+    /// If this is different from before, the font atlas and all laid out text is thrown away,
+    /// so don't call this with a new value every frame.
+    pub fn set_rasterization(&self, rasterization: TextRasterization) {
+        let mut fonts_and_cache = self.0.lock();
+        if fonts_and_cache.fonts.rasterization != rasterization {
+            let old_fonts = &fonts_and_cache.fonts;
+            let fonts = FontsImpl::new_with_rasterization(
+                old_fonts.pixels_per_point,
+                old_fonts.max_texture_side,
+                old_fonts.definitions.clone(),
+                rasterization,
+            );
+            fonts.replaces(old_fonts);
+            *fonts_and_cache = FontsAndCache {
+                fonts,
+                galley_cache: Default::default(),
+            };
+        }
+    }
+
+    /// How text is rasterized and positioned, see [`Self::set_rasterization`].
+    #[inline]
+    pub fn rasterization(&self) -> TextRasterization {
+        self.lock().fonts.rasterization
+    }
+
     /// Call at the end of each frame (before painting) to get the change to the font texture since last call.
     pub fn font_image_delta(&self) -> Option<crate::ImageDelta> {
         self.lock().fonts.atlas.lock().take_delta()
@@ -489,6 +812,11 @@ impl Fonts {
         self.lock().fonts.atlas.lock().fill_ratio()
     }
 
+    /// Statistics about the font atlas, e.g. for [`crate::stats::PaintStats`].
+    pub fn font_atlas_stats(&self) -> crate::stats::FontAtlasStats {
+        self.lock().fonts.atlas.lock().stats()
+    }
+
     /// Will wrap text at the given width and line break at `\n`.
     ///
     /// The implementation uses memoization so repeated calls are cheap.
@@ -571,9 +899,15 @@ pub struct FontsImpl {
     pixels_per_point: f32,
     max_texture_side: usize,
     definitions: FontDefinitions,
+    rasterization: TextRasterization,
     atlas: Arc<Mutex<TextureAtlas>>,
-    font_impl_cache: FontImplCache,
-    sized_family: ahash::HashMap<(HashableF32, FontFamily), Font>,
+    font_impl_cache: Arc<Mutex<FontImplCache>>,
+
+    /// The fonts, and the frame they were last used in.
+    sized_family: ahash::HashMap<(HashableF32, FontFamily, FontWeight, FontStyle), (u32, Font)>,
+
+    /// Frame counter, used to find the fonts that are no longer used.
+    generation: u32,
 }
 
 impl FontsImpl {
@@ -583,6 +917,21 @@ impl FontsImpl {
         pixels_per_point: f32,
         max_texture_side: usize,
         definitions: FontDefinitions,
+    ) -> Self {
+        Self::new_with_rasterization(
+            pixels_per_point,
+            max_texture_side,
+            definitions,
+            Default::default(),
+        )
+    }
+
+    /// Like [`Self::new`], but with a non-default [`TextRasterization`].
+    pub fn new_with_rasterization(
+        pixels_per_point: f32,
+        max_texture_side: usize,
+        definitions: FontDefinitions,
+        rasterization: TextRasterization,
     ) -> Self {
         assert!(
             0.0 < pixels_per_point && pixels_per_point < 100.0,
@@ -592,20 +941,93 @@ impl FontsImpl {
 
         let texture_width = max_texture_side.at_most(8 * 1024);
         let initial_height = 64;
-        let atlas = TextureAtlas::new([texture_width, initial_height]);
+        let mut atlas = TextureAtlas::new([texture_width, initial_height]);
+        atlas.set_gamma(rasterization.gamma);
+        atlas.set_sdf(rasterization.sdf);
 
         let atlas = Arc::new(Mutex::new(atlas));
 
-        let font_impl_cache =
-            FontImplCache::new(atlas.clone(), pixels_per_point, &definitions.font_data);
+        let font_impl_cache = FontImplCache::new(
+            atlas.clone(),
+            pixels_per_point,
+            rasterization,
+            &definitions.font_data,
+        );
+        let font_impl_cache = Arc::new(Mutex::new(font_impl_cache));
 
         Self {
             pixels_per_point,
             max_texture_side,
             definitions,
+            rasterization,
             atlas,
             font_impl_cache,
             sized_family: Default::default(),
+            generation: 0,
+        }
+    }
+
+    /// When the font atlas gets fuller than this, fonts that are no longer used are evicted,
+    /// and if that isn't enough, the font atlas is recreated.
+    const MAX_FILL_RATIO: f32 = 0.8;
+
+    /// Keep the counts of [`TextureAtlas::stats`] going when replacing `previous`.
+    fn replaces(&self, previous: &Self) {
+        self.atlas.lock().replaces(&previous.atlas.lock());
+    }
+
+    /// If the font atlas is getting full, free the glyphs of the fonts that weren't used
+    /// in the last frame, least recently used first.
+    fn evict_unused_fonts(&mut self) {
+        /// How full we want the atlas to be after evicting.
+        const TARGET_FILL_RATIO: f32 = 0.5;
+
+        if self.atlas.lock().fill_ratio() <= Self::MAX_FILL_RATIO {
+            return;
+        }
+
+        // A font implementation can be used by several fonts:
+        // How many frames ago each group of the atlas was used:
+        let mut ages: ahash::HashMap<AtlasGroup, u32> = Default::default();
+        for (font_last_used, font) in self.sized_family.values() {
+            for font_impl in font.font_impls() {
+                let age = self.generation.wrapping_sub(*font_last_used);
+                let group_age = ages.entry(font_impl.atlas_group()).or_insert(age);
+                *group_age = (*group_age).min(age);
+            }
+        }
+        for font_impl in self.font_impl_cache.lock().cache.values() {
+            // Not used by any font:
+            ages.entry(font_impl.atlas_group()).or_insert(u32::MAX);
+        }
+
+        let mut unused: Vec<(u32, AtlasGroup)> = ages
+            .into_iter()
+            .filter(|&(_, age)| age > 0)
+            .map(|(group, age)| (age, group))
+            .collect();
+        unused.sort_by_key(|&(age, _)| std::cmp::Reverse(age));
+
+        let mut atlas = self.atlas.lock();
+        let mut evicted = ahash::HashSet::default();
+        for (_, group) in unused {
+            if atlas.fill_ratio() <= TARGET_FILL_RATIO {
+                break;
+            }
+            atlas.free_group(group);
+            evicted.insert(group);
+        }
+        drop(atlas);
+
+        if !evicted.is_empty() {
+            let uses_evicted =
+                |font_impl: &Arc<FontImpl>| evicted.contains(&font_impl.atlas_group());
+            self.sized_family
+                .retain(|_, (_, font)| !font.font_impls().iter().any(uses_evicted));
+            self.font_impl_cache
+                .lock()
+                .cache
+                .retain(|_, font_impl| !uses_evicted(font_impl));
         }
     }
 
@@ -619,25 +1041,59 @@ impl FontsImpl {
         &self.definitions
     }
 
+    #[inline]
+    pub fn rasterization(&self) -> TextRasterization {
+        self.rasterization
+    }
+
     /// Get the right font implementation from size and [`FontFamily`].
     pub fn font(&mut self, font_id: &FontId) -> &mut Font {
-        let FontId { size, family } = font_id;
-
-        self.sized_family
-            .entry((HashableF32(*size), family.clone()))
+        let FontId {
+            size,
+            family,
+            weight,
+            style,
+        } = font_id;
+        let definitions = &self.definitions;
+        let font_impl_cache = &self.font_impl_cache;
+
+        let (last_used, font) = self
+            .sized_family
+            .entry((HashableF32(*size), family.clone(), *weight, *style))
             .or_insert_with(|| {
-                let fonts = &self.definitions.families.get(family);
+                let fonts = &definitions.families.get(family);
                 let fonts = fonts.unwrap_or_else(|| {
                     panic!("FontFamily::{:?} is not bound to any fonts", family)
                 });
-
-                let fonts: Vec<Arc<FontImpl>> = fonts
+                let faces: Vec<&str> = fonts
                     .iter()
-                    .map(|font_name| self.font_impl_cache.font_impl(*size, font_name))
+                    .map(|font_name| definitions.resolve_face(font_name, *weight, *style))
                     .collect();
 
-                Font::new(fonts)
-            })
+                let fonts: Vec<Arc<FontImpl>> = {
+                    let mut font_impl_cache = font_impl_cache.lock();
+                    faces
+                        .iter()
+                        .map(|face| font_impl_cache.font_impl(*size, face, *weight))
+                        .collect()
+                };
+
+                // Slant the glyphs if we don't have an italic face:
+                let synthetic_italics = *style == FontStyle::Italic
+                    && faces.first().map_or(false, |face| {
+                        definitions
+                            .font_data
+                            .get(*face)
+                            .map_or(false, |data| data.style != FontStyle::Italic)
+                    });
+
+                let font = Font::new(fonts).with_synthetic_italics(synthetic_italics);
+                #[cfg(feature = "system_fonts")]
+                let font = font.with_system_fallback(font_impl_cache.clone(), *size, *weight);
+                (0, font)
+            });
+        *last_used = self.generation;
+        font
     }
 
     /// Width of this character in points.
@@ -684,6 +1140,10 @@ impl GalleyCache {
             std::collections::hash_map::Entry::Occupied(entry) => {
                 let cached = entry.into_mut();
                 cached.last_used = self.generation;
+                // The glyphs in the atlas must not be evicted while the galley is used:
+                for section in &job.sections {
+                    fonts.font(&section.format.font_id);
+                }
                 cached.galley.clone()
             }
             std::collections::hash_map::Entry::Vacant(entry) => {
@@ -714,47 +1174,155 @@ impl GalleyCache {
 
 // ----------------------------------------------------------------------------
 
-struct FontImplCache {
+pub(crate) struct FontImplCache {
     atlas: Arc<Mutex<TextureAtlas>>,
     pixels_per_point: f32,
+
+    rasterization: TextRasterization,
+
     ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
 
-    /// Map font pixel sizes and names to the cached [`FontImpl`].
-    cache: ahash::HashMap<(u32, String), Arc<FontImpl>>,
+    /// The font files.
+    #[cfg(any(feature = "shaping", feature = "color_emoji"))]
+    font_data: BTreeMap<String, Arc<FontData>>,
+
+    /// The variable fonts with a weight axis: their font files, and their instances for each weight.
+    variable_weight_fonts:
+        BTreeMap<String, (Arc<FontData>, BTreeMap<FontWeight, ab_glyph::FontArc>)>,
+
+    /// The signed distance fields of the glyphs of each font and variable weight,
+    /// with [`TextRasterization::sdf`].
+    sdf_glyphs: ahash::HashMap<(String, Option<FontWeight>), Arc<super::font::SdfGlyphCache>>,
+
+    /// Map font pixel sizes, names and variable weights to the cached [`FontImpl`].
+    cache: ahash::HashMap<(u32, String, Option<FontWeight>), Arc<FontImpl>>,
+
+    #[cfg(feature = "system_fonts")]
+    system_fallback: SystemFallback,
 }
 
 impl FontImplCache {
     pub fn new(
         atlas: Arc<Mutex<TextureAtlas>>,
         pixels_per_point: f32,
+        rasterization: TextRasterization,
         font_data: &BTreeMap<String, FontData>,
     ) -> Self {
-        let ab_glyph_fonts = font_data
-            .iter()
-            .map(|(name, font_data)| {
-                let tweak = font_data.tweak;
-                let ab_glyph = ab_glyph_font_from_font_data(name, font_data);
-                (name.clone(), (tweak, ab_glyph))
-            })
-            .collect();
-
-        Self {
+        let mut slf = Self {
             atlas,
             pixels_per_point,
-            ab_glyph_fonts,
+            rasterization,
+            ab_glyph_fonts: Default::default(),
+            #[cfg(any(feature = "shaping", feature = "color_emoji"))]
+            font_data: Default::default(),
+            variable_weight_fonts: Default::default(),
+            sdf_glyphs: Default::default(),
             cache: Default::default(),
+            #[cfg(feature = "system_fonts")]
+            system_fallback: Default::default(),
+        };
+        for (name, font_data) in font_data {
+            slf.add_font(name.clone(), font_data);
+        }
+        slf
+    }
+
+    fn add_font(&mut self, name: String, font_data: &FontData) {
+        let ab_glyph = ab_glyph_font_from_font_data(&name, font_data, None);
+        self.ab_glyph_fonts
+            .insert(name.clone(), (font_data.tweak, ab_glyph));
+
+        // Only keep a copy of the font file if we need it:
+        let is_variable = has_weight_axis(font_data);
+        if !is_variable && !cfg!(any(feature = "shaping", feature = "color_emoji")) {
+            return;
+        }
+        let font_data = Arc::new(font_data.clone());
+        if is_variable {
+            self.variable_weight_fonts
+                .insert(name.clone(), (font_data.clone(), Default::default()));
+        }
+        #[cfg(any(feature = "shaping", feature = "color_emoji"))]
+        self.font_data.insert(name, font_data);
+    }
+
+    /// A font from the system with a glyph for this character, if there is one.
+    ///
+    /// The system fonts are scanned the first time this is called.
+    #[cfg(feature = "system_fonts")]
+    pub(crate) fn system_font_impl(
+        &mut self,
+        scale_in_points: f32,
+        weight: FontWeight,
+        c: char,
+    ) -> Option<Arc<FontImpl>> {
+        let font_name = if let Some(font_name) = self.system_fallback.chars.get(&c) {
+            font_name.clone()
+        } else {
+            let font_name = self.load_system_font_for(c);
+            self.system_fallback.chars.insert(c, font_name.clone());
+            font_name
+        }?;
+        Some(self.font_impl(scale_in_points, &font_name, weight))
+    }
+
+    #[cfg(feature = "system_fonts")]
+    fn load_system_font_for(&mut self, c: char) -> Option<String> {
+        use ab_glyph::Font as _;
+
+        // Prefer the system fonts we have already loaded:
+        let ab_glyph_fonts = &self.ab_glyph_fonts;
+        if let Some(font_name) = self
+            .system_fallback
+            .loaded
+            .iter()
+            .find(|font_name| ab_glyph_fonts[*font_name].1.glyph_id(c).0 != 0)
+        {
+            return Some(font_name.clone());
         }
+
+        let system_fonts = self
+            .system_fallback
+            .system_fonts
+            .get_or_insert_with(SystemFonts::cached);
+        let (face, font_data) = system_fonts.load_fallback_for(c)?;
+        let font_name = face.font_name();
+
+        #[cfg(feature = "log")]
+        log::debug!("Loaded system font {:?} for {:?}", font_name, c);
+
+        self.add_font(font_name.clone(), &font_data);
+        self.system_fallback.loaded.push(font_name.clone());
+        Some(font_name)
     }
 
-    pub fn font_impl(&mut self, scale_in_points: f32, font_name: &str) -> Arc<FontImpl> {
+    /// A sized font. `weight` is only used for variable fonts.
+    pub fn font_impl(
+        &mut self,
+        scale_in_points: f32,
+        font_name: &str,
+        weight: FontWeight,
+    ) -> Arc<FontImpl> {
         use ab_glyph::Font as _;
 
-        let (tweak, ab_glyph_font) = self
+        let (tweak, mut ab_glyph_font) = self
             .ab_glyph_fonts
             .get(font_name)
             .unwrap_or_else(|| panic!("No font data found for {:?}", font_name))
             .clone();
 
+        let variable_weight = if let Some((font_data, instances)) =
+            self.variable_weight_fonts.get_mut(font_name)
+        {
+            ab_glyph_font = instances
+                .entry(weight)
+                .or_insert_with(|| ab_glyph_font_from_font_data(font_name, font_data, Some(weight)))
+                .clone();
+            Some(weight)
+        } else {
+            None
+        };
+
         let scale_in_pixels = self.pixels_per_point * scale_in_points;
 
         // Scale the font properly (see https://github.com/emilk/egui/issues/2068).
@@ -771,17 +1339,152 @@ impl FontImplCache {
             .entry((
                 (scale_in_pixels * tweak.scale).round() as u32,
                 font_name.to_owned(),
+                variable_weight,
             ))
             .or_insert_with(|| {
-                Arc::new(FontImpl::new(
+                let font_impl = FontImpl::new(
                     self.atlas.clone(),
                     self.pixels_per_point,
                     font_name.to_owned(),
                     ab_glyph_font,
                     scale_in_pixels,
                     tweak,
-                ))
+                )
+                .with_subpixel_bins(self.rasterization.subpixel_bins());
+                let font_impl = if self.rasterization.sdf {
+                    let sdf_glyphs = self
+                        .sdf_glyphs
+                        .entry((font_name.to_owned(), variable_weight))
+                        .or_default()
+                        .clone();
+                    font_impl.with_sdf_glyphs(sdf_glyphs)
+                } else {
+                    font_impl
+                };
+                #[cfg(any(feature = "shaping", feature = "color_emoji"))]
+                let font_impl = match self.font_data.get(font_name) {
+                    Some(font_data) => font_impl.with_font_data(font_data.clone()),
+                    None => font_impl,
+                };
+                Arc::new(font_impl)
             })
             .clone()
     }
 }
+
+/// Fonts loaded from the system when none of the fonts in the [`FontDefinitions`] have a glyph.
+#[cfg(feature = "system_fonts")]
+#[derive(Default)]
+struct SystemFallback {
+    /// Scanned on first use, and shared with all other [`Fonts`].
+    system_fonts: Option<Arc<SystemFonts>>,
+
+    /// The names of the system fonts loaded so far.
+    loaded: Vec<String>,
+
+    /// The system font to use for each character, or `None` if no system font has it.
+    chars: ahash::HashMap<char, Option<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_face() {
+        let mut definitions = FontDefinitions::default();
+        let bytes: &'static [u8] = include_bytes!("../../fonts/Ubuntu-Light.ttf");
+        for (name, weight, style) in [
+            ("Bold", FontWeight::BOLD, FontStyle::Normal),
+            ("Italic", FontWeight::NORMAL, FontStyle::Italic),
+        ] {
+            definitions.font_data.insert(
+                name.to_owned(),
+                FontData::from_static(bytes).weight(weight).style(style),
+            );
+        }
+        definitions.faces.insert(
+            "Ubuntu-Light".to_owned(),
+            vec!["Bold".to_owned(), "Italic".to_owned()],
+        );
+
+        let resolve = |weight, style| definitions.resolve_face("Ubuntu-Light", weight, style);
+        assert_eq!(
+            resolve(FontWeight::NORMAL, FontStyle::Normal),
+            "Ubuntu-Light"
+        );
+        assert_eq!(resolve(FontWeight::EXTRA_BOLD, FontStyle::Normal), "Bold");
+        assert_eq!(resolve(FontWeight::BOLD, FontStyle::Italic), "Italic");
+        assert_eq!(resolve(FontWeight::BOLD, FontStyle::Normal), "Bold");
+        assert_eq!(
+            definitions.resolve_face("Hack", FontWeight::BOLD, FontStyle::Normal),
+            "Hack"
+        );
+
+        let mut fonts = FontsImpl::new(1.0, 1024, definitions);
+        assert!(!fonts.font(&FontId::proportional(14.0)).synthetic_italics());
+        assert!(!fonts
+            .font(&FontId::proportional(14.0).italic())
+            .synthetic_italics());
+        assert!(fonts
+            .font(&FontId::monospace(14.0).italic())
+            .synthetic_italics());
+    }
+
+    #[test]
+    fn test_sdf_glyphs_are_shared_between_sizes() {
+        let rasterization = TextRasterization {
+            sdf: true,
+            ..Default::default()
+        };
+        let mut fonts =
+            FontsImpl::new_with_rasterization(1.0, 1024, FontDefinitions::default(), rasterization);
+
+        let small = fonts.font(&FontId::proportional(10.0));
+        small.glyph_width('A');
+        let small_uv = small.uv_rect('A');
+        let large = fonts.font(&FontId::proportional(40.0));
+        large.glyph_width('A');
+        let large_uv = large.uv_rect('A');
+
+        assert!(!small_uv.is_nothing());
+        assert_eq!(small_uv.min, large_uv.min, "Same place in the atlas");
+        assert_eq!(small_uv.max, large_uv.max, "Same place in the atlas");
+        assert!(
+            (large_uv.size.x / small_uv.size.x - 4.0).abs() < 0.2,
+            "Scaled to the font size (up to rounding of the pixel size)"
+        );
+        assert!(fonts.atlas.lock().prepared_discs().is_empty());
+    }
+
+    #[test]
+    fn test_evict_unused_fonts() {
+        let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+        let text = "The quick brown fox jumps over the lazy dog 0123456789";
+        for size in 10..60 {
+            fonts.begin_frame(1.0, 1024);
+            fonts.layout_no_wrap(
+                text.to_owned(),
+                FontId::proportional(size as f32),
+                crate::Color32::WHITE,
+            );
+            fonts.layout_no_wrap(
+                text.to_owned(),
+                FontId::proportional(14.0),
+                crate::Color32::WHITE,
+            );
+        }
+
+        let stats = fonts.font_atlas_stats();
+        assert!(stats.num_evictions > 0);
+        assert_eq!(stats.num_resets, 0, "Evicting should make enough room");
+
+        // The font used every frame was never evicted:
+        let uv = fonts
+            .lock()
+            .fonts
+            .font(&FontId::proportional(14.0))
+            .uv_rect('T');
+        assert!(!uv.is_nothing());
+    }
+}
@@ -1,8 +1,14 @@
 //! Everything related to text, fonts, text layout, cursors etc.
 
+mod bidi;
+#[cfg(feature = "color_emoji")]
+mod color_glyph;
 pub mod cursor;
 mod font;
 mod fonts;
+mod sdf;
+#[cfg(feature = "system_fonts")]
+mod system_fonts;
 mod text_layout;
 mod text_layout_types;
 
@@ -10,10 +16,16 @@ mod text_layout_types;
 pub const TAB_SIZE: usize = 4;
 
 pub use {
-    fonts::{FontData, FontDefinitions, FontFamily, FontId, FontTweak, Fonts, FontsImpl},
+    fonts::{
+        FontData, FontDefinitions, FontFamily, FontId, FontStyle, FontTweak, FontVariation,
+        FontWeight, Fonts, FontsImpl, TextRasterization,
+    },
     text_layout::layout,
     text_layout_types::*,
 };
 
+#[cfg(feature = "system_fonts")]
+pub use system_fonts::{FaceInfo, FontQuery, SystemFonts};
+
 /// Suggested character to use to replace those in password text fields.
 pub const PASSWORD_REPLACEMENT_CHAR: char = '•';
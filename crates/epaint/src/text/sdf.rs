@@ -0,0 +1,168 @@
+//! Signed distance fields of glyphs, see [`super::TextRasterization::sdf`].
+//!
+//! A signed distance field stores, for each texel, the distance to the closest edge of the glyph.
+//! Unlike coverage it can be scaled up and down with linear filtering,
+//! with the renderer thresholding it at [`EDGE`] to get a sharp edge at any size.
+
+/// Glyphs are rasterized at this scale (font height in pixels), and then scaled to all sizes.
+pub(crate) const SDF_SCALE: f32 = 48.0;
+
+/// How far from the edge of a glyph we store distances, in texels (at [`SDF_SCALE`]).
+///
+/// This is also the padding around each glyph.
+pub(crate) const SDF_SPREAD: usize = 6;
+
+/// The value at the edge of a glyph. Larger values are inside.
+pub(crate) const EDGE: f32 = 0.5;
+
+/// Turn the coverage of a glyph (0-1 per pixel) into a signed distance field.
+///
+/// The edge ends up at [`EDGE`], with `1.0` at [`SDF_SPREAD`] pixels inside the glyph
+/// and `0.0` at [`SDF_SPREAD`] pixels outside it.
+pub(crate) fn sdf_from_coverage(coverage: &[f32], [w, h]: [usize; 2]) -> Vec<f32> {
+    assert_eq!(coverage.len(), w * h);
+
+    let inside = |i: usize| coverage[i] >= 0.5;
+    let distance_to_outside = distance_transform(w, h, |i| !inside(i));
+    let distance_to_inside = distance_transform(w, h, inside);
+
+    (0..w * h)
+        .map(|i| {
+            let c = coverage[i];
+            // Distance from the pixel center to the edge, negative inside:
+            let signed_distance = if 0.0 < c && c < 1.0 {
+                // On the edge: use the coverage for subpixel precision.
+                0.5 - c
+            } else if inside(i) {
+                0.5 - distance_to_outside[i]
+            } else {
+                distance_to_inside[i] - 0.5
+            };
+            (EDGE - signed_distance / (2.0 * SDF_SPREAD as f32)).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// The Euclidean distance from each pixel to the closest pixel for which `is_target` is true.
+///
+/// Uses the algorithm from "Distance Transforms of Sampled Functions" by Felzenszwalb and Huttenlocher.
+fn distance_transform(w: usize, h: usize, is_target: impl Fn(usize) -> bool) -> Vec<f32> {
+    const FAR: f32 = 1e20;
+
+    let mut squared: Vec<f32> = (0..w * h)
+        .map(|i| if is_target(i) { 0.0 } else { FAR })
+        .collect();
+
+    let mut scratch = DistanceTransform1D::new(w.max(h));
+    let mut line = vec![0.0; w.max(h)];
+    for x in 0..w {
+        for y in 0..h {
+            line[y] = squared[y * w + x];
+        }
+        scratch.transform(&mut line[..h]);
+        for y in 0..h {
+            squared[y * w + x] = line[y];
+        }
+    }
+    for y in 0..h {
+        scratch.transform(&mut squared[y * w..(y + 1) * w]);
+    }
+
+    squared.iter().map(|d| d.sqrt()).collect()
+}
+
+/// Scratch space for the squared distance transform of one row or column.
+struct DistanceTransform1D {
+    /// Locations of the parabolas in the lower envelope.
+    v: Vec<usize>,
+
+    /// Boundaries between the parabolas.
+    z: Vec<f32>,
+
+    /// The input values.
+    f: Vec<f32>,
+}
+
+impl DistanceTransform1D {
+    fn new(len: usize) -> Self {
+        Self {
+            v: vec![0; len],
+            z: vec![0.0; len + 1],
+            f: vec![0.0; len],
+        }
+    }
+
+    fn transform(&mut self, values: &mut [f32]) {
+        let n = values.len();
+        if n == 0 {
+            return;
+        }
+        let Self { v, z, f } = self;
+        f[..n].copy_from_slice(values);
+
+        let intersection = |f: &[f32], q: usize, p: usize| {
+            ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2.0 * q as f32 - 2.0 * p as f32)
+        };
+
+        let mut k = 0;
+        v[0] = 0;
+        z[0] = f32::NEG_INFINITY;
+        z[1] = f32::INFINITY;
+        for q in 1..n {
+            let mut s = intersection(f, q, v[k]);
+            while s <= z[k] {
+                k -= 1;
+                s = intersection(f, q, v[k]);
+            }
+            k += 1;
+            v[k] = q;
+            z[k] = s;
+            z[k + 1] = f32::INFINITY;
+        }
+
+        k = 0;
+        for (q, value) in values.iter_mut().enumerate() {
+            while z[k + 1] < q as f32 {
+                k += 1;
+            }
+            let dq = q as f32 - v[k] as f32;
+            *value = dq * dq + f[v[k]];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sdf_from_coverage() {
+        // A 20x20 square in the middle of a 40x40 image:
+        let [w, h] = [40, 40];
+        let coverage: Vec<f32> = (0..w * h)
+            .map(|i| {
+                let (x, y) = (i % w, i / w);
+                if (10..30).contains(&x) && (10..30).contains(&y) {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let sdf = sdf_from_coverage(&coverage, [w, h]);
+        let at = |x: usize, y: usize| sdf[y * w + x];
+
+        assert_eq!(at(20, 20), 1.0, "Deep inside");
+        assert_eq!(at(0, 0), 0.0, "Far outside");
+        assert!(
+            at(10, 20) > EDGE && at(9, 20) < EDGE,
+            "The edge is between the pixels"
+        );
+        assert!(
+            (at(10, 20) - EDGE - (EDGE - at(9, 20))).abs() < 1e-6,
+            "Symmetric"
+        );
+        assert!(at(11, 20) > at(10, 20));
+        assert!(at(8, 20) < at(9, 20));
+    }
+}
@@ -0,0 +1,513 @@
+//! Discovery of the fonts installed on the system.
+//!
+//! [`SystemFonts`] scans the usual font directories of the platform,
+//! and can find fonts by family, weight and style, fontconfig-style.
+//!
+//! [`FontsImpl`](super::FontsImpl) uses this to load fallback fonts when none
+//! of the fonts in [`FontDefinitions`](super::FontDefinitions) has a glyph for a character,
+//! so that e.g. Chinese, Japanese and Korean text works without bundling large fonts.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use super::FontData;
+
+/// Common families for the generic family names, in order of preference.
+const SANS_SERIF: &[&str] = &[
+    "Segoe UI",
+    "Helvetica Neue",
+    "Helvetica",
+    "Arial",
+    "Noto Sans",
+    "DejaVu Sans",
+    "Liberation Sans",
+    "Ubuntu",
+];
+const SERIF: &[&str] = &[
+    "Times New Roman",
+    "Times",
+    "Noto Serif",
+    "DejaVu Serif",
+    "Liberation Serif",
+];
+const MONOSPACE: &[&str] = &[
+    "Consolas",
+    "SF Mono",
+    "Menlo",
+    "Noto Sans Mono",
+    "DejaVu Sans Mono",
+    "Liberation Mono",
+    "Ubuntu Mono",
+];
+
+/// At most this many font files are checked when looking for a fallback for a character.
+const MAX_FALLBACK_CANDIDATES: usize = 8;
+
+/// The tables we read when scanning are rarely larger than this.
+const MAX_SCANNED_TABLE_SIZE: usize = 1024 * 1024;
+
+/// The character map of a large CJK font can be a few megabytes.
+const MAX_CMAP_SIZE: usize = 16 * 1024 * 1024;
+
+/// See [`SystemFonts::cached`].
+static CACHED_SCAN: Mutex<Option<Arc<SystemFonts>>> = Mutex::new(None);
+
+/// What font to look for with [`SystemFonts::query`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontQuery {
+    /// A family name, e.g. `"Noto Sans"`, or one of the generic families
+    /// `"sans-serif"`, `"serif"` or `"monospace"`.
+    ///
+    /// Case insensitive.
+    pub family: String,
+
+    /// From 100 (thin) to 900 (black). 400 is regular, 700 is bold.
+    pub weight: u16,
+
+    pub italic: bool,
+}
+
+impl FontQuery {
+    /// The regular (not bold, not italic) style of this family.
+    pub fn new(family: impl Into<String>) -> Self {
+        Self {
+            family: family.into(),
+            weight: 400,
+            italic: false,
+        }
+    }
+
+    /// From 100 (thin) to 900 (black). 400 is regular, 700 is bold.
+    pub fn weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = italic;
+        self
+    }
+}
+
+/// A font face found by [`SystemFonts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FaceInfo {
+    /// The font file.
+    pub path: PathBuf,
+
+    /// Which font face in the file this is.
+    pub index: u32,
+
+    /// Where the face starts in the file.
+    offset: u32,
+
+    /// The family name, e.g. `"Noto Sans CJK JP"`.
+    pub family: String,
+
+    /// From 100 (thin) to 900 (black). 400 is regular, 700 is bold.
+    pub weight: u16,
+
+    pub italic: bool,
+
+    pub monospace: bool,
+
+    /// The Unicode ranges the font claims to support, from the `OS/2` table.
+    unicode_ranges: Option<u128>,
+}
+
+impl FaceInfo {
+    /// Might this font have a glyph for this character?
+    ///
+    /// This is a quick check based on what the font claims to support.
+    /// Use [`Self::has_glyph`] to know for sure.
+    pub fn may_contain(&self, c: char) -> bool {
+        self.unicode_ranges.map_or(true, |bits| {
+            ttf_parser::UnicodeRanges(bits).contains_char(c)
+        })
+    }
+
+    /// Does this font have a glyph for this character?
+    ///
+    /// Only the character map is read from the font file.
+    pub fn has_glyph(&self, c: char) -> bool {
+        let cmap = File::open(&self.path).and_then(|mut file| {
+            let records = table_records(&mut file, self.offset)?;
+            read_table(&mut file, &records, b"cmap", MAX_CMAP_SIZE)
+        });
+        let Ok(Some(cmap)) = cmap else {
+            return false;
+        };
+        ttf_parser::cmap::Table::parse(&cmap).map_or(false, |cmap| {
+            cmap.subtables
+                .into_iter()
+                .any(|subtable| subtable.is_unicode() && subtable.glyph_index(c as u32).is_some())
+        })
+    }
+
+    /// Read the font file.
+    ///
+    /// # Errors
+    /// If the file can't be read.
+    pub fn load(&self) -> std::io::Result<FontData> {
+        let style = if self.italic {
+            super::FontStyle::Italic
+        } else {
+            super::FontStyle::Normal
+        };
+        Ok(FontData {
+            index: self.index,
+            weight: super::FontWeight(self.weight),
+            style,
+            ..FontData::from_owned(std::fs::read(&self.path)?)
+        })
+    }
+
+    /// The name to use for this font in [`Fonts`](super::Fonts).
+    pub(crate) fn font_name(&self) -> String {
+        format!("{} ({}#{})", self.family, self.path.display(), self.index)
+    }
+
+    /// How well this face matches the given weight and style. Lower is better.
+    fn style_distance(&self, weight: u16, italic: bool) -> u32 {
+        let italic_mismatch = if self.italic == italic { 0 } else { 1000 };
+        italic_mismatch + (self.weight as i32 - weight as i32).unsigned_abs()
+    }
+}
+
+/// The fonts installed on the system.
+///
+/// ```no_run
+/// # use epaint::text::{FontData, FontDefinitions, FontQuery, SystemFonts};
+/// let system_fonts = SystemFonts::scan();
+/// let mut fonts = FontDefinitions::default();
+/// if let Some(face) = system_fonts.query(&FontQuery::new("sans-serif")) {
+///     fonts.font_data.insert("system-sans".to_owned(), face.load().unwrap());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SystemFonts {
+    faces: Vec<FaceInfo>,
+}
+
+impl SystemFonts {
+    /// Scan the font directories of this platform, see [`Self::default_dirs`].
+    ///
+    /// This only reads the headers of the font files, but can still take a while
+    /// if there are many fonts installed.
+    pub fn scan() -> Self {
+        Self::scan_dirs(&Self::default_dirs())
+    }
+
+    /// Like [`Self::scan`], but only scans the first time it is called.
+    ///
+    /// Later calls return the same fonts, e.g. for each new [`Fonts`](super::Fonts).
+    pub fn cached() -> Arc<Self> {
+        CACHED_SCAN
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get_or_insert_with(|| Arc::new(Self::scan()))
+            .clone()
+    }
+
+    /// Scan these directories, and their sub-directories, for `.ttf`, `.otf`, `.ttc` and `.otc` files.
+    ///
+    /// Directories that don't exist, and files that can't be parsed, are ignored.
+    pub fn scan_dirs(dirs: &[PathBuf]) -> Self {
+        let mut faces = vec![];
+        let mut visited = HashSet::new();
+        for dir in dirs {
+            scan_dir(dir, &mut visited, &mut faces);
+        }
+        faces.sort_by(|a, b| (&a.family, &a.path, a.index).cmp(&(&b.family, &b.path, b.index)));
+        Self { faces }
+    }
+
+    /// The directories where fonts are usually installed on this platform.
+    pub fn default_dirs() -> Vec<PathBuf> {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let mut dirs = vec![];
+        if cfg!(target_os = "windows") {
+            let windir = std::env::var_os("WINDIR").unwrap_or_else(|| "C:\\Windows".into());
+            dirs.push(PathBuf::from(windir).join("Fonts"));
+            if let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") {
+                dirs.push(PathBuf::from(local_app_data).join("Microsoft\\Windows\\Fonts"));
+            }
+        } else if cfg!(target_os = "macos") {
+            dirs.push("/System/Library/Fonts".into());
+            dirs.push("/Library/Fonts".into());
+            dirs.extend(home.map(|home| home.join("Library/Fonts")));
+        } else {
+            dirs.push("/usr/share/fonts".into());
+            dirs.push("/usr/local/share/fonts".into());
+            if let Some(data_home) = std::env::var_os("XDG_DATA_HOME") {
+                dirs.push(PathBuf::from(data_home).join("fonts"));
+            } else if let Some(home) = &home {
+                dirs.push(home.join(".local/share/fonts"));
+            }
+            dirs.extend(home.map(|home| home.join(".fonts")));
+        }
+        dirs
+    }
+
+    /// All the font faces found, sorted by family.
+    pub fn faces(&self) -> &[FaceInfo] {
+        &self.faces
+    }
+
+    /// Find the font face that best matches the family, weight and style.
+    ///
+    /// Returns `None` if no font of the family is installed.
+    pub fn query(&self, query: &FontQuery) -> Option<&FaceInfo> {
+        let generic: &[&str] = match query.family.to_lowercase().as_str() {
+            "sans-serif" | "sans" => SANS_SERIF,
+            "serif" => SERIF,
+            "monospace" => MONOSPACE,
+            _ => &[],
+        };
+
+        let best_in_family = |family: &str| {
+            best_match(
+                self.faces
+                    .iter()
+                    .filter(|face| face.family.eq_ignore_ascii_case(family)),
+                query.weight,
+                query.italic,
+            )
+        };
+
+        if generic.is_empty() {
+            best_in_family(&query.family)
+        } else if let Some(face) = generic.iter().find_map(|family| best_in_family(family)) {
+            Some(face)
+        } else if generic == MONOSPACE {
+            // Any monospace font will do:
+            best_match(
+                self.faces.iter().filter(|face| face.monospace),
+                query.weight,
+                query.italic,
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Find and load a regular font with a glyph for this character.
+    ///
+    /// Only fonts that claim to support the character are tried, and only the character maps
+    /// of at most a few of them are read. Only the font file that is used is read in full.
+    pub fn load_fallback_for(&self, c: char) -> Option<(&FaceInfo, FontData)> {
+        let mut candidates: Vec<&FaceInfo> = self
+            .faces
+            .iter()
+            .filter(|face| face.may_contain(c))
+            .collect();
+        candidates.sort_by_key(|face| face.style_distance(400, false));
+
+        candidates
+            .into_iter()
+            .take(MAX_FALLBACK_CANDIDATES)
+            .filter(|face| face.has_glyph(c))
+            .find_map(|face| Some((face, face.load().ok()?)))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+fn best_match<'a>(
+    faces: impl Iterator<Item = &'a FaceInfo>,
+    weight: u16,
+    italic: bool,
+) -> Option<&'a FaceInfo> {
+    faces.min_by_key(|face| face.style_distance(weight, italic))
+}
+
+/// Scan `dir` and its sub-directories, skipping the directories in `visited`.
+///
+/// Symlinks are followed, but each directory is only scanned once, so that symlink loops end.
+fn scan_dir(dir: &Path, visited: &mut HashSet<PathBuf>, faces: &mut Vec<FaceInfo>) {
+    let Ok(canonical) = dir.canonicalize() else {
+        return;
+    };
+    if !visited.insert(canonical) {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_dir(&path, visited, faces);
+        } else {
+            let extension = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map(|extension| extension.to_lowercase());
+            if matches!(extension.as_deref(), Some("ttf" | "otf" | "ttc" | "otc")) {
+                scan_file(&path, faces).ok();
+            }
+        }
+    }
+}
+
+/// Read the faces of a font file, only reading the tables we need.
+fn scan_file(path: &Path, faces: &mut Vec<FaceInfo>) -> std::io::Result<()> {
+    let mut file = File::open(path)?;
+    let header = read_at(&mut file, 0, 12)?;
+
+    let face_offsets: Vec<u32> = if &header[0..4] == b"ttcf" {
+        let num_fonts = read_u32(&header[8..12]).min(256) as usize;
+        let offsets = read_at(&mut file, 12, 4 * num_fonts)?;
+        offsets.chunks_exact(4).map(read_u32).collect()
+    } else {
+        vec![0]
+    };
+
+    for (index, offset) in face_offsets.into_iter().enumerate() {
+        if let Some(face) = scan_face(&mut file, offset)? {
+            faces.push(FaceInfo {
+                path: path.to_owned(),
+                index: index as u32,
+                offset,
+                ..face
+            });
+        }
+    }
+    Ok(())
+}
+
+fn scan_face(file: &mut File, offset: u32) -> std::io::Result<Option<FaceInfo>> {
+    let records = table_records(file, offset)?;
+    let mut table = |tag: &[u8; 4]| read_table(file, &records, tag, MAX_SCANNED_TABLE_SIZE);
+
+    let Some(name) = table(b"name")? else {
+        return Ok(None);
+    };
+    let os2 = table(b"OS/2")?;
+    let post = table(b"post")?;
+
+    let Some(family) = ttf_parser::name::Table::parse(&name).and_then(|name| family_name(&name))
+    else {
+        return Ok(None);
+    };
+    let os2 = os2.as_deref().and_then(ttf_parser::os2::Table::parse);
+    let post = post.as_deref().and_then(ttf_parser::post::Table::parse);
+
+    Ok(Some(FaceInfo {
+        path: PathBuf::new(),
+        index: 0,
+        offset: 0,
+        family,
+        weight: os2.map_or(400, |os2| os2.weight().to_number()),
+        italic: os2.map_or(false, |os2| os2.style() != ttf_parser::Style::Normal),
+        monospace: post.map_or(false, |post| post.is_monospaced),
+        unicode_ranges: os2.map(|os2| os2.unicode_ranges().0),
+    }))
+}
+
+/// The typographic family name, preferring English.
+fn family_name(name: &ttf_parser::name::Table<'_>) -> Option<String> {
+    const ENGLISH_US: u16 = 0x0409;
+    let names = |name_id: u16| {
+        name.names
+            .into_iter()
+            .filter(move |name| name.name_id == name_id)
+    };
+    [
+        ttf_parser::name_id::TYPOGRAPHIC_FAMILY,
+        ttf_parser::name_id::FAMILY,
+    ]
+    .into_iter()
+    .find_map(|name_id| {
+        names(name_id)
+            .find(|name| name.language_id == ENGLISH_US)
+            .and_then(|name| name.to_string())
+            .or_else(|| names(name_id).find_map(|name| name.to_string()))
+    })
+}
+
+/// The table records of the face starting at this offset in the file.
+fn table_records(file: &mut File, face_offset: u32) -> std::io::Result<Vec<u8>> {
+    let face_offset = face_offset as u64;
+    let header = read_at(file, face_offset, 12)?;
+    let num_tables = u16::from_be_bytes([header[4], header[5]]) as usize;
+    read_at(file, face_offset + 12, 16 * num_tables)
+}
+
+/// Read a table of a face, or at most `max_size` bytes of it.
+fn read_table(
+    file: &mut File,
+    records: &[u8],
+    tag: &[u8; 4],
+    max_size: usize,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let Some(record) = records.chunks_exact(16).find(|record| &record[0..4] == tag) else {
+        return Ok(None);
+    };
+    let table_offset = read_u32(&record[8..12]) as u64;
+    let length = (read_u32(&record[12..16]) as usize).min(max_size);
+    read_at(file, table_offset, length).map(Some)
+}
+
+fn read_at(file: &mut File, offset: u64, len: usize) -> std::io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = vec![0; len];
+    file.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_fonts() {
+        let fonts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fonts");
+        let system_fonts = SystemFonts::scan_dirs(&[fonts_dir]);
+        assert!(system_fonts.faces().len() >= 4);
+
+        let hack = system_fonts.query(&FontQuery::new("hack")).unwrap();
+        assert_eq!(hack.family, "Hack");
+        assert!(hack.monospace);
+        assert_eq!(
+            system_fonts.query(&FontQuery::new("monospace")).unwrap(),
+            hack
+        );
+        assert!(system_fonts.query(&FontQuery::new("Comic Sans")).is_none());
+
+        let (face, font_data) = system_fonts.load_fallback_for('√').unwrap();
+        assert!(face.may_contain('√'));
+        assert!(face.has_glyph('√'));
+        assert!(!hack.has_glyph('\u{10FFFD}'));
+        assert!(!font_data.font.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_symlink_loop() {
+        let dir = std::env::temp_dir().join(format!("epaint_fonts_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let fonts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fonts");
+        std::os::unix::fs::symlink(&fonts_dir, dir.join("fonts")).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("loop")).unwrap();
+
+        // The fonts are found once, through the symlink, and the loop doesn't recurse forever:
+        let num_faces = SystemFonts::scan_dirs(std::slice::from_ref(&dir))
+            .faces()
+            .len();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            num_faces,
+            SystemFonts::scan_dirs(&[fonts_dir]).faces().len()
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::ops::RangeInclusive;
 use std::sync::Arc;
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+use super::{font::Font, FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
 use crate::{Color32, Mesh, Stroke, Vertex};
 use emath::*;
 
@@ -11,12 +11,18 @@ use emath::*;
 #[derive(Clone, Copy)]
 struct PointScale {
     pub pixels_per_point: f32,
+
+    /// Don't round glyph positions to whole pixels.
+    pub subpixel_positioning: bool,
 }
 
 impl PointScale {
     #[inline(always)]
-    pub fn new(pixels_per_point: f32) -> Self {
-        Self { pixels_per_point }
+    pub fn new(pixels_per_point: f32, subpixel_positioning: bool) -> Self {
+        Self {
+            pixels_per_point,
+            subpixel_positioning,
+        }
     }
 
     #[inline(always)]
@@ -33,6 +39,16 @@ impl PointScale {
     pub fn floor_to_pixel(&self, point: f32) -> f32 {
         (point * self.pixels_per_point).floor() / self.pixels_per_point
     }
+
+    /// Round the x position of a glyph to a whole pixel, unless glyphs are positioned with subpixel precision.
+    #[inline(always)]
+    pub fn round_glyph_x(&self, x: f32) -> f32 {
+        if self.subpixel_positioning {
+            x
+        } else {
+            self.round_to_pixel(x)
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -59,7 +75,10 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         layout_section(fonts, &job, section_index as u32, section, &mut paragraphs);
     }
 
-    let point_scale = PointScale::new(fonts.pixels_per_point());
+    let point_scale = PointScale::new(
+        fonts.pixels_per_point(),
+        fonts.rasterization().subpixel_positioning,
+    );
 
     let mut rows = rows_from_paragraphs(fonts, paragraphs, &job);
 
@@ -80,7 +99,20 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         }
     }
 
-    galley_from_rows(point_scale, job, rows)
+    for row in &mut rows {
+        super::bidi::reorder_row(&mut row.glyphs);
+    }
+
+    // Slant the glyphs of sections that ask for italics that their font doesn't have:
+    let slanted_sections = job
+        .sections
+        .iter()
+        .map(|section| {
+            section.format.italics || fonts.font(&section.format.font_id).synthetic_italics()
+        })
+        .collect();
+
+    galley_from_rows(point_scale, job, rows, slanted_sections)
 }
 
 fn layout_section(
@@ -105,35 +137,175 @@ fn layout_section(
 
     paragraph.cursor_x += leading_space;
 
-    let mut last_glyph_id = None;
-
-    for chr in job.text[byte_range.clone()].chars() {
-        if job.break_on_newline && chr == '\n' {
+    let text = &job.text[byte_range.clone()];
+    if job.break_on_newline {
+        let mut lines = text.split('\n');
+        if let Some(first_line) = lines.next() {
+            layout_text(font, first_line, section_index, paragraph);
+        }
+        for line in lines {
             out_paragraphs.push(Paragraph::default());
             paragraph = out_paragraphs.last_mut().unwrap();
             paragraph.empty_paragraph_height = font_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
-        } else {
-            let (font_impl, glyph_info) = font.glyph_info_and_font_impl(chr);
-            if let Some(font_impl) = font_impl {
-                if let Some(last_glyph_id) = last_glyph_id {
-                    paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
-                }
-            }
+            layout_text(font, line, section_index, paragraph);
+        }
+    } else {
+        layout_text(font, text, section_index, paragraph);
+    }
+}
+
+/// Lay out a piece of text without line breaks, one character at a time.
+#[cfg(not(feature = "shaping"))]
+fn layout_text(font: &mut Font, text: &str, section_index: u32, paragraph: &mut Paragraph) {
+    let mut last_glyph_id = None;
+    for chr in text.chars() {
+        layout_char(font, chr, section_index, paragraph, &mut last_glyph_id);
+    }
+}
+
+/// Lay out a piece of text without line breaks, shaping runs of characters that use the same font.
+///
+/// Each character still gets exactly one [`Glyph`], so cursors work as usual.
+/// A cluster of characters that is shaped into a single glyph (e.g. a ligature) is drawn by its first character,
+/// and the width of the cluster is divided evenly between its characters.
+#[cfg(feature = "shaping")]
+fn layout_text(font: &mut Font, text: &str, section_index: u32, paragraph: &mut Paragraph) {
+    let mut run_start = 0;
+    let mut run_font_index = None;
+
+    for (byte_index, chr) in text.char_indices() {
+        if super::font::invisible_char(chr) && run_font_index.is_some() {
+            continue; // Zero-width joiners etc. are part of the run they are in.
+        }
+        let font_index = font.shaping_font_index(chr);
+        if font_index.is_none() || font_index != run_font_index {
+            layout_run(
+                font,
+                &text[run_start..byte_index],
+                run_font_index,
+                section_index,
+                paragraph,
+            );
+            run_start = byte_index;
+            run_font_index = font_index;
+        }
+    }
+    layout_run(
+        font,
+        &text[run_start..],
+        run_font_index,
+        section_index,
+        paragraph,
+    );
+}
+
+#[cfg(feature = "shaping")]
+fn layout_run(
+    font: &mut Font,
+    run: &str,
+    font_index: Option<super::font::FontIndex>,
+    section_index: u32,
+    paragraph: &mut Paragraph,
+) {
+    if run.is_empty() {
+        return;
+    }
+
+    let font_impl = font_index.map(|font_index| font.font_impl(font_index).clone());
+    let shaped = font_impl
+        .as_ref()
+        .and_then(|font_impl| Some((font_impl, font_impl.shape(run)?)));
+    let Some((font_impl, shaped)) = shaped else {
+        let mut last_glyph_id = None;
+        for chr in run.chars() {
+            layout_char(font, chr, section_index, paragraph, &mut last_glyph_id);
+        }
+        return;
+    };
 
+    let pixels_per_point = font_impl.pixels_per_point();
+    let mut cluster_glyphs = vec![];
+    let mut glyph_index = 0;
+    while glyph_index < shaped.len() {
+        let cluster_start = shaped[glyph_index].cluster;
+        let cluster_len = shaped[glyph_index..]
+            .iter()
+            .take_while(|glyph| glyph.cluster == cluster_start)
+            .count();
+        let cluster = &shaped[glyph_index..glyph_index + cluster_len];
+        glyph_index += cluster_len;
+        let cluster_end = shaped
+            .get(glyph_index)
+            .map_or(run.len(), |glyph| glyph.cluster)
+            .max(cluster_start);
+
+        cluster_glyphs.clear();
+        let mut pen_x = 0.0;
+        for glyph in cluster {
+            cluster_glyphs.push((glyph.id, glyph.offset + vec2(pen_x, 0.0)));
+            pen_x += glyph.advance;
+        }
+        let uv_rect = font_impl.cluster_uv_rect(&cluster_glyphs);
+        let cluster_width = pen_x / pixels_per_point;
+
+        let chars = &run[cluster_start..cluster_end];
+        let num_chars = chars.chars().count().max(1);
+        let char_width = cluster_width / num_chars as f32;
+        // Right-to-left clusters are mirrored by the bidi reordering,
+        // so their last char ends up where the cluster starts:
+        let drawing_char = if chars.chars().any(super::bidi::is_rtl_char) {
+            num_chars - 1
+        } else {
+            0
+        };
+        for (i, chr) in chars.chars().enumerate() {
             paragraph.glyphs.push(Glyph {
                 chr,
-                pos: pos2(paragraph.cursor_x, f32::NAN),
-                size: vec2(glyph_info.advance_width, glyph_info.row_height),
-                ascent: glyph_info.ascent,
-                uv_rect: glyph_info.uv_rect,
+                pos: pos2(paragraph.cursor_x + i as f32 * char_width, f32::NAN),
+                size: vec2(char_width, font_impl.row_height()),
+                ascent: font_impl.ascent(),
+                uv_rect: if i == drawing_char {
+                    uv_rect
+                } else {
+                    Default::default()
+                },
                 section_index,
+                bidi_level: 0,
             });
+        }
+
+        paragraph.cursor_x += cluster_width;
+        paragraph.cursor_x = font.round_glyph_x(paragraph.cursor_x);
+    }
+}
 
-            paragraph.cursor_x += glyph_info.advance_width;
-            paragraph.cursor_x = font.round_to_pixel(paragraph.cursor_x);
-            last_glyph_id = Some(glyph_info.id);
+fn layout_char(
+    font: &mut Font,
+    chr: char,
+    section_index: u32,
+    paragraph: &mut Paragraph,
+    last_glyph_id: &mut Option<ab_glyph::GlyphId>,
+) {
+    let (font_impl, glyph_info) = font.glyph_info_and_font_impl(chr);
+    if let Some(font_impl) = font_impl {
+        if let Some(last_glyph_id) = *last_glyph_id {
+            paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
         }
     }
+
+    paragraph.glyphs.push(Glyph {
+        chr,
+        pos: pos2(paragraph.cursor_x, f32::NAN),
+        size: vec2(glyph_info.advance_width, glyph_info.row_height),
+        ascent: glyph_info.ascent,
+        uv_rect: glyph_info.uv_rect,
+        section_index,
+        bidi_level: 0,
+    });
+
+    paragraph.cursor_x += glyph_info.advance_width;
+    paragraph.cursor_x = font.round_glyph_x(paragraph.cursor_x);
+    *last_glyph_id = Some(glyph_info.id);
 }
 
 /// We ignore y at this stage
@@ -150,8 +322,14 @@ fn rows_from_paragraphs(
 
     let mut rows = vec![];
 
-    for (i, paragraph) in paragraphs.into_iter().enumerate() {
+    for (i, mut paragraph) in paragraphs.into_iter().enumerate() {
         let is_last_paragraph = (i + 1) == num_paragraphs;
+        let first_row_of_paragraph = rows.len();
+
+        let bidi_base_level = super::bidi::resolve_levels(&mut paragraph.glyphs);
+        if bidi_base_level.is_some() {
+            mirror_rtl_brackets(fonts, job, &mut paragraph.glyphs);
+        }
 
         if paragraph.glyphs.is_empty() {
             rows.push(Row {
@@ -179,11 +357,31 @@ fn rows_from_paragraphs(
                 rows.last_mut().unwrap().ends_with_newline = !is_last_paragraph;
             }
         }
+
+        if let Some(base_level) = bidi_base_level {
+            for row in &mut rows[first_row_of_paragraph..] {
+                super::bidi::reset_trailing_whitespace(&mut row.glyphs, base_level);
+            }
+        }
     }
 
     rows
 }
 
+/// Show brackets in right-to-left text mirrored, so that `(` still opens.
+fn mirror_rtl_brackets(fonts: &mut FontsImpl, job: &LayoutJob, glyphs: &mut [Glyph]) {
+    for glyph in glyphs {
+        if glyph.is_rtl() {
+            if let Some(mirrored) = super::bidi::mirrored_char(glyph.chr) {
+                let section = &job.sections[glyph.section_index as usize];
+                let font = fonts.font(&section.format.font_id);
+                let (_, glyph_info) = font.glyph_info_and_font_impl(mirrored);
+                glyph.uv_rect = glyph_info.uv_rect;
+            }
+        }
+    }
+}
+
 fn line_break(
     fonts: &mut FontsImpl,
     paragraph: &Paragraph,
@@ -415,7 +613,7 @@ fn halign_and_justify_row(
 
     for glyph in &mut row.glyphs {
         glyph.pos.x += translate_x;
-        glyph.pos.x = point_scale.round_to_pixel(glyph.pos.x);
+        glyph.pos.x = point_scale.round_glyph_x(glyph.pos.x);
         translate_x += extra_x_per_glyph;
         if glyph.chr.is_whitespace() {
             translate_x += extra_x_per_space;
@@ -428,7 +626,12 @@ fn halign_and_justify_row(
 }
 
 /// Calculate the Y positions and tessellate the text.
-fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<Row>) -> Galley {
+fn galley_from_rows(
+    point_scale: PointScale,
+    job: Arc<LayoutJob>,
+    mut rows: Vec<Row>,
+    slanted_sections: Vec<bool>,
+) -> Galley {
     let mut first_row_min_height = job.first_row_min_height;
     let mut cursor_y = 0.0;
     let mut min_x: f32 = 0.0;
@@ -471,7 +674,10 @@ fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<
         cursor_y = point_scale.round_to_pixel(cursor_y);
     }
 
-    let format_summary = format_summary(&job);
+    let format_summary = FormatSummary {
+        slanted_sections,
+        ..format_summary(&job)
+    };
 
     let mut mesh_bounds = Rect::NOTHING;
     let mut num_vertices = 0;
@@ -502,6 +708,9 @@ struct FormatSummary {
     any_background: bool,
     any_underline: bool,
     any_strikethrough: bool,
+
+    /// For each section: should the glyphs be slanted, to fake italics?
+    slanted_sections: Vec<bool>,
 }
 
 fn format_summary(job: &LayoutJob) -> FormatSummary {
@@ -534,8 +743,10 @@ fn tessellate_row(
     }
 
     let glyph_vertex_start = mesh.vertices.len();
-    tessellate_glyphs(point_scale, job, row, &mut mesh);
+    tessellate_glyphs(point_scale, job, format_summary, row, &mut mesh, false);
     let glyph_vertex_end = mesh.vertices.len();
+    tessellate_glyphs(point_scale, job, format_summary, row, &mut mesh, true);
+    let color_glyph_vertex_end = mesh.vertices.len();
 
     if format_summary.any_underline {
         add_row_hline(point_scale, row, &mut mesh, |glyph| {
@@ -561,6 +772,7 @@ fn tessellate_row(
         mesh,
         mesh_bounds,
         glyph_vertex_range: glyph_vertex_start..glyph_vertex_end,
+        color_glyph_vertex_range: glyph_vertex_end..color_glyph_vertex_end,
     }
 }
 
@@ -609,25 +821,57 @@ fn add_row_backgrounds(job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
     end_run(run_start.take(), last_rect.right());
 }
 
-fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
+/// Tessellate either the normal glyphs, or the color glyphs (e.g. emoji).
+fn tessellate_glyphs(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    format_summary: &FormatSummary,
+    row: &Row,
+    mesh: &mut Mesh,
+    color_glyphs: bool,
+) {
     for glyph in &row.glyphs {
         let uv_rect = glyph.uv_rect;
-        if !uv_rect.is_nothing() {
+        if !uv_rect.is_nothing() && uv_rect.is_color == color_glyphs {
             let mut left_top = glyph.pos + uv_rect.offset;
-            left_top.x = point_scale.round_to_pixel(left_top.x);
             left_top.y = point_scale.round_to_pixel(left_top.y);
 
+            // Round to the closest pixel, or the closest subpixel copy of the glyph:
+            let bins = uv_rect.subpixel_bins.max(1) as u16;
+            let x_in_pixels = left_top.x * point_scale.pixels_per_point();
+            let mut pixel_x = x_in_pixels.floor();
+            let mut bin = ((x_in_pixels - pixel_x) * bins as f32).round() as u16;
+            if bin == bins {
+                pixel_x += 1.0;
+                bin = 0;
+            }
+            left_top.x = pixel_x / point_scale.pixels_per_point();
+            let uv_x = uv_rect.min[0] + bin * (uv_rect.max[0] - uv_rect.min[0]);
+
             let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
             let uv = Rect::from_min_max(
-                pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32),
-                pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
+                pos2(uv_x as f32, uv_rect.min[1] as f32),
+                pos2(
+                    (uv_x + uv_rect.max[0] - uv_rect.min[0]) as f32,
+                    uv_rect.max[1] as f32,
+                ),
             );
 
             let format = &job.sections[glyph.section_index as usize].format;
 
-            let color = format.color;
+            let color = if uv_rect.is_color {
+                // The texture has the colors, so we only fade it:
+                Color32::from_white_alpha(format.color.a())
+            } else {
+                format.color
+            };
 
-            if format.italics {
+            let slanted = format_summary
+                .slanted_sections
+                .get(glyph.section_index as usize)
+                .copied()
+                .unwrap_or(format.italics);
+            if slanted {
                 let idx = mesh.vertices.len() as u32;
                 mesh.add_triangle(idx, idx + 1, idx + 2);
                 mesh.add_triangle(idx + 2, idx + 1, idx + 3);
@@ -871,3 +1115,90 @@ fn test_pre_cjk() {
         vec!["日本語とEnglish", "の混在した文章"]
     );
 }
+
+#[test]
+fn test_one_glyph_per_char() {
+    // Ligatures and combining marks must not change the number of glyphs, or cursors would break:
+    let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
+    let text = "office affluent\ne\u{301}te\u{301}\tfin";
+    let layout_job = LayoutJob::single_section(text.into(), super::TextFormat::default());
+    let galley = super::layout(&mut fonts, layout_job.into());
+    assert_eq!(galley.rows.len(), 2);
+    let num_glyphs: usize = galley.rows.iter().map(|row| row.glyphs.len()).sum();
+    assert_eq!(num_glyphs, text.chars().count() - 1);
+    for row in &galley.rows {
+        for pair in row.glyphs.windows(2) {
+            assert!(pair[0].pos.x <= pair[1].pos.x);
+        }
+    }
+}
+
+#[test]
+fn test_rtl_cursor() {
+    use super::cursor::CCursor;
+
+    let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
+    let text = "ab שלום";
+    let layout_job = LayoutJob::single_section(text.into(), super::TextFormat::default());
+    let galley = super::layout(&mut fonts, layout_job.into());
+    let row = &galley.rows[0];
+    assert!(row.has_rtl());
+
+    // The glyphs stay in logical order, but the Hebrew is positioned right-to-left:
+    let shin = &row.glyphs[3];
+    let mem = &row.glyphs[6];
+    assert_eq!((shin.chr, mem.chr), ('ש', 'ם'));
+    assert!(mem.pos.x < shin.pos.x);
+
+    // The cursor before a right-to-left char is on its right side:
+    let cursor_x = |ccursor_index| {
+        galley
+            .pos_from_cursor(&galley.from_ccursor(CCursor::new(ccursor_index)))
+            .center()
+            .x
+    };
+    assert_eq!(cursor_x(3), shin.max_x());
+    assert_eq!(cursor_x(7), mem.pos.x);
+
+    // Clicking on the right half of a right-to-left char puts the cursor before it:
+    for index in 3..7 {
+        let glyph = &row.glyphs[index];
+        let pos = pos2(glyph.pos.x + 0.75 * glyph.size.x, glyph.pos.y);
+        assert_eq!(galley.cursor_from_pos(pos.to_vec2()).ccursor.index, index);
+    }
+}
+
+#[test]
+fn test_subpixel_positioning() {
+    let pixels_per_point = 1.5;
+    let rasterization = super::TextRasterization {
+        subpixel_positioning: true,
+        ..Default::default()
+    };
+    let mut fonts = FontsImpl::new_with_rasterization(
+        pixels_per_point,
+        1024,
+        super::FontDefinitions::default(),
+        rasterization,
+    );
+    let layout_job =
+        LayoutJob::single_section("illicit lilies".into(), super::TextFormat::default());
+    let galley = super::layout(&mut fonts, layout_job.into());
+
+    let glyphs = &galley.rows[0].glyphs;
+    assert!(glyphs
+        .iter()
+        .all(|glyph| glyph.uv_rect.is_nothing() || glyph.uv_rect.subpixel_bins == 4));
+    assert!(
+        glyphs
+            .iter()
+            .any(|glyph| (glyph.pos.x * pixels_per_point).fract() != 0.0),
+        "Glyphs should not be rounded to whole pixels"
+    );
+
+    // The glyphs are still drawn at whole pixels, using the copy with the right subpixel offset:
+    for vertex in &galley.rows[0].visuals.mesh.vertices {
+        let x = vertex.pos.x * pixels_per_point;
+        assert!((x - x.round()).abs() < 1e-3, "{x}");
+    }
+}
@@ -386,8 +386,15 @@ pub struct RowVisuals {
     pub mesh_bounds: Rect,
 
     /// The range of vertices in the mesh the contain glyphs.
-    /// Before comes backgrounds (if any), and after any underlines and strikethrough.
+    /// Before comes backgrounds (if any), and after any color glyphs, underlines and strikethrough.
     pub glyph_vertex_range: Range<usize>,
+
+    /// The range of vertices in the mesh that contain color glyphs (e.g. emoji).
+    /// Comes right after [`Self::glyph_vertex_range`].
+    ///
+    /// These are not tinted by the text color, only faded by its alpha.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color_glyph_vertex_range: Range<usize>,
 }
 
 impl Default for RowVisuals {
@@ -396,6 +403,7 @@ impl Default for RowVisuals {
             mesh: Default::default(),
             mesh_bounds: Rect::NOTHING,
             glyph_vertex_range: 0..0,
+            color_glyph_vertex_range: 0..0,
         }
     }
 }
@@ -421,6 +429,11 @@ pub struct Glyph {
 
     /// Index into [`LayoutJob::sections`]. Decides color etc.
     pub section_index: u32,
+
+    /// The bidirectional embedding level. Odd levels are right-to-left, e.g. Hebrew or Arabic.
+    ///
+    /// The glyphs of a [`Row`] are always in logical order, but right-to-left glyphs are positioned right-to-left.
+    pub bidi_level: u8,
 }
 
 impl Glyph {
@@ -428,6 +441,12 @@ impl Glyph {
         self.pos.x + self.size.x
     }
 
+    /// Is this glyph laid out right-to-left?
+    #[inline]
+    pub fn is_rtl(&self) -> bool {
+        self.bidi_level % 2 == 1
+    }
+
     /// Same y range for all characters with the same [`TextFormat`].
     #[inline]
     pub fn logical_rect(&self) -> Rect {
@@ -465,24 +484,90 @@ impl Row {
         self.rect.height()
     }
 
+    /// Does this row contain right-to-left text?
+    #[inline]
+    pub fn has_rtl(&self) -> bool {
+        self.glyphs.iter().any(|glyph| glyph.bidi_level > 0)
+    }
+
     /// Closest char at the desired x coordinate.
     /// Returns something in the range `[0, char_count_excluding_newline()]`.
     pub fn char_at(&self, desired_x: f32) -> usize {
-        for (i, glyph) in self.glyphs.iter().enumerate() {
-            if desired_x < glyph.logical_rect().center().x {
-                return i;
+        if !self.has_rtl() {
+            for (i, glyph) in self.glyphs.iter().enumerate() {
+                if desired_x < glyph.logical_rect().center().x {
+                    return i;
+                }
+            }
+            return self.char_count_excluding_newline();
+        }
+
+        // The glyphs are not in visual order, so find the closest one:
+        let distance = |glyph: &Glyph| {
+            if desired_x < glyph.pos.x {
+                glyph.pos.x - desired_x
+            } else {
+                (desired_x - glyph.max_x()).max(0.0)
             }
+        };
+        let Some((i, glyph)) = self
+            .glyphs
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+        else {
+            return 0;
+        };
+        let left_half = desired_x < glyph.logical_rect().center().x;
+        if left_half != glyph.is_rtl() {
+            i
+        } else {
+            i + 1
         }
-        self.char_count_excluding_newline()
     }
 
+    /// The x coordinate of the cursor before the given char.
+    ///
+    /// For right-to-left text this is the right side of the char.
     pub fn x_offset(&self, column: usize) -> f32 {
         if let Some(glyph) = self.glyphs.get(column) {
-            glyph.pos.x
+            if glyph.is_rtl() {
+                glyph.max_x()
+            } else {
+                glyph.pos.x
+            }
+        } else if let Some(last) = self.glyphs.last().filter(|glyph| glyph.is_rtl()) {
+            last.pos.x
         } else {
             self.rect.right()
         }
     }
+
+    /// The x ranges covered by the chars in the given range of columns, e.g. for painting a selection.
+    ///
+    /// Without right-to-left text this is a single range,
+    /// but mixed-direction text can be split into several.
+    pub fn x_ranges(&self, columns: std::ops::Range<usize>) -> Vec<std::ops::RangeInclusive<f32>> {
+        let columns = columns.start.min(self.glyphs.len())..columns.end.min(self.glyphs.len());
+        if !self.has_rtl() {
+            return vec![self.x_offset(columns.start)..=self.x_offset(columns.end)];
+        }
+
+        let mut ranges: Vec<(f32, f32)> = self.glyphs[columns]
+            .iter()
+            .map(|glyph| (glyph.pos.x, glyph.max_x()))
+            .collect();
+        ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut merged: Vec<(f32, f32)> = vec![];
+        for (min, max) in ranges {
+            match merged.last_mut() {
+                Some(last) if min <= last.1 + 0.5 => last.1 = last.1.max(max),
+                _ => merged.push((min, max)),
+            }
+        }
+        merged.into_iter().map(|(min, max)| min..=max).collect()
+    }
 }
 
 impl Galley {
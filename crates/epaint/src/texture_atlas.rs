@@ -1,6 +1,6 @@
 use emath::{remap_clamp, Rect};
 
-use crate::{FontImage, ImageDelta};
+use crate::{stats::FontAtlasStats, FontImage, ImageDelta};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct Rectu {
@@ -18,6 +18,13 @@ struct Rectu {
 }
 
 impl Rectu {
+    fn extend_with(&mut self, other: Self) {
+        self.min_x = self.min_x.min(other.min_x);
+        self.min_y = self.min_y.min(other.min_y);
+        self.max_x = self.max_x.max(other.max_x);
+        self.max_y = self.max_y.max(other.max_y);
+    }
+
     const NOTHING: Self = Self {
         min_x: usize::MAX,
         min_y: usize::MAX,
@@ -32,6 +39,34 @@ impl Rectu {
     };
 }
 
+/// A set of allocations in a [`TextureAtlas`] that are freed together,
+/// e.g. all the glyphs of one font at one size.
+///
+/// Create one with [`TextureAtlas::new_group`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AtlasGroup(u32);
+
+impl AtlasGroup {
+    /// Allocations that are never freed, like the pre-rasterized discs.
+    pub const PERMANENT: Self = Self(0);
+}
+
+/// A row of the texture atlas, which all allocations of one [`AtlasGroup`] are packed into
+/// side by side.
+#[derive(Clone, Copy, Debug)]
+struct Shelf {
+    y: usize,
+
+    /// Including padding.
+    height: usize,
+
+    /// Where the next allocation in this shelf goes.
+    cursor_x: usize,
+
+    /// `None` if this space has been freed.
+    group: Option<AtlasGroup>,
+}
+
 #[derive(Copy, Clone, Debug)]
 struct PrerasterizedDisc {
     r: f32,
@@ -55,6 +90,9 @@ pub struct PreparedDisc {
 /// Contains font data in an atlas, where each character occupied a small rectangle.
 ///
 /// More characters can be added, possibly expanding the texture.
+///
+/// Each allocation belongs to an [`AtlasGroup`], and all the allocations of a group
+/// can be freed with [`Self::free_group`] to make room for new ones.
 #[derive(Clone)]
 pub struct TextureAtlas {
     image: FontImage,
@@ -62,14 +100,21 @@ pub struct TextureAtlas {
     /// What part of the image that is dirty
     dirty: Rectu,
 
-    /// Used for when allocating new rectangles.
-    cursor: (usize, usize),
-
-    row_height: usize,
+    /// Sorted by `y`, without gaps, starting at the top of the image.
+    shelves: Vec<Shelf>,
 
     /// Set when someone requested more space than was available.
     overflowed: bool,
 
+    /// The last id handed out by [`Self::new_group`].
+    last_group: u32,
+
+    /// Number of groups freed by [`Self::free_group`], for [`FontAtlasStats`].
+    num_evictions: usize,
+
+    /// Number of times the atlas was recreated, for [`FontAtlasStats`].
+    num_resets: usize,
+
     /// pre-rasterized discs of radii `2^i`, where `i` is the index.
     discs: Vec<PrerasterizedDisc>,
 }
@@ -80,9 +125,11 @@ impl TextureAtlas {
         let mut atlas = Self {
             image: FontImage::new(size),
             dirty: Rectu::EVERYTHING,
-            cursor: (0, 0),
-            row_height: 0,
+            shelves: vec![],
             overflowed: false,
+            last_group: 0,
+            num_evictions: 0,
+            num_resets: 0,
             discs: vec![], // will be filled in below
         };
 
@@ -129,12 +176,34 @@ impl TextureAtlas {
         atlas
     }
 
+    /// Set the coverage gamma of the font image, see [`FontImage::gamma`].
+    pub fn set_gamma(&mut self, gamma: f32) {
+        if self.image.gamma != Some(gamma) {
+            self.image.gamma = Some(gamma);
+            self.dirty = Rectu::EVERYTHING;
+        }
+    }
+
+    /// Mark the font image as containing signed distance fields, see [`FontImage::sdf`].
+    ///
+    /// The pre-rasterized discs can't be used with signed distance fields,
+    /// so [`Self::prepared_discs`] will return nothing.
+    pub fn set_sdf(&mut self, sdf: bool) {
+        if self.image.sdf != sdf {
+            self.image.sdf = sdf;
+            self.dirty = Rectu::EVERYTHING;
+        }
+    }
+
     pub fn size(&self) -> [usize; 2] {
         self.image.size
     }
 
     /// Returns the locations and sizes of pre-rasterized discs (filled circles) in this atlas.
     pub fn prepared_discs(&self) -> Vec<PreparedDisc> {
+        if self.image.sdf {
+            return vec![];
+        }
         let size = self.size();
         let inv_w = 1.0 / size[0] as f32;
         let inv_h = 1.0 / size[1] as f32;
@@ -163,12 +232,96 @@ impl TextureAtlas {
         self.image.width()
     }
 
-    /// When this get high, it might be time to clear and start over!
+    /// When this get high, it might be time to free some groups, or clear and start over!
     pub fn fill_ratio(&self) -> f32 {
         if self.overflowed {
             1.0
         } else {
-            (self.cursor.1 + self.row_height) as f32 / self.max_height() as f32
+            let used_height: usize = self
+                .shelves
+                .iter()
+                .filter(|shelf| shelf.group.is_some())
+                .map(|shelf| shelf.height)
+                .sum();
+            used_height as f32 / self.max_height() as f32
+        }
+    }
+
+    /// Statistics about the use of the atlas.
+    pub fn stats(&self) -> FontAtlasStats {
+        FontAtlasStats {
+            size: self.size(),
+            fill_ratio: self.fill_ratio(),
+            num_evictions: self.num_evictions,
+            num_resets: self.num_resets,
+        }
+    }
+
+    /// Call when this atlas replaces `previous`, which had become full or outdated.
+    ///
+    /// This keeps the counts in [`Self::stats`] going.
+    pub fn replaces(&mut self, previous: &Self) {
+        self.num_evictions = previous.num_evictions;
+        self.num_resets = previous.num_resets + 1;
+    }
+
+    /// Create a new group to allocate into with [`Self::allocate_in_group`].
+    pub fn new_group(&mut self) -> AtlasGroup {
+        self.last_group += 1;
+        AtlasGroup(self.last_group)
+    }
+
+    /// Free all allocations of this group, so that the space can be reused.
+    ///
+    /// The freed space is cleared, and anything still referring to it will be blank.
+    pub fn free_group(&mut self, group: AtlasGroup) {
+        assert_ne!(
+            group,
+            AtlasGroup::PERMANENT,
+            "Can't free the permanent group"
+        );
+
+        let width = self.image.width();
+        let mut freed_any = false;
+        for shelf in &mut self.shelves {
+            if shelf.group == Some(group) {
+                shelf.group = None;
+                shelf.cursor_x = 0;
+                freed_any = true;
+
+                let rows = shelf.y * width..(shelf.y + shelf.height) * width;
+                self.image.pixels[rows.clone()].fill(0.0);
+                if self.image.has_colors() {
+                    self.image.colors[rows].fill(crate::Color32::TRANSPARENT);
+                }
+                self.dirty.extend_with(Rectu {
+                    min_x: 0,
+                    min_y: shelf.y,
+                    max_x: width,
+                    max_y: shelf.y + shelf.height,
+                });
+            }
+        }
+        if !freed_any {
+            return;
+        }
+        self.num_evictions += 1;
+
+        // Merge neighboring free shelves, and give back the free space at the bottom:
+        self.shelves.dedup_by(|below, above| {
+            if below.group.is_none() && above.group.is_none() {
+                above.height += below.height;
+                true
+            } else {
+                false
+            }
+        });
+        if self
+            .shelves
+            .last()
+            .map_or(false, |shelf| shelf.group.is_none())
+        {
+            self.shelves.pop();
         }
     }
 
@@ -191,51 +344,159 @@ impl TextureAtlas {
 
     /// Returns the coordinates of where the rect ended up,
     /// and invalidates the region.
-    pub fn allocate(&mut self, (w, h): (usize, usize)) -> ((usize, usize), &mut FontImage) {
-        /// On some low-precision GPUs (my old iPad) characters get muddled up
-        /// if we don't add some empty pixels between the characters.
-        /// On modern high-precision GPUs this is not needed.
-        const PADDING: usize = 1;
+    ///
+    /// The space is never freed, see [`AtlasGroup::PERMANENT`].
+    pub fn allocate(&mut self, size: (usize, usize)) -> ((usize, usize), &mut FontImage) {
+        self.allocate_in_group(AtlasGroup::PERMANENT, size)
+    }
 
+    /// Returns the coordinates of where the rect ended up,
+    /// and invalidates the region.
+    ///
+    /// The space can be freed with [`Self::free_group`].
+    pub fn allocate_in_group(
+        &mut self,
+        group: AtlasGroup,
+        (w, h): (usize, usize),
+    ) -> ((usize, usize), &mut FontImage) {
         assert!(
             w <= self.image.width(),
             "Tried to allocate a {} wide glyph in a {} wide texture atlas",
             w,
             self.image.width()
         );
-        if self.cursor.0 + w > self.image.width() {
-            // New row:
-            self.cursor.0 = 0;
-            self.cursor.1 += self.row_height + PADDING;
-            self.row_height = 0;
-        }
-
-        self.row_height = self.row_height.max(h);
 
-        let required_height = self.cursor.1 + self.row_height;
-
-        if required_height > self.max_height() {
+        let pos = if let Some(pos) = self.find_space(group, (w, h)) {
+            pos
+        } else {
             // This is a bad place to be - we need to start reusing space :/
 
             #[cfg(feature = "log")]
             log::warn!("epaint texture atlas overflowed!");
 
-            self.cursor = (0, self.image.height() / 3); // Restart a bit down - the top of the atlas has too many important things in it
             self.overflowed = true; // this will signal the user that we need to recreate the texture atlas next frame.
-        } else if resize_to_min_height(&mut self.image, required_height) {
-            self.dirty = Rectu::EVERYTHING;
-        }
 
-        let pos = self.cursor;
-        self.cursor.0 += w + PADDING;
+            // Overwrite something a bit down - the top of the atlas has too many important things in it:
+            (
+                0,
+                (self.image.height() / 3).min(self.image.height().saturating_sub(h)),
+            )
+        };
 
-        self.dirty.min_x = self.dirty.min_x.min(pos.0);
-        self.dirty.min_y = self.dirty.min_y.min(pos.1);
-        self.dirty.max_x = self.dirty.max_x.max(pos.0 + w);
-        self.dirty.max_y = self.dirty.max_y.max(pos.1 + h);
+        self.dirty.extend_with(Rectu {
+            min_x: pos.0,
+            min_y: pos.1,
+            max_x: pos.0 + w,
+            max_y: pos.1 + h,
+        });
 
         (pos, &mut self.image)
     }
+
+    fn find_space(&mut self, group: AtlasGroup, (w, h): (usize, usize)) -> Option<(usize, usize)> {
+        /// On some low-precision GPUs (my old iPad) characters get muddled up
+        /// if we don't add some empty pixels between the characters.
+        /// On modern high-precision GPUs this is not needed.
+        const PADDING: usize = 1;
+
+        let width = self.image.width();
+        let height = h + PADDING;
+        let has_room = |shelf: &Shelf| shelf.cursor_x + w <= width;
+
+        // Next to the previous allocations of the group:
+        let shelf_index = self.shelves.iter().rposition(|shelf| {
+            shelf.group == Some(group) && has_room(shelf) && height <= shelf.height
+        });
+
+        // Next to the previous allocations of the group, making the shelf taller:
+        let shelf_index = shelf_index.or_else(|| {
+            let index = (0..self.shelves.len()).rev().find(|&index| {
+                let shelf = &self.shelves[index];
+                shelf.group == Some(group) && has_room(shelf) && self.can_grow(index, height)
+            })?;
+            self.grow(index, height);
+            Some(index)
+        });
+
+        // In space that has been freed:
+        let shelf_index = shelf_index.or_else(|| {
+            let index = self
+                .shelves
+                .iter()
+                .position(|shelf| shelf.group.is_none() && height <= shelf.height)?;
+            let shelf = &mut self.shelves[index];
+            let remaining = shelf.height - height;
+            shelf.height = height;
+            shelf.group = Some(group);
+            if remaining > 0 {
+                let free = Shelf {
+                    y: shelf.y + height,
+                    height: remaining,
+                    cursor_x: 0,
+                    group: None,
+                };
+                self.shelves.insert(index + 1, free);
+            }
+            Some(index)
+        });
+
+        // In a new shelf at the bottom:
+        let shelf_index = match shelf_index {
+            Some(index) => index,
+            None => {
+                let y = self.bottom();
+                if y + h > self.max_height() {
+                    return None;
+                }
+                if resize_to_min_height(&mut self.image, y + h) {
+                    self.dirty = Rectu::EVERYTHING;
+                }
+                self.shelves.push(Shelf {
+                    y,
+                    height,
+                    cursor_x: 0,
+                    group: Some(group),
+                });
+                self.shelves.len() - 1
+            }
+        };
+
+        let shelf = &mut self.shelves[shelf_index];
+        let pos = (shelf.cursor_x, shelf.y);
+        shelf.cursor_x += w + PADDING;
+        Some(pos)
+    }
+
+    /// Where the unused space at the bottom of the atlas starts.
+    fn bottom(&self) -> usize {
+        self.shelves
+            .last()
+            .map_or(0, |shelf| shelf.y + shelf.height)
+    }
+
+    /// Can this shelf be made `height` tall, by taking space from below it?
+    fn can_grow(&self, index: usize, height: usize) -> bool {
+        let shelf = &self.shelves[index];
+        match self.shelves.get(index + 1) {
+            None => shelf.y + height <= self.max_height(),
+            Some(next) => next.group.is_none() && shelf.height + next.height >= height,
+        }
+    }
+
+    /// Make the shelf `height` tall, see [`Self::can_grow`].
+    fn grow(&mut self, index: usize, height: usize) {
+        let extra = height - self.shelves[index].height;
+        self.shelves[index].height = height;
+        if let Some(next) = self.shelves.get_mut(index + 1) {
+            next.y += extra;
+            next.height -= extra;
+            if next.height == 0 {
+                self.shelves.remove(index + 1);
+            }
+        } else if resize_to_min_height(&mut self.image, self.shelves[index].y + height) {
+            self.dirty = Rectu::EVERYTHING;
+        }
+    }
 }
 
 fn resize_to_min_height(image: &mut FontImage, required_height: usize) -> bool {
@@ -245,8 +506,45 @@ fn resize_to_min_height(image: &mut FontImage, required_height: usize) -> bool {
 
     if image.width() * image.height() > image.pixels.len() {
         image.pixels.resize(image.width() * image.height(), 0.0);
+        if image.has_colors() {
+            image
+                .colors
+                .resize(image.width() * image.height(), crate::Color32::TRANSPARENT);
+        }
         true
     } else {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_group() {
+        let mut atlas = TextureAtlas::new([1024, 64]);
+        let permanent_fill_ratio = atlas.fill_ratio();
+
+        let a = atlas.new_group();
+        let b = atlas.new_group();
+        let (pos_a, image) = atlas.allocate_in_group(a, (100, 40));
+        image[pos_a] = 1.0;
+        let (pos_b, _) = atlas.allocate_in_group(b, (100, 20));
+        assert_ne!(pos_a.1, pos_b.1, "Different groups go on different shelves");
+        let (pos_b2, _) = atlas.allocate_in_group(b, (100, 20));
+        assert_eq!(pos_b.1, pos_b2.1, "Same group, same shelf");
+
+        atlas.free_group(a);
+        assert_eq!(atlas.image[pos_a], 0.0, "Freed space is cleared");
+        assert_eq!(atlas.stats().num_evictions, 1);
+
+        let c = atlas.new_group();
+        let (pos_c, _) = atlas.allocate_in_group(c, (100, 30));
+        assert_eq!(pos_c, pos_a, "Freed space is reused");
+
+        atlas.free_group(b);
+        atlas.free_group(c);
+        assert_eq!(atlas.fill_ratio(), permanent_fill_ratio);
+    }
+}
@@ -145,7 +145,7 @@ impl TextureMeta {
 
 // ----------------------------------------------------------------------------
 
-/// How the texture texels are filtered.
+/// How the texture texels are filtered and sampled.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TextureOptions {
@@ -154,6 +154,10 @@ pub struct TextureOptions {
 
     /// How to filter when minifying (when texels are smaller than pixels).
     pub minification: TextureFilter,
+
+    /// How to sample outside the 0-1 UV range, in both directions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wrap_mode: TextureWrapMode,
 }
 
 impl TextureOptions {
@@ -161,17 +165,43 @@ impl TextureOptions {
     pub const LINEAR: Self = Self {
         magnification: TextureFilter::Linear,
         minification: TextureFilter::Linear,
+        wrap_mode: TextureWrapMode::ClampToEdge,
     };
 
     /// Nearest magnification and minification.
     pub const NEAREST: Self = Self {
         magnification: TextureFilter::Nearest,
         minification: TextureFilter::Nearest,
+        wrap_mode: TextureWrapMode::ClampToEdge,
+    };
+
+    /// Linear magnification and minification, repeating the texture outside the 0-1 UV range.
+    pub const LINEAR_REPEAT: Self = Self {
+        wrap_mode: TextureWrapMode::Repeat,
+        ..Self::LINEAR
+    };
+
+    /// Linear magnification and minification, mirroring the texture outside the 0-1 UV range.
+    pub const LINEAR_MIRRORED_REPEAT: Self = Self {
+        wrap_mode: TextureWrapMode::MirroredRepeat,
+        ..Self::LINEAR
+    };
+
+    /// Nearest magnification and minification, repeating the texture outside the 0-1 UV range.
+    pub const NEAREST_REPEAT: Self = Self {
+        wrap_mode: TextureWrapMode::Repeat,
+        ..Self::NEAREST
+    };
+
+    /// Nearest magnification and minification, mirroring the texture outside the 0-1 UV range.
+    pub const NEAREST_MIRRORED_REPEAT: Self = Self {
+        wrap_mode: TextureWrapMode::MirroredRepeat,
+        ..Self::NEAREST
     };
 }
 
 impl Default for TextureOptions {
-    /// The default is linear for both magnification and minification.
+    /// The default is linear for both magnification and minification, clamping at the edges.
     fn default() -> Self {
         Self::LINEAR
     }
@@ -191,6 +221,25 @@ pub enum TextureFilter {
     Linear,
 }
 
+/// How the texture is sampled outside the 0-1 UV range.
+///
+/// This is what makes it possible to tile a texture by using a UV rectangle larger than 0-1.
+///
+/// Note that WebGL 1 only supports [`Self::ClampToEdge`] for textures whose sides aren't powers of two.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TextureWrapMode {
+    /// Stretch the edge texels out to infinity.
+    #[default]
+    ClampToEdge,
+
+    /// Tile the texture.
+    Repeat,
+
+    /// Tile the texture, mirroring every other copy so that the edges line up.
+    MirroredRepeat,
+}
+
 // ----------------------------------------------------------------------------
 
 /// What has been allocated and freed during the last period.
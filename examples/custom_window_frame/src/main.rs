@@ -50,7 +50,7 @@ fn custom_window_frame(
     use egui::*;
 
     let panel_frame = egui::Frame {
-        fill: ctx.style().visuals.window_fill(),
+        fill: ctx.style().visuals.window_fill().into(),
         rounding: 10.0.into(),
         stroke: ctx.style().visuals.widgets.noninteractive.fg_stroke,
         outer_margin: 0.5.into(), // so the stroke is within the bounds
@@ -36,6 +36,8 @@ cargo doc --document-private-items --no-deps --all-features
 (cd crates/egui_demo_lib && cargo check --no-default-features)
 (cd crates/egui_extras && cargo check --no-default-features)
 (cd crates/egui_glow && cargo check --no-default-features)
+(cd crates/egui_software && cargo check --no-default-features)
+(cd crates/egui_test && cargo check --no-default-features)
 (cd crates/egui-winit && cargo check --no-default-features --features "wayland")
 (cd crates/egui-winit && cargo check --no-default-features --features "winit/x11")
 (cd crates/emath && cargo check --no-default-features)
@@ -47,6 +49,8 @@ cargo doc --document-private-items --no-deps --all-features
 (cd crates/egui_demo_app && cargo check --all-features)
 (cd crates/egui_extras && cargo check --all-features)
 (cd crates/egui_glow && cargo check --all-features)
+(cd crates/egui_software && cargo check --all-features)
+(cd crates/egui_test && cargo check --all-features)
 (cd crates/egui-winit && cargo check --all-features)
 (cd crates/emath && cargo check --all-features)
 (cd crates/epaint && cargo check --all-features)